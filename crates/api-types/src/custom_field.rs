@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::Type;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::some_if_present;
+
+/// Key in `Issue.extension_metadata` holding custom field values, as an
+/// object keyed by [`CustomField::key`].
+pub const CUSTOM_FIELDS_METADATA_KEY: &str = "custom_fields";
+
+/// How a custom field's value is stored in `extension_metadata`:
+/// text, select, user (UUID) and date (`YYYY-MM-DD`) values are strings,
+/// numbers are JSON numbers and multi-select values are arrays of strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "custom_field_type", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Select,
+    MultiSelect,
+    User,
+    Date,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CustomField {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    /// Stable identifier used in `extension_metadata` and issue queries.
+    pub key: String,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    /// Allowed values of select and multi-select fields.
+    pub options: Vec<String>,
+    pub sort_order: f64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateCustomFieldRequest {
+    /// Optional client-generated ID. If not provided, server generates one.
+    /// Using client-generated IDs enables stable optimistic updates.
+    #[ts(optional)]
+    pub id: Option<Uuid>,
+    pub organization_id: Uuid,
+    pub key: String,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    #[serde(default)]
+    pub options: Vec<String>,
+    pub sort_order: f64,
+}
+
+/// A field's key and type can't change once issues may hold values for it.
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateCustomFieldRequest {
+    #[serde(default, deserialize_with = "some_if_present")]
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "some_if_present")]
+    pub options: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "some_if_present")]
+    pub sort_order: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListCustomFieldsQuery {
+    pub organization_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListCustomFieldsResponse {
+    pub custom_fields: Vec<CustomField>,
+}
+
+/// Custom field values stored in an issue's `extension_metadata`.
+pub fn custom_field_values(extension_metadata: &Value) -> Option<&Map<String, Value>> {
+    extension_metadata
+        .get(CUSTOM_FIELDS_METADATA_KEY)
+        .and_then(Value::as_object)
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sqlx::Type;
@@ -17,6 +17,16 @@ pub enum IssuePriority {
     Low,
 }
 
+/// What an issue's estimate counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "estimate_unit", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateUnit {
+    #[default]
+    Points,
+    Hours,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct Issue {
@@ -30,6 +40,8 @@ pub struct Issue {
     pub priority: Option<IssuePriority>,
     pub start_date: Option<DateTime<Utc>>,
     pub target_date: Option<DateTime<Utc>>,
+    pub estimate: Option<f64>,
+    pub estimate_unit: EstimateUnit,
     pub completed_at: Option<DateTime<Utc>>,
     pub sort_order: f64,
     pub parent_issue_id: Option<Uuid>,
@@ -53,6 +65,13 @@ pub struct CreateIssueRequest {
     pub priority: Option<IssuePriority>,
     pub start_date: Option<DateTime<Utc>>,
     pub target_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub estimate: Option<f64>,
+    /// Defaults to points.
+    #[serde(default)]
+    #[ts(optional)]
+    pub estimate_unit: Option<EstimateUnit>,
     pub completed_at: Option<DateTime<Utc>>,
     pub sort_order: f64,
     pub parent_issue_id: Option<Uuid>,
@@ -103,6 +122,20 @@ pub struct UpdateIssueRequest {
         deserialize_with = "some_if_present",
         skip_serializing_if = "Option::is_none"
     )]
+    #[ts(optional)]
+    pub estimate: Option<Option<f64>>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[ts(optional)]
+    pub estimate_unit: Option<EstimateUnit>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub completed_at: Option<Option<DateTime<Utc>>>,
     #[serde(
         default,
@@ -133,9 +166,55 @@ pub struct UpdateIssueRequest {
 #[derive(Debug, Clone, Deserialize)]
 pub struct ListIssuesQuery {
     pub project_id: Uuid,
+    /// Filter in the issue query language, e.g.
+    /// `status:"In Review" priority>=high assignee:me tag:backend updated:<7d has:pr`.
+    #[serde(default)]
+    pub q: Option<String>,
+    /// Saved view whose query is applied before `q`.
+    #[serde(default)]
+    pub view_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ListIssuesResponse {
     pub issues: Vec<Issue>,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssueBurndownQuery {
+    pub project_id: Uuid,
+    /// Only estimates in this unit are summed.
+    #[serde(default)]
+    pub unit: EstimateUnit,
+    /// First day of the series; defaults to 30 days ago.
+    #[serde(default)]
+    pub from: Option<NaiveDate>,
+    /// Last day of the series; defaults to today.
+    #[serde(default)]
+    pub to: Option<NaiveDate>,
+}
+
+/// Issues sitting in one status at the end of one day (UTC).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct StatusBurndownPoint {
+    pub day: NaiveDate,
+    pub status_id: Uuid,
+    pub issue_count: i64,
+    pub estimate: f64,
+}
+
+/// Total and completed estimate at the end of one day (UTC).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BurnupPoint {
+    pub day: NaiveDate,
+    pub scope: f64,
+    pub completed: f64,
+    pub remaining: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct IssueBurndownResponse {
+    pub unit: EstimateUnit,
+    pub statuses: Vec<StatusBurndownPoint>,
+    pub totals: Vec<BurnupPoint>,
+}
@@ -49,7 +49,34 @@ pub struct ListIssueRelationshipsQuery {
     pub issue_id: Uuid,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct ListIssueRelationshipsResponse {
     pub issue_relationships: Vec<IssueRelationship>,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssueDependencyGraphQuery {
+    pub project_id: Uuid,
+    /// Also return the issues transitively blocked by this issue.
+    #[serde(default)]
+    pub issue_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct IssueDependencyEdge {
+    pub blocking_issue_id: Uuid,
+    pub blocked_issue_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct IssueDependencyGraphResponse {
+    /// Every issue in the project, blockers before the issues they block.
+    pub topological_order: Vec<Uuid>,
+    pub edges: Vec<IssueDependencyEdge>,
+    /// Chain of open issues gating the latest `target_date`, first blocker
+    /// first.
+    pub critical_path: Vec<Uuid>,
+    /// Issues transitively blocked by the requested `issue_id`, in
+    /// topological order.
+    pub blocked_issue_ids: Option<Vec<Uuid>>,
+}
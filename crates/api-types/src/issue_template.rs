@@ -0,0 +1,160 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::{IssuePriority, some_if_present};
+
+/// Reusable issue content. `title` and `description` may contain the
+/// placeholders `{{date}}`, `{{week}}`, `{{month}}` and `{{year}}`, filled in
+/// when an issue is created from the template.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IssueTemplate {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status_id: Uuid,
+    pub priority: Option<IssuePriority>,
+    pub tag_ids: Vec<Uuid>,
+    pub assignee_ids: Vec<Uuid>,
+    /// Five-field cron expression (`minute hour day-of-month month
+    /// day-of-week`) evaluated in UTC. Templates without one are only
+    /// instantiated manually.
+    pub recurrence: Option<String>,
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// Executor profile the local scheduler starts a workspace with for
+    /// issues created from this template.
+    #[ts(type = "AutoStartExecutorProfile | null")]
+    pub auto_start_executor_profile: Option<Json<AutoStartExecutorProfile>>,
+    pub creator_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Executor profile an auto-start template starts workspaces with. Mirrors the
+/// local `ExecutorProfileId`; `executor` is the coding agent's
+/// `SCREAMING_SNAKE_CASE` name, which the local scheduler validates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+pub struct AutoStartExecutorProfile {
+    pub executor: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub variant: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct CreateIssueTemplateRequest {
+    /// Optional client-generated ID. If not provided, server generates one.
+    /// Using client-generated IDs enables stable optimistic updates.
+    #[ts(optional)]
+    pub id: Option<Uuid>,
+    pub project_id: Uuid,
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status_id: Uuid,
+    pub priority: Option<IssuePriority>,
+    #[serde(default)]
+    pub tag_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub assignee_ids: Vec<Uuid>,
+    pub recurrence: Option<String>,
+    pub auto_start_executor_profile: Option<AutoStartExecutorProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UpdateIssueTemplateRequest {
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub title: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Option<String>>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_id: Option<Uuid>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub priority: Option<Option<IssuePriority>>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tag_ids: Option<Vec<Uuid>>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub assignee_ids: Option<Vec<Uuid>>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub recurrence: Option<Option<String>>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub auto_start_executor_profile: Option<Option<AutoStartExecutorProfile>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListIssueTemplatesQuery {
+    pub project_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ListIssueTemplatesResponse {
+    pub issue_templates: Vec<IssueTemplate>,
+}
+
+/// Key in `Issue::extension_metadata` under which issues created from a
+/// template with auto-start carry the executor profile to start with. Only the
+/// server writes it; see `protect_auto_start_metadata`.
+pub const AUTO_START_METADATA_KEY: &str = "auto_start_executor_profile";
+
+/// Keep client-supplied `extension_metadata` from setting or changing the
+/// auto-start profile: the key is reset to its value in `existing` (the
+/// issue's current metadata), or removed for new issues.
+pub fn protect_auto_start_metadata(
+    extension_metadata: &mut serde_json::Value,
+    existing: Option<&serde_json::Value>,
+) {
+    let Some(metadata) = extension_metadata.as_object_mut() else {
+        return;
+    };
+    match existing.and_then(|existing| existing.get(AUTO_START_METADATA_KEY)) {
+        Some(profile) => {
+            metadata.insert(AUTO_START_METADATA_KEY.to_string(), profile.clone());
+        }
+        None => {
+            metadata.remove(AUTO_START_METADATA_KEY);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::Type;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "time_entry_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TimeEntrySource {
+    /// A coding agent run on a linked workspace.
+    Agent,
+    /// Time between agent runs on a linked workspace, spent reviewing and
+    /// writing follow-ups.
+    Human,
+    Manual,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IssueTimeEntry {
+    pub id: Uuid,
+    pub issue_id: Uuid,
+    pub workspace_id: Option<Uuid>,
+    pub user_id: Uuid,
+    pub source: TimeEntrySource,
+    pub started_at: DateTime<Utc>,
+    pub duration_seconds: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct CreateIssueTimeEntryRequest {
+    /// Optional client-generated ID. If not provided, server generates one.
+    /// Reporting an entry again with the same ID replaces its duration.
+    #[ts(optional)]
+    pub id: Option<Uuid>,
+    pub issue_id: Uuid,
+    #[ts(optional)]
+    pub workspace_id: Option<Uuid>,
+    pub source: TimeEntrySource,
+    pub started_at: DateTime<Utc>,
+    pub duration_seconds: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListIssueTimeEntriesQuery {
+    pub issue_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListIssueTimeEntriesResponse {
+    pub issue_time_entries: Vec<IssueTimeEntry>,
+}
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::{IssuePriority, IssueRelationshipType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueImportFormat {
+    /// Header row followed by one issue per row.
+    Csv,
+    /// An array of issue objects, or an object with an `issues` array such
+    /// as the output of the export endpoint.
+    Json,
+    /// A Linear GraphQL export (`issues.nodes`).
+    Linear,
+    /// A Jira REST search result (`issues[].fields`).
+    Jira,
+}
+
+/// Issue field a CSV column or JSON key maps to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueImportField {
+    /// Identifier in the source system, used to link parents and
+    /// relationships and to skip issues that were already imported.
+    Id,
+    Title,
+    Description,
+    Status,
+    Priority,
+    Tags,
+    Assignees,
+    StartDate,
+    TargetDate,
+    CompletedAt,
+    Parent,
+    Ignore,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ImportIssuesRequest {
+    pub project_id: Uuid,
+    pub format: IssueImportFormat,
+    /// Raw contents of the export file.
+    pub content: String,
+    /// Overrides for CSV headers and JSON keys that don't match a field
+    /// name. Matching is case-insensitive.
+    #[serde(default)]
+    pub column_mapping: HashMap<String, IssueImportField>,
+    /// Source status name -> project status name. Unmapped statuses that
+    /// don't match a project status fall back to the first status.
+    #[serde(default)]
+    pub status_mapping: HashMap<String, String>,
+    /// Only report what would be imported. Defaults to true so a plain
+    /// request never writes.
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueImportAction {
+    Create,
+    /// An issue with the same source id already exists in the project.
+    SkipExisting,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ImportIssuePreview {
+    /// 1-based position of the issue in the source file.
+    pub row: usize,
+    pub external_id: Option<String>,
+    pub action: IssueImportAction,
+    pub title: String,
+    pub status: String,
+    pub priority: Option<IssuePriority>,
+    pub tags: Vec<String>,
+    /// Emails of the project members the issue will be assigned to.
+    pub assignees: Vec<String>,
+    pub parent_external_id: Option<String>,
+    pub comment_count: usize,
+    pub relationship_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ImportRowError {
+    pub row: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ImportIssuesResponse {
+    pub dry_run: bool,
+    pub issues: Vec<ImportIssuePreview>,
+    /// Tags that don't exist in the project yet and will be created.
+    pub new_tags: Vec<String>,
+    /// Source statuses that matched no project status.
+    pub unmapped_statuses: Vec<String>,
+    /// Assignees and comment authors that matched no organization member.
+    /// Assignees are dropped; comments are attributed to the importer.
+    pub unknown_users: Vec<String>,
+    /// Rows that could not be parsed. An import is refused while any remain.
+    pub errors: Vec<ImportRowError>,
+    /// Blocking relationships left out because they would create a cycle.
+    /// Only known once the import runs.
+    pub skipped_relationships: Vec<ImportRowError>,
+    pub created_issue_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueExportFormat {
+    /// Full backup including comments and relationships.
+    #[default]
+    Json,
+    /// One row per issue, without comments or relationships.
+    Csv,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExportIssuesQuery {
+    pub project_id: Uuid,
+    #[serde(default)]
+    pub format: IssueExportFormat,
+}
+
+/// A project's issues in the shape the `json` import format reads back.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct IssueExport {
+    pub project_id: Uuid,
+    pub exported_at: DateTime<Utc>,
+    pub issues: Vec<ExportedIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ExportedIssue {
+    /// The issue's `simple_id`.
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: String,
+    pub priority: Option<IssuePriority>,
+    pub tags: Vec<String>,
+    /// Assignee emails.
+    pub assignees: Vec<String>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub target_date: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// `simple_id` of the parent issue.
+    pub parent: Option<String>,
+    pub comments: Vec<ExportedIssueComment>,
+    pub relationships: Vec<ExportedIssueRelationship>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ExportedIssueComment {
+    /// Author email, if the author still exists.
+    pub author: Option<String>,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ExportedIssueRelationship {
+    pub relationship_type: IssueRelationshipType,
+    /// `simple_id` of the related issue.
+    pub related: String,
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::some_if_present;
+
+/// A named issue query saved for reuse. Views without an owner are shared
+/// with every member of the project's organization.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct IssueView {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub owner_user_id: Option<Uuid>,
+    pub name: String,
+    /// Query in the issue query language, e.g.
+    /// `status:"In Review" priority>=high assignee:me`.
+    pub query: String,
+    pub sort_order: f64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct CreateIssueViewRequest {
+    /// Optional client-generated ID. If not provided, server generates one.
+    /// Using client-generated IDs enables stable optimistic updates.
+    #[ts(optional)]
+    pub id: Option<Uuid>,
+    pub project_id: Uuid,
+    pub name: String,
+    pub query: String,
+    /// Share the view with the whole organization instead of keeping it
+    /// private to its creator.
+    #[serde(default)]
+    pub shared: bool,
+    #[serde(default)]
+    pub sort_order: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UpdateIssueViewRequest {
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub query: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub shared: Option<bool>,
+    #[serde(
+        default,
+        deserialize_with = "some_if_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sort_order: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListIssueViewsQuery {
+    pub project_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ListIssueViewsResponse {
+    pub issue_views: Vec<IssueView>,
+}
@@ -7,6 +7,7 @@
 
 use serde::{Deserialize, Deserializer};
 
+pub mod custom_field;
 pub mod issue;
 pub mod issue_assignee;
 pub mod issue_comment;
@@ -14,6 +15,10 @@ pub mod issue_comment_reaction;
 pub mod issue_follower;
 pub mod issue_relationship;
 pub mod issue_tag;
+pub mod issue_template;
+pub mod issue_time_entry;
+pub mod issue_transfer;
+pub mod issue_view;
 pub mod migration;
 pub mod notification;
 pub mod oauth;
@@ -29,6 +34,7 @@ pub mod user;
 pub mod workspace;
 pub mod workspaces;
 
+pub use custom_field::*;
 pub use issue::*;
 pub use issue_assignee::*;
 pub use issue_comment::*;
@@ -36,6 +42,10 @@ pub use issue_comment_reaction::*;
 pub use issue_follower::*;
 pub use issue_relationship::*;
 pub use issue_tag::*;
+pub use issue_template::*;
+pub use issue_time_entry::*;
+pub use issue_transfer::*;
+pub use issue_view::*;
 pub use migration::*;
 pub use notification::*;
 pub use oauth::*;
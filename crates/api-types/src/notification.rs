@@ -15,6 +15,12 @@ pub enum NotificationType {
     IssueStatusChanged,
     IssueAssigneeChanged,
     IssueDeleted,
+    IssueMentioned,
+    IssueWorkspaceFinished,
+    IssuePullRequestOpened,
+    IssuePullRequestMerged,
+    IssueBlocked,
+    IssueUnblocked,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -57,3 +63,27 @@ pub struct ListNotificationsQuery {
 pub struct ListNotificationsResponse {
     pub notifications: Vec<Notification>,
 }
+
+/// How often unseen notifications are emailed as a digest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type, TS)]
+#[sqlx(type_name = "digest_frequency", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[ts(export)]
+pub enum DigestFrequency {
+    #[default]
+    Off,
+    Daily,
+    Weekly,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct NotificationDigestSettings {
+    pub frequency: DigestFrequency,
+    pub last_sent_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct UpdateNotificationDigestSettingsRequest {
+    pub frequency: DigestFrequency,
+}
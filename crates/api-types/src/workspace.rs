@@ -20,3 +20,26 @@ pub struct Workspace {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+/// When an issue reached each delivery stage through one of its workspaces.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueDeliveryTimeline {
+    pub issue_id: Uuid,
+    pub workspace_id: Uuid,
+    pub local_workspace_id: Option<Uuid>,
+    pub issue_created_at: DateTime<Utc>,
+    pub workspace_created_at: DateTime<Utc>,
+    /// First pull request opened from the workspace.
+    pub pull_request_opened_at: Option<DateTime<Utc>>,
+    pub pull_request_merged_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListIssueDeliveryTimelinesQuery {
+    pub project_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListIssueDeliveryTimelinesResponse {
+    pub timelines: Vec<IssueDeliveryTimeline>,
+}
@@ -37,3 +37,11 @@ pub struct UpdateWorkspaceRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lines_removed: Option<Option<i32>>,
 }
+
+/// Sent when a coding agent run in a linked workspace ends, so the remote
+/// server can notify the people watching the workspace's issue.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WorkspaceFinishedRequest {
+    pub local_workspace_id: Uuid,
+    pub success: bool,
+}
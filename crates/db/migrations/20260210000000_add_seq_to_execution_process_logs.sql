@@ -0,0 +1,5 @@
+-- Add seq column to execution_process_logs
+-- Stores the MsgStore sequence number of each persisted line so clients can
+-- resume log streams by cursor after the in-memory store is gone.
+-- NULL for lines persisted before sequencing existed.
+ALTER TABLE execution_process_logs ADD COLUMN seq INTEGER;
@@ -0,0 +1,29 @@
+-- Per remote project configuration for automatically starting workspaces for
+-- ready issues, and a record of which issues the scheduler has started.
+CREATE TABLE issue_schedulers (
+    id                  BLOB PRIMARY KEY,
+    remote_project_id   BLOB NOT NULL UNIQUE,
+    project_id          BLOB NOT NULL,
+    enabled             INTEGER NOT NULL DEFAULT 1,
+    ready_status        TEXT NOT NULL,
+    done_statuses       TEXT NOT NULL DEFAULT '["Done"]',
+    executor_profile_id TEXT NOT NULL,
+    repos               TEXT NOT NULL DEFAULT '[]',
+    max_concurrent      INTEGER NOT NULL DEFAULT 1,
+    created_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at          TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE TABLE issue_scheduler_runs (
+    id           BLOB PRIMARY KEY,
+    scheduler_id BLOB NOT NULL,
+    issue_id     BLOB NOT NULL,
+    workspace_id BLOB,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (scheduler_id) REFERENCES issue_schedulers(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL,
+    UNIQUE (scheduler_id, issue_id)
+);
+
+CREATE INDEX idx_issue_scheduler_runs_workspace_id ON issue_scheduler_runs(workspace_id);
@@ -0,0 +1,5 @@
+-- Whether creating a PR first runs a review agent over the branch:
+-- 'off', 'warn' (include findings in the PR description) or 'block'
+-- (refuse while there are high-severity findings)
+ALTER TABLE repos ADD COLUMN pr_review_gate TEXT NOT NULL DEFAULT 'off'
+    CHECK (pr_review_gate IN ('off', 'warn', 'block'));
@@ -0,0 +1,2 @@
+-- JSON sandbox settings for the project's executions (see executors::sandbox::SandboxConfig)
+ALTER TABLE projects ADD COLUMN sandbox_config TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,2 @@
+-- JSON container settings for the repo's executions (see executors::oci::ContainerConfig)
+ALTER TABLE repos ADD COLUMN container_config TEXT NOT NULL DEFAULT '{}';
@@ -0,0 +1,2 @@
+-- JSON egress policy for the project's executions (see executors::egress::EgressPolicy)
+ALTER TABLE projects ADD COLUMN egress_policy TEXT NOT NULL DEFAULT '{}';
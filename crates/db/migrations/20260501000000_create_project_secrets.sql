@@ -0,0 +1,12 @@
+-- Per-project secrets, encrypted with the local master key and referenced
+-- from environment variables as ${secret:NAME}.
+CREATE TABLE project_secrets (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL,
+    name            TEXT NOT NULL,
+    encrypted_value TEXT NOT NULL,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project_id, name)
+);
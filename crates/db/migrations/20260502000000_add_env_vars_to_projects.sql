@@ -0,0 +1,3 @@
+-- JSON object of environment variables set for all of the project's executions,
+-- including scripts. Values may reference project secrets as ${secret:NAME}.
+ALTER TABLE projects ADD COLUMN env_vars TEXT NOT NULL DEFAULT '{}';
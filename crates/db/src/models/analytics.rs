@@ -0,0 +1,80 @@
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+/// What happened to a workspace, as far as the local database knows.
+#[derive(Debug, Clone)]
+pub struct WorkspaceOutcome {
+    pub id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub archived: bool,
+    /// Executor of the workspace's first session.
+    pub executor: Option<String>,
+    /// Coding agent runs, the initial run included.
+    pub coding_agent_runs: i64,
+    /// Merged directly or through a pull request.
+    pub merged: bool,
+}
+
+/// A finished coding agent run.
+#[derive(Debug, Clone)]
+pub struct AgentRun {
+    pub workspace_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl WorkspaceOutcome {
+    pub async fn find_created_since(
+        pool: &SqlitePool,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WorkspaceOutcome,
+            r#"SELECT w.id AS "id!: Uuid",
+                      w.created_at AS "created_at!: DateTime<Utc>",
+                      w.archived AS "archived!: bool",
+                      (SELECT s.executor FROM sessions s
+                        WHERE s.workspace_id = w.id
+                        ORDER BY s.created_at ASC
+                        LIMIT 1) AS "executor: String",
+                      (SELECT COUNT(*) FROM execution_processes ep
+                        JOIN sessions s ON s.id = ep.session_id
+                        WHERE s.workspace_id = w.id
+                          AND ep.run_reason = 'codingagent'
+                          AND ep.dropped = FALSE) AS "coding_agent_runs!: i64",
+                      EXISTS (SELECT 1 FROM merges m
+                        WHERE m.workspace_id = w.id
+                          AND (m.merge_type = 'direct' OR m.pr_status = 'merged')) AS "merged!: bool"
+               FROM workspaces w
+               WHERE w.created_at >= $1"#,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+impl AgentRun {
+    /// Finished coding agent runs in workspaces created since `since`.
+    pub async fn find_for_workspaces_created_since(
+        pool: &SqlitePool,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AgentRun,
+            r#"SELECT s.workspace_id AS "workspace_id!: Uuid",
+                      ep.started_at AS "started_at!: DateTime<Utc>",
+                      ep.completed_at AS "completed_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               WHERE ep.run_reason = 'codingagent'
+                 AND ep.completed_at IS NOT NULL
+                 AND w.created_at >= $1"#,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -418,6 +418,31 @@ impl ExecutionProcess {
         .await
     }
 
+    /// When the workspace's last coding agent run before `boundary_process_id`
+    /// finished, across all sessions.
+    pub async fn find_prev_coding_agent_completed_at(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+        boundary_process_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        let result = sqlx::query_scalar!(
+            r#"SELECT ep.completed_at as "completed_at?: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+              WHERE s.workspace_id = $1
+                AND ep.run_reason = 'codingagent'
+                AND ep.dropped = FALSE
+                AND ep.created_at < (SELECT created_at FROM execution_processes WHERE id = $2)
+              ORDER BY ep.created_at DESC
+              LIMIT 1"#,
+            workspace_id,
+            boundary_process_id
+        )
+        .fetch_optional(pool)
+        .await?;
+        Ok(result.flatten())
+    }
+
     /// Create a new execution process
     ///
     /// Note: We intentionally avoid using a transaction here. SQLite update
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use ts_rs::TS;
-use utils::log_msg::LogMsg;
+use utils::{log_msg::LogMsg, msg_store::SequencedLogMsg};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
@@ -11,6 +11,16 @@ pub struct ExecutionProcessLogs {
     pub logs: String, // JSONL format
     pub byte_size: i64,
     pub inserted_at: DateTime<Utc>,
+    /// MsgStore sequence number of the line; `None` for legacy rows.
+    pub seq: Option<i64>,
+}
+
+/// A stored log row addressed by its SQLite rowid, as the table has no
+/// primary key
+#[derive(Debug, Clone, FromRow)]
+pub struct StoredLogRow {
+    pub rowid: i64,
+    pub logs: String,
 }
 
 impl ExecutionProcessLogs {
@@ -25,10 +35,11 @@ impl ExecutionProcessLogs {
                 execution_id as "execution_id!: Uuid",
                 logs,
                 byte_size,
-                inserted_at as "inserted_at!: DateTime<Utc>"
-               FROM execution_process_logs 
+                inserted_at as "inserted_at!: DateTime<Utc>",
+                seq
+               FROM execution_process_logs
                WHERE execution_id = $1
-               ORDER BY inserted_at ASC"#,
+               ORDER BY inserted_at ASC, seq ASC"#,
             execution_id
         )
         .fetch_all(pool)
@@ -47,18 +58,165 @@ impl ExecutionProcessLogs {
         Ok(messages)
     }
 
+    /// Parse JSONL logs back into sequenced messages. Legacy rows without a
+    /// stored seq are numbered by their position.
+    pub fn parse_sequenced_logs(
+        records: &[Self],
+    ) -> Result<Vec<SequencedLogMsg>, serde_json::Error> {
+        let mut messages = Vec::new();
+        for record in records {
+            for line in record.logs.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let msg: LogMsg = serde_json::from_str(line)?;
+                let seq = record
+                    .seq
+                    .map(|seq| seq as u64)
+                    .unwrap_or(messages.len() as u64);
+                messages.push(SequencedLogMsg { seq, msg });
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Normalized conversation patches persisted with the seq they had in the
+    /// live store; once the run finished, a single patch building the final
+    /// conversation. Empty for executions recorded before patches were
+    /// persisted, which have to be re-normalized from their raw output.
+    pub fn parse_sequenced_patches(
+        records: &[Self],
+    ) -> Result<Vec<SequencedLogMsg>, serde_json::Error> {
+        let mut patches = Vec::new();
+        for record in records {
+            let Some(seq) = record.seq else {
+                continue;
+            };
+            for line in record.logs.lines() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let msg: LogMsg = serde_json::from_str(line)?;
+                if matches!(msg, LogMsg::JsonPatch(_)) {
+                    patches.push(SequencedLogMsg {
+                        seq: seq as u64,
+                        msg,
+                    });
+                }
+            }
+        }
+        Ok(patches)
+    }
+
     /// Append a JSONL line to the logs for an execution process
     pub async fn append_log_line(
         pool: &SqlitePool,
         execution_id: Uuid,
         jsonl_line: &str,
+        seq: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        let byte_size = jsonl_line.len() as i64;
+        sqlx::query!(
+            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at, seq)
+               VALUES ($1, $2, $3, datetime('now', 'subsec'), $4)"#,
+            execution_id,
+            jsonl_line,
+            byte_size,
+            seq
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replace the conversation patches persisted during a run with a single
+    /// patch, e.g. one that rebuilds the final conversation
+    pub async fn replace_patches(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        jsonl_line: &str,
+        seq: i64,
     ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!(
+            r#"DELETE FROM execution_process_logs
+               WHERE execution_id = $1 AND seq IS NOT NULL AND logs LIKE '{"JsonPatch"%'"#,
+            execution_id
+        )
+        .execute(&mut *tx)
+        .await?;
         let byte_size = jsonl_line.len() as i64;
         sqlx::query!(
-            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at)
-               VALUES ($1, $2, $3, datetime('now', 'subsec'))"#,
+            r#"INSERT INTO execution_process_logs (execution_id, logs, byte_size, inserted_at, seq)
+               VALUES ($1, $2, $3, datetime('now', 'subsec'), $4)"#,
             execution_id,
             jsonl_line,
+            byte_size,
+            seq
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await
+    }
+
+    /// Up to `limit` rows of an execution before `before_rowid` (or its
+    /// newest rows), newest first, for reading logs back from the end
+    pub async fn find_rows_before(
+        pool: &SqlitePool,
+        execution_id: Uuid,
+        before_rowid: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<StoredLogRow>, sqlx::Error> {
+        sqlx::query_as!(
+            StoredLogRow,
+            r#"SELECT rowid as "rowid!: i64", logs
+               FROM execution_process_logs
+               WHERE execution_id = $1 AND ($2 IS NULL OR rowid < $2)
+               ORDER BY rowid DESC
+               LIMIT $3"#,
+            execution_id,
+            before_rowid,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Rows after `after_rowid` in rowid order, for walking the whole table in
+    /// batches
+    pub async fn find_rows_after(
+        pool: &SqlitePool,
+        after_rowid: i64,
+        limit: i64,
+    ) -> Result<Vec<StoredLogRow>, sqlx::Error> {
+        sqlx::query_as!(
+            StoredLogRow,
+            r#"SELECT rowid as "rowid!: i64", logs
+               FROM execution_process_logs
+               WHERE rowid > $1
+               ORDER BY rowid ASC
+               LIMIT $2"#,
+            after_rowid,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the logs of a row, e.g. after redacting them
+    pub async fn update_row_logs(
+        pool: &SqlitePool,
+        rowid: i64,
+        logs: &str,
+    ) -> Result<(), sqlx::Error> {
+        let byte_size = logs.len() as i64;
+        sqlx::query!(
+            r#"UPDATE execution_process_logs
+               SET logs = $2, byte_size = $3
+               WHERE rowid = $1"#,
+            rowid,
+            logs,
             byte_size
         )
         .execute(pool)
@@ -0,0 +1,228 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, types::Json};
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SchedulerRepo {
+    pub repo_id: Uuid,
+    pub target_branch: String,
+}
+
+/// Auto-scheduling configuration for a remote project: ready issues are
+/// started as workspaces under the local `project_id`.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct IssueScheduler {
+    pub id: Uuid,
+    pub remote_project_id: Uuid,
+    pub project_id: Uuid,
+    /// Whether issues in the ready status are picked up. Issues created from
+    /// an auto-start template are started either way.
+    pub enabled: bool,
+    /// Name of the status an issue must be in to be picked up.
+    pub ready_status: String,
+    /// Status names that count a blocker as resolved.
+    #[ts(type = "Array<string>")]
+    pub done_statuses: Json<Vec<String>>,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    #[ts(type = "Array<SchedulerRepo>")]
+    pub repos: Json<Vec<SchedulerRepo>>,
+    pub max_concurrent: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpsertIssueScheduler {
+    pub project_id: Uuid,
+    pub enabled: bool,
+    pub ready_status: String,
+    pub done_statuses: Vec<String>,
+    pub executor_profile_id: ExecutorProfileId,
+    pub repos: Vec<SchedulerRepo>,
+    pub max_concurrent: i64,
+}
+
+/// An issue the scheduler has started a workspace for. `workspace_id` is
+/// cleared if the workspace is deleted; the issue is still not rescheduled.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct IssueSchedulerRun {
+    pub id: Uuid,
+    pub scheduler_id: Uuid,
+    pub issue_id: Uuid,
+    pub workspace_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A scheduled issue and how far its workspace has got
+#[derive(Debug, Clone, FromRow)]
+pub struct IssueSchedulerRunState {
+    pub issue_id: Uuid,
+    /// The workspace still exists and isn't archived
+    pub live: bool,
+    /// The workspace was merged directly or through a merged PR
+    pub merged: bool,
+}
+
+impl IssueScheduler {
+    pub async fn find_by_remote_project_id(
+        pool: &SqlitePool,
+        remote_project_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IssueScheduler,
+            r#"SELECT id as "id!: Uuid",
+                      remote_project_id as "remote_project_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      enabled as "enabled!: bool",
+                      ready_status,
+                      done_statuses as "done_statuses!: Json<Vec<String>>",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      repos as "repos!: Json<Vec<SchedulerRepo>>",
+                      max_concurrent,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM issue_schedulers
+               WHERE remote_project_id = $1"#,
+            remote_project_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            IssueScheduler,
+            r#"SELECT id as "id!: Uuid",
+                      remote_project_id as "remote_project_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      enabled as "enabled!: bool",
+                      ready_status,
+                      done_statuses as "done_statuses!: Json<Vec<String>>",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      repos as "repos!: Json<Vec<SchedulerRepo>>",
+                      max_concurrent,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM issue_schedulers
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn upsert(
+        pool: &SqlitePool,
+        remote_project_id: Uuid,
+        data: &UpsertIssueScheduler,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let done_statuses = Json(&data.done_statuses);
+        let executor_profile_id = Json(&data.executor_profile_id);
+        let repos = Json(&data.repos);
+        sqlx::query_as!(
+            IssueScheduler,
+            r#"INSERT INTO issue_schedulers (
+                    id, remote_project_id, project_id, enabled, ready_status,
+                    done_statuses, executor_profile_id, repos, max_concurrent
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               ON CONFLICT(remote_project_id) DO UPDATE SET
+                    project_id = excluded.project_id,
+                    enabled = excluded.enabled,
+                    ready_status = excluded.ready_status,
+                    done_statuses = excluded.done_statuses,
+                    executor_profile_id = excluded.executor_profile_id,
+                    repos = excluded.repos,
+                    max_concurrent = excluded.max_concurrent,
+                    updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                      remote_project_id as "remote_project_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      enabled as "enabled!: bool",
+                      ready_status,
+                      done_statuses as "done_statuses!: Json<Vec<String>>",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      repos as "repos!: Json<Vec<SchedulerRepo>>",
+                      max_concurrent,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            remote_project_id,
+            data.project_id,
+            data.enabled,
+            data.ready_status,
+            done_statuses,
+            executor_profile_id,
+            repos,
+            data.max_concurrent
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete_by_remote_project_id(
+        pool: &SqlitePool,
+        remote_project_id: Uuid,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM issue_schedulers WHERE remote_project_id = $1",
+            remote_project_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl IssueSchedulerRun {
+    pub async fn find_states_by_scheduler_id(
+        pool: &SqlitePool,
+        scheduler_id: Uuid,
+    ) -> Result<Vec<IssueSchedulerRunState>, sqlx::Error> {
+        sqlx::query_as!(
+            IssueSchedulerRunState,
+            r#"SELECT r.issue_id as "issue_id!: Uuid",
+                      (w.id IS NOT NULL AND w.archived = 0) as "live!: bool",
+                      EXISTS (
+                          SELECT 1 FROM merges m
+                          WHERE m.workspace_id = r.workspace_id
+                            AND (m.merge_type = 'direct' OR m.pr_status = 'merged')
+                      ) as "merged!: bool"
+               FROM issue_scheduler_runs r
+               LEFT JOIN workspaces w ON w.id = r.workspace_id
+               WHERE r.scheduler_id = $1
+               ORDER BY r.created_at ASC"#,
+            scheduler_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        scheduler_id: Uuid,
+        issue_id: Uuid,
+        workspace_id: Uuid,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            IssueSchedulerRun,
+            r#"INSERT INTO issue_scheduler_runs (id, scheduler_id, issue_id, workspace_id)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                      scheduler_id as "scheduler_id!: Uuid",
+                      issue_id as "issue_id!: Uuid",
+                      workspace_id as "workspace_id?: Uuid",
+                      created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            scheduler_id,
+            issue_id,
+            workspace_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+}
@@ -1,12 +1,15 @@
+pub mod analytics;
 pub mod coding_agent_turn;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
 pub mod image;
+pub mod issue_scheduler;
 pub mod merge;
 pub mod migration_state;
 pub mod project;
 pub mod project_repo;
+pub mod project_secret;
 pub mod repo;
 pub mod scratch;
 pub mod session;
@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
+use executors::{egress::EgressPolicy, sandbox::SandboxConfig};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use thiserror::Error;
@@ -23,6 +26,14 @@ pub struct Project {
     pub name: String,
     pub default_agent_working_dir: Option<String>,
     pub remote_project_id: Option<Uuid>,
+    #[ts(type = "SandboxConfig")]
+    pub sandbox_config: sqlx::types::Json<SandboxConfig>,
+    #[ts(type = "EgressPolicy")]
+    pub egress_policy: sqlx::types::Json<EgressPolicy>,
+    /// Environment variables for every execution in the project, scripts
+    /// included. Values may reference secrets as `${secret:NAME}`.
+    #[ts(type = "Record<string, string>")]
+    pub env_vars: sqlx::types::Json<HashMap<String, String>>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -38,6 +49,12 @@ pub struct CreateProject {
 #[derive(Debug, Deserialize, TS)]
 pub struct UpdateProject {
     pub name: Option<String>,
+    #[ts(optional)]
+    pub sandbox_config: Option<SandboxConfig>,
+    #[ts(optional)]
+    pub egress_policy: Option<EgressPolicy>,
+    #[ts(optional)]
+    pub env_vars: Option<HashMap<String, String>>,
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -71,6 +88,9 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      sandbox_config as "sandbox_config!: sqlx::types::Json<SandboxConfig>",
+                      egress_policy as "egress_policy!: sqlx::types::Json<EgressPolicy>",
+                      env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -88,6 +108,9 @@ impl Project {
             SELECT p.id as "id!: Uuid", p.name,
                    p.default_agent_working_dir,
                    p.remote_project_id as "remote_project_id: Uuid",
+                   p.sandbox_config as "sandbox_config!: sqlx::types::Json<SandboxConfig>",
+                   p.egress_policy as "egress_policy!: sqlx::types::Json<EgressPolicy>",
+                   p.env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
                    p.created_at as "created_at!: DateTime<Utc>", p.updated_at as "updated_at!: DateTime<Utc>"
             FROM projects p
             WHERE p.id IN (
@@ -111,6 +134,9 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      sandbox_config as "sandbox_config!: sqlx::types::Json<SandboxConfig>",
+                      egress_policy as "egress_policy!: sqlx::types::Json<EgressPolicy>",
+                      env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -128,6 +154,9 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      sandbox_config as "sandbox_config!: sqlx::types::Json<SandboxConfig>",
+                      egress_policy as "egress_policy!: sqlx::types::Json<EgressPolicy>",
+                      env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -148,6 +177,9 @@ impl Project {
                       name,
                       default_agent_working_dir,
                       remote_project_id as "remote_project_id: Uuid",
+                      sandbox_config as "sandbox_config!: sqlx::types::Json<SandboxConfig>",
+                      egress_policy as "egress_policy!: sqlx::types::Json<EgressPolicy>",
+                      env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM projects
@@ -176,6 +208,9 @@ impl Project {
                           name,
                           default_agent_working_dir,
                           remote_project_id as "remote_project_id: Uuid",
+                          sandbox_config as "sandbox_config!: sqlx::types::Json<SandboxConfig>",
+                          egress_policy as "egress_policy!: sqlx::types::Json<EgressPolicy>",
+                          env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
                           created_at as "created_at!: DateTime<Utc>",
                           updated_at as "updated_at!: DateTime<Utc>""#,
             project_id,
@@ -195,20 +230,39 @@ impl Project {
             .ok_or(sqlx::Error::RowNotFound)?;
 
         let name = payload.name.clone().unwrap_or(existing.name);
+        let sandbox_config = sqlx::types::Json(
+            payload
+                .sandbox_config
+                .clone()
+                .unwrap_or(existing.sandbox_config.0),
+        );
+        let egress_policy = sqlx::types::Json(
+            payload
+                .egress_policy
+                .clone()
+                .unwrap_or(existing.egress_policy.0),
+        );
+        let env_vars = sqlx::types::Json(payload.env_vars.clone().unwrap_or(existing.env_vars.0));
 
         sqlx::query_as!(
             Project,
             r#"UPDATE projects
-               SET name = $2
+               SET name = $2, sandbox_config = $3, egress_policy = $4, env_vars = $5
                WHERE id = $1
                RETURNING id as "id!: Uuid",
                          name,
                          default_agent_working_dir,
                          remote_project_id as "remote_project_id: Uuid",
+                         sandbox_config as "sandbox_config!: sqlx::types::Json<SandboxConfig>",
+                         egress_policy as "egress_policy!: sqlx::types::Json<EgressPolicy>",
+                         env_vars as "env_vars!: sqlx::types::Json<HashMap<String, String>>",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
             name,
+            sandbox_config,
+            egress_policy,
+            env_vars,
         )
         .fetch_one(pool)
         .await
@@ -1,13 +1,14 @@
 use std::path::Path;
 
 use chrono::{DateTime, Utc};
+use executors::oci::ContainerConfig;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::repo::Repo;
+use super::repo::{PrReviewGate, Repo};
 
 #[derive(Debug, Error)]
 pub enum ProjectRepoError {
@@ -85,6 +86,8 @@ impl ProjectRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.pr_review_gate as "pr_review_gate!: PrReviewGate",
+                      r.container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A project secret. The value is encrypted with the local master key and
+/// never leaves the server; environment variables reference it as
+/// `${secret:NAME}`.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ProjectSecret {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub encrypted_value: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpsertProjectSecret {
+    pub name: String,
+    pub value: String,
+}
+
+impl ProjectSecret {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ProjectSecret,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      encrypted_value,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM project_secrets
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Create the secret, or replace its value if the project already has one
+    /// with this name
+    pub async fn upsert(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        encrypted_value: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ProjectSecret,
+            r#"INSERT INTO project_secrets (id, project_id, name, encrypted_value)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT(project_id, name) DO UPDATE SET
+                    encrypted_value = excluded.encrypted_value,
+                    updated_at = datetime('now', 'subsec')
+               RETURNING id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      encrypted_value,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            name,
+            encrypted_value
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            "DELETE FROM project_secrets WHERE project_id = $1 AND name = $2",
+            project_id,
+            name
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
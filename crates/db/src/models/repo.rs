@@ -1,9 +1,10 @@
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
+use executors::oci::ContainerConfig;
 use serde::{Deserialize, Serialize};
 use serde_with::rust::double_option;
-use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -16,6 +17,19 @@ pub enum RepoError {
     NotFound,
 }
 
+/// Whether creating a PR first runs a review agent over the branch
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
+#[sqlx(type_name = "pr_review_gate", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum PrReviewGate {
+    #[default]
+    Off,
+    /// Create the PR with the findings in its description
+    Warn,
+    /// Refuse to create the PR while there are high-severity findings
+    Block,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Repo {
     pub id: Uuid,
@@ -30,6 +44,9 @@ pub struct Repo {
     pub dev_server_script: Option<String>,
     pub default_target_branch: Option<String>,
     pub default_working_dir: Option<String>,
+    pub pr_review_gate: PrReviewGate,
+    #[ts(type = "ContainerConfig")]
+    pub container_config: sqlx::types::Json<ContainerConfig>,
     #[ts(type = "Date")]
     pub created_at: DateTime<Utc>,
     #[ts(type = "Date")]
@@ -110,6 +127,14 @@ pub struct UpdateRepo {
     )]
     #[ts(optional, type = "string | null")]
     pub default_working_dir: Option<Option<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub pr_review_gate: Option<PrReviewGate>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub container_config: Option<ContainerConfig>,
 }
 
 impl Repo {
@@ -130,6 +155,8 @@ impl Repo {
                       dev_server_script,
                       default_target_branch,
                       default_working_dir,
+                      pr_review_gate as "pr_review_gate!: PrReviewGate",
+                      container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -171,6 +198,8 @@ impl Repo {
                       dev_server_script,
                       default_target_branch,
                       default_working_dir,
+                      pr_review_gate as "pr_review_gate!: PrReviewGate",
+                      container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -229,6 +258,8 @@ impl Repo {
                          dev_server_script,
                          default_target_branch,
                          default_working_dir,
+                         pr_review_gate as "pr_review_gate!: PrReviewGate",
+                         container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             id,
@@ -266,6 +297,8 @@ impl Repo {
                       dev_server_script,
                       default_target_branch,
                       default_working_dir,
+                      pr_review_gate as "pr_review_gate!: PrReviewGate",
+                      container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                       created_at as "created_at!: DateTime<Utc>",
                       updated_at as "updated_at!: DateTime<Utc>"
                FROM repos
@@ -292,6 +325,8 @@ impl Repo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.pr_review_gate as "pr_review_gate!: PrReviewGate",
+                      r.container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -354,6 +389,13 @@ impl Repo {
             None => existing.default_working_dir,
             Some(v) => v.clone(),
         };
+        let pr_review_gate = payload.pr_review_gate.unwrap_or(existing.pr_review_gate);
+        let container_config = sqlx::types::Json(
+            payload
+                .container_config
+                .clone()
+                .unwrap_or(existing.container_config.0),
+        );
 
         sqlx::query_as!(
             Repo,
@@ -367,8 +409,10 @@ impl Repo {
                    dev_server_script = $7,
                    default_target_branch = $8,
                    default_working_dir = $9,
+                   pr_review_gate = $10,
+                   container_config = $11,
                    updated_at = datetime('now', 'subsec')
-               WHERE id = $10
+               WHERE id = $12
                RETURNING id as "id!: Uuid",
                          path,
                          name,
@@ -381,6 +425,8 @@ impl Repo {
                          dev_server_script,
                          default_target_branch,
                          default_working_dir,
+                         pr_review_gate as "pr_review_gate!: PrReviewGate",
+                         container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                          created_at as "created_at!: DateTime<Utc>",
                          updated_at as "updated_at!: DateTime<Utc>""#,
             display_name,
@@ -392,6 +438,8 @@ impl Repo {
             dev_server_script,
             default_target_branch,
             default_working_dir,
+            pr_review_gate,
+            container_config,
             id
         )
         .fetch_one(pool)
@@ -1,12 +1,13 @@
 use std::path::PathBuf;
 
 use chrono::{DateTime, Utc};
+use executors::oci::ContainerConfig;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::repo::Repo;
+use super::repo::{PrReviewGate, Repo};
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct WorkspaceRepo {
@@ -123,6 +124,8 @@ impl WorkspaceRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.pr_review_gate as "pr_review_gate!: PrReviewGate",
+                      r.container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
@@ -152,6 +155,8 @@ impl WorkspaceRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.pr_review_gate as "pr_review_gate!: PrReviewGate",
+                      r.container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>",
                       wr.target_branch
@@ -180,6 +185,8 @@ impl WorkspaceRepo {
                     dev_server_script: row.dev_server_script,
                     default_target_branch: row.default_target_branch,
                     default_working_dir: row.default_working_dir,
+                    pr_review_gate: row.pr_review_gate,
+                    container_config: row.container_config,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
                 },
@@ -269,6 +276,8 @@ impl WorkspaceRepo {
                       r.dev_server_script,
                       r.default_target_branch,
                       r.default_working_dir,
+                      r.pr_review_gate as "pr_review_gate!: PrReviewGate",
+                      r.container_config as "container_config!: sqlx::types::Json<ContainerConfig>",
                       r.created_at as "created_at!: DateTime<Utc>",
                       r.updated_at as "updated_at!: DateTime<Utc>"
                FROM repos r
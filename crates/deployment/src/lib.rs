@@ -20,7 +20,7 @@ use services::services::{
     analytics::AnalyticsService,
     approvals::Approvals,
     auth::AuthContext,
-    config::{Config, ConfigError},
+    config::{Config, ConfigError, save_config_to_file},
     container::{ContainerError, ContainerService},
     events::{EventError, EventService},
     file_search::FileSearchCache,
@@ -36,7 +36,7 @@ use services::services::{
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::sync::RwLock;
-use utils::sentry as sentry_utils;
+use utils::{assets::config_path, redaction::Redactor, sentry as sentry_utils};
 
 #[derive(Debug, Clone, Copy, Error)]
 #[error("Remote client not configured")]
@@ -132,6 +132,33 @@ pub trait Deployment: Clone + Send + Sync + 'static {
         }
     }
 
+    /// Redact stored logs with the current redaction settings, then record
+    /// that they were, unless the settings changed again in the meantime
+    async fn scrub_stored_logs(&self) {
+        let log_redaction = self.config().read().await.log_redaction.clone();
+        let rules_hash = log_redaction.rules_hash();
+        let redactor = Redactor::new(&log_redaction);
+        match self.container().scrub_stored_logs(&redactor).await {
+            Ok(scrubbed) => tracing::info!("Redacted secrets from {scrubbed} stored log rows"),
+            Err(e) => {
+                tracing::warn!("Failed to redact stored logs: {}", e);
+                return;
+            }
+        }
+
+        let mut config = self.config().write().await;
+        if config.log_redaction.rules_hash() != rules_hash {
+            return;
+        }
+        let mut new_config = config.clone();
+        new_config.log_redaction.stored_logs_scrubbed_with = Some(rules_hash);
+        if let Err(e) = save_config_to_file(&new_config, &config_path()).await {
+            tracing::warn!("Failed to save config after redacting stored logs: {}", e);
+            return;
+        }
+        *config = new_config;
+    }
+
     /// Trigger background auto-setup of default projects for new users
     async fn trigger_auto_project_setup(&self) {
         // soft timeout to give the filesystem search a chance to complete
@@ -1,7 +1,10 @@
 use std::{path::Path, sync::Arc};
 
 use async_trait::async_trait;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -18,6 +21,9 @@ pub struct RepoReviewContext {
     pub repo_id: Uuid,
     pub repo_name: String,
     pub base_commit: String,
+    /// HEAD when the review started, the end of the reviewed range
+    #[serde(default)]
+    pub head_commit: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -46,6 +52,99 @@ impl ReviewRequest {
     }
 }
 
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    TS,
+    JsonSchema,
+    Display,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum FindingSeverity {
+    Low,
+    Medium,
+    High,
+}
+
+/// A problem a review agent found in the changes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReviewFinding {
+    pub severity: FindingSeverity,
+    /// Path relative to the repository root
+    pub file: String,
+    /// Line in the new version of the file, if the finding is about one line
+    #[serde(default)]
+    pub line: Option<u32>,
+    pub message: String,
+}
+
+/// The structured result a review agent is asked to end its review with
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReviewFindings {
+    pub findings: Vec<ReviewFinding>,
+}
+
+#[derive(Debug, Error)]
+pub enum ReviewFindingsError {
+    #[error("the review did not end with a JSON object")]
+    MissingJson,
+    #[error("the review findings do not match the schema: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+}
+
+impl ReviewFindings {
+    /// JSON schema the agent's final message must match
+    pub fn json_schema() -> String {
+        let schema = schemars::schema_for!(ReviewFindings);
+        serde_json::to_string_pretty(&schema).unwrap_or_default()
+    }
+
+    /// Parse the findings from an agent's final message. Agents tend to wrap
+    /// the JSON in a code fence or a sentence of prose, so the last fenced
+    /// block or outermost braces are used when the message itself isn't JSON.
+    pub fn parse(message: &str) -> Result<Self, ReviewFindingsError> {
+        let message = message.trim();
+        if message.starts_with('{') {
+            return Ok(serde_json::from_str(message)?);
+        }
+
+        let fenced = message
+            .rsplit("```")
+            .nth(1)
+            .map(|block| block.trim_start_matches("json").trim())
+            .filter(|block| block.starts_with('{'));
+
+        let json = match fenced {
+            Some(block) => block,
+            None => {
+                let start = message.find('{').ok_or(ReviewFindingsError::MissingJson)?;
+                let end = message.rfind('}').ok_or(ReviewFindingsError::MissingJson)?;
+                message
+                    .get(start..=end)
+                    .ok_or(ReviewFindingsError::MissingJson)?
+            }
+        };
+
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn has_high_severity(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|finding| finding.severity == FindingSeverity::High)
+    }
+}
+
 #[async_trait]
 impl Executable for ReviewRequest {
     async fn spawn(
@@ -79,3 +178,35 @@ impl Executable for ReviewRequest {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_findings() {
+        let message = r#"I found one problem.
+
+```json
+{"findings": [{"severity": "high", "file": "src/lib.rs", "line": 12, "message": "Panics on empty input"}]}
+```"#;
+        let findings = ReviewFindings::parse(message).unwrap();
+        assert_eq!(findings.findings.len(), 1);
+        assert_eq!(findings.findings[0].line, Some(12));
+        assert!(findings.has_high_severity());
+
+        let findings = ReviewFindings::parse(r#"{"findings": []}"#).unwrap();
+        assert!(findings.findings.is_empty());
+
+        assert!(matches!(
+            ReviewFindings::parse("Looks good to me!"),
+            Err(ReviewFindingsError::MissingJson)
+        ));
+        assert!(matches!(
+            ReviewFindings::parse(
+                r#"{"findings": [{"severity": "critical", "file": "a", "message": "b"}]}"#
+            ),
+            Err(ReviewFindingsError::InvalidJson(_))
+        ));
+    }
+}
@@ -3,7 +3,6 @@ use std::{path::Path, sync::Arc};
 use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
 use serde::{Deserialize, Serialize};
-use tokio::process::Command;
 use ts_rs::TS;
 use workspace_utils::shell::get_shell_command;
 
@@ -53,8 +52,12 @@ impl Executable for ScriptRequest {
             None => current_dir.to_path_buf(),
         };
 
-        let (shell_cmd, shell_arg) = get_shell_command();
-        let mut command = Command::new(shell_cmd);
+        let (mut shell_cmd, shell_arg) = get_shell_command();
+        // The user's shell is a host path; images are only expected to have sh
+        if env.container.is_some() {
+            shell_cmd = "sh".to_string();
+        }
+        let mut command = env.command(shell_cmd, &effective_dir);
         command
             .kill_on_drop(true)
             .stdin(std::process::Stdio::null())
@@ -72,3 +75,49 @@ impl Executable for ScriptRequest {
         Ok(child.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        approvals::NoopExecutorApprovalService,
+        env::{RepoContext, Secrets},
+    };
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_script_resolves_project_secrets() {
+        let project_vars = HashMap::from([(
+            "NPM_CONFIG_TOKEN".to_string(),
+            "token=${secret:NPM_TOKEN}".to_string(),
+        )]);
+        let mut env = ExecutionEnv::new(RepoContext::default(), false, String::new()).with_secrets(
+            Secrets::new(HashMap::from([(
+                "NPM_TOKEN".to_string(),
+                "s3cret".to_string(),
+            )])),
+        );
+        env.merge(&project_vars);
+
+        let request = ScriptRequest {
+            script: "printf '%s' \"$NPM_CONFIG_TOKEN\"".to_string(),
+            language: ScriptRequestLanguage::Bash,
+            context: ScriptContext::SetupScript,
+            working_dir: None,
+        };
+        let spawned = request
+            .spawn(
+                &std::env::temp_dir(),
+                Arc::new(NoopExecutorApprovalService),
+                &env,
+            )
+            .await
+            .unwrap();
+        let output = spawned.child.wait_with_output().await.unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "token=s3cret");
+    }
+}
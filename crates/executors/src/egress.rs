@@ -0,0 +1,396 @@
+//! Egress control for executions: a local HTTP proxy that only lets requests
+//! through to the hosts a project's policy allows.
+//!
+//! The proxy is injected through `HTTP_PROXY`/`HTTPS_PROXY`, so it only sees
+//! traffic from tools that honour those variables. Denied requests get a
+//! `403` and are logged to the execution's [`MsgStore`]. The proxy listens on
+//! the host's loopback interface, or where the execution's container can reach
+//! the host. Sandboxed executions without network can't reach it, so their
+//! proxied requests fail rather than bypass it.
+
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+use ts_rs::TS;
+use workspace_utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+use crate::{env::ExecutionEnv, oci::HostEndpoint};
+
+/// Lines on stderr starting with this are proxy notices rather than process
+/// output, and are normalized into system messages
+pub const EGRESS_LOG_PREFIX: &str = "[egress] ";
+
+/// Requests with larger heads are rejected
+const MAX_HEAD_LEN: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EgressMode {
+    #[default]
+    AllowAll,
+    DenyAll,
+    Allowlist,
+}
+
+/// Per-project egress policy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
+#[serde(default)]
+pub struct EgressPolicy {
+    pub mode: EgressMode,
+    /// Hosts reachable in `allowlist` mode. An entry also allows its
+    /// subdomains, so `github.com` allows `api.github.com`.
+    pub allowed_hosts: Vec<String>,
+}
+
+impl EgressPolicy {
+    /// Whether executions need the proxy at all
+    pub fn restricted(&self) -> bool {
+        self.mode != EgressMode::AllowAll
+    }
+
+    pub fn allows(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        match self.mode {
+            EgressMode::AllowAll => true,
+            EgressMode::DenyAll => false,
+            EgressMode::Allowlist => self.allowed_hosts.iter().any(|allowed| {
+                let allowed = allowed
+                    .trim()
+                    .trim_start_matches("*.")
+                    .trim_end_matches('.')
+                    .to_ascii_lowercase();
+                !allowed.is_empty()
+                    && (host == allowed
+                        || host
+                            .strip_suffix(&allowed)
+                            .is_some_and(|rest| rest.ends_with('.')))
+            }),
+        }
+    }
+}
+
+/// A running proxy for one execution. It stops once the execution's
+/// [`MsgStore`] is finished.
+#[derive(Debug, Clone)]
+pub struct EgressProxy {
+    /// `host:port` the execution reaches the proxy at
+    authority: String,
+}
+
+impl EgressProxy {
+    /// Start a proxy on `endpoint`, or on the loopback interface for
+    /// executions that run directly on the host
+    pub async fn start(
+        policy: EgressPolicy,
+        msg_store: Arc<MsgStore>,
+        endpoint: Option<HostEndpoint>,
+    ) -> std::io::Result<EgressProxy> {
+        let endpoint = endpoint.unwrap_or_else(|| HostEndpoint {
+            bind: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            host: Ipv4Addr::LOCALHOST.to_string(),
+        });
+        let listener = TcpListener::bind((endpoint.bind, 0)).await?;
+        let authority = format!("{}:{}", endpoint.host, listener.local_addr()?.port());
+        let policy = Arc::new(policy);
+        // Subscribe before the process starts so `Finished` can't be missed
+        let mut receiver = msg_store.get_receiver();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => {
+                        let Ok((stream, _)) = accepted else {
+                            continue;
+                        };
+                        let policy = policy.clone();
+                        let msg_store = msg_store.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = handle_connection(stream, &policy, &msg_store).await {
+                                tracing::debug!("Egress proxy connection failed: {e}");
+                            }
+                        });
+                    }
+                    msg = receiver.recv() => match msg {
+                        Ok(LogMsg::Finished) | Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                        _ => {}
+                    },
+                }
+            }
+        });
+
+        Ok(EgressProxy { authority })
+    }
+
+    /// Point the execution's HTTP clients at the proxy. Loopback traffic,
+    /// e.g. to a dev server, bypasses it.
+    pub fn apply_to_env(&self, env: &mut ExecutionEnv) {
+        let url = format!("http://{}", self.authority);
+        for key in ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"] {
+            env.insert(key, &url);
+        }
+        for key in ["NO_PROXY", "no_proxy"] {
+            env.insert(key, "localhost,127.0.0.1,::1");
+        }
+        // Node's built-in fetch ignores the proxy variables otherwise
+        env.insert("NODE_USE_ENV_PROXY", "1");
+    }
+}
+
+/// Where a proxied request goes
+#[derive(Debug, PartialEq, Eq)]
+struct ProxyTarget {
+    host: String,
+    port: u16,
+    /// `CONNECT` tunnel rather than a plain HTTP request
+    tunnel: bool,
+    /// The request head to send upstream, for plain HTTP requests
+    head: Option<String>,
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    policy: &EgressPolicy,
+    msg_store: &MsgStore,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut head = String::new();
+    while !head.ends_with("\r\n\r\n") && !head.ends_with("\n\n") {
+        if reader.read_line(&mut head).await? == 0 || head.len() > MAX_HEAD_LEN {
+            return Ok(());
+        }
+    }
+
+    let Some(target) = parse_request_head(&head) else {
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n")
+            .await?;
+        return Ok(());
+    };
+
+    if !policy.allows(&target.host) {
+        msg_store.push_stderr(format!(
+            "{EGRESS_LOG_PREFIX}Blocked request to {}:{} (not allowed by the project's egress policy)\n",
+            target.host, target.port
+        ));
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+            .await?;
+        return Ok(());
+    }
+
+    let mut upstream = match TcpStream::connect((target.host.as_str(), target.port)).await {
+        Ok(upstream) => upstream,
+        Err(_) => {
+            reader
+                .get_mut()
+                .write_all(
+                    b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                )
+                .await?;
+            return Ok(());
+        }
+    };
+
+    if target.tunnel {
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+            .await?;
+    } else if let Some(head) = &target.head {
+        upstream.write_all(head.as_bytes()).await?;
+    }
+    // Bytes the client sent after the head, e.g. a request body
+    upstream.write_all(reader.buffer()).await?;
+
+    let mut client = reader.into_inner();
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Parse a proxy request head: either `CONNECT host:port` or a plain HTTP
+/// request with an absolute URL, which is rewritten to origin form. Plain
+/// requests are sent with `Connection: close` so a kept-alive connection
+/// can't reach a second host without passing the policy again.
+fn parse_request_head(head: &str) -> Option<ProxyTarget> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let (method, target, version) = (
+        request_line.next()?,
+        request_line.next()?,
+        request_line.next()?,
+    );
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let (host, port) = split_authority(target, 443)?;
+        return Some(ProxyTarget {
+            host,
+            port,
+            tunnel: true,
+            head: None,
+        });
+    }
+
+    let rest = target.strip_prefix("http://")?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let (host, port) = split_authority(authority, 80)?;
+
+    let mut upstream_head = format!("{method} {path} {version}\r\n");
+    for line in lines.filter(|line| !line.is_empty()) {
+        let name = line.split(':').next().unwrap_or_default().trim();
+        if name.eq_ignore_ascii_case("connection")
+            || name.eq_ignore_ascii_case("proxy-connection")
+            || name.eq_ignore_ascii_case("proxy-authorization")
+        {
+            continue;
+        }
+        upstream_head.push_str(line);
+        upstream_head.push_str("\r\n");
+    }
+    upstream_head.push_str("Connection: close\r\n\r\n");
+
+    Some(ProxyTarget {
+        host,
+        port,
+        tunnel: false,
+        head: Some(upstream_head),
+    })
+}
+
+/// Split `host[:port]`, including bracketed IPv6 hosts
+fn split_authority(authority: &str, default_port: u16) -> Option<(String, u16)> {
+    let (host, port) = if let Some(rest) = authority.strip_prefix('[') {
+        let (host, rest) = rest.split_once(']')?;
+        (host, rest.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => default_port,
+    };
+    (!host.is_empty()).then(|| (host.to_string(), port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowlist() {
+        let policy = EgressPolicy {
+            mode: EgressMode::Allowlist,
+            allowed_hosts: vec!["github.com".into(), "*.npmjs.org".into()],
+        };
+        assert!(policy.allows("github.com"));
+        assert!(policy.allows("API.github.com"));
+        assert!(policy.allows("registry.npmjs.org"));
+        assert!(!policy.allows("evilgithub.com"));
+        assert!(!policy.allows("example.com"));
+        assert!(
+            !EgressPolicy {
+                mode: EgressMode::DenyAll,
+                ..policy
+            }
+            .allows("github.com")
+        );
+    }
+
+    /// Open a `CONNECT` tunnel to `127.0.0.1:port` and read the status line
+    async fn connect_through(proxy: &EgressProxy, port: u16) -> (String, BufReader<TcpStream>) {
+        let mut stream = TcpStream::connect(&proxy.authority).await.unwrap();
+        stream
+            .write_all(format!("CONNECT 127.0.0.1:{port} HTTP/1.1\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut status = String::new();
+        reader.read_line(&mut status).await.unwrap();
+        (status, reader)
+    }
+
+    #[tokio::test]
+    async fn test_connect_allowed_and_denied() {
+        // Echo server standing in for the upstream host
+        let upstream = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = upstream.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = upstream.accept().await {
+                tokio::spawn(async move {
+                    let (mut read, mut write) = stream.split();
+                    let _ = tokio::io::copy(&mut read, &mut write).await;
+                });
+            }
+        });
+
+        let allowed = EgressPolicy {
+            mode: EgressMode::Allowlist,
+            allowed_hosts: vec!["127.0.0.1".into()],
+        };
+        let proxy = EgressProxy::start(allowed, Arc::new(MsgStore::new()), None)
+            .await
+            .unwrap();
+        let (status, mut tunnel) = connect_through(&proxy, port).await;
+        assert!(status.starts_with("HTTP/1.1 200"), "{status}");
+        let mut blank = String::new();
+        tunnel.read_line(&mut blank).await.unwrap();
+        tunnel.get_mut().write_all(b"ping\n").await.unwrap();
+        let mut echoed = String::new();
+        tunnel.read_line(&mut echoed).await.unwrap();
+        assert_eq!(echoed, "ping\n");
+
+        let denied = EgressPolicy {
+            mode: EgressMode::Allowlist,
+            allowed_hosts: vec!["example.com".into()],
+        };
+        let store = Arc::new(MsgStore::new());
+        let proxy = EgressProxy::start(denied, store.clone(), None)
+            .await
+            .unwrap();
+        let (status, _) = connect_through(&proxy, port).await;
+        assert!(status.starts_with("HTTP/1.1 403"), "{status}");
+        assert!(store.get_history().iter().any(|msg| matches!(
+            msg,
+            LogMsg::Stderr(line) if line.starts_with(EGRESS_LOG_PREFIX)
+        )));
+    }
+
+    #[test]
+    fn test_parse_request_head() {
+        let target = parse_request_head("CONNECT api.github.com:443 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(target.host, "api.github.com");
+        assert_eq!(target.port, 443);
+        assert!(target.tunnel);
+
+        let target = parse_request_head(
+            "GET http://example.com:8080/a?b HTTP/1.1\r\nHost: example.com\r\nProxy-Connection: keep-alive\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(target.host, "example.com");
+        assert_eq!(target.port, 8080);
+        assert_eq!(
+            target.head.as_deref(),
+            Some("GET /a?b HTTP/1.1\r\nHost: example.com\r\nConnection: close\r\n\r\n")
+        );
+
+        let target = parse_request_head("CONNECT [::1]:8443 HTTP/1.1\r\n\r\n").unwrap();
+        assert_eq!(target.host, "::1");
+        assert_eq!(target.port, 8443);
+
+        assert!(parse_request_head("GET /relative HTTP/1.1\r\n\r\n").is_none());
+    }
+}
@@ -1,9 +1,17 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    ffi::OsStr,
+    fmt,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
 
 use git::GitService;
+use regex::{Captures, Regex};
 use tokio::process::Command;
 
-use crate::command::CmdOverrides;
+use crate::{command::CmdOverrides, oci::OciContainer, sandbox::Sandbox};
 
 /// Repository context for executor operations
 #[derive(Debug, Clone, Default)]
@@ -74,6 +82,42 @@ impl RepoContext {
     }
 }
 
+/// `${secret:NAME}` in an environment variable value
+static SECRET_REFERENCE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{secret:([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+
+/// Secret values by name. `Debug` only shows the names.
+#[derive(Clone, Default)]
+pub struct Secrets(HashMap<String, String>);
+
+impl Secrets {
+    pub fn new(secrets: HashMap<String, String>) -> Self {
+        Self(secrets)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &String> {
+        self.0.values()
+    }
+
+    /// Substitute the secret references in `value`. References to unknown
+    /// secrets are left as they are.
+    pub fn resolve<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        SECRET_REFERENCE.replace_all(value, |caps: &Captures| match self.0.get(&caps[1]) {
+            Some(secret) => secret.clone(),
+            None => {
+                tracing::warn!("Environment references unknown secret {}", &caps[1]);
+                caps[0].to_string()
+            }
+        })
+    }
+}
+
+impl fmt::Debug for Secrets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
 /// Environment variables to inject into executor processes
 #[derive(Debug, Clone)]
 pub struct ExecutionEnv {
@@ -81,6 +125,13 @@ pub struct ExecutionEnv {
     pub repo_context: RepoContext,
     pub commit_reminder: bool,
     pub commit_reminder_prompt: String,
+    /// Run the execution's processes in this sandbox
+    pub sandbox: Option<Sandbox>,
+    /// Run the execution's processes in this container instead of on the host
+    pub container: Option<OciContainer>,
+    /// Project secrets, substituted for `${secret:NAME}` references in
+    /// variable values when the env is applied to a command
+    pub secrets: Secrets,
 }
 
 impl ExecutionEnv {
@@ -94,7 +145,42 @@ impl ExecutionEnv {
             repo_context,
             commit_reminder,
             commit_reminder_prompt,
+            sandbox: None,
+            container: None,
+            secrets: Secrets::default(),
+        }
+    }
+
+    /// Return a new env whose processes run in `sandbox`
+    pub fn with_sandbox(mut self, sandbox: Option<Sandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// Return a new env whose processes run in `container`
+    pub fn with_container(mut self, container: Option<OciContainer>) -> Self {
+        self.container = container;
+        self
+    }
+
+    /// Return a new env that resolves references to `secrets`
+    pub fn with_secrets(mut self, secrets: Secrets) -> Self {
+        self.secrets = secrets;
+        self
+    }
+
+    /// A command for `program` running in `current_dir`, wrapped in the
+    /// container or sandbox if there is one
+    pub fn command(&self, program: impl AsRef<OsStr>, current_dir: &Path) -> Command {
+        if let Some(container) = &self.container {
+            return container.command(program, current_dir, self.vars.keys());
         }
+        let mut command = match &self.sandbox {
+            Some(sandbox) => sandbox.command(program),
+            None => Command::new(program),
+        };
+        command.current_dir(current_dir);
+        command
     }
 
     /// Insert an environment variable
@@ -123,10 +209,11 @@ impl ExecutionEnv {
         }
     }
 
-    /// Apply all environment variables to a Command
+    /// Apply all environment variables to a Command, with secret references
+    /// resolved
     pub fn apply_to_command(&self, command: &mut Command) {
         for (key, value) in &self.vars {
-            command.env(key, value);
+            command.env(key, self.secrets.resolve(value).as_ref());
         }
     }
 
@@ -159,4 +246,20 @@ mod tests {
         assert_eq!(merged.vars.get("FOO").unwrap(), "profile"); // overrides
         assert_eq!(merged.vars.get("BAR").unwrap(), "profile");
     }
+
+    #[test]
+    fn resolves_secret_references() {
+        let secrets = Secrets::new(HashMap::from([(
+            "NPM_TOKEN".to_string(),
+            "s3cret".to_string(),
+        )]));
+
+        assert_eq!(
+            secrets.resolve("//registry.npmjs.org/:_authToken=${secret:NPM_TOKEN}"),
+            "//registry.npmjs.org/:_authToken=s3cret"
+        );
+        assert_eq!(secrets.resolve("${secret:MISSING}"), "${secret:MISSING}");
+        assert!(matches!(secrets.resolve("plain"), Cow::Borrowed("plain")));
+        assert_eq!(format!("{secrets:?}"), r#"{"NPM_TOKEN"}"#);
+    }
 }
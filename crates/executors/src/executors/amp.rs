@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use command_group::AsyncCommandGroup;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::io::AsyncWriteExt;
 use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
@@ -56,7 +56,7 @@ impl StandardCodingAgentExecutor for Amp {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(executable_path);
+        let mut command = env.command(executable_path, current_dir);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -97,7 +97,8 @@ impl StandardCodingAgentExecutor for Amp {
             session_id.to_string(),
         ])?;
         let (fork_program, fork_args) = fork_line.into_resolved().await?;
-        let fork_output = Command::new(fork_program)
+        let fork_output = env
+            .command(fork_program, current_dir)
             .kill_on_drop(true)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -132,7 +133,7 @@ impl StandardCodingAgentExecutor for Amp {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(continue_program);
+        let mut command = env.command(continue_program, current_dir);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -6,7 +6,7 @@ use command_group::AsyncCommandGroup;
 use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::io::AsyncWriteExt;
 use ts_rs::TS;
 use workspace_utils::{
     diff::{create_unified_diff, normalize_unified_diff},
@@ -85,7 +85,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(executable_path);
+        let mut command = env.command(executable_path, current_dir);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -126,7 +126,7 @@ impl StandardCodingAgentExecutor for CursorAgent {
 
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
 
-        let mut command = Command::new(executable_path);
+        let mut command = env.command(executable_path, current_dir);
         command
             .kill_on_drop(true)
             .stdin(Stdio::piped())
@@ -5,7 +5,7 @@ use command_group::AsyncCommandGroup;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use strum_macros::AsRefStr;
-use tokio::{io::AsyncWriteExt, process::Command};
+use tokio::io::AsyncWriteExt;
 use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
@@ -111,7 +111,7 @@ async fn spawn_droid(
 ) -> Result<SpawnedChild, ExecutorError> {
     let (program_path, args) = command_parts.into_resolved().await?;
 
-    let mut command = Command::new(program_path);
+    let mut command = env.command(program_path, current_dir);
     command
         .kill_on_drop(true)
         .stdin(Stdio::piped())
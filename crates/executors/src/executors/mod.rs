@@ -16,7 +16,10 @@ use workspace_utils::msg_store::MsgStore;
 #[cfg(feature = "qa-mode")]
 use crate::executors::qa_mock::QaMockExecutor;
 use crate::{
-    actions::{ExecutorAction, review::RepoReviewContext},
+    actions::{
+        ExecutorAction,
+        review::{RepoReviewContext, ReviewFindings},
+    },
     approvals::ExecutorApprovalService,
     command::CommandBuildError,
     env::ExecutionEnv,
@@ -374,6 +377,24 @@ pub fn build_review_prompt(
     prompt
 }
 
+/// Prompt for the review run before a PR is created. The agent must end with
+/// its findings as JSON matching [`ReviewFindings::json_schema`], which is
+/// what decides whether the PR can be created.
+pub fn build_pre_pr_review_prompt(context: &[RepoReviewContext]) -> String {
+    let instructions = format!(
+        "This review runs before a pull request is opened. Do not modify any files.\n\n\
+        Report bugs, security problems and regressions, not style preferences. Use \
+        `high` severity only for problems that should block the pull request, and keep \
+        each message to one or two sentences.\n\n\
+        End your reply with a JSON object matching this schema, and nothing after it:\n\n\
+        ```json\n{}\n```\n\n\
+        If you found nothing, reply with `{{\"findings\": []}}`.",
+        ReviewFindings::json_schema()
+    );
+
+    build_review_prompt(Some(context), Some(&instructions))
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -7,7 +7,7 @@ use futures::StreamExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use tokio::{io::AsyncBufReadExt, process::Command};
+use tokio::io::AsyncBufReadExt;
 use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
 
@@ -103,7 +103,7 @@ impl Opencode {
 
         let server_password = generate_server_password();
 
-        let mut command = Command::new(program_path);
+        let mut command = env.command(program_path, current_dir);
         command
             .kill_on_drop(true)
             .stdin(std::process::Stdio::null())
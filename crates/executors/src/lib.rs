@@ -1,9 +1,12 @@
 pub mod actions;
 pub mod approvals;
 pub mod command;
+pub mod egress;
 pub mod env;
 pub mod executors;
 pub mod logs;
 pub mod mcp_config;
+pub mod oci;
 pub mod profile;
+pub mod sandbox;
 pub mod stdout_dup;
@@ -2,6 +2,7 @@
 //!
 //! Uses `PlainTextLogProcessor` with a 2-second `latency_threshold` to split stderr streams into entries.
 //! Each entry is normalized as `ErrorMessage` and emitted as JSON patches to the message store.
+//! Egress proxy notices get entries of their own, normalized as `SystemMessage`.
 //!
 //! Example:
 //! ```rust,ignore
@@ -15,9 +16,9 @@ use workspace_utils::msg_store::MsgStore;
 
 use super::{
     NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
-    plain_text_processor::PlainTextLogProcessor,
+    plain_text_processor::{MessageBoundary, PlainTextLogProcessor},
 };
-use crate::logs::utils::EntryIndexProvider;
+use crate::{egress::EGRESS_LOG_PREFIX, logs::utils::EntryIndexProvider};
 
 /// Standard stderr log normalizer that uses PlainTextLogProcessor to stream error logs.
 ///
@@ -42,14 +43,25 @@ pub fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: Ent
 
         // Create a processor with time-based emission for stderr
         let mut processor = PlainTextLogProcessor::builder()
-            .normalized_entry_producer(Box::new(|content: String| NormalizedEntry {
-                timestamp: None,
-                entry_type: NormalizedEntryType::ErrorMessage {
-                    error_type: NormalizedEntryError::Other,
-                },
-                content: strip_ansi_escapes::strip_str(&content),
-                metadata: None,
+            .normalized_entry_producer(Box::new(|content: String| {
+                match content.strip_prefix(EGRESS_LOG_PREFIX) {
+                    Some(notice) => NormalizedEntry {
+                        timestamp: None,
+                        entry_type: NormalizedEntryType::SystemMessage,
+                        content: notice.trim_end().to_string(),
+                        metadata: None,
+                    },
+                    None => NormalizedEntry {
+                        timestamp: None,
+                        entry_type: NormalizedEntryType::ErrorMessage {
+                            error_type: NormalizedEntryError::Other,
+                        },
+                        content: strip_ansi_escapes::strip_str(&content),
+                        metadata: None,
+                    },
+                }
             }))
+            .message_boundary_predicate(Box::new(egress_notice_boundary))
             .time_gap(Duration::from_secs(2)) // Break messages if they are 2 seconds apart
             .index_provider(entry_index_provider)
             .build();
@@ -61,3 +73,18 @@ pub fn normalize_stderr_logs(msg_store: Arc<MsgStore>, entry_index_provider: Ent
         }
     });
 }
+
+/// Keep each egress proxy notice in an entry of its own
+fn egress_notice_boundary(lines: &[String]) -> Option<MessageBoundary> {
+    match lines.first() {
+        Some(first) if first.starts_with(EGRESS_LOG_PREFIX) => Some(if first.ends_with('\n') {
+            MessageBoundary::Split(1)
+        } else {
+            MessageBoundary::IncompleteContent
+        }),
+        _ => lines
+            .iter()
+            .position(|line| line.starts_with(EGRESS_LOG_PREFIX))
+            .map(MessageBoundary::Split),
+    }
+}
@@ -0,0 +1,510 @@
+//! Runs executions in OCI containers with Docker or Podman.
+//!
+//! Every execution gets its own `run --rm` container from the repo's image, so
+//! the container lives exactly as long as the process and is stopped by the
+//! same signals. The workspace, the repos' git directories and the agents'
+//! state directories are bind-mounted at their host paths, so paths in
+//! prompts, logs and diffs mean the same thing inside and outside. Git
+//! directories are read-only apart from what the worktrees commit to.
+
+use std::{
+    ffi::OsStr,
+    net::{IpAddr, Ipv4Addr},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::process::Command;
+use ts_rs::TS;
+use workspace_utils::shell::resolve_executable_path;
+
+use crate::sandbox::AGENT_STATE_PATHS;
+
+/// Label put on every container, holding the workspace id
+pub const WORKSPACE_LABEL: &str = "vibe-kanban.workspace";
+
+/// Set on the command by executors rather than through [`crate::env::ExecutionEnv`],
+/// so they have to be forwarded by name
+const FORWARDED_ENV: &[&str] = &[
+    "NPM_CONFIG_LOGLEVEL",
+    "NODE_NO_WARNINGS",
+    "NO_COLOR",
+    "RUST_LOG",
+    "OPENCODE_SERVER_USERNAME",
+    "OPENCODE_SERVER_PASSWORD",
+];
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerRuntime {
+    #[default]
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    fn program(self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+        }
+    }
+}
+
+/// Per-repo container settings. Executions run in a container when the repo
+/// has an image or a Dockerfile.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
+#[serde(default)]
+pub struct ContainerConfig {
+    pub runtime: ContainerRuntime,
+    /// Image to run, e.g. `node:22`. It has to provide the coding agent CLIs
+    /// (or `npx`) and whatever the scripts need.
+    pub image: Option<String>,
+    /// Dockerfile to build the image from, relative to the repo root, which is
+    /// also the build context. Takes precedence over `image`.
+    pub dockerfile: Option<String>,
+    /// Ports published for dev servers, each on a free host port. Dev servers
+    /// have to listen on `0.0.0.0` inside the container.
+    pub ports: Vec<u16>,
+}
+
+impl ContainerConfig {
+    pub fn enabled(&self) -> bool {
+        self.image.is_some() || self.dockerfile.is_some()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum OciError {
+    #[error(
+        "This repository runs executions in containers but `{0}` was not found in PATH. \
+        Install it or remove the container image from the repository settings."
+    )]
+    RuntimeNotFound(&'static str),
+    #[error("Failed to read Dockerfile {path}: {source}")]
+    Dockerfile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to build container image: {0}")]
+    BuildFailed(String),
+    #[error("Container port {0} was not published")]
+    PortNotPublished(u16),
+    #[error("Could not find the gateway of the `{0}` container network")]
+    GatewayNotFound(&'static str),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// The image a repo's executions run in, built if needed
+#[derive(Debug, Clone)]
+pub struct ContainerImage {
+    runtime: ContainerRuntime,
+    runtime_path: PathBuf,
+    image: String,
+}
+
+impl ContainerImage {
+    /// Resolve the runtime and the image for `config`, building the repo's
+    /// Dockerfile if it has one
+    pub async fn prepare(config: &ContainerConfig, repo_path: &Path) -> Result<Self, OciError> {
+        let runtime = config.runtime;
+        let runtime_path = resolve_runtime(runtime).await?;
+
+        let image = match (&config.dockerfile, &config.image) {
+            (Some(dockerfile), _) => {
+                let dockerfile = repo_path.join(dockerfile);
+                build_image(&runtime_path, &dockerfile, repo_path).await?
+            }
+            (None, Some(image)) => image.clone(),
+            (None, None) => return Err(OciError::BuildFailed("No image configured".into())),
+        };
+
+        Ok(Self {
+            runtime,
+            runtime_path,
+            image,
+        })
+    }
+
+    /// A container for one execution
+    pub fn container(
+        &self,
+        name: String,
+        workspace_id: String,
+        mounts: Vec<PathBuf>,
+        read_only: Vec<PathBuf>,
+        ports: Vec<u16>,
+    ) -> OciContainer {
+        OciContainer {
+            image: self.clone(),
+            name,
+            workspace_id,
+            mounts,
+            read_only,
+            ports,
+            home: dirs::home_dir(),
+        }
+    }
+}
+
+/// The container one execution runs in
+#[derive(Debug, Clone)]
+pub struct OciContainer {
+    image: ContainerImage,
+    name: String,
+    workspace_id: String,
+    /// Bind-mounted read-write at the same path. The first one is the
+    /// workspace, whose owner the container runs as.
+    mounts: Vec<PathBuf>,
+    /// Bind-mounted read-only, beneath any of `mounts` inside them
+    read_only: Vec<PathBuf>,
+    ports: Vec<u16>,
+    home: Option<PathBuf>,
+}
+
+impl OciContainer {
+    /// A command that runs `program` in `current_dir` inside a new container.
+    /// Arguments and stdio set on it apply to `program`; environment variables
+    /// are forwarded for the keys in `env_keys` and a few executor defaults.
+    pub fn command<'a>(
+        &self,
+        program: impl AsRef<OsStr>,
+        current_dir: &Path,
+        env_keys: impl IntoIterator<Item = &'a String>,
+    ) -> Command {
+        let mut command = Command::new(&self.image.runtime_path);
+        command.args(self.run_args(current_dir, env_keys));
+        command.arg(&self.image.image);
+        // Executors resolve programs on the host; inside the container the
+        // image's PATH decides
+        let program = Path::new(program.as_ref());
+        command.arg(program.file_name().unwrap_or(program.as_os_str()));
+        command
+    }
+
+    fn run_args<'a>(
+        &self,
+        current_dir: &Path,
+        env_keys: impl IntoIterator<Item = &'a String>,
+    ) -> Vec<String> {
+        let label = format!("{WORKSPACE_LABEL}={}", self.workspace_id);
+        let workdir = current_dir.to_string_lossy();
+        let base: [&str; 10] = [
+            "run",
+            "--rm",
+            "--interactive",
+            "--init",
+            "--name",
+            &self.name,
+            "--label",
+            &label,
+            "--workdir",
+            &workdir,
+        ];
+        let mut args: Vec<String> = base.into_iter().map(str::to_string).collect();
+
+        match self.image.runtime {
+            // Rootless Podman maps the host user into the container itself
+            ContainerRuntime::Podman => args.push("--userns=keep-id".to_string()),
+            ContainerRuntime::Docker => {
+                if let Some(user) = self.mounts.first().and_then(|dir| owner(dir)) {
+                    args.extend(["--user".to_string(), user]);
+                }
+            }
+        }
+
+        for path in self.read_only.iter().filter(|path| path.exists()) {
+            let path = path.to_string_lossy();
+            args.extend(["--volume".to_string(), format!("{path}:{path}:ro")]);
+        }
+
+        let mut volumes: Vec<&Path> = self.mounts.iter().map(PathBuf::as_path).collect();
+        let state_paths: Vec<PathBuf> = self
+            .home
+            .iter()
+            .flat_map(|home| AGENT_STATE_PATHS.iter().map(|path| home.join(path)))
+            .collect();
+        volumes.extend(state_paths.iter().map(PathBuf::as_path));
+        for path in volumes.into_iter().filter(|path| path.exists()) {
+            let path = path.to_string_lossy();
+            args.extend(["--volume".to_string(), format!("{path}:{path}")]);
+        }
+
+        if let Some(home) = &self.home {
+            args.extend(["--env".to_string(), format!("HOME={}", home.display())]);
+        }
+        // `--env KEY` without a value copies KEY from the runtime's own
+        // environment and skips it when unset
+        for key in env_keys
+            .into_iter()
+            .map(String::as_str)
+            .chain(FORWARDED_ENV.iter().copied())
+        {
+            args.extend(["--env".to_string(), key.to_string()]);
+        }
+
+        // Host port 0 lets the runtime pick a free one, so two workspaces can
+        // run the same dev server; see `published_ports`
+        for port in &self.ports {
+            args.extend(["--publish".to_string(), format!("0:{port}")]);
+        }
+
+        args
+    }
+
+    /// The host ports the container's ports were published on, as
+    /// `(container_port, host_port)`. Waits a few seconds for the container
+    /// to start.
+    pub async fn published_ports(&self) -> Result<Vec<(u16, u16)>, OciError> {
+        const ATTEMPTS: usize = 20;
+
+        let mut published = Vec::new();
+        for &port in &self.ports {
+            for attempt in 1..=ATTEMPTS {
+                let output = Command::new(&self.image.runtime_path)
+                    .args(["port", &self.name, &format!("{port}/tcp")])
+                    .output()
+                    .await?;
+                if let Some(host_port) = parse_port_output(&String::from_utf8_lossy(&output.stdout))
+                {
+                    published.push((port, host_port));
+                    break;
+                }
+                if attempt == ATTEMPTS {
+                    return Err(OciError::PortNotPublished(port));
+                }
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+        }
+        Ok(published)
+    }
+}
+
+/// Where a service on the host has to listen for containers to reach it, and
+/// the host name they reach it by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostEndpoint {
+    pub bind: IpAddr,
+    pub host: String,
+}
+
+impl OciContainer {
+    /// Where to run host services, like the egress proxy, that this container
+    /// has to reach. On Linux that is the host's address on the runtime's
+    /// default network; elsewhere the runtime runs in a VM that forwards its
+    /// `host.*.internal` name to the host's loopback interface.
+    pub async fn host_endpoint(&self) -> Result<HostEndpoint, OciError> {
+        let (network, format, host_name) = match self.image.runtime {
+            ContainerRuntime::Docker => (
+                "bridge",
+                "{{range .IPAM.Config}}{{.Gateway}} {{end}}",
+                "host.docker.internal",
+            ),
+            ContainerRuntime::Podman => (
+                "podman",
+                "{{range .Subnets}}{{.Gateway}} {{end}}",
+                "host.containers.internal",
+            ),
+        };
+
+        if !cfg!(target_os = "linux") {
+            return Ok(HostEndpoint {
+                bind: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                host: host_name.to_string(),
+            });
+        }
+
+        let output = Command::new(&self.image.runtime_path)
+            .args(["network", "inspect", network, "--format", format])
+            .output()
+            .await?;
+        let gateway = parse_gateway(&String::from_utf8_lossy(&output.stdout))
+            .ok_or(OciError::GatewayNotFound(network))?;
+        Ok(HostEndpoint {
+            bind: gateway,
+            host: gateway.to_string(),
+        })
+    }
+}
+
+/// The first IPv4 gateway in `network inspect` output
+fn parse_gateway(output: &str) -> Option<IpAddr> {
+    output
+        .split_whitespace()
+        .filter_map(|gateway| gateway.parse::<IpAddr>().ok())
+        .find(IpAddr::is_ipv4)
+}
+
+/// The host port in `docker port` output, e.g. `0.0.0.0:49153`
+fn parse_port_output(output: &str) -> Option<u16> {
+    output
+        .lines()
+        .find_map(|line| line.trim().rsplit_once(':')?.1.parse().ok())
+}
+
+/// Name of the container an execution runs in
+pub fn container_name(execution_id: &str) -> String {
+    format!("vk-{execution_id}")
+}
+
+/// Remove a container if it is still running, e.g. after its runtime client
+/// was killed before it could stop it
+pub async fn remove_container(runtime: ContainerRuntime, name: &str) -> Result<(), OciError> {
+    let runtime_path = resolve_runtime(runtime).await?;
+    Command::new(runtime_path)
+        .args(["rm", "--force", name])
+        .output()
+        .await?;
+    Ok(())
+}
+
+/// Remove every container left over from a workspace's executions
+pub async fn remove_workspace_containers(
+    runtime: ContainerRuntime,
+    workspace_id: &str,
+) -> Result<(), OciError> {
+    let runtime_path = resolve_runtime(runtime).await?;
+    let output = Command::new(&runtime_path)
+        .args(["ps", "--all", "--quiet", "--filter"])
+        .arg(format!("label={WORKSPACE_LABEL}={workspace_id}"))
+        .output()
+        .await?;
+
+    let ids: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .map(str::to_string)
+        .collect();
+    if !ids.is_empty() {
+        Command::new(&runtime_path)
+            .args(["rm", "--force"])
+            .args(&ids)
+            .output()
+            .await?;
+    }
+    Ok(())
+}
+
+async fn resolve_runtime(runtime: ContainerRuntime) -> Result<PathBuf, OciError> {
+    resolve_executable_path(runtime.program())
+        .await
+        .ok_or(OciError::RuntimeNotFound(runtime.program()))
+}
+
+/// Build `dockerfile` with `context` as the build context
+///
+/// This runs before every execution: the runtime's layer cache makes it cheap
+/// when nothing changed, and anything in the context the Dockerfile copies is
+/// picked up when it did. The tag is stable per Dockerfile, so rebuilds replace
+/// the previous image.
+async fn build_image(
+    runtime_path: &Path,
+    dockerfile: &Path,
+    context: &Path,
+) -> Result<String, OciError> {
+    if !tokio::fs::try_exists(dockerfile).await? {
+        return Err(OciError::Dockerfile {
+            path: dockerfile.to_path_buf(),
+            source: std::io::ErrorKind::NotFound.into(),
+        });
+    }
+    let digest = format!(
+        "{:x}",
+        Sha256::digest(dockerfile.to_string_lossy().as_bytes())
+    );
+    let tag = format!("vibe-kanban/workspace:{}", &digest[..16]);
+
+    tracing::debug!(
+        "Building container image {tag} from {}",
+        dockerfile.display()
+    );
+    let output = Command::new(runtime_path)
+        .args(["build", "--tag", &tag, "--file"])
+        .arg(dockerfile)
+        .arg(context)
+        .output()
+        .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let tail: Vec<&str> = stderr.lines().rev().take(20).collect();
+        return Err(OciError::BuildFailed(
+            tail.into_iter().rev().collect::<Vec<_>>().join("\n"),
+        ));
+    }
+
+    Ok(tag)
+}
+
+/// `uid:gid` of the owner of `path`, so files the container writes to the
+/// workspace stay owned by the user
+#[cfg(unix)]
+fn owner(path: &Path) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = std::fs::metadata(path).ok()?;
+    Some(format!("{}:{}", metadata.uid(), metadata.gid()))
+}
+
+#[cfg(not(unix))]
+fn owner(_path: &Path) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_args() {
+        let workspace = std::env::temp_dir();
+        let container = OciContainer {
+            image: ContainerImage {
+                runtime: ContainerRuntime::Docker,
+                runtime_path: PathBuf::from("/usr/bin/docker"),
+                image: "node:22".to_string(),
+            },
+            name: container_name("exec"),
+            workspace_id: "ws".to_string(),
+            mounts: vec![workspace.clone(), PathBuf::from("/does/not/exist")],
+            read_only: vec![workspace.join("does-not-exist.git"), std::env::temp_dir()],
+            ports: vec![3000],
+            home: None,
+        };
+
+        let keys = ["VK_WORKSPACE_ID".to_string()];
+        let args = container.run_args(&workspace, &keys);
+        let workspace = workspace.to_string_lossy();
+        let has = |pair: [&str; 2]| args.windows(2).any(|w| w[0] == pair[0] && w[1] == pair[1]);
+
+        assert!(has(["--name", "vk-exec"]));
+        assert!(has(["--label", "vibe-kanban.workspace=ws"]));
+        assert!(has(["--workdir", &workspace]));
+        assert!(has(["--volume", &format!("{workspace}:{workspace}")]));
+        assert!(has(["--env", "VK_WORKSPACE_ID"]));
+        assert!(has(["--volume", &format!("{workspace}:{workspace}:ro")]));
+        assert!(has(["--publish", "0:3000"]));
+        assert!(!args.iter().any(|arg| arg.contains("/does/not/exist")));
+        assert!(!args.iter().any(|arg| arg.contains("does-not-exist.git")));
+    }
+
+    #[test]
+    fn test_parse_gateway() {
+        assert_eq!(
+            parse_gateway("fd00::1 172.17.0.1 \n"),
+            Some(IpAddr::V4(Ipv4Addr::new(172, 17, 0, 1)))
+        );
+        assert_eq!(parse_gateway("\n"), None);
+    }
+
+    #[test]
+    fn test_parse_port_output() {
+        assert_eq!(
+            parse_port_output("0.0.0.0:49153\n[::]:49153\n"),
+            Some(49153)
+        );
+        assert_eq!(parse_port_output(""), None);
+    }
+}
@@ -0,0 +1,236 @@
+//! Runs executions under [bubblewrap](https://github.com/containers/bubblewrap)
+//! instead of directly on the host.
+//!
+//! Inside the sandbox the whole filesystem is read-only except the workspace,
+//! the parts of the git directories its worktrees commit to, the agents' own state
+//! directories and a per-workspace `/tmp`, which also holds package manager
+//! caches. The home directory can be hidden entirely, and coding agents can be
+//! cut off from the network.
+
+use std::{
+    ffi::{OsStr, OsString},
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::process::Command;
+use ts_rs::TS;
+use workspace_utils::shell::resolve_executable_path;
+
+const BWRAP: &str = "bwrap";
+
+/// Where agents keep sessions, credentials and caches, relative to the home
+/// directory. These stay writable so agents can run and resume sessions.
+pub(crate) const AGENT_STATE_PATHS: &[&str] = &[
+    ".claude",
+    ".claude.json",
+    ".codex",
+    ".gemini",
+    ".qwen",
+    ".cursor",
+    ".factory",
+    ".copilot",
+    ".config/amp",
+    ".config/cursor",
+    ".config/github-copilot",
+    ".config/opencode",
+    ".local/share/amp",
+    ".local/share/opencode",
+    ".local/state/opencode",
+];
+
+/// Package manager caches go to the per-workspace `/tmp` instead of the shared
+/// ones in the home directory, which executions could poison for each other
+const CACHE_ENV: &[(&str, &str)] = &[
+    ("XDG_CACHE_HOME", "/tmp/.cache"),
+    ("npm_config_cache", "/tmp/.npm"),
+];
+
+/// Per-project sandbox settings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
+#[serde(default)]
+pub struct SandboxConfig {
+    /// Run the project's executions under bubblewrap
+    pub enabled: bool,
+    /// Cut coding agents off from the network. Scripts keep network access so
+    /// setup scripts can still install dependencies.
+    pub disable_network: bool,
+    /// Hide the home directory instead of mounting it read-only. Agent state
+    /// directories are still mounted, but tools installed under the home
+    /// directory have to be listed in `read_only_paths`.
+    pub hide_home: bool,
+    /// Extra paths mounted read-only, e.g. `~/.nvm` when the home is hidden
+    pub read_only_paths: Vec<String>,
+    /// Extra paths mounted writable
+    pub writable_paths: Vec<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum SandboxError {
+    #[error("Sandboxed execution is only supported on Linux")]
+    Unsupported,
+    #[error(
+        "Sandboxing is enabled for this project but `bwrap` was not found in PATH. \
+        Install bubblewrap (e.g. `apt install bubblewrap` or `dnf install bubblewrap`) \
+        or turn off the sandbox in the project settings."
+    )]
+    BwrapNotFound,
+}
+
+/// The sandbox one execution runs in
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    bwrap: PathBuf,
+    home: Option<PathBuf>,
+    hide_home: bool,
+    network: bool,
+    /// Mounted at `/tmp`
+    tmp_dir: PathBuf,
+    read_only: Vec<PathBuf>,
+    writable: Vec<PathBuf>,
+}
+
+impl Sandbox {
+    /// Resolve `config` for one execution. `writable` are the paths the
+    /// execution works in, `tmp_dir` is mounted at `/tmp` and `network` is
+    /// whether the execution keeps network access.
+    pub async fn new(
+        config: &SandboxConfig,
+        writable: Vec<PathBuf>,
+        tmp_dir: PathBuf,
+        network: bool,
+    ) -> Result<Self, SandboxError> {
+        if !cfg!(target_os = "linux") {
+            return Err(SandboxError::Unsupported);
+        }
+
+        let bwrap = resolve_executable_path(BWRAP)
+            .await
+            .ok_or(SandboxError::BwrapNotFound)?;
+        let home = dirs::home_dir();
+
+        let expand = |path: &String| match (path.strip_prefix("~/"), &home) {
+            (Some(rest), Some(home)) => home.join(rest),
+            _ => PathBuf::from(path),
+        };
+
+        Ok(Self {
+            bwrap,
+            hide_home: config.hide_home,
+            network,
+            tmp_dir,
+            read_only: config.read_only_paths.iter().map(expand).collect(),
+            writable: writable
+                .into_iter()
+                .chain(config.writable_paths.iter().map(expand))
+                .collect(),
+            home,
+        })
+    }
+
+    /// A command that runs `program` in the sandbox. Arguments, environment,
+    /// working directory and stdio set on it apply to `program`.
+    pub fn command(&self, program: impl AsRef<OsStr>) -> Command {
+        let mut command = Command::new(&self.bwrap);
+        command.args(self.bwrap_args()).arg("--").arg(program);
+        command
+    }
+
+    fn bwrap_args(&self) -> Vec<OsString> {
+        let mut args: Vec<OsString> = Vec::new();
+        let mut push = |parts: &[&OsStr]| args.extend(parts.iter().map(|p| p.to_os_string()));
+
+        // No --new-session: the agent has to stay in the process group the
+        // container kills on stop
+        push(&["--die-with-parent".as_ref(), "--unshare-pid".as_ref()]);
+        if !self.network {
+            push(&["--unshare-net".as_ref()]);
+        }
+
+        push(&["--ro-bind".as_ref(), "/".as_ref(), "/".as_ref()]);
+        push(&["--dev".as_ref(), "/dev".as_ref()]);
+        push(&["--proc".as_ref(), "/proc".as_ref()]);
+        push(&["--bind".as_ref(), self.tmp_dir.as_os_str(), "/tmp".as_ref()]);
+        for (key, value) in CACHE_ENV {
+            push(&["--setenv".as_ref(), key.as_ref(), value.as_ref()]);
+        }
+
+        if let Some(home) = &self.home {
+            if self.hide_home {
+                push(&["--tmpfs".as_ref(), home.as_os_str()]);
+            }
+            for path in AGENT_STATE_PATHS.iter().map(|p| home.join(p)) {
+                if path.exists() {
+                    push(&["--bind".as_ref(), path.as_os_str(), path.as_os_str()]);
+                }
+            }
+        }
+
+        // Later mounts win, so the workspace stays writable even when it lives
+        // under a hidden home or /tmp
+        for path in self.read_only.iter().filter(|path| path.exists()) {
+            push(&["--ro-bind".as_ref(), path.as_os_str(), path.as_os_str()]);
+        }
+        for path in self.writable.iter().filter(|path| path.exists()) {
+            push(&["--bind".as_ref(), path.as_os_str(), path.as_os_str()]);
+        }
+
+        args
+    }
+}
+
+/// The directory a workspace's sandboxed executions use as `/tmp`
+pub fn sandbox_tmp_dir(workspace_id: &str) -> PathBuf {
+    std::env::temp_dir()
+        .join("vibe-kanban-sandbox")
+        .join(workspace_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_sandbox(network: bool) -> Sandbox {
+        Sandbox {
+            bwrap: PathBuf::from("/usr/bin/bwrap"),
+            home: None,
+            hide_home: false,
+            network,
+            tmp_dir: PathBuf::from("/tmp/vibe-kanban-sandbox/ws"),
+            read_only: Vec::new(),
+            writable: vec![std::env::temp_dir(), PathBuf::from("/does/not/exist")],
+        }
+    }
+
+    #[test]
+    fn test_bwrap_args() {
+        let args: Vec<String> = test_sandbox(true)
+            .bwrap_args()
+            .into_iter()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+
+        assert!(!args.iter().any(|arg| arg == "--unshare-net"));
+        assert!(!args.iter().any(|arg| arg == "/does/not/exist"));
+
+        // The workspace is mounted writable over the read-only root
+        let root = args.iter().position(|arg| arg == "--ro-bind").unwrap();
+        let workspace = args.iter().rposition(|arg| arg == "--bind").unwrap();
+        assert!(root < workspace);
+        assert_eq!(args[workspace + 1], std::env::temp_dir().to_string_lossy());
+        assert_eq!(args[workspace + 1], args[workspace + 2]);
+
+        // Caches are per workspace
+        let cache = args
+            .iter()
+            .position(|arg| arg == "npm_config_cache")
+            .unwrap();
+        assert_eq!(args[cache - 1], "--setenv");
+        assert_eq!(args[cache + 1], "/tmp/.npm");
+        assert!(!AGENT_STATE_PATHS.contains(&".npm"));
+
+        let args = test_sandbox(false).bwrap_args();
+        assert!(args.iter().any(|arg| arg == "--unshare-net"));
+    }
+}
@@ -26,6 +26,7 @@ use thiserror::Error;
 use utils::{path::ALWAYS_SKIP_DIRS, shell::resolve_executable_path_blocking};
 
 use super::Commit;
+use crate::worktree_guard;
 
 #[derive(Debug, Error)]
 pub enum GitCliError {
@@ -39,6 +40,8 @@ pub enum GitCliError {
     PushRejected(String),
     #[error("rebase in progress in this worktree")]
     RebaseInProgress,
+    #[error("refusing to run git in tampered worktree {0}")]
+    UntrustedWorktree(String),
 }
 
 #[derive(Clone, Default)]
@@ -622,15 +625,13 @@ impl GitCli {
     /// Return true if there are staged changes (index differs from HEAD)
     pub fn has_staged_changes(&self, repo_path: &Path) -> Result<bool, GitCliError> {
         // `git diff --cached --quiet` returns exit code 1 if there are differences
-        let out =
-            Command::new(resolve_executable_path_blocking("git").ok_or(GitCliError::NotAvailable)?)
-                .arg("-C")
-                .arg(repo_path)
-                .arg("diff")
-                .arg("--cached")
-                .arg("--quiet")
-                .output()
-                .map_err(|e| GitCliError::CommandFailed(e.to_string()))?;
+        let out = self
+            .command(repo_path)?
+            .arg("diff")
+            .arg("--cached")
+            .arg("--quiet")
+            .output()
+            .map_err(|e| GitCliError::CommandFailed(e.to_string()))?;
         match out.status.code() {
             Some(0) => Ok(false),
             Some(1) => Ok(true),
@@ -741,6 +742,19 @@ impl GitCli {
         }
     }
 
+    /// `git -C <repo_path>` without hooks. Worktrees an execution may have
+    /// tampered with are verified first.
+    fn command(&self, repo_path: &Path) -> Result<Command, GitCliError> {
+        worktree_guard::check(repo_path)?;
+        let git = resolve_executable_path_blocking("git").ok_or(GitCliError::NotAvailable)?;
+        let mut cmd = Command::new(&git);
+        cmd.arg("-c")
+            .arg("core.hooksPath=/dev/null")
+            .arg("-C")
+            .arg(repo_path);
+        Ok(cmd)
+    }
+
     /// Run `git -C <repo_path> <args...>` and return stdout bytes on success.
     /// Prefer adding specific helpers (e.g. `get_worktree_status`, `diff_status`)
     /// instead of calling this directly, so all parsing and command choices are
@@ -766,9 +780,7 @@ impl GitCli {
         S: AsRef<OsStr>,
     {
         self.ensure_available()?;
-        let git = resolve_executable_path_blocking("git").ok_or(GitCliError::NotAvailable)?;
-        let mut cmd = Command::new(&git);
-        cmd.arg("-C").arg(repo_path);
+        let mut cmd = self.command(repo_path)?;
 
         if let Some(envs) = envs {
             for (k, v) in envs {
@@ -12,6 +12,7 @@ use utils::diff::{Diff, DiffChangeKind, FileDiffDetails, compute_line_change_cou
 
 mod cli;
 mod validation;
+mod worktree_guard;
 
 use cli::{ChangeType, StatusDiffEntry, StatusDiffOptions};
 pub use cli::{GitCli, GitCliError, StatusEntry, WorktreeStatus};
@@ -53,6 +54,7 @@ pub enum GitServiceError {
     #[error("Rebase in progress; resolve or abort it before retrying")]
     RebaseInProgress,
 }
+
 /// Service for managing Git operations in task execution workflows
 #[derive(Clone)]
 pub struct GitService {}
@@ -178,9 +180,21 @@ impl GitService {
 
     /// Open the repository
     pub fn open_repo(&self, repo_path: &Path) -> Result<Repository, GitServiceError> {
+        worktree_guard::check(repo_path)?;
         Repository::open(repo_path).map_err(GitServiceError::from)
     }
 
+    /// Verify that the worktree at `worktree_path` belongs to the repository
+    /// at `repo_path`, and verify it again before every git command the host
+    /// runs in it from now on
+    pub fn register_worktree(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+    ) -> Result<(), GitServiceError> {
+        Ok(worktree_guard::register(repo_path, worktree_path)?)
+    }
+
     /// Ensure local (repo-scoped) identity exists for CLI commits.
     /// Sets user.name/email only if missing in the repo config.
     fn ensure_cli_commit_identity(&self, repo_path: &Path) -> Result<(), GitServiceError> {
@@ -333,7 +347,7 @@ impl GitService {
                 base_commit,
             } => {
                 // Use Git CLI to compute diff vs base to avoid sparse false deletions
-                let repo = Repository::open(worktree_path)?;
+                let repo = self.open_repo(worktree_path)?;
                 let base_tree = repo
                     .find_commit(base_commit.as_oid())?
                     .tree()
@@ -939,7 +953,7 @@ impl GitService {
         branch_name: &str,
         base_branch_name: &str,
     ) -> Result<(usize, usize), GitServiceError> {
-        let repo = Repository::open(repo_path)?;
+        let repo = self.open_repo(repo_path)?;
         let branch = Self::find_branch(&repo, branch_name)?;
         let base_branch = Self::find_branch(&repo, base_branch_name)?;
         self.get_branch_status_inner(
@@ -955,7 +969,7 @@ impl GitService {
         branch_name: &str,
         base_branch_name: &str,
     ) -> Result<Commit, GitServiceError> {
-        let repo = Repository::open(repo_path)?;
+        let repo = self.open_repo(repo_path)?;
         let branch = Self::find_branch(&repo, branch_name)?;
         let base_branch = Self::find_branch(&repo, base_branch_name)?;
         // Find the common ancestor (merge base)
@@ -974,7 +988,7 @@ impl GitService {
         branch_name: &str,
         base_branch_name: Option<&str>,
     ) -> Result<(usize, usize), GitServiceError> {
-        let repo = Repository::open(repo_path)?;
+        let repo = self.open_repo(repo_path)?;
         let branch_ref = Self::find_branch(&repo, branch_name)?.into_reference();
         // base branch is either given or upstream of branch_name
         let base_branch_ref = if let Some(bn) = base_branch_name {
@@ -1222,7 +1236,7 @@ impl GitService {
         let git = GitCli::new();
         git.worktree_add(repo_path, worktree_path, branch, create_branch)
             .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
-        Ok(())
+        self.register_worktree(repo_path, worktree_path)
     }
 
     /// Remove a worktree
@@ -1233,6 +1247,7 @@ impl GitService {
         force: bool,
     ) -> Result<(), GitServiceError> {
         let git = GitCli::new();
+        worktree_guard::unregister(worktree_path);
         git.worktree_remove(repo_path, worktree_path, force)
             .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
         Ok(())
@@ -1248,7 +1263,8 @@ impl GitService {
         let git = GitCli::new();
         git.worktree_move(repo_path, old_path, new_path)
             .map_err(|e| GitServiceError::InvalidRepository(e.to_string()))?;
-        Ok(())
+        worktree_guard::unregister(old_path);
+        self.register_worktree(repo_path, new_path)
     }
 
     pub fn prune_worktrees(&self, repo_path: &Path) -> Result<(), GitServiceError> {
@@ -1389,7 +1405,7 @@ impl GitService {
         old_base_branch: &str,
         task_branch: &str,
     ) -> Result<String, GitServiceError> {
-        let worktree_repo = Repository::open(worktree_path)?;
+        let worktree_repo = self.open_repo(worktree_path)?;
         let main_repo = self.open_repo(repo_path)?;
 
         // Safety guard: never operate on a dirty worktree. This preserves any
@@ -1647,7 +1663,7 @@ impl GitService {
         repo_path: &Path,
         branch_name: &str,
     ) -> Result<GitRemote, GitServiceError> {
-        let repo = Repository::open(repo_path)?;
+        let repo = self.open_repo(repo_path)?;
         let branch_ref = Self::find_branch(&repo, branch_name)?.into_reference();
         let remote = self.get_remote_from_branch_ref(&repo, &branch_ref)?;
         let name = remote.name().map(|name| name.to_string()).ok_or_else(|| {
@@ -1713,6 +1729,48 @@ impl GitService {
             .map_err(GitServiceError::from)
     }
 
+    /// Bring `branch_name` up to date with its remote before basing new work
+    /// on it. The local branch is fast-forwarded when that is safe (it has not
+    /// diverged and is not checked out in the main repository); otherwise the
+    /// freshly fetched remote-tracking branch is returned instead. Returns the
+    /// branch name to base on.
+    pub fn refresh_base_branch(
+        &self,
+        repo_path: &Path,
+        branch_name: &str,
+    ) -> Result<String, GitServiceError> {
+        let repo = self.open_repo(repo_path)?;
+        let remote = self.resolve_remote_for_branch(repo_path, branch_name)?;
+        let tracking_ref = format!("refs/remotes/{}/{branch_name}", remote.name);
+        GitCli::new().fetch_with_refspec(
+            repo_path,
+            &remote.url,
+            &format!("+refs/heads/{branch_name}:{tracking_ref}"),
+        )?;
+
+        let local_ref = format!("refs/heads/{branch_name}");
+        let remote_oid = repo.refname_to_id(&tracking_ref)?;
+        let Ok(local_oid) = repo.refname_to_id(&local_ref) else {
+            return Ok(format!("{}/{branch_name}", remote.name));
+        };
+        if local_oid == remote_oid || repo.graph_descendant_of(local_oid, remote_oid)? {
+            // Already up to date, or ahead with unpushed commits.
+            return Ok(branch_name.to_string());
+        }
+
+        let checked_out = repo
+            .head()
+            .ok()
+            .and_then(|head| head.name().map(|name| name == local_ref))
+            .unwrap_or(false);
+        if checked_out || !repo.graph_descendant_of(remote_oid, local_oid)? {
+            return Ok(format!("{}/{branch_name}", remote.name));
+        }
+
+        repo.reference(&local_ref, remote_oid, true, "fast-forward to remote")?;
+        Ok(branch_name.to_string())
+    }
+
     pub fn resolve_remote_for_branch(
         &self,
         repo_path: &Path,
@@ -1753,7 +1811,7 @@ impl GitService {
         branch_name: &str,
         force: bool,
     ) -> Result<(), GitServiceError> {
-        let repo = Repository::open(worktree_path)?;
+        let repo = self.open_repo(worktree_path)?;
         self.check_worktree_clean(&repo)?;
 
         // Get the remote
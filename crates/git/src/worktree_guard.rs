@@ -0,0 +1,116 @@
+//! Checks that worktrees still belong to their repository before the host runs
+//! git in them.
+//!
+//! Sandboxed and containerized executions can write a worktree's `.git` gitfile
+//! and its `.git/worktrees/<name>` directory. Pointing the gitfile at a git
+//! directory of their own, or adding a `config.worktree`, would make hooks and
+//! config of their choosing apply to the git commands the host later runs in
+//! the worktree. Worktrees are registered with their repository's git directory
+//! once verified, and verified again before every host git command in them.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{LazyLock, RwLock},
+};
+
+use git2::Repository;
+
+use crate::cli::GitCliError;
+
+/// Canonical worktree roots and the canonical git common dirs they belong to
+static WORKTREES: LazyLock<RwLock<HashMap<PathBuf, PathBuf>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Verify the worktree at `worktree_path` against the repository at
+/// `repo_path` and check it before host git commands from now on
+pub fn register(repo_path: &Path, worktree_path: &Path) -> Result<(), GitCliError> {
+    let untrusted = |reason: String| untrusted_error(worktree_path, reason);
+    let repo = Repository::open(repo_path).map_err(|e| untrusted(e.message().to_string()))?;
+    let common_dir = canonical(repo.commondir()).map_err(untrusted)?;
+    let worktree_root = canonical(worktree_path).map_err(untrusted)?;
+
+    verify(&worktree_root, &common_dir)?;
+    WORKTREES.write().unwrap().insert(worktree_root, common_dir);
+    Ok(())
+}
+
+/// Stop checking the worktree at `worktree_path`, e.g. once it is removed
+pub fn unregister(worktree_path: &Path) {
+    if let Ok(worktree_root) = canonical(worktree_path) {
+        WORKTREES.write().unwrap().remove(&worktree_root);
+    }
+}
+
+/// Verify the registered worktree containing `path`, if there is one
+pub(crate) fn check(path: &Path) -> Result<(), GitCliError> {
+    let Ok(path) = canonical(path) else {
+        return Ok(());
+    };
+    let registered = {
+        let worktrees = WORKTREES.read().unwrap();
+        path.ancestors().find_map(|ancestor| {
+            worktrees
+                .get(ancestor)
+                .map(|common_dir| (ancestor.to_path_buf(), common_dir.clone()))
+        })
+    };
+    match registered {
+        Some((worktree_root, common_dir)) => verify(&worktree_root, &common_dir),
+        None => Ok(()),
+    }
+}
+
+/// The gitfile must point at one of the repository's `worktrees/<name>`
+/// directories, that directory must point back at the repository, and it must
+/// not carry worktree config other than what git copied from the main worktree
+fn verify(worktree_root: &Path, common_dir: &Path) -> Result<(), GitCliError> {
+    let untrusted = |reason: String| untrusted_error(worktree_root, reason);
+
+    let gitfile = worktree_root.join(".git");
+    let metadata = fs::symlink_metadata(&gitfile).map_err(|e| untrusted(e.to_string()))?;
+    if !metadata.is_file() {
+        return Err(untrusted(".git is not a gitfile".to_string()));
+    }
+    let gitdir = read_pointer(&gitfile, "gitdir: ").map_err(untrusted)?;
+    let gitdir = canonical(&worktree_root.join(gitdir)).map_err(untrusted)?;
+    if gitdir.parent() != Some(common_dir.join("worktrees").as_path()) {
+        return Err(untrusted(format!(
+            "gitfile points at {} instead of the repository",
+            gitdir.display()
+        )));
+    }
+
+    let commondir = read_pointer(&gitdir.join("commondir"), "").map_err(untrusted)?;
+    if canonical(&gitdir.join(commondir)).map_err(untrusted)? != common_dir {
+        return Err(untrusted(
+            "commondir points away from the repository".to_string(),
+        ));
+    }
+
+    let worktree_config = fs::read(gitdir.join("config.worktree")).ok();
+    if worktree_config.is_some()
+        && worktree_config != fs::read(common_dir.join("config.worktree")).ok()
+    {
+        return Err(untrusted("it has its own config.worktree".to_string()));
+    }
+    Ok(())
+}
+
+fn read_pointer(path: &Path, prefix: &str) -> Result<PathBuf, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    content
+        .trim_end_matches(['\r', '\n'])
+        .strip_prefix(prefix)
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("{} is malformed", path.display()))
+}
+
+fn canonical(path: &Path) -> Result<PathBuf, String> {
+    fs::canonicalize(path).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+fn untrusted_error(worktree_path: &Path, reason: String) -> GitCliError {
+    GitCliError::UntrustedWorktree(format!("{}: {reason}", worktree_path.display()))
+}
@@ -1406,3 +1406,71 @@ fn merge_base_ahead_of_task_should_error() {
         "Merge should error when base branch is ahead of task branch"
     );
 }
+
+#[test]
+fn tampered_worktree_gitfile_is_rejected() {
+    let td = TempDir::new().unwrap();
+    let (_repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let git = GitCli::new();
+    assert!(git.has_changes(&worktree_path).is_ok());
+
+    // An execution points the gitfile at a git directory it controls
+    let fake_gitdir = td.path().join("fake/.git/worktrees/wt-feature");
+    fs::create_dir_all(fake_gitdir.join("hooks")).unwrap();
+    write_file(&fake_gitdir, "commondir", "../..\n");
+    write_file(
+        &worktree_path,
+        ".git",
+        &format!("gitdir: {}\n", fake_gitdir.display()),
+    );
+
+    assert!(matches!(
+        git.has_changes(&worktree_path),
+        Err(GitCliError::UntrustedWorktree(_))
+    ));
+    assert!(GitService::new().open_repo(&worktree_path).is_err());
+}
+
+#[test]
+fn worktree_config_added_by_execution_is_rejected() {
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let git = GitCli::new();
+
+    let gitdir = git.git(&worktree_path, ["rev-parse", "--git-dir"]).unwrap();
+    let gitdir = worktree_path.join(gitdir.trim());
+    write_file(
+        &gitdir,
+        "config.worktree",
+        "[core]\n\tfsmonitor = touch pwned\n",
+    );
+
+    assert!(matches!(
+        git.has_changes(&worktree_path),
+        Err(GitCliError::UntrustedWorktree(_))
+    ));
+    assert!(
+        GitService::new()
+            .register_worktree(&repo_path, &worktree_path)
+            .is_err()
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn host_git_skips_repo_hooks() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let td = TempDir::new().unwrap();
+    let (repo_path, worktree_path) = setup_repo_with_worktree(&td);
+    let hook = repo_path.join(".git/hooks/pre-commit");
+    write_file(&repo_path, ".git/hooks/pre-commit", "#!/bin/sh\nexit 1\n");
+    fs::set_permissions(&hook, fs::Permissions::from_mode(0o755)).unwrap();
+
+    write_file(&worktree_path, "hooked.txt", "change\n");
+    assert!(
+        GitService::new()
+            .commit(&worktree_path, "commit without hooks")
+            .unwrap()
+    );
+}
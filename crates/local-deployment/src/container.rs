@@ -17,6 +17,7 @@ use db::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
         },
         execution_process_repo_state::ExecutionProcessRepoState,
+        project::Project,
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
         session::{Session, SessionError},
@@ -33,9 +34,12 @@ use executors::{
         coding_agent_initial::CodingAgentInitialRequest,
     },
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
-    env::{ExecutionEnv, RepoContext},
+    egress::EgressProxy,
+    env::{ExecutionEnv, RepoContext, Secrets},
     executors::{BaseCodingAgent, CancellationToken, ExecutorExitResult, ExecutorExitSignal},
     logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
+    oci::{self, ContainerConfig, ContainerImage, OciContainer},
+    sandbox::{Sandbox, sandbox_tmp_dir},
 };
 use futures::{FutureExt, TryStreamExt, stream::select};
 use git::GitService;
@@ -51,13 +55,16 @@ use services::services::{
     queued_message::QueuedMessageService,
     remote_client::RemoteClient,
     remote_sync,
+    secrets::SecretVault,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
+    worktree_manager::WorktreeManager,
 };
 use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
+    redaction::Redactor,
     text::{git_branch_id, short_uuid, truncate_to_char_boundary},
 };
 use uuid::Uuid;
@@ -202,6 +209,24 @@ impl LocalContainerService {
                 });
         }
 
+        if let Some(config) = Self::container_config(&repositories)
+            && let Err(e) =
+                oci::remove_workspace_containers(config.runtime, &workspace.id.to_string()).await
+        {
+            tracing::warn!(
+                "Failed to remove containers for workspace {}: {}",
+                workspace.id,
+                e
+            );
+        }
+
+        let sandbox_tmp = sandbox_tmp_dir(&workspace.id.to_string());
+        if sandbox_tmp.exists()
+            && let Err(e) = tokio::fs::remove_dir_all(&sandbox_tmp).await
+        {
+            tracing::warn!("Failed to remove sandbox temp directory: {}", e);
+        }
+
         // Clear container_ref so this workspace won't be picked up again
         let _ = Workspace::clear_container_ref(&db.pool, workspace.id).await;
     }
@@ -627,6 +652,28 @@ impl LocalContainerService {
                     let client = client.clone();
                     let workspace_id = ctx.workspace.id;
                     let archived = ctx.workspace.archived;
+                    // Runs stopped by the user aren't worth a notification.
+                    let finished = match ctx.execution_process.status {
+                        ExecutionProcessStatus::Completed => Some(true),
+                        ExecutionProcessStatus::Failed => Some(false),
+                        _ => None,
+                    };
+                    let prev_completed_at = ExecutionProcess::find_prev_coding_agent_completed_at(
+                        &container.db.pool,
+                        workspace_id,
+                        exec_id,
+                    )
+                    .await
+                    .ok()
+                    .flatten();
+                    let time_entries = remote_sync::coding_agent_time_entries(
+                        exec_id,
+                        ctx.execution_process.started_at,
+                        ctx.execution_process
+                            .completed_at
+                            .unwrap_or_else(chrono::Utc::now),
+                        prev_completed_at,
+                    );
                     tokio::spawn(async move {
                         remote_sync::sync_workspace_to_remote(
                             &client,
@@ -636,6 +683,11 @@ impl LocalContainerService {
                             stats.as_ref(),
                         )
                         .await;
+                        if let Some(success) = finished {
+                            remote_sync::report_workspace_finished(&client, workspace_id, success)
+                                .await;
+                        }
+                        remote_sync::report_time_entries(&client, workspace_id, time_entries).await;
                     });
                 }
             }
@@ -700,9 +752,12 @@ impl LocalContainerService {
         format!("{}-{}", short_uuid(workspace_id), task_title_id)
     }
 
-    async fn track_child_msgs_in_store(&self, id: Uuid, child: &mut AsyncGroupChild) {
-        let store = Arc::new(MsgStore::new());
-
+    async fn track_child_msgs_in_store(
+        &self,
+        id: Uuid,
+        store: Arc<MsgStore>,
+        child: &mut AsyncGroupChild,
+    ) {
         let out = child.inner().stdout.take().expect("no stdout");
         let err = child.inner().stderr.take().expect("no stderr");
 
@@ -954,6 +1009,104 @@ impl LocalContainerService {
         )
         .await
     }
+
+    /// The parts of the repos' git directories that commits from the
+    /// workspace's worktrees write to: each worktree's own metadata, the object
+    /// store, refs and reflogs. Hooks and config are left out so an execution
+    /// can't plant code that later runs outside its sandbox or container. The
+    /// worktree's gitfile and metadata stay writable, so the host verifies them
+    /// before running git in the worktree and runs it without hooks.
+    fn worktree_git_paths(repos: &[Repo], current_dir: &Path) -> Vec<PathBuf> {
+        repos
+            .iter()
+            .flat_map(|repo| {
+                let git_dir = repo.path.join(".git");
+                let worktree_dir = WorktreeManager::find_worktree_git_internal_name(
+                    &repo.path,
+                    &current_dir.join(&repo.name),
+                )
+                .ok()
+                .flatten()
+                .map(|name| git_dir.join("worktrees").join(name));
+
+                worktree_dir
+                    .into_iter()
+                    .chain(["objects", "refs", "logs"].map(|dir| git_dir.join(dir)))
+            })
+            .collect()
+    }
+
+    /// The sandbox an execution runs in, if its project has sandboxing enabled.
+    /// The workspace and the git paths its worktrees commit to stay writable.
+    async fn execution_sandbox(
+        &self,
+        workspace: &Workspace,
+        project: &Project,
+        repos: &[Repo],
+        current_dir: &Path,
+        executor_action: &ExecutorAction,
+    ) -> Result<Option<Sandbox>, ContainerError> {
+        let config = &project.sandbox_config;
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let tmp_dir = sandbox_tmp_dir(&workspace.id.to_string());
+        tokio::fs::create_dir_all(&tmp_dir).await?;
+
+        let writable = std::iter::once(current_dir.to_path_buf())
+            .chain(Self::worktree_git_paths(repos, current_dir))
+            .collect();
+        // Only coding agents lose network access; setup and dev scripts
+        // usually need it
+        let network = !config.disable_network || executor_action.base_executor().is_none();
+
+        Ok(Some(
+            Sandbox::new(config, writable, tmp_dir, network).await?,
+        ))
+    }
+
+    /// The container settings of the first of a workspace's repos that has any
+    fn container_config(repos: &[Repo]) -> Option<&ContainerConfig> {
+        repos
+            .iter()
+            .map(|repo| &*repo.container_config)
+            .find(|config| config.enabled())
+    }
+
+    /// The container an execution runs in, if one of the workspace's repos has
+    /// a container image. Dev servers get the configured ports published. The
+    /// repos' git directories are read-only apart from what worktrees commit to.
+    async fn execution_container(
+        &self,
+        workspace: &Workspace,
+        repos: &[Repo],
+        current_dir: &Path,
+        execution_process: &ExecutionProcess,
+    ) -> Result<Option<OciContainer>, ContainerError> {
+        let Some(repo) = repos.iter().find(|repo| repo.container_config.enabled()) else {
+            return Ok(None);
+        };
+        let config = &repo.container_config;
+
+        let image = ContainerImage::prepare(config, &repo.path).await?;
+        let mounts = std::iter::once(current_dir.to_path_buf())
+            .chain(Self::worktree_git_paths(repos, current_dir))
+            .collect();
+        let read_only = repos.iter().map(|repo| repo.path.join(".git")).collect();
+        let ports = match execution_process.run_reason {
+            ExecutionProcessRunReason::DevServer => config.ports.clone(),
+            _ => Vec::new(),
+        };
+
+        Ok(Some(image.container(
+            oci::container_name(&execution_process.id.to_string()),
+            workspace.id.to_string(),
+            mounts,
+            read_only,
+            ports,
+        )))
+    }
 }
 
 fn failure_exit_status() -> std::process::ExitStatus {
@@ -1182,20 +1335,60 @@ impl ContainerService for LocalContainerService {
             .commit_reminder_prompt
             .clone()
             .unwrap_or_else(|| DEFAULT_COMMIT_REMINDER_PROMPT.to_string());
+        let redactor = Redactor::new(&config.log_redaction);
         drop(config);
+        let task = workspace
+            .parent_task(&self.db.pool)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let project = Project::find_by_id(&self.db.pool, task.project_id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+        let sandbox = self
+            .execution_sandbox(workspace, &project, &repos, &current_dir, executor_action)
+            .await?;
+        let oci_container = self
+            .execution_container(workspace, &repos, &current_dir, execution_process)
+            .await?;
+        let secrets = SecretVault::project_secrets(&self.db.pool, project.id).await?;
         let mut env = ExecutionEnv::new(
             repo_context,
             commit_reminder_enabled,
             commit_reminder_prompt,
-        );
+        )
+        .with_sandbox(sandbox)
+        .with_container(oci_container)
+        .with_secrets(Secrets::new(secrets));
+        env.merge(&project.env_vars);
 
         // Always inject workspace/session context
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
         env.insert("VK_SESSION_ID", execution_process.session_id.to_string());
 
+        // Created before the process so the egress proxy can log to it. Only
+        // agents and dev servers produce enough output to spill to disk.
+        let store = Arc::new(match execution_process.run_reason {
+            ExecutionProcessRunReason::CodingAgent | ExecutionProcessRunReason::DevServer => {
+                MsgStore::with_spill(execution_process.id)
+            }
+            _ => MsgStore::with_id(execution_process.id),
+        });
+        store.set_redactor(redactor.with_values(env.secrets.values().cloned()));
+        if project.egress_policy.restricted() {
+            // Containers can't reach the host's loopback interface
+            let endpoint = match &env.container {
+                Some(container) => Some(container.host_endpoint().await?),
+                None => None,
+            };
+            let proxy =
+                EgressProxy::start(project.egress_policy.0.clone(), store.clone(), endpoint)
+                    .await?;
+            proxy.apply_to_env(&mut env);
+        }
+
         // Create the child and stream, add to execution tracker with timeout
-        let mut spawned = tokio::time::timeout(
+        let spawned = tokio::time::timeout(
             Duration::from_secs(30),
             executor_action.spawn(&current_dir, approvals_service, &env),
         )
@@ -1204,9 +1397,38 @@ impl ContainerService for LocalContainerService {
             ContainerError::Other(anyhow!(
                 "Timeout: process took more than 30 seconds to start"
             ))
-        })??;
+        })
+        .and_then(|spawned| spawned.map_err(ContainerError::from));
+        let mut spawned = match spawned {
+            Ok(spawned) => spawned,
+            Err(e) => {
+                // Stops the egress proxy
+                store.push_finished();
+                return Err(e);
+            }
+        };
+
+        // Dev server ports are published on free host ports; log where, so the
+        // preview finds the host port before the dev server prints its own
+        if execution_process.run_reason == ExecutionProcessRunReason::DevServer
+            && let Some(container) = env.container.clone()
+        {
+            let store = store.clone();
+            tokio::spawn(async move {
+                match container.published_ports().await {
+                    Ok(ports) => {
+                        for (port, host_port) in ports {
+                            store.push_stdout(format!(
+                                "Container port {port} is available at http://localhost:{host_port}\n"
+                            ));
+                        }
+                    }
+                    Err(e) => tracing::warn!("Failed to read published container ports: {}", e),
+                }
+            });
+        }
 
-        self.track_child_msgs_in_store(execution_process.id, &mut spawned.child)
+        self.track_child_msgs_in_store(execution_process.id, store, &mut spawned.child)
             .await;
 
         self.add_child_to_store(execution_process.id, spawned.child)
@@ -1288,8 +1510,23 @@ impl ContainerService for LocalContainerService {
             let _ = tokio::time::timeout(Duration::from_secs(5), handle).await;
         }
 
+        let ctx = ExecutionProcess::load_context(&self.db.pool, execution_process.id)
+            .await
+            .ok();
+
+        // The runtime client may have been killed before it stopped the container
+        if let Some(config) = ctx
+            .as_ref()
+            .and_then(|ctx| Self::container_config(&ctx.repos))
+        {
+            let name = oci::container_name(&execution_process.id.to_string());
+            if let Err(e) = oci::remove_container(config.runtime, &name).await {
+                tracing::warn!("Failed to remove container {name}: {e}");
+            }
+        }
+
         // Update task status to InReview when execution is stopped
-        if let Ok(ctx) = ExecutionProcess::load_context(&self.db.pool, execution_process.id).await
+        if let Some(ctx) = ctx
             && !matches!(
                 ctx.execution_process.run_reason,
                 ExecutionProcessRunReason::DevServer
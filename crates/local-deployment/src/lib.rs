@@ -16,6 +16,7 @@ use services::services::{
     file_search::FileSearchCache,
     filesystem::FilesystemService,
     image::ImageService,
+    issue_scheduler::IssueSchedulerService,
     oauth_credentials::OAuthCredentials,
     pr_monitor::PrMonitorService,
     project::ProjectService,
@@ -189,6 +190,7 @@ impl Deployment for LocalDeployment {
         let file_search_cache = Arc::new(FileSearchCache::new());
 
         let pty = PtyService::new();
+        pty.spawn_reaper();
         {
             let db = db.clone();
             let analytics = analytics.as_ref().map(|s| AnalyticsContext {
@@ -199,6 +201,9 @@ impl Deployment for LocalDeployment {
             let rc = remote_client.clone().ok();
             PrMonitorService::spawn(db, analytics, container, rc).await;
         }
+        if let Ok(rc) = &remote_client {
+            IssueSchedulerService::spawn(db.clone(), container.clone(), rc.clone()).await;
+        }
 
         let deployment = Self {
             config,
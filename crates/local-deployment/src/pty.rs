@@ -1,17 +1,32 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     io::{Read, Write},
     path::PathBuf,
-    sync::{Arc, Mutex},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
     thread,
+    time::{Duration, Instant},
 };
 
-use portable_pty::{CommandBuilder, NativePtySystem, PtySize, PtySystem};
+use chrono::{DateTime, Utc};
+use portable_pty::{ChildKiller, CommandBuilder, NativePtySystem, PtySize, PtySystem};
 use thiserror::Error;
-use tokio::sync::mpsc;
+use tokio::{sync::broadcast, task::JoinHandle};
 use utils::shell::get_interactive_shell;
 use uuid::Uuid;
 
+/// Output retained per session and replayed to clients when they (re)attach.
+const SCROLLBACK_BYTES: usize = 512 * 1024;
+/// How long a session with no attached clients is kept alive before its shell
+/// is killed.
+const DETACHED_SESSION_TTL: Duration = Duration::from_secs(60 * 60);
+/// How often detached sessions are checked against `DETACHED_SESSION_TTL`.
+const REAP_INTERVAL: Duration = Duration::from_secs(60);
+/// How far past the trim point to look for a newline to cut the scrollback at.
+const TRIM_SEARCH_BYTES: usize = 4 * 1024;
+
 #[derive(Debug, Error)]
 pub enum PtyError {
     #[error("Failed to create PTY: {0}")]
@@ -24,35 +39,209 @@ pub enum PtyError {
     ResizeFailed(String),
     #[error("Session already closed")]
     SessionClosed,
+    #[error("Session is attached read-only")]
+    ReadOnly,
+}
+
+#[derive(Debug, Clone)]
+pub enum PtyOutput {
+    Data(Vec<u8>),
+    Exited,
+}
+
+#[derive(Debug, Clone)]
+pub struct PtySessionInfo {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub attached_clients: usize,
+    pub exited: bool,
+}
+
+#[derive(Default)]
+struct Scrollback {
+    buf: VecDeque<u8>,
+    exited: bool,
+}
+
+/// State shared between a session, its output thread and attached clients.
+struct SessionOutput {
+    scrollback: Mutex<Scrollback>,
+    sender: broadcast::Sender<PtyOutput>,
+    clients: AtomicUsize,
+    /// Attachments allowed to type into and resize the session.
+    writers: Mutex<HashSet<Uuid>>,
+    detached_at: Mutex<Option<Instant>>,
+}
+
+impl SessionOutput {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(1024);
+        Self {
+            scrollback: Mutex::new(Scrollback::default()),
+            sender,
+            clients: AtomicUsize::new(0),
+            writers: Mutex::new(HashSet::new()),
+            detached_at: Mutex::new(Some(Instant::now())),
+        }
+    }
+
+    fn push(&self, data: &[u8]) {
+        let mut scrollback = self.scrollback.lock().unwrap();
+        scrollback.buf.extend(data);
+        trim_scrollback(&mut scrollback.buf);
+        // Broadcast under the lock so attach never misses or duplicates a chunk.
+        let _ = self.sender.send(PtyOutput::Data(data.to_vec()));
+    }
+
+    fn mark_exited(&self) {
+        let mut scrollback = self.scrollback.lock().unwrap();
+        scrollback.exited = true;
+        let _ = self.sender.send(PtyOutput::Exited);
+    }
+
+    fn is_exited(&self) -> bool {
+        self.scrollback.lock().unwrap().exited
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        if self.clients.load(Ordering::SeqCst) > 0 {
+            return false;
+        }
+        match *self.detached_at.lock().unwrap() {
+            Some(at) => self.is_exited() || at.elapsed() > ttl,
+            None => false,
+        }
+    }
+
+    fn is_writer(&self, attachment_id: Uuid) -> bool {
+        self.writers.lock().unwrap().contains(&attachment_id)
+    }
+}
+
+/// Drop the oldest output beyond `SCROLLBACK_BYTES`. The cut is moved forward
+/// to just after a newline, so a replay doesn't start mid-line or inside an
+/// escape sequence, or failing that to a UTF-8 character boundary.
+fn trim_scrollback(buf: &mut VecDeque<u8>) {
+    let excess = buf.len().saturating_sub(SCROLLBACK_BYTES);
+    if excess == 0 {
+        return;
+    }
+    let cut = buf
+        .iter()
+        .skip(excess)
+        .take(TRIM_SEARCH_BYTES)
+        .position(|&b| b == b'\n')
+        .map(|pos| excess + pos + 1)
+        .unwrap_or_else(|| {
+            excess
+                + buf
+                    .iter()
+                    .skip(excess)
+                    .take_while(|&&b| b & 0xC0 == 0x80)
+                    .count()
+        });
+    buf.drain(..cut);
+}
+
+/// A client's view of a session: the scrollback at the time of attaching,
+/// followed by live output. The session counts as detached once every
+/// attachment has been dropped.
+pub struct PtyAttachment {
+    /// Identifies the client to `write` and `resize`.
+    pub id: Uuid,
+    /// Input and resizes from a read-only attachment are rejected.
+    pub read_only: bool,
+    pub scrollback: Vec<u8>,
+    pub exited: bool,
+    pub output: broadcast::Receiver<PtyOutput>,
+    shared: Arc<SessionOutput>,
+}
+
+impl Drop for PtyAttachment {
+    fn drop(&mut self) {
+        self.shared.writers.lock().unwrap().remove(&self.id);
+        if self.shared.clients.fetch_sub(1, Ordering::SeqCst) == 1 {
+            *self.shared.detached_at.lock().unwrap() = Some(Instant::now());
+        }
+    }
 }
 
 struct PtySession {
+    workspace_id: Uuid,
+    created_at: DateTime<Utc>,
     writer: Box<dyn Write + Send>,
     master: Box<dyn portable_pty::MasterPty + Send>,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    output: Arc<SessionOutput>,
     _output_handle: thread::JoinHandle<()>,
     closed: bool,
 }
 
+impl PtySession {
+    fn info(&self, id: Uuid) -> PtySessionInfo {
+        PtySessionInfo {
+            id,
+            workspace_id: self.workspace_id,
+            created_at: self.created_at,
+            attached_clients: self.output.clients.load(Ordering::SeqCst),
+            exited: self.output.is_exited(),
+        }
+    }
+
+    fn kill(mut self) {
+        self.closed = true;
+        if !self.output.is_exited() {
+            let _ = self.killer.kill();
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct PtyService {
     sessions: Arc<Mutex<HashMap<Uuid, PtySession>>>,
+    detached_ttl: Duration,
 }
 
 impl PtyService {
     pub fn new() -> Self {
+        Self::with_detached_ttl(DETACHED_SESSION_TTL)
+    }
+
+    fn with_detached_ttl(detached_ttl: Duration) -> Self {
         Self {
             sessions: Arc::new(Mutex::new(HashMap::new())),
+            detached_ttl,
         }
     }
 
+    /// Periodically reap detached sessions, so an idle server doesn't keep
+    /// abandoned shells alive until the next session is created or listed.
+    pub fn spawn_reaper(&self) -> JoinHandle<()> {
+        let service = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(REAP_INTERVAL);
+            loop {
+                interval.tick().await;
+                service.reap_detached();
+            }
+        })
+    }
+
+    /// Spawn a shell for `workspace_id`. The session outlives any single
+    /// client; use `attach` to receive its output.
     pub async fn create_session(
         &self,
+        workspace_id: Uuid,
         working_dir: PathBuf,
         cols: u16,
         rows: u16,
-    ) -> Result<(Uuid, mpsc::UnboundedReceiver<Vec<u8>>), PtyError> {
+    ) -> Result<Uuid, PtyError> {
+        self.reap_detached();
+
         let session_id = Uuid::new_v4();
-        let (output_tx, output_rx) = mpsc::unbounded_channel();
+        let output = Arc::new(SessionOutput::new());
+        let output_for_thread = output.clone();
         let shell = get_interactive_shell().await;
 
         let result = tokio::task::spawn_blocking(move || {
@@ -98,6 +287,7 @@ impl PtyService {
                 .slave
                 .spawn_command(cmd)
                 .map_err(|e| PtyError::CreateFailed(e.to_string()))?;
+            let killer = child.clone_killer();
 
             let mut writer = pty_pair
                 .master
@@ -121,27 +311,28 @@ impl PtyService {
                 loop {
                     match reader.read(&mut buf) {
                         Ok(0) => break,
-                        Ok(n) => {
-                            if output_tx.send(buf[..n].to_vec()).is_err() {
-                                break;
-                            }
-                        }
+                        Ok(n) => output_for_thread.push(&buf[..n]),
                         Err(_) => break,
                     }
                 }
+                output_for_thread.mark_exited();
                 drop(child);
             });
 
-            Ok::<_, PtyError>((pty_pair.master, writer, output_handle))
+            Ok::<_, PtyError>((pty_pair.master, writer, killer, output_handle))
         })
         .await
         .map_err(|e| PtyError::CreateFailed(e.to_string()))??;
 
-        let (master, writer, output_handle) = result;
+        let (master, writer, killer, output_handle) = result;
 
         let session = PtySession {
+            workspace_id,
+            created_at: Utc::now(),
             writer,
             master,
+            killer,
+            output,
             _output_handle: output_handle,
             closed: false,
         };
@@ -151,10 +342,114 @@ impl PtyService {
             .map_err(|e| PtyError::CreateFailed(e.to_string()))?
             .insert(session_id, session);
 
-        Ok((session_id, output_rx))
+        Ok(session_id)
+    }
+
+    /// Attach a client to a running session, replaying its scrollback.
+    /// Read-only attachments only receive output.
+    pub fn attach(&self, session_id: Uuid, read_only: bool) -> Result<PtyAttachment, PtyError> {
+        let shared = self
+            .sessions
+            .lock()
+            .map_err(|_| PtyError::SessionClosed)?
+            .get(&session_id)
+            .ok_or(PtyError::SessionNotFound(session_id))?
+            .output
+            .clone();
+
+        let id = Uuid::new_v4();
+        if !read_only {
+            shared.writers.lock().unwrap().insert(id);
+        }
+
+        let scrollback = shared.scrollback.lock().unwrap();
+        let output = shared.sender.subscribe();
+        let attachment = PtyAttachment {
+            id,
+            read_only,
+            scrollback: scrollback.buf.iter().copied().collect(),
+            exited: scrollback.exited,
+            output,
+            shared: shared.clone(),
+        };
+        drop(scrollback);
+
+        shared.clients.fetch_add(1, Ordering::SeqCst);
+        *shared.detached_at.lock().unwrap() = None;
+        Ok(attachment)
+    }
+
+    /// The last `max_bytes` of a session's scrollback, without attaching.
+    pub fn recent_output(
+        &self,
+        session_id: Uuid,
+        max_bytes: usize,
+    ) -> Result<(Vec<u8>, bool), PtyError> {
+        let shared = self
+            .sessions
+            .lock()
+            .map_err(|_| PtyError::SessionClosed)?
+            .get(&session_id)
+            .ok_or(PtyError::SessionNotFound(session_id))?
+            .output
+            .clone();
+
+        let scrollback = shared.scrollback.lock().unwrap();
+        let skip = scrollback.buf.len().saturating_sub(max_bytes);
+        Ok((
+            scrollback.buf.iter().skip(skip).copied().collect(),
+            scrollback.exited,
+        ))
+    }
+
+    pub fn list_sessions(&self, workspace_id: Uuid) -> Vec<PtySessionInfo> {
+        self.reap_detached();
+
+        let Ok(sessions) = self.sessions.lock() else {
+            return Vec::new();
+        };
+        let mut infos: Vec<PtySessionInfo> = sessions
+            .iter()
+            .filter(|(_, session)| session.workspace_id == workspace_id)
+            .map(|(id, session)| session.info(*id))
+            .collect();
+        infos.sort_by_key(|info| info.created_at);
+        infos
+    }
+
+    pub fn session_workspace(&self, session_id: &Uuid) -> Option<Uuid> {
+        self.sessions
+            .lock()
+            .ok()?
+            .get(session_id)
+            .map(|session| session.workspace_id)
+    }
+
+    /// Kill sessions whose shell has exited, or that have had no attached
+    /// client for longer than `DETACHED_SESSION_TTL`.
+    fn reap_detached(&self) {
+        let Ok(mut sessions) = self.sessions.lock() else {
+            return;
+        };
+        let expired: Vec<Uuid> = sessions
+            .iter()
+            .filter(|(_, session)| session.output.is_expired(self.detached_ttl))
+            .map(|(id, _)| *id)
+            .collect();
+        for id in expired {
+            if let Some(session) = sessions.remove(&id) {
+                tracing::debug!("Reaping detached PTY session {}", id);
+                session.kill();
+            }
+        }
     }
 
-    pub async fn write(&self, session_id: Uuid, data: &[u8]) -> Result<(), PtyError> {
+    pub async fn write(
+        &self,
+        session_id: Uuid,
+        attachment_id: Uuid,
+        data: &[u8],
+    ) -> Result<(), PtyError> {
         let mut sessions = self
             .sessions
             .lock()
@@ -166,6 +461,9 @@ impl PtyService {
         if session.closed {
             return Err(PtyError::SessionClosed);
         }
+        if !session.output.is_writer(attachment_id) {
+            return Err(PtyError::ReadOnly);
+        }
 
         session
             .writer
@@ -180,7 +478,13 @@ impl PtyService {
         Ok(())
     }
 
-    pub async fn resize(&self, session_id: Uuid, cols: u16, rows: u16) -> Result<(), PtyError> {
+    pub async fn resize(
+        &self,
+        session_id: Uuid,
+        attachment_id: Uuid,
+        cols: u16,
+        rows: u16,
+    ) -> Result<(), PtyError> {
         let sessions = self
             .sessions
             .lock()
@@ -192,6 +496,9 @@ impl PtyService {
         if session.closed {
             return Err(PtyError::SessionClosed);
         }
+        if !session.output.is_writer(attachment_id) {
+            return Err(PtyError::ReadOnly);
+        }
 
         session
             .master
@@ -206,14 +513,34 @@ impl PtyService {
         Ok(())
     }
 
+    /// Kill a session on behalf of an attached client, which must be able to
+    /// write to it.
+    pub async fn close_attached_session(
+        &self,
+        session_id: Uuid,
+        attachment_id: Uuid,
+    ) -> Result<(), PtyError> {
+        let mut sessions = self.sessions.lock().map_err(|_| PtyError::SessionClosed)?;
+        let session = sessions
+            .get(&session_id)
+            .ok_or(PtyError::SessionNotFound(session_id))?;
+        if !session.output.is_writer(attachment_id) {
+            return Err(PtyError::ReadOnly);
+        }
+        if let Some(session) = sessions.remove(&session_id) {
+            session.kill();
+        }
+        Ok(())
+    }
+
     pub async fn close_session(&self, session_id: Uuid) -> Result<(), PtyError> {
-        if let Some(mut session) = self
+        if let Some(session) = self
             .sessions
             .lock()
             .map_err(|_| PtyError::SessionClosed)?
             .remove(&session_id)
         {
-            session.closed = true;
+            session.kill();
         }
         Ok(())
     }
@@ -231,3 +558,90 @@ impl Default for PtyService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    async fn wait_for_output(attachment: &mut PtyAttachment, needle: &str) {
+        let mut seen = String::from_utf8_lossy(&attachment.scrollback).into_owned();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !seen.contains(needle) {
+                match attachment.output.recv().await {
+                    Ok(PtyOutput::Data(data)) => seen.push_str(&String::from_utf8_lossy(&data)),
+                    Ok(PtyOutput::Exited) | Err(_) => break,
+                }
+            }
+        })
+        .await
+        .expect("timed out waiting for PTY output");
+        assert!(seen.contains(needle), "missing {needle:?} in {seen:?}");
+    }
+
+    #[test]
+    fn trims_scrollback_after_a_newline() {
+        let mut buf: VecDeque<u8> = std::iter::repeat_n(b'a', 200).collect();
+        buf.push_back(b'\n');
+        buf.extend(std::iter::repeat_n(b'b', SCROLLBACK_BYTES - 100));
+        trim_scrollback(&mut buf);
+        assert_eq!(buf.len(), SCROLLBACK_BYTES - 100);
+        assert!(buf.iter().all(|&b| b == b'b'));
+    }
+
+    #[test]
+    fn trims_scrollback_at_a_char_boundary() {
+        let mut buf: VecDeque<u8> = "é".repeat(SCROLLBACK_BYTES).into_bytes().into();
+        trim_scrollback(&mut buf);
+        let bytes: Vec<u8> = buf.into_iter().collect();
+        assert!(String::from_utf8(bytes).is_ok());
+    }
+
+    #[tokio::test]
+    async fn reattach_replays_scrollback() {
+        let dir = TempDir::new().unwrap();
+        let service = PtyService::new();
+        let session_id = service
+            .create_session(Uuid::new_v4(), dir.path().to_path_buf(), 80, 24)
+            .await
+            .unwrap();
+
+        let mut first = service.attach(session_id, false).unwrap();
+        service
+            .write(session_id, first.id, b"echo pty-marker-$((40 + 2))\n")
+            .await
+            .unwrap();
+        wait_for_output(&mut first, "pty-marker-42").await;
+        drop(first);
+
+        let viewer = service.attach(session_id, true).unwrap();
+        assert!(String::from_utf8_lossy(&viewer.scrollback).contains("pty-marker-42"));
+        assert!(matches!(
+            service.write(session_id, viewer.id, b"exit\n").await,
+            Err(PtyError::ReadOnly)
+        ));
+
+        service.close_session(session_id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reaps_sessions_detached_past_ttl() {
+        let dir = TempDir::new().unwrap();
+        let workspace_id = Uuid::new_v4();
+        let service = PtyService::with_detached_ttl(Duration::ZERO);
+        let session_id = service
+            .create_session(workspace_id, dir.path().to_path_buf(), 80, 24)
+            .await
+            .unwrap();
+
+        let attachment = service.attach(session_id, false).unwrap();
+        service.reap_detached();
+        assert!(service.session_exists(&session_id));
+
+        drop(attachment);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        service.reap_detached();
+        assert!(!service.session_exists(&session_id));
+    }
+}
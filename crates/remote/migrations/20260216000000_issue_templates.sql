@@ -0,0 +1,31 @@
+-- Issue templates: reusable issue content with title/description placeholders
+-- and default fields, optionally filed on a cron-like recurrence schedule.
+CREATE TABLE issue_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    status_id UUID NOT NULL REFERENCES project_statuses(id) ON DELETE CASCADE,
+    priority issue_priority,
+    tag_ids UUID[] NOT NULL DEFAULT '{}',
+    assignee_ids UUID[] NOT NULL DEFAULT '{}',
+    -- Five-field cron expression evaluated in UTC; NULL for manual-only templates
+    recurrence TEXT,
+    next_run_at TIMESTAMPTZ,
+    -- Executor profile for the local scheduler to auto-start a workspace with
+    auto_start_executor_profile JSONB,
+    creator_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (project_id, name)
+);
+
+CREATE INDEX idx_issue_templates_project_id ON issue_templates(project_id);
+CREATE INDEX idx_issue_templates_next_run_at ON issue_templates(next_run_at)
+    WHERE next_run_at IS NOT NULL;
+
+CREATE TRIGGER trg_issue_templates_updated_at
+    BEFORE UPDATE ON issue_templates
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
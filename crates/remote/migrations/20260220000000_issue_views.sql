@@ -0,0 +1,23 @@
+-- Saved issue views: named queries in the issue query language. Views with
+-- an owner are private to that user; views without one are shared with the
+-- project's organization.
+CREATE TABLE issue_views (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id UUID NOT NULL REFERENCES projects(id) ON DELETE CASCADE,
+    owner_user_id UUID REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    query TEXT NOT NULL,
+    sort_order DOUBLE PRECISION NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX uniq_issue_views_shared_name
+    ON issue_views(project_id, name) WHERE owner_user_id IS NULL;
+CREATE UNIQUE INDEX uniq_issue_views_private_name
+    ON issue_views(project_id, owner_user_id, name) WHERE owner_user_id IS NOT NULL;
+
+CREATE TRIGGER trg_issue_views_updated_at
+    BEFORE UPDATE ON issue_views
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
@@ -0,0 +1,28 @@
+-- Notifications for mentions, workspace runs, pull requests and blocking
+-- changes, plus per-user email digests of unseen notifications.
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'issue_mentioned';
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'issue_workspace_finished';
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'issue_pull_request_opened';
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'issue_pull_request_merged';
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'issue_blocked';
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'issue_unblocked';
+
+CREATE TYPE digest_frequency AS ENUM ('off', 'daily', 'weekly');
+
+CREATE TABLE notification_digest_settings (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    frequency digest_frequency NOT NULL DEFAULT 'off',
+    -- Notifications created after this point go into the next digest
+    last_sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notification_digest_settings_enabled
+    ON notification_digest_settings(last_sent_at)
+    WHERE frequency <> 'off';
+
+CREATE TRIGGER trg_notification_digest_settings_updated_at
+    BEFORE UPDATE ON notification_digest_settings
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
@@ -0,0 +1,55 @@
+-- Issue estimates, logged effort and the status history behind burndown
+-- charts.
+CREATE TYPE estimate_unit AS ENUM ('points', 'hours');
+
+ALTER TABLE issues
+    ADD COLUMN estimate DOUBLE PRECISION CHECK (estimate IS NULL OR estimate >= 0),
+    ADD COLUMN estimate_unit estimate_unit NOT NULL DEFAULT 'points';
+
+CREATE TYPE time_entry_source AS ENUM ('agent', 'human', 'manual');
+
+-- Effort spent on an issue. Agent and human entries are reported by the
+-- local app from execution processes on the issue's linked workspaces;
+-- manual entries are logged by hand.
+CREATE TABLE issue_time_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    workspace_id UUID REFERENCES workspaces(id) ON DELETE SET NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    source time_entry_source NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    duration_seconds BIGINT NOT NULL CHECK (duration_seconds >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_time_entries_issue_id ON issue_time_entries(issue_id);
+
+-- Every status an issue has been in, so the board can be replayed day by day.
+CREATE TABLE issue_status_changes (
+    id BIGSERIAL PRIMARY KEY,
+    issue_id UUID NOT NULL REFERENCES issues(id) ON DELETE CASCADE,
+    status_id UUID NOT NULL REFERENCES project_statuses(id) ON DELETE CASCADE,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_issue_status_changes_issue_id
+    ON issue_status_changes(issue_id, changed_at);
+
+INSERT INTO issue_status_changes (issue_id, status_id, changed_at)
+SELECT id, status_id, created_at FROM issues;
+
+CREATE OR REPLACE FUNCTION record_issue_status_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' OR NEW.status_id IS DISTINCT FROM OLD.status_id THEN
+        INSERT INTO issue_status_changes (issue_id, status_id)
+        VALUES (NEW.id, NEW.status_id);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_issues_status_change
+    AFTER INSERT OR UPDATE OF status_id ON issues
+    FOR EACH ROW
+    EXECUTE FUNCTION record_issue_status_change();
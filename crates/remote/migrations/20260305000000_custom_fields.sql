@@ -0,0 +1,31 @@
+-- Organization-level custom field definitions. Issue values live in
+-- issues.extension_metadata under "custom_fields", keyed by field key, and
+-- are validated against these definitions by the API.
+CREATE TYPE custom_field_type AS ENUM (
+    'text',
+    'number',
+    'select',
+    'multi_select',
+    'user',
+    'date'
+);
+
+CREATE TABLE custom_fields (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    organization_id UUID NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    key VARCHAR(64) NOT NULL CHECK (key ~ '^[a-z][a-z0-9_]*$'),
+    name VARCHAR(255) NOT NULL,
+    field_type custom_field_type NOT NULL,
+    options TEXT[] NOT NULL DEFAULT '{}',
+    sort_order DOUBLE PRECISION NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (organization_id, key)
+);
+
+CREATE TRIGGER trg_custom_fields_updated_at
+    BEFORE UPDATE ON custom_fields
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+SELECT electric_sync_table('public', 'custom_fields');
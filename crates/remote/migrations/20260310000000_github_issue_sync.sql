@@ -0,0 +1,62 @@
+-- Optional two-way sync between a project's issues and the GitHub Issues of a
+-- repository the organization's GitHub App installation can access.
+CREATE TABLE github_issue_syncs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    project_id UUID NOT NULL UNIQUE REFERENCES projects(id) ON DELETE CASCADE,
+    installation_id UUID NOT NULL REFERENCES github_app_installations(id) ON DELETE CASCADE,
+    github_repo_id BIGINT NOT NULL,
+    repo_full_name TEXT NOT NULL,
+    -- Board statuses for open and closed GitHub issues.
+    open_status_id UUID NOT NULL REFERENCES project_statuses(id) ON DELETE CASCADE,
+    closed_status_id UUID NOT NULL REFERENCES project_statuses(id) ON DELETE CASCADE,
+    -- Also open GitHub issues for board issues created after sync was set up.
+    export_new_issues BOOLEAN NOT NULL DEFAULT false,
+    enabled BOOLEAN NOT NULL DEFAULT true,
+    created_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    last_reconciled_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_github_issue_syncs_repo ON github_issue_syncs(github_repo_id);
+
+CREATE TRIGGER trg_github_issue_syncs_updated_at
+    BEFORE UPDATE ON github_issue_syncs
+    FOR EACH ROW
+    EXECUTE FUNCTION set_updated_at();
+
+-- Which GitHub issue each board issue mirrors, with the synced fields as
+-- they were after the last sync. Later changes on either side are merged
+-- against this base. Deleting either side leaves the link as a tombstone
+-- (no issue, or `unlinked`) so the other side isn't mirrored again.
+CREATE TABLE github_issue_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    sync_id UUID NOT NULL REFERENCES github_issue_syncs(id) ON DELETE CASCADE,
+    issue_id UUID UNIQUE REFERENCES issues(id) ON DELETE SET NULL,
+    github_issue_number BIGINT NOT NULL,
+    title TEXT NOT NULL,
+    body TEXT NOT NULL,
+    closed BOOLEAN NOT NULL,
+    -- Sorted label names.
+    labels TEXT[] NOT NULL DEFAULT '{}',
+    unlinked BOOLEAN NOT NULL DEFAULT false,
+    synced_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (sync_id, github_issue_number)
+);
+
+-- Comments mirrored between a linked issue pair. Bodies are kept per side
+-- because comments from GitHub gain an attribution line on the board.
+-- Deleted comments leave tombstones like issue links.
+CREATE TABLE github_comment_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    link_id UUID NOT NULL REFERENCES github_issue_links(id) ON DELETE CASCADE,
+    comment_id UUID UNIQUE REFERENCES issue_comments(id) ON DELETE SET NULL,
+    github_comment_id BIGINT NOT NULL UNIQUE,
+    message TEXT NOT NULL,
+    github_body TEXT NOT NULL,
+    unlinked BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_github_comment_links_link_id ON github_comment_links(link_id);
@@ -0,0 +1,4 @@
+-- Publish webhook review results back to the pull request as a GitHub review
+-- with inline comments, instead of only linking to the hosted story.
+ALTER TABLE github_app_repositories
+ADD COLUMN review_inline_comments BOOLEAN NOT NULL DEFAULT false;
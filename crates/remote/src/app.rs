@@ -15,9 +15,11 @@ use crate::{
     config::RemoteServerConfig,
     db,
     github_app::GitHubAppService,
-    mail::LoopsMailer,
+    github_issue_sync,
+    mail::{LoopsMailer, Mailer},
+    notifications,
     r2::R2Service,
-    routes,
+    recurrence, routes,
 };
 
 pub struct Server;
@@ -80,7 +82,13 @@ impl Server {
 
         let api_key = std::env::var("LOOPS_EMAIL_API_KEY")
             .context("LOOPS_EMAIL_API_KEY environment variable is required")?;
-        let mailer = Arc::new(LoopsMailer::new(api_key));
+        let digest_template_id = std::env::var("LOOPS_DIGEST_TEMPLATE_ID").ok();
+        let mailer: Arc<dyn Mailer> = match digest_template_id.clone() {
+            Some(template_id) => {
+                Arc::new(LoopsMailer::new(api_key).with_digest_template(template_id))
+            }
+            None => Arc::new(LoopsMailer::new(api_key)),
+        };
 
         let server_public_base_url = config.server_public_base_url.clone().ok_or_else(|| {
             anyhow::anyhow!(
@@ -145,13 +153,17 @@ impl Server {
             }
         };
 
+        if let Some(github_app) = &github_app {
+            github_issue_sync::spawn_worker(pool.clone(), github_app.clone());
+        }
+
         let state = AppState::new(
             pool.clone(),
             config.clone(),
             jwt,
             handoff_service,
             oauth_token_validator,
-            mailer,
+            mailer.clone(),
             server_public_base_url,
             http_client,
             r2,
@@ -160,6 +172,15 @@ impl Server {
             analytics,
         );
 
+        recurrence::spawn_worker(pool.clone());
+        if digest_template_id.is_some() {
+            notifications::digest::spawn_worker(pool.clone(), mailer);
+        } else {
+            tracing::info!(
+                "Notification digests not configured. Set LOOPS_DIGEST_TEMPLATE_ID to enable."
+            );
+        }
+
         let router = routes::router(state);
         let addr: SocketAddr = config
             .listen_addr
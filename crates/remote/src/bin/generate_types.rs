@@ -6,15 +6,19 @@ use remote::{
 };
 use ts_rs::TS;
 use api_types::{
+    AutoStartExecutorProfile, CreateCustomFieldRequest, CustomField, CustomFieldType, UpdateCustomFieldRequest,
     CreateIssueAssigneeRequest, CreateIssueCommentReactionRequest, CreateIssueCommentRequest,
     CreateIssueFollowerRequest, CreateIssueRelationshipRequest, CreateIssueRequest,
-    CreateIssueTagRequest, CreateNotificationRequest, CreateProjectRequest,
-    CreateProjectStatusRequest, CreateTagRequest, Issue, IssueAssignee, IssueComment,
-    IssueCommentReaction, IssueFollower, IssueRelationship, IssueRelationshipType, IssueTag,
-    IssuePriority, MemberRole, Notification, NotificationType, OrganizationMember, Project,
-    ProjectStatus, PullRequest, PullRequestStatus, Tag, UpdateIssueCommentReactionRequest,
-    UpdateIssueCommentRequest, UpdateIssueRequest, UpdateNotificationRequest, UpdateProjectRequest,
-    UpdateProjectStatusRequest, UpdateTagRequest, User, UserData, Workspace,
+    CreateIssueTagRequest, CreateIssueTemplateRequest, CreateIssueTimeEntryRequest,
+    CreateIssueViewRequest, EstimateUnit, IssueTimeEntry, TimeEntrySource,
+    CreateNotificationRequest, CreateProjectRequest, CreateProjectStatusRequest, CreateTagRequest,
+    DigestFrequency, Issue, IssueAssignee, IssueComment, IssueCommentReaction, IssueFollower,
+    IssueRelationship, IssueRelationshipType, IssueTag, IssueTemplate, IssueView, IssuePriority,
+    MemberRole, Notification, NotificationDigestSettings, NotificationType, OrganizationMember,
+    Project, ProjectStatus, PullRequest, PullRequestStatus, Tag, UpdateIssueCommentReactionRequest,
+    UpdateIssueCommentRequest, UpdateIssueRequest, UpdateIssueTemplateRequest,
+    UpdateIssueViewRequest, UpdateNotificationDigestSettingsRequest, UpdateNotificationRequest,
+    UpdateProjectRequest, UpdateProjectStatusRequest, UpdateTagRequest, User, UserData, Workspace,
 };
 
 fn main() {
@@ -67,18 +71,27 @@ fn export_shapes() -> String {
         Project::decl(),
         Notification::decl(),
         NotificationType::decl(),
+        DigestFrequency::decl(),
+        NotificationDigestSettings::decl(),
         Workspace::decl(),
         ProjectStatus::decl(),
         Tag::decl(),
+        CustomField::decl(),
+        CustomFieldType::decl(),
         Issue::decl(),
         IssueAssignee::decl(),
         IssueFollower::decl(),
         IssueTag::decl(),
         IssueRelationship::decl(),
         IssueRelationshipType::decl(),
+        IssueTemplate::decl(),
+        IssueView::decl(),
+        IssueTimeEntry::decl(),
+        TimeEntrySource::decl(),
         IssueComment::decl(),
         IssueCommentReaction::decl(),
         IssuePriority::decl(),
+        EstimateUnit::decl(),
         PullRequestStatus::decl(),
         PullRequest::decl(),
         UserData::decl(),
@@ -90,8 +103,11 @@ fn export_shapes() -> String {
         UpdateProjectRequest::decl(),
         CreateNotificationRequest::decl(),
         UpdateNotificationRequest::decl(),
+        UpdateNotificationDigestSettingsRequest::decl(),
         CreateTagRequest::decl(),
         UpdateTagRequest::decl(),
+        CreateCustomFieldRequest::decl(),
+        UpdateCustomFieldRequest::decl(),
         CreateProjectStatusRequest::decl(),
         UpdateProjectStatusRequest::decl(),
         CreateIssueRequest::decl(),
@@ -100,6 +116,12 @@ fn export_shapes() -> String {
         CreateIssueFollowerRequest::decl(),
         CreateIssueTagRequest::decl(),
         CreateIssueRelationshipRequest::decl(),
+        AutoStartExecutorProfile::decl(),
+        CreateIssueTemplateRequest::decl(),
+        UpdateIssueTemplateRequest::decl(),
+        CreateIssueViewRequest::decl(),
+        UpdateIssueViewRequest::decl(),
+        CreateIssueTimeEntryRequest::decl(),
         CreateIssueCommentRequest::decl(),
         UpdateIssueCommentRequest::decl(),
         CreateIssueCommentReactionRequest::decl(),
@@ -0,0 +1,219 @@
+//! Validation of custom field definitions and of the values issues store
+//! for them under `extension_metadata.custom_fields`.
+
+use std::collections::HashSet;
+
+use api_types::{CUSTOM_FIELDS_METADATA_KEY, CustomField, CustomFieldType};
+use chrono::NaiveDate;
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+const MAX_KEY_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub(crate) enum CustomFieldValueError {
+    #[error("`custom_fields` must be an object")]
+    NotAnObject,
+    #[error("unknown custom field `{0}`")]
+    Unknown(String),
+    #[error("custom field `{key}` expects {expected}")]
+    WrongType { key: String, expected: &'static str },
+    #[error("`{value}` is not an option of custom field `{key}`")]
+    UnknownOption { key: String, value: String },
+}
+
+/// Keys are lowercase identifiers so they can be used in issue queries.
+pub(crate) fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    key.len() <= MAX_KEY_LEN
+        && chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Select fields need a set of distinct, non-empty options; other fields
+/// take none.
+pub(crate) fn validate_options(
+    field_type: CustomFieldType,
+    options: &[String],
+) -> Result<(), &'static str> {
+    match field_type {
+        CustomFieldType::Select | CustomFieldType::MultiSelect => {
+            if options.is_empty() {
+                return Err("select fields need at least one option");
+            }
+            let mut seen = HashSet::new();
+            if options
+                .iter()
+                .any(|option| option.trim().is_empty() || !seen.insert(option.as_str()))
+            {
+                return Err("options must be distinct and non-empty");
+            }
+            Ok(())
+        }
+        _ if options.is_empty() => Ok(()),
+        _ => Err("only select fields take options"),
+    }
+}
+
+/// Check the custom field values in `extension_metadata` against the
+/// organization's definitions. `null` clears a value. Returns the users
+/// referenced by user fields, whose membership the caller must check.
+pub(crate) fn validate_values(
+    fields: &[CustomField],
+    extension_metadata: &Value,
+) -> Result<Vec<Uuid>, CustomFieldValueError> {
+    let Some(values) = extension_metadata.get(CUSTOM_FIELDS_METADATA_KEY) else {
+        return Ok(Vec::new());
+    };
+    let values = values
+        .as_object()
+        .ok_or(CustomFieldValueError::NotAnObject)?;
+
+    let mut users = Vec::new();
+    for (key, value) in values {
+        let field = fields
+            .iter()
+            .find(|field| &field.key == key)
+            .ok_or_else(|| CustomFieldValueError::Unknown(key.clone()))?;
+        if value.is_null() {
+            continue;
+        }
+
+        let wrong_type = |expected| CustomFieldValueError::WrongType {
+            key: key.clone(),
+            expected,
+        };
+        let check_option = |option: &str| {
+            if field.options.iter().any(|o| o == option) {
+                Ok(())
+            } else {
+                Err(CustomFieldValueError::UnknownOption {
+                    key: key.clone(),
+                    value: option.to_string(),
+                })
+            }
+        };
+
+        match field.field_type {
+            CustomFieldType::Text => {
+                value.as_str().ok_or_else(|| wrong_type("a string"))?;
+            }
+            CustomFieldType::Number => {
+                value.as_f64().ok_or_else(|| wrong_type("a number"))?;
+            }
+            CustomFieldType::Select => {
+                check_option(value.as_str().ok_or_else(|| wrong_type("a string"))?)?;
+            }
+            CustomFieldType::MultiSelect => {
+                let options = value
+                    .as_array()
+                    .ok_or_else(|| wrong_type("an array of strings"))?;
+                for option in options {
+                    check_option(
+                        option
+                            .as_str()
+                            .ok_or_else(|| wrong_type("an array of strings"))?,
+                    )?;
+                }
+            }
+            CustomFieldType::User => {
+                let user_id = value
+                    .as_str()
+                    .and_then(|id| Uuid::parse_str(id).ok())
+                    .ok_or_else(|| wrong_type("a user ID"))?;
+                users.push(user_id);
+            }
+            CustomFieldType::Date => {
+                value
+                    .as_str()
+                    .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                    .ok_or_else(|| wrong_type("a date (YYYY-MM-DD)"))?;
+            }
+        }
+    }
+
+    Ok(users)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::*;
+
+    fn field(key: &str, field_type: CustomFieldType, options: &[&str]) -> CustomField {
+        CustomField {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            key: key.to_string(),
+            name: key.to_string(),
+            field_type,
+            options: options.iter().map(|o| o.to_string()).collect(),
+            sort_order: 0.0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn validates_values_by_type() {
+        let owner = Uuid::new_v4();
+        let fields = vec![
+            field("severity", CustomFieldType::Select, &["low", "high"]),
+            field("areas", CustomFieldType::MultiSelect, &["api", "ui"]),
+            field("points", CustomFieldType::Number, &[]),
+            field("owner", CustomFieldType::User, &[]),
+            field("launch", CustomFieldType::Date, &[]),
+            field("notes", CustomFieldType::Text, &[]),
+        ];
+
+        let metadata = json!({
+            "other": true,
+            "custom_fields": {
+                "severity": "high",
+                "areas": ["api", "ui"],
+                "points": 3,
+                "owner": owner.to_string(),
+                "launch": "2026-06-01",
+                "notes": null,
+            }
+        });
+        assert_eq!(validate_values(&fields, &metadata), Ok(vec![owner]));
+        assert_eq!(validate_values(&fields, &json!({})), Ok(vec![]));
+
+        let check = |values| validate_values(&fields, &json!({ "custom_fields": values }));
+        assert_eq!(
+            check(json!({ "severity": "medium" })),
+            Err(CustomFieldValueError::UnknownOption {
+                key: "severity".into(),
+                value: "medium".into(),
+            })
+        );
+        assert!(matches!(
+            check(json!({ "points": "3" })),
+            Err(CustomFieldValueError::WrongType { .. })
+        ));
+        assert!(matches!(
+            check(json!({ "launch": "June" })),
+            Err(CustomFieldValueError::WrongType { .. })
+        ));
+        assert_eq!(
+            check(json!({ "team": "core" })),
+            Err(CustomFieldValueError::Unknown("team".into()))
+        );
+        assert_eq!(check(json!([])), Err(CustomFieldValueError::NotAnObject));
+    }
+
+    #[test]
+    fn validates_definitions() {
+        assert!(is_valid_key("story_points"));
+        assert!(!is_valid_key("Story Points"));
+        assert!(!is_valid_key("2fa"));
+        assert!(validate_options(CustomFieldType::Select, &["a".into(), "b".into()]).is_ok());
+        assert!(validate_options(CustomFieldType::Select, &[]).is_err());
+        assert!(validate_options(CustomFieldType::MultiSelect, &["a".into(), "a".into()]).is_err());
+        assert!(validate_options(CustomFieldType::Text, &["a".into()]).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use api_types::{CustomField, CustomFieldType, DeleteResponse, MutationResponse};
+use chrono::{DateTime, Utc};
+use sqlx::{Executor, PgPool, Postgres};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::get_txid;
+
+#[derive(Debug, Error)]
+pub enum CustomFieldError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct CustomFieldRepository;
+
+impl CustomFieldRepository {
+    pub async fn find_by_id(
+        pool: &PgPool,
+        id: Uuid,
+    ) -> Result<Option<CustomField>, CustomFieldError> {
+        let record = sqlx::query_as!(
+            CustomField,
+            r#"
+            SELECT
+                id              AS "id!: Uuid",
+                organization_id AS "organization_id!: Uuid",
+                key             AS "key!",
+                name            AS "name!",
+                field_type      AS "field_type!: CustomFieldType",
+                options         AS "options!: Vec<String>",
+                sort_order      AS "sort_order!",
+                created_at      AS "created_at!: DateTime<Utc>",
+                updated_at      AS "updated_at!: DateTime<Utc>"
+            FROM custom_fields
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn list_by_organization<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+    ) -> Result<Vec<CustomField>, CustomFieldError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            CustomField,
+            r#"
+            SELECT
+                id              AS "id!: Uuid",
+                organization_id AS "organization_id!: Uuid",
+                key             AS "key!",
+                name            AS "name!",
+                field_type      AS "field_type!: CustomFieldType",
+                options         AS "options!: Vec<String>",
+                sort_order      AS "sort_order!",
+                created_at      AS "created_at!: DateTime<Utc>",
+                updated_at      AS "updated_at!: DateTime<Utc>"
+            FROM custom_fields
+            WHERE organization_id = $1
+            ORDER BY sort_order ASC, created_at ASC
+            "#,
+            organization_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        id: Option<Uuid>,
+        organization_id: Uuid,
+        key: String,
+        name: String,
+        field_type: CustomFieldType,
+        options: Vec<String>,
+        sort_order: f64,
+    ) -> Result<MutationResponse<CustomField>, CustomFieldError> {
+        let mut tx = pool.begin().await?;
+
+        let id = id.unwrap_or_else(Uuid::new_v4);
+        let data = sqlx::query_as!(
+            CustomField,
+            r#"
+            INSERT INTO custom_fields (
+                id, organization_id, key, name, field_type, options, sort_order
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING
+                id              AS "id!: Uuid",
+                organization_id AS "organization_id!: Uuid",
+                key             AS "key!",
+                name            AS "name!",
+                field_type      AS "field_type!: CustomFieldType",
+                options         AS "options!: Vec<String>",
+                sort_order      AS "sort_order!",
+                created_at      AS "created_at!: DateTime<Utc>",
+                updated_at      AS "updated_at!: DateTime<Utc>"
+            "#,
+            id,
+            organization_id,
+            key,
+            name,
+            field_type as CustomFieldType,
+            &options,
+            sort_order
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    /// Update a custom field with partial fields. Uses COALESCE to preserve
+    /// existing values when None is provided.
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        name: Option<String>,
+        options: Option<Vec<String>>,
+        sort_order: Option<f64>,
+    ) -> Result<MutationResponse<CustomField>, CustomFieldError> {
+        let mut tx = pool.begin().await?;
+
+        let data = sqlx::query_as!(
+            CustomField,
+            r#"
+            UPDATE custom_fields
+            SET
+                name = COALESCE($1, name),
+                options = COALESCE($2, options),
+                sort_order = COALESCE($3, sort_order)
+            WHERE id = $4
+            RETURNING
+                id              AS "id!: Uuid",
+                organization_id AS "organization_id!: Uuid",
+                key             AS "key!",
+                name            AS "name!",
+                field_type      AS "field_type!: CustomFieldType",
+                options         AS "options!: Vec<String>",
+                sort_order      AS "sort_order!",
+                created_at      AS "created_at!: DateTime<Utc>",
+                updated_at      AS "updated_at!: DateTime<Utc>"
+            "#,
+            name,
+            options.as_deref(),
+            sort_order,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, CustomFieldError> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!("DELETE FROM custom_fields WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(DeleteResponse { txid })
+    }
+}
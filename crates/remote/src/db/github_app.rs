@@ -36,6 +36,8 @@ pub struct GitHubAppRepository {
     pub github_repo_id: i64,
     pub repo_full_name: String,
     pub review_enabled: bool,
+    /// Publish review results to the PR as inline comments
+    pub review_inline_comments: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -342,6 +344,7 @@ impl<'a> GitHubAppRepository2<'a> {
                 github_repo_id,
                 repo_full_name,
                 review_enabled,
+                review_inline_comments,
                 created_at
             FROM github_app_repositories
             WHERE installation_id = $1
@@ -417,6 +420,7 @@ impl<'a> GitHubAppRepository2<'a> {
                 github_repo_id,
                 repo_full_name,
                 review_enabled,
+                review_inline_comments,
                 created_at
             "#,
             repo_id,
@@ -430,6 +434,64 @@ impl<'a> GitHubAppRepository2<'a> {
         Ok(repo)
     }
 
+    /// Update the review_inline_comments flag for a repository
+    pub async fn update_repository_review_inline_comments(
+        &self,
+        repo_id: Uuid,
+        installation_id: Uuid,
+        enabled: bool,
+    ) -> Result<GitHubAppRepository, GitHubAppDbError> {
+        let repo = sqlx::query_as!(
+            GitHubAppRepository,
+            r#"
+            UPDATE github_app_repositories
+            SET review_inline_comments = $3
+            WHERE id = $1 AND installation_id = $2
+            RETURNING
+                id,
+                installation_id,
+                github_repo_id,
+                repo_full_name,
+                review_enabled,
+                review_inline_comments,
+                created_at
+            "#,
+            repo_id,
+            installation_id,
+            enabled
+        )
+        .fetch_optional(self.pool)
+        .await?
+        .ok_or(GitHubAppDbError::NotFound)?;
+
+        Ok(repo)
+    }
+
+    /// Check if review results for a repository should be published as
+    /// inline PR comments. Looked up by GitHub IDs, as stored on reviews.
+    pub async fn is_repository_review_inline_comments_enabled(
+        &self,
+        github_installation_id: i64,
+        repo_full_name: &str,
+    ) -> Result<bool, GitHubAppDbError> {
+        let result = sqlx::query_scalar!(
+            r#"
+            SELECT r.review_inline_comments
+            FROM github_app_repositories r
+            JOIN github_app_installations i ON i.id = r.installation_id
+            WHERE i.github_installation_id = $1
+              AND LOWER(r.repo_full_name) = LOWER($2)
+            "#,
+            github_installation_id,
+            repo_full_name
+        )
+        .fetch_optional(self.pool)
+        .await?;
+
+        // Opt-in, so repos not in the DB yet keep the plain completion comment
+        Ok(result.unwrap_or(false))
+    }
+
     /// Check if a repository has reviews enabled (for webhook filtering)
     pub async fn is_repository_review_enabled(
         &self,
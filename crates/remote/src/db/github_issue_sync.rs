@@ -0,0 +1,649 @@
+use api_types::IssueComment;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::Tx;
+
+#[derive(Debug, Error)]
+pub enum GitHubIssueSyncError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// A project's GitHub Issues sync settings, with the GitHub installation ID
+/// API calls go through.
+#[derive(Debug, Clone)]
+pub struct GitHubIssueSync {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub installation_id: Uuid,
+    pub github_installation_id: i64,
+    pub github_repo_id: i64,
+    pub repo_full_name: String,
+    pub open_status_id: Uuid,
+    pub closed_status_id: Uuid,
+    pub export_new_issues: bool,
+    pub enabled: bool,
+    pub created_by_user_id: Option<Uuid>,
+    pub last_reconciled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A board issue mirrored to a GitHub issue, with the synced fields as of
+/// the last sync.
+#[derive(Debug, Clone)]
+pub struct GitHubIssueLink {
+    pub id: Uuid,
+    pub sync_id: Uuid,
+    /// `None` once the board issue was deleted.
+    pub issue_id: Option<Uuid>,
+    pub github_issue_number: i64,
+    pub title: String,
+    pub body: String,
+    pub closed: bool,
+    pub labels: Vec<String>,
+    /// Set once the GitHub issue was deleted or transferred.
+    pub unlinked: bool,
+    pub synced_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GitHubIssueLink {
+    /// The board issue still mirrored by this link. `None` for tombstones,
+    /// whose GitHub issue must not be imported again.
+    pub fn linked_issue_id(&self) -> Option<Uuid> {
+        self.issue_id.filter(|_| !self.unlinked)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GitHubCommentLink {
+    pub id: Uuid,
+    pub link_id: Uuid,
+    /// `None` once the board comment was deleted.
+    pub comment_id: Option<Uuid>,
+    pub github_comment_id: i64,
+    pub message: String,
+    pub github_body: String,
+    /// Set once the GitHub comment was deleted.
+    pub unlinked: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GitHubCommentLink {
+    /// The board comment still mirrored by this link, like
+    /// [`GitHubIssueLink::linked_issue_id`].
+    pub fn linked_comment_id(&self) -> Option<Uuid> {
+        self.comment_id.filter(|_| !self.unlinked)
+    }
+}
+
+pub struct GitHubIssueSyncRepository;
+
+impl GitHubIssueSyncRepository {
+    pub async fn find_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Option<GitHubIssueSync>, GitHubIssueSyncError> {
+        let record = sqlx::query_as!(
+            GitHubIssueSync,
+            r#"
+            SELECT
+                s.id                 AS "id!: Uuid",
+                s.project_id         AS "project_id!: Uuid",
+                s.installation_id    AS "installation_id!: Uuid",
+                i.github_installation_id AS "github_installation_id!",
+                s.github_repo_id     AS "github_repo_id!",
+                s.repo_full_name     AS "repo_full_name!",
+                s.open_status_id     AS "open_status_id!: Uuid",
+                s.closed_status_id   AS "closed_status_id!: Uuid",
+                s.export_new_issues  AS "export_new_issues!",
+                s.enabled            AS "enabled!",
+                s.created_by_user_id AS "created_by_user_id?: Uuid",
+                s.last_reconciled_at AS "last_reconciled_at?: DateTime<Utc>",
+                s.created_at         AS "created_at!: DateTime<Utc>",
+                s.updated_at         AS "updated_at!: DateTime<Utc>"
+            FROM github_issue_syncs s
+            JOIN github_app_installations i ON i.id = s.installation_id
+            WHERE s.project_id = $1
+            "#,
+            project_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Enabled syncs whose installation isn't suspended.
+    pub async fn list_active(pool: &PgPool) -> Result<Vec<GitHubIssueSync>, GitHubIssueSyncError> {
+        let records = sqlx::query_as!(
+            GitHubIssueSync,
+            r#"
+            SELECT
+                s.id                 AS "id!: Uuid",
+                s.project_id         AS "project_id!: Uuid",
+                s.installation_id    AS "installation_id!: Uuid",
+                i.github_installation_id AS "github_installation_id!",
+                s.github_repo_id     AS "github_repo_id!",
+                s.repo_full_name     AS "repo_full_name!",
+                s.open_status_id     AS "open_status_id!: Uuid",
+                s.closed_status_id   AS "closed_status_id!: Uuid",
+                s.export_new_issues  AS "export_new_issues!",
+                s.enabled            AS "enabled!",
+                s.created_by_user_id AS "created_by_user_id?: Uuid",
+                s.last_reconciled_at AS "last_reconciled_at?: DateTime<Utc>",
+                s.created_at         AS "created_at!: DateTime<Utc>",
+                s.updated_at         AS "updated_at!: DateTime<Utc>"
+            FROM github_issue_syncs s
+            JOIN github_app_installations i ON i.id = s.installation_id
+            WHERE s.enabled AND i.suspended_at IS NULL
+            ORDER BY s.last_reconciled_at ASC NULLS FIRST
+            "#
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Enabled syncs for a repository, for routing webhook events.
+    pub async fn list_active_by_repo(
+        pool: &PgPool,
+        github_installation_id: i64,
+        github_repo_id: i64,
+    ) -> Result<Vec<GitHubIssueSync>, GitHubIssueSyncError> {
+        let records = sqlx::query_as!(
+            GitHubIssueSync,
+            r#"
+            SELECT
+                s.id                 AS "id!: Uuid",
+                s.project_id         AS "project_id!: Uuid",
+                s.installation_id    AS "installation_id!: Uuid",
+                i.github_installation_id AS "github_installation_id!",
+                s.github_repo_id     AS "github_repo_id!",
+                s.repo_full_name     AS "repo_full_name!",
+                s.open_status_id     AS "open_status_id!: Uuid",
+                s.closed_status_id   AS "closed_status_id!: Uuid",
+                s.export_new_issues  AS "export_new_issues!",
+                s.enabled            AS "enabled!",
+                s.created_by_user_id AS "created_by_user_id?: Uuid",
+                s.last_reconciled_at AS "last_reconciled_at?: DateTime<Utc>",
+                s.created_at         AS "created_at!: DateTime<Utc>",
+                s.updated_at         AS "updated_at!: DateTime<Utc>"
+            FROM github_issue_syncs s
+            JOIN github_app_installations i ON i.id = s.installation_id
+            WHERE s.enabled
+              AND i.suspended_at IS NULL
+              AND i.github_installation_id = $1
+              AND s.github_repo_id = $2
+            "#,
+            github_installation_id,
+            github_repo_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        project_id: Uuid,
+        installation_id: Uuid,
+        github_repo_id: i64,
+        repo_full_name: &str,
+        open_status_id: Uuid,
+        closed_status_id: Uuid,
+        export_new_issues: bool,
+        enabled: bool,
+        created_by_user_id: Uuid,
+    ) -> Result<GitHubIssueSync, GitHubIssueSyncError> {
+        let record = sqlx::query_as!(
+            GitHubIssueSync,
+            r#"
+            WITH s AS (
+                INSERT INTO github_issue_syncs (
+                    project_id, installation_id, github_repo_id, repo_full_name,
+                    open_status_id, closed_status_id, export_new_issues, enabled,
+                    created_by_user_id
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING *
+            )
+            SELECT
+                s.id                 AS "id!: Uuid",
+                s.project_id         AS "project_id!: Uuid",
+                s.installation_id    AS "installation_id!: Uuid",
+                i.github_installation_id AS "github_installation_id!",
+                s.github_repo_id     AS "github_repo_id!",
+                s.repo_full_name     AS "repo_full_name!",
+                s.open_status_id     AS "open_status_id!: Uuid",
+                s.closed_status_id   AS "closed_status_id!: Uuid",
+                s.export_new_issues  AS "export_new_issues!",
+                s.enabled            AS "enabled!",
+                s.created_by_user_id AS "created_by_user_id?: Uuid",
+                s.last_reconciled_at AS "last_reconciled_at?: DateTime<Utc>",
+                s.created_at         AS "created_at!: DateTime<Utc>",
+                s.updated_at         AS "updated_at!: DateTime<Utc>"
+            FROM s
+            JOIN github_app_installations i ON i.id = s.installation_id
+            "#,
+            project_id,
+            installation_id,
+            github_repo_id,
+            repo_full_name,
+            open_status_id,
+            closed_status_id,
+            export_new_issues,
+            enabled,
+            created_by_user_id
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Update the settings that don't change which repository is synced.
+    pub async fn update_settings(
+        pool: &PgPool,
+        id: Uuid,
+        open_status_id: Uuid,
+        closed_status_id: Uuid,
+        export_new_issues: bool,
+        enabled: bool,
+    ) -> Result<(), GitHubIssueSyncError> {
+        sqlx::query!(
+            r#"
+            UPDATE github_issue_syncs
+            SET open_status_id = $2,
+                closed_status_id = $3,
+                export_new_issues = $4,
+                enabled = $5
+            WHERE id = $1
+            "#,
+            id,
+            open_status_id,
+            closed_status_id,
+            export_new_issues,
+            enabled
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<bool, GitHubIssueSyncError> {
+        let result = sqlx::query!(
+            "DELETE FROM github_issue_syncs WHERE project_id = $1",
+            project_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn set_last_reconciled_at(
+        pool: &PgPool,
+        id: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<(), GitHubIssueSyncError> {
+        sqlx::query!(
+            "UPDATE github_issue_syncs SET last_reconciled_at = $2 WHERE id = $1",
+            id,
+            at
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Serialize sync work for a GitHub App installation until `tx` ends, so
+    /// a webhook and a reconciliation can't both import the same issue.
+    pub async fn lock_installation(
+        tx: &mut Tx<'_>,
+        github_installation_id: i64,
+    ) -> Result<(), GitHubIssueSyncError> {
+        sqlx::query!("SELECT pg_advisory_xact_lock($1)", github_installation_id)
+            .execute(&mut **tx)
+            .await?;
+
+        Ok(())
+    }
+
+    // ========== Issue links ==========
+
+    pub async fn find_link_by_number(
+        pool: &PgPool,
+        sync_id: Uuid,
+        github_issue_number: i64,
+    ) -> Result<Option<GitHubIssueLink>, GitHubIssueSyncError> {
+        let record = sqlx::query_as!(
+            GitHubIssueLink,
+            r#"
+            SELECT
+                id                  AS "id!: Uuid",
+                sync_id             AS "sync_id!: Uuid",
+                issue_id            AS "issue_id?: Uuid",
+                github_issue_number AS "github_issue_number!",
+                title               AS "title!",
+                body                AS "body!",
+                closed              AS "closed!",
+                labels              AS "labels!: Vec<String>",
+                unlinked            AS "unlinked!",
+                synced_at           AS "synced_at!: DateTime<Utc>",
+                created_at          AS "created_at!: DateTime<Utc>"
+            FROM github_issue_links
+            WHERE sync_id = $1 AND github_issue_number = $2
+            "#,
+            sync_id,
+            github_issue_number
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Links whose board issue changed since the last sync: the issue row
+    /// was updated, its tags no longer match the synced labels, or it has
+    /// new or edited comments.
+    pub async fn list_locally_changed_links(
+        pool: &PgPool,
+        sync_id: Uuid,
+    ) -> Result<Vec<GitHubIssueLink>, GitHubIssueSyncError> {
+        let records = sqlx::query_as!(
+            GitHubIssueLink,
+            r#"
+            SELECT
+                l.id                  AS "id!: Uuid",
+                l.sync_id             AS "sync_id!: Uuid",
+                l.issue_id            AS "issue_id?: Uuid",
+                l.github_issue_number AS "github_issue_number!",
+                l.title               AS "title!",
+                l.body                AS "body!",
+                l.closed              AS "closed!",
+                l.labels              AS "labels!: Vec<String>",
+                l.unlinked            AS "unlinked!",
+                l.synced_at           AS "synced_at!: DateTime<Utc>",
+                l.created_at          AS "created_at!: DateTime<Utc>"
+            FROM github_issue_links l
+            JOIN issues i ON i.id = l.issue_id
+            CROSS JOIN LATERAL (
+                SELECT COALESCE(array_agg(t.name::text ORDER BY t.name COLLATE "C"), '{}') AS names
+                FROM issue_tags it
+                JOIN tags t ON t.id = it.tag_id
+                WHERE it.issue_id = i.id
+            ) tags
+            WHERE l.sync_id = $1
+              AND NOT l.unlinked
+              AND (
+                  i.updated_at > l.synced_at
+                  OR tags.names <> l.labels
+                  OR EXISTS (
+                      SELECT 1
+                      FROM issue_comments c
+                      LEFT JOIN github_comment_links cl ON cl.comment_id = c.id
+                      WHERE c.issue_id = i.id
+                        AND (
+                            (cl.id IS NULL AND c.author_id IS NOT NULL)
+                            OR (NOT cl.unlinked AND c.message <> cl.message)
+                        )
+                  )
+              )
+            "#,
+            sync_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_link(
+        pool: &PgPool,
+        sync_id: Uuid,
+        issue_id: Uuid,
+        github_issue_number: i64,
+        title: &str,
+        body: &str,
+        closed: bool,
+        labels: &[String],
+    ) -> Result<GitHubIssueLink, GitHubIssueSyncError> {
+        let record = sqlx::query_as!(
+            GitHubIssueLink,
+            r#"
+            INSERT INTO github_issue_links (
+                sync_id, issue_id, github_issue_number, title, body, closed, labels
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING
+                id                  AS "id!: Uuid",
+                sync_id             AS "sync_id!: Uuid",
+                issue_id            AS "issue_id?: Uuid",
+                github_issue_number AS "github_issue_number!",
+                title               AS "title!",
+                body                AS "body!",
+                closed              AS "closed!",
+                labels              AS "labels!: Vec<String>",
+                unlinked            AS "unlinked!",
+                synced_at           AS "synced_at!: DateTime<Utc>",
+                created_at          AS "created_at!: DateTime<Utc>"
+            "#,
+            sync_id,
+            issue_id,
+            github_issue_number,
+            title,
+            body,
+            closed,
+            labels
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Record the merged fields as the new base.
+    pub async fn update_link(
+        pool: &PgPool,
+        id: Uuid,
+        title: &str,
+        body: &str,
+        closed: bool,
+        labels: &[String],
+    ) -> Result<(), GitHubIssueSyncError> {
+        sqlx::query!(
+            r#"
+            UPDATE github_issue_links
+            SET title = $2, body = $3, closed = $4, labels = $5, synced_at = NOW()
+            WHERE id = $1
+            "#,
+            id,
+            title,
+            body,
+            closed,
+            labels
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Unlink a GitHub issue that was deleted or transferred. The board
+    /// issue stays, and the link is kept as a tombstone.
+    pub async fn unlink(
+        pool: &PgPool,
+        sync_id: Uuid,
+        github_issue_number: i64,
+    ) -> Result<(), GitHubIssueSyncError> {
+        sqlx::query!(
+            r#"
+            UPDATE github_issue_links
+            SET unlinked = true
+            WHERE sync_id = $1 AND github_issue_number = $2
+            "#,
+            sync_id,
+            github_issue_number
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Board issues created since the sync was set up that have no GitHub
+    /// issue yet.
+    pub async fn list_unlinked_issue_ids(
+        pool: &PgPool,
+        sync_id: Uuid,
+    ) -> Result<Vec<Uuid>, GitHubIssueSyncError> {
+        let ids = sqlx::query_scalar!(
+            r#"
+            SELECT i.id AS "id!: Uuid"
+            FROM github_issue_syncs s
+            JOIN issues i ON i.project_id = s.project_id
+            WHERE s.id = $1
+              AND i.created_at >= s.created_at
+              AND NOT EXISTS (SELECT 1 FROM github_issue_links l WHERE l.issue_id = i.id)
+            ORDER BY i.created_at
+            "#,
+            sync_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    // ========== Comment links ==========
+
+    pub async fn list_comment_links(
+        pool: &PgPool,
+        link_id: Uuid,
+    ) -> Result<Vec<GitHubCommentLink>, GitHubIssueSyncError> {
+        let records = sqlx::query_as!(
+            GitHubCommentLink,
+            r#"
+            SELECT
+                id                AS "id!: Uuid",
+                link_id           AS "link_id!: Uuid",
+                comment_id        AS "comment_id?: Uuid",
+                github_comment_id AS "github_comment_id!",
+                message           AS "message!",
+                github_body       AS "github_body!",
+                unlinked          AS "unlinked!",
+                created_at        AS "created_at!: DateTime<Utc>"
+            FROM github_comment_links
+            WHERE link_id = $1
+            "#,
+            link_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn create_comment_link(
+        pool: &PgPool,
+        link_id: Uuid,
+        comment_id: Uuid,
+        github_comment_id: i64,
+        message: &str,
+        github_body: &str,
+    ) -> Result<(), GitHubIssueSyncError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO github_comment_links (
+                link_id, comment_id, github_comment_id, message, github_body
+            )
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            link_id,
+            comment_id,
+            github_comment_id,
+            message,
+            github_body
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Unlink a GitHub comment that was deleted. The board comment stays.
+    pub async fn unlink_comment(
+        pool: &PgPool,
+        github_comment_id: i64,
+    ) -> Result<(), GitHubIssueSyncError> {
+        sqlx::query!(
+            "UPDATE github_comment_links SET unlinked = true WHERE github_comment_id = $1",
+            github_comment_id
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_comment_link(
+        pool: &PgPool,
+        id: Uuid,
+        message: &str,
+        github_body: &str,
+    ) -> Result<(), GitHubIssueSyncError> {
+        sqlx::query!(
+            "UPDATE github_comment_links SET message = $2, github_body = $3 WHERE id = $1",
+            id,
+            message,
+            github_body
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Add a comment mirrored from GitHub. It has no board author.
+    pub async fn create_mirrored_comment(
+        pool: &PgPool,
+        issue_id: Uuid,
+        message: &str,
+    ) -> Result<IssueComment, GitHubIssueSyncError> {
+        let record = sqlx::query_as!(
+            IssueComment,
+            r#"
+            INSERT INTO issue_comments (issue_id, message)
+            VALUES ($1, $2)
+            RETURNING
+                id          AS "id!: Uuid",
+                issue_id    AS "issue_id!: Uuid",
+                author_id   AS "author_id: Uuid",
+                parent_id   AS "parent_id: Uuid",
+                message     AS "message!",
+                created_at  AS "created_at!: DateTime<Utc>",
+                updated_at  AS "updated_at!: DateTime<Utc>"
+            "#,
+            issue_id,
+            message
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record)
+    }
+}
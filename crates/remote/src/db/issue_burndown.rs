@@ -0,0 +1,110 @@
+//! Day-by-day replays of a project's board for burndown and burnup charts.
+//! Days are UTC calendar days; each point describes the board at the end of
+//! its day.
+
+use api_types::{EstimateUnit, StatusBurndownPoint};
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum IssueBurndownError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Estimate in scope and completed at the end of one day.
+#[derive(Debug, Clone)]
+pub struct BurnupRow {
+    pub day: NaiveDate,
+    pub scope: f64,
+    pub completed: f64,
+}
+
+pub struct IssueBurndownRepository;
+
+impl IssueBurndownRepository {
+    /// Issues per status per day, using each issue's most recent status
+    /// change as of the end of the day. Only estimates in `unit` are summed.
+    pub async fn status_points(
+        pool: &PgPool,
+        project_id: Uuid,
+        unit: EstimateUnit,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<StatusBurndownPoint>, IssueBurndownError> {
+        let records = sqlx::query_as!(
+            StatusBurndownPoint,
+            r#"
+            SELECT
+                d.day::date AS "day!: NaiveDate",
+                s.status_id AS "status_id!: Uuid",
+                COUNT(*) AS "issue_count!",
+                COALESCE(SUM(i.estimate) FILTER (WHERE i.estimate_unit = $2), 0)
+                    AS "estimate!: f64"
+            FROM generate_series($3::date, $4::date, INTERVAL '1 day') AS d(day)
+            CROSS JOIN LATERAL (
+                SELECT (d.day AT TIME ZONE 'UTC') + INTERVAL '1 day' AS ends_at
+            ) e
+            JOIN issues i ON i.project_id = $1 AND i.created_at < e.ends_at
+            CROSS JOIN LATERAL (
+                SELECT c.status_id
+                FROM issue_status_changes c
+                WHERE c.issue_id = i.id AND c.changed_at < e.ends_at
+                ORDER BY c.changed_at DESC, c.id DESC
+                LIMIT 1
+            ) s
+            GROUP BY d.day, s.status_id
+            ORDER BY d.day, s.status_id
+            "#,
+            project_id,
+            unit as EstimateUnit,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Total estimate of the issues that existed at the end of each day, and
+    /// how much of it was completed by then.
+    pub async fn burnup(
+        pool: &PgPool,
+        project_id: Uuid,
+        unit: EstimateUnit,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<BurnupRow>, IssueBurndownError> {
+        let records = sqlx::query_as!(
+            BurnupRow,
+            r#"
+            SELECT
+                d.day::date AS "day!: NaiveDate",
+                COALESCE(SUM(i.estimate), 0) AS "scope!: f64",
+                COALESCE(SUM(i.estimate) FILTER (WHERE i.completed_at < e.ends_at), 0)
+                    AS "completed!: f64"
+            FROM generate_series($3::date, $4::date, INTERVAL '1 day') AS d(day)
+            CROSS JOIN LATERAL (
+                SELECT (d.day AT TIME ZONE 'UTC') + INTERVAL '1 day' AS ends_at
+            ) e
+            LEFT JOIN issues i
+                ON i.project_id = $1
+               AND i.estimate_unit = $2
+               AND i.created_at < e.ends_at
+            GROUP BY d.day
+            ORDER BY d.day
+            "#,
+            project_id,
+            unit as EstimateUnit,
+            from,
+            to
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+}
@@ -1,5 +1,5 @@
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{Executor, PgPool, Postgres};
 use thiserror::Error;
 use api_types::{IssueRelationship, IssueRelationshipType};
 use uuid::Uuid;
@@ -11,6 +11,8 @@ use api_types::{DeleteResponse, MutationResponse};
 pub enum IssueRelationshipError {
     #[error("database error: {0}")]
     Database(#[from] sqlx::Error),
+    #[error("blocking relationship would create a cycle")]
+    Cycle,
 }
 
 pub struct IssueRelationshipRepository;
@@ -73,6 +75,25 @@ impl IssueRelationshipRepository {
     ) -> Result<MutationResponse<IssueRelationship>, IssueRelationshipError> {
         let id = id.unwrap_or_else(Uuid::new_v4);
         let mut tx = pool.begin().await?;
+        if relationship_type == IssueRelationshipType::Blocking {
+            // Serialize blocking-edge writes per project so two concurrent
+            // inserts cannot each close half of a cycle.
+            sqlx::query!(
+                r#"
+                SELECT p.id
+                FROM projects p
+                JOIN issues i ON i.project_id = p.id
+                WHERE i.id = $1
+                FOR UPDATE OF p
+                "#,
+                issue_id
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+            if Self::blocks_transitively(&mut *tx, related_issue_id, issue_id).await? {
+                return Err(IssueRelationshipError::Cycle);
+            }
+        }
         let data = sqlx::query_as!(
             IssueRelationship,
             r#"
@@ -97,6 +118,123 @@ impl IssueRelationshipRepository {
         Ok(MutationResponse { data, txid })
     }
 
+    /// Whether `from` already blocks `to`, directly or through a chain of
+    /// blocking relationships.
+    pub async fn blocks_transitively<'e, E>(
+        executor: E,
+        from: Uuid,
+        to: Uuid,
+    ) -> Result<bool, IssueRelationshipError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let exists = sqlx::query_scalar!(
+            r#"
+            WITH RECURSIVE reachable(issue_id) AS (
+                SELECT $1::uuid
+                UNION
+                SELECT r.related_issue_id
+                FROM issue_relationships r
+                JOIN reachable ON r.issue_id = reachable.issue_id
+                WHERE r.relationship_type = 'blocking'
+            )
+            SELECT EXISTS (SELECT 1 FROM reachable WHERE issue_id = $2) AS "exists!"
+            "#,
+            from,
+            to
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Number of unfinished issues blocking `issue_id`.
+    pub async fn count_open_blockers<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<i64, IssueRelationshipError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM issue_relationships r
+            JOIN issues blocker ON blocker.id = r.issue_id
+            WHERE r.related_issue_id = $1
+              AND r.relationship_type = 'blocking'
+              AND blocker.completed_at IS NULL
+            "#,
+            issue_id
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Unfinished issues blocked by `blocker_id` and by no other unfinished
+    /// issue, i.e. those whose blocked state follows `blocker_id`'s.
+    pub async fn list_dependents_blocked_only_by<'e, E>(
+        executor: E,
+        blocker_id: Uuid,
+    ) -> Result<Vec<Uuid>, IssueRelationshipError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let ids = sqlx::query_scalar!(
+            r#"
+            SELECT r.related_issue_id AS "id!: Uuid"
+            FROM issue_relationships r
+            JOIN issues dependent ON dependent.id = r.related_issue_id
+            WHERE r.issue_id = $1
+              AND r.relationship_type = 'blocking'
+              AND dependent.completed_at IS NULL
+              AND NOT EXISTS (
+                  SELECT 1
+                  FROM issue_relationships other
+                  JOIN issues blocker ON blocker.id = other.issue_id
+                  WHERE other.related_issue_id = r.related_issue_id
+                    AND other.relationship_type = 'blocking'
+                    AND other.issue_id <> $1
+                    AND blocker.completed_at IS NULL
+              )
+            "#,
+            blocker_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// All blocking edges between issues of a project, as
+    /// `(blocking_issue_id, blocked_issue_id)`.
+    pub async fn list_blocking_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<(Uuid, Uuid)>, IssueRelationshipError> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                r.issue_id         AS "issue_id!: Uuid",
+                r.related_issue_id AS "related_issue_id!: Uuid"
+            FROM issue_relationships r
+            JOIN issues i ON i.id = r.issue_id
+            WHERE i.project_id = $1 AND r.relationship_type = 'blocking'
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.issue_id, row.related_issue_id))
+            .collect())
+    }
+
     pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueRelationshipError> {
         let mut tx = pool.begin().await?;
         sqlx::query!("DELETE FROM issue_relationships WHERE id = $1", id)
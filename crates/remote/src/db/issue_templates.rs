@@ -0,0 +1,317 @@
+use api_types::{
+    AutoStartExecutorProfile, DeleteResponse, IssuePriority, IssueTemplate, MutationResponse,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, types::Json};
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{Tx, get_txid};
+
+#[derive(Debug, Error)]
+pub enum IssueTemplateError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct IssueTemplateRepository;
+
+impl IssueTemplateRepository {
+    pub async fn find_by_id(
+        pool: &PgPool,
+        id: Uuid,
+    ) -> Result<Option<IssueTemplate>, IssueTemplateError> {
+        let record = sqlx::query_as!(
+            IssueTemplate,
+            r#"
+            SELECT
+                id                          AS "id!: Uuid",
+                project_id                  AS "project_id!: Uuid",
+                name                        AS "name!",
+                title                       AS "title!",
+                description                 AS "description?",
+                status_id                   AS "status_id!: Uuid",
+                priority                    AS "priority: IssuePriority",
+                tag_ids                     AS "tag_ids!: Vec<Uuid>",
+                assignee_ids                AS "assignee_ids!: Vec<Uuid>",
+                recurrence                  AS "recurrence?",
+                next_run_at                 AS "next_run_at?: DateTime<Utc>",
+                auto_start_executor_profile AS "auto_start_executor_profile?: Json<AutoStartExecutorProfile>",
+                creator_user_id             AS "creator_user_id?: Uuid",
+                created_at                  AS "created_at!: DateTime<Utc>",
+                updated_at                  AS "updated_at!: DateTime<Utc>"
+            FROM issue_templates
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn list_by_project(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<IssueTemplate>, IssueTemplateError> {
+        let records = sqlx::query_as!(
+            IssueTemplate,
+            r#"
+            SELECT
+                id                          AS "id!: Uuid",
+                project_id                  AS "project_id!: Uuid",
+                name                        AS "name!",
+                title                       AS "title!",
+                description                 AS "description?",
+                status_id                   AS "status_id!: Uuid",
+                priority                    AS "priority: IssuePriority",
+                tag_ids                     AS "tag_ids!: Vec<Uuid>",
+                assignee_ids                AS "assignee_ids!: Vec<Uuid>",
+                recurrence                  AS "recurrence?",
+                next_run_at                 AS "next_run_at?: DateTime<Utc>",
+                auto_start_executor_profile AS "auto_start_executor_profile?: Json<AutoStartExecutorProfile>",
+                creator_user_id             AS "creator_user_id?: Uuid",
+                created_at                  AS "created_at!: DateTime<Utc>",
+                updated_at                  AS "updated_at!: DateTime<Utc>"
+            FROM issue_templates
+            WHERE project_id = $1
+            ORDER BY name
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        id: Option<Uuid>,
+        project_id: Uuid,
+        name: String,
+        title: String,
+        description: Option<String>,
+        status_id: Uuid,
+        priority: Option<IssuePriority>,
+        tag_ids: Vec<Uuid>,
+        assignee_ids: Vec<Uuid>,
+        recurrence: Option<String>,
+        next_run_at: Option<DateTime<Utc>>,
+        auto_start_executor_profile: Option<AutoStartExecutorProfile>,
+        creator_user_id: Uuid,
+    ) -> Result<MutationResponse<IssueTemplate>, IssueTemplateError> {
+        let mut tx = pool.begin().await?;
+
+        let id = id.unwrap_or_else(Uuid::new_v4);
+        let data = sqlx::query_as!(
+            IssueTemplate,
+            r#"
+            INSERT INTO issue_templates (
+                id, project_id, name, title, description, status_id, priority,
+                tag_ids, assignee_ids, recurrence, next_run_at,
+                auto_start_executor_profile, creator_user_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            RETURNING
+                id                          AS "id!: Uuid",
+                project_id                  AS "project_id!: Uuid",
+                name                        AS "name!",
+                title                       AS "title!",
+                description                 AS "description?",
+                status_id                   AS "status_id!: Uuid",
+                priority                    AS "priority: IssuePriority",
+                tag_ids                     AS "tag_ids!: Vec<Uuid>",
+                assignee_ids                AS "assignee_ids!: Vec<Uuid>",
+                recurrence                  AS "recurrence?",
+                next_run_at                 AS "next_run_at?: DateTime<Utc>",
+                auto_start_executor_profile AS "auto_start_executor_profile?: Json<AutoStartExecutorProfile>",
+                creator_user_id             AS "creator_user_id?: Uuid",
+                created_at                  AS "created_at!: DateTime<Utc>",
+                updated_at                  AS "updated_at!: DateTime<Utc>"
+            "#,
+            id,
+            project_id,
+            name,
+            title,
+            description,
+            status_id,
+            priority as Option<IssuePriority>,
+            &tag_ids,
+            &assignee_ids,
+            recurrence,
+            next_run_at,
+            auto_start_executor_profile.map(Json) as Option<Json<AutoStartExecutorProfile>>,
+            creator_user_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    /// Update a template with partial fields. Nullable fields follow the
+    /// same convention as issues:
+    /// - None: don't update the field
+    /// - Some(None): set the field to NULL
+    /// - Some(Some(value)): set the field to the value
+    ///
+    /// `next_run_at` is always written, since it is derived from
+    /// `recurrence` by the caller.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        name: Option<String>,
+        title: Option<String>,
+        description: Option<Option<String>>,
+        status_id: Option<Uuid>,
+        priority: Option<Option<IssuePriority>>,
+        tag_ids: Option<Vec<Uuid>>,
+        assignee_ids: Option<Vec<Uuid>>,
+        recurrence: Option<Option<String>>,
+        next_run_at: Option<DateTime<Utc>>,
+        auto_start_executor_profile: Option<Option<AutoStartExecutorProfile>>,
+    ) -> Result<MutationResponse<IssueTemplate>, IssueTemplateError> {
+        let update_description = description.is_some();
+        let description_value = description.flatten();
+        let update_priority = priority.is_some();
+        let priority_value = priority.flatten();
+        let update_recurrence = recurrence.is_some();
+        let recurrence_value = recurrence.flatten();
+        let update_auto_start = auto_start_executor_profile.is_some();
+        let auto_start_value = auto_start_executor_profile.flatten().map(Json);
+
+        let mut tx = pool.begin().await?;
+
+        let data = sqlx::query_as!(
+            IssueTemplate,
+            r#"
+            UPDATE issue_templates
+            SET
+                name = COALESCE($1, name),
+                title = COALESCE($2, title),
+                description = CASE WHEN $3 THEN $4 ELSE description END,
+                status_id = COALESCE($5, status_id),
+                priority = CASE WHEN $6 THEN $7 ELSE priority END,
+                tag_ids = COALESCE($8, tag_ids),
+                assignee_ids = COALESCE($9, assignee_ids),
+                recurrence = CASE WHEN $10 THEN $11 ELSE recurrence END,
+                next_run_at = $12,
+                auto_start_executor_profile = CASE WHEN $13 THEN $14 ELSE auto_start_executor_profile END
+            WHERE id = $15
+            RETURNING
+                id                          AS "id!: Uuid",
+                project_id                  AS "project_id!: Uuid",
+                name                        AS "name!",
+                title                       AS "title!",
+                description                 AS "description?",
+                status_id                   AS "status_id!: Uuid",
+                priority                    AS "priority: IssuePriority",
+                tag_ids                     AS "tag_ids!: Vec<Uuid>",
+                assignee_ids                AS "assignee_ids!: Vec<Uuid>",
+                recurrence                  AS "recurrence?",
+                next_run_at                 AS "next_run_at?: DateTime<Utc>",
+                auto_start_executor_profile AS "auto_start_executor_profile?: Json<AutoStartExecutorProfile>",
+                creator_user_id             AS "creator_user_id?: Uuid",
+                created_at                  AS "created_at!: DateTime<Utc>",
+                updated_at                  AS "updated_at!: DateTime<Utc>"
+            "#,
+            name,
+            title,
+            update_description,
+            description_value,
+            status_id,
+            update_priority,
+            priority_value as Option<IssuePriority>,
+            tag_ids.as_deref(),
+            assignee_ids.as_deref(),
+            update_recurrence,
+            recurrence_value,
+            next_run_at,
+            update_auto_start,
+            auto_start_value as Option<Json<AutoStartExecutorProfile>>,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueTemplateError> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!("DELETE FROM issue_templates WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(DeleteResponse { txid })
+    }
+
+    /// Lock recurring templates that are due. Rows locked by another server
+    /// instance are skipped, so each run is claimed once.
+    pub async fn lock_due(
+        tx: &mut Tx<'_>,
+        limit: i64,
+    ) -> Result<Vec<IssueTemplate>, IssueTemplateError> {
+        let records = sqlx::query_as!(
+            IssueTemplate,
+            r#"
+            SELECT
+                id                          AS "id!: Uuid",
+                project_id                  AS "project_id!: Uuid",
+                name                        AS "name!",
+                title                       AS "title!",
+                description                 AS "description?",
+                status_id                   AS "status_id!: Uuid",
+                priority                    AS "priority: IssuePriority",
+                tag_ids                     AS "tag_ids!: Vec<Uuid>",
+                assignee_ids                AS "assignee_ids!: Vec<Uuid>",
+                recurrence                  AS "recurrence?",
+                next_run_at                 AS "next_run_at?: DateTime<Utc>",
+                auto_start_executor_profile AS "auto_start_executor_profile?: Json<AutoStartExecutorProfile>",
+                creator_user_id             AS "creator_user_id?: Uuid",
+                created_at                  AS "created_at!: DateTime<Utc>",
+                updated_at                  AS "updated_at!: DateTime<Utc>"
+            FROM issue_templates
+            WHERE next_run_at IS NOT NULL AND next_run_at <= NOW()
+            ORDER BY next_run_at
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+            "#,
+            limit
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn set_next_run_at(
+        tx: &mut Tx<'_>,
+        id: Uuid,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<(), IssueTemplateError> {
+        sqlx::query!(
+            "UPDATE issue_templates SET next_run_at = $1 WHERE id = $2",
+            next_run_at,
+            id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
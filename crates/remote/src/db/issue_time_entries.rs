@@ -0,0 +1,131 @@
+use api_types::{DeleteResponse, IssueTimeEntry, MutationResponse, TimeEntrySource};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::get_txid;
+
+#[derive(Debug, Error)]
+pub enum IssueTimeEntryError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct IssueTimeEntryRepository;
+
+impl IssueTimeEntryRepository {
+    pub async fn find_by_id(
+        pool: &PgPool,
+        id: Uuid,
+    ) -> Result<Option<IssueTimeEntry>, IssueTimeEntryError> {
+        let record = sqlx::query_as!(
+            IssueTimeEntry,
+            r#"
+            SELECT
+                id               AS "id!: Uuid",
+                issue_id         AS "issue_id!: Uuid",
+                workspace_id     AS "workspace_id?: Uuid",
+                user_id          AS "user_id!: Uuid",
+                source           AS "source!: TimeEntrySource",
+                started_at       AS "started_at!: DateTime<Utc>",
+                duration_seconds AS "duration_seconds!",
+                created_at       AS "created_at!: DateTime<Utc>"
+            FROM issue_time_entries
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    pub async fn list_by_issue(
+        pool: &PgPool,
+        issue_id: Uuid,
+    ) -> Result<Vec<IssueTimeEntry>, IssueTimeEntryError> {
+        let records = sqlx::query_as!(
+            IssueTimeEntry,
+            r#"
+            SELECT
+                id               AS "id!: Uuid",
+                issue_id         AS "issue_id!: Uuid",
+                workspace_id     AS "workspace_id?: Uuid",
+                user_id          AS "user_id!: Uuid",
+                source           AS "source!: TimeEntrySource",
+                started_at       AS "started_at!: DateTime<Utc>",
+                duration_seconds AS "duration_seconds!",
+                created_at       AS "created_at!: DateTime<Utc>"
+            FROM issue_time_entries
+            WHERE issue_id = $1
+            ORDER BY started_at ASC
+            "#,
+            issue_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Record an entry. Reporting an existing ID again replaces its duration,
+    /// so the local app can report a run more than once.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upsert(
+        pool: &PgPool,
+        id: Option<Uuid>,
+        issue_id: Uuid,
+        workspace_id: Option<Uuid>,
+        user_id: Uuid,
+        source: TimeEntrySource,
+        started_at: DateTime<Utc>,
+        duration_seconds: i64,
+    ) -> Result<MutationResponse<IssueTimeEntry>, IssueTimeEntryError> {
+        let id = id.unwrap_or_else(Uuid::new_v4);
+        let mut tx = pool.begin().await?;
+        let data = sqlx::query_as!(
+            IssueTimeEntry,
+            r#"
+            INSERT INTO issue_time_entries (
+                id, issue_id, workspace_id, user_id, source, started_at, duration_seconds
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO UPDATE SET duration_seconds = EXCLUDED.duration_seconds
+            RETURNING
+                id               AS "id!: Uuid",
+                issue_id         AS "issue_id!: Uuid",
+                workspace_id     AS "workspace_id?: Uuid",
+                user_id          AS "user_id!: Uuid",
+                source           AS "source!: TimeEntrySource",
+                started_at       AS "started_at!: DateTime<Utc>",
+                duration_seconds AS "duration_seconds!",
+                created_at       AS "created_at!: DateTime<Utc>"
+            "#,
+            id,
+            issue_id,
+            workspace_id,
+            user_id,
+            source as TimeEntrySource,
+            started_at,
+            duration_seconds
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueTimeEntryError> {
+        let mut tx = pool.begin().await?;
+        sqlx::query!("DELETE FROM issue_time_entries WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+        Ok(DeleteResponse { txid })
+    }
+}
@@ -0,0 +1,308 @@
+use api_types::{IssuePriority, IssueRelationshipType};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::Tx;
+
+#[derive(Debug, Error)]
+pub enum IssueTransferError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Organization member of a project, as matched against assignees and
+/// comment authors in import files.
+#[derive(Debug, Clone)]
+pub struct MemberIdentity {
+    pub user_id: Uuid,
+    pub email: String,
+    pub username: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IssueLabel {
+    pub issue_id: Uuid,
+    pub value: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportComment {
+    pub issue_id: Uuid,
+    pub author_email: Option<String>,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportRelationship {
+    pub issue_id: Uuid,
+    pub related_simple_id: String,
+    pub relationship_type: IssueRelationshipType,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewImportedIssue {
+    pub status_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: Option<IssuePriority>,
+    pub start_date: Option<DateTime<Utc>>,
+    pub target_date: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub sort_order: f64,
+    pub extension_metadata: Value,
+}
+
+/// Project-wide reads for export and the inserts behind a bulk import. The
+/// inserts take a transaction so an import either lands completely or not
+/// at all; issues themselves go through `IssueRepository::insert`.
+pub struct IssueTransferRepository;
+
+impl IssueTransferRepository {
+    pub async fn list_members(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<MemberIdentity>, IssueTransferError> {
+        let records = sqlx::query_as!(
+            MemberIdentity,
+            r#"
+            SELECT
+                u.id       AS "user_id!: Uuid",
+                u.email    AS "email!",
+                u.username AS "username?"
+            FROM projects p
+            JOIN organization_member_metadata omm ON omm.organization_id = p.organization_id
+            JOIN users u ON u.id = omm.user_id
+            WHERE p.id = $1
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Tag names of every issue in the project.
+    pub async fn list_issue_tag_names(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<IssueLabel>, IssueTransferError> {
+        let records = sqlx::query_as!(
+            IssueLabel,
+            r#"
+            SELECT
+                it.issue_id AS "issue_id!: Uuid",
+                t.name      AS "value!"
+            FROM issue_tags it
+            JOIN tags t ON t.id = it.tag_id
+            WHERE t.project_id = $1
+            ORDER BY t.name
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Assignee emails of every issue in the project.
+    pub async fn list_issue_assignee_emails(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<IssueLabel>, IssueTransferError> {
+        let records = sqlx::query_as!(
+            IssueLabel,
+            r#"
+            SELECT
+                ia.issue_id AS "issue_id!: Uuid",
+                u.email     AS "value!"
+            FROM issue_assignees ia
+            JOIN issues i ON i.id = ia.issue_id
+            JOIN users u ON u.id = ia.user_id
+            WHERE i.project_id = $1
+            ORDER BY ia.assigned_at
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn list_comments(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<ExportComment>, IssueTransferError> {
+        let records = sqlx::query_as!(
+            ExportComment,
+            r#"
+            SELECT
+                c.issue_id   AS "issue_id!: Uuid",
+                u.email      AS "author_email?",
+                c.message    AS "message!",
+                c.created_at AS "created_at!: DateTime<Utc>"
+            FROM issue_comments c
+            JOIN issues i ON i.id = c.issue_id
+            LEFT JOIN users u ON u.id = c.author_id
+            WHERE i.project_id = $1
+            ORDER BY c.created_at
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn list_relationships(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<ExportRelationship>, IssueTransferError> {
+        let records = sqlx::query_as!(
+            ExportRelationship,
+            r#"
+            SELECT
+                r.issue_id          AS "issue_id!: Uuid",
+                related.simple_id   AS "related_simple_id!",
+                r.relationship_type AS "relationship_type!: IssueRelationshipType"
+            FROM issue_relationships r
+            JOIN issues i ON i.id = r.issue_id
+            JOIN issues related ON related.id = r.related_issue_id
+            WHERE i.project_id = $1
+            ORDER BY r.created_at
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Take the same project lock as blocking-relationship creation, so
+    /// the cycle checks of an import see a stable graph.
+    pub async fn lock_project(tx: &mut Tx<'_>, project_id: Uuid) -> Result<(), IssueTransferError> {
+        sqlx::query!(
+            "SELECT id FROM projects WHERE id = $1 FOR UPDATE",
+            project_id
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn insert_tag(
+        tx: &mut Tx<'_>,
+        project_id: Uuid,
+        name: &str,
+        color: &str,
+    ) -> Result<Uuid, IssueTransferError> {
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO tags (project_id, name, color)
+            VALUES ($1, $2, $3)
+            RETURNING id AS "id!: Uuid"
+            "#,
+            project_id,
+            name,
+            color
+        )
+        .fetch_one(&mut **tx)
+        .await?;
+
+        Ok(id)
+    }
+
+    pub async fn insert_issue_tags(
+        tx: &mut Tx<'_>,
+        issue_id: Uuid,
+        tag_ids: &[Uuid],
+    ) -> Result<(), IssueTransferError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO issue_tags (issue_id, tag_id)
+            SELECT $1, tag_id FROM UNNEST($2::uuid[]) AS t(tag_id)
+            ON CONFLICT (issue_id, tag_id) DO NOTHING
+            "#,
+            issue_id,
+            tag_ids
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn insert_issue_assignees(
+        tx: &mut Tx<'_>,
+        issue_id: Uuid,
+        user_ids: &[Uuid],
+    ) -> Result<(), IssueTransferError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO issue_assignees (issue_id, user_id)
+            SELECT $1, user_id FROM UNNEST($2::uuid[]) AS t(user_id)
+            ON CONFLICT (issue_id, user_id) DO NOTHING
+            "#,
+            issue_id,
+            user_ids
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn insert_comment(
+        tx: &mut Tx<'_>,
+        issue_id: Uuid,
+        author_id: Uuid,
+        message: &str,
+        created_at: DateTime<Utc>,
+    ) -> Result<(), IssueTransferError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO issue_comments (issue_id, author_id, message, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $4)
+            "#,
+            issue_id,
+            author_id,
+            message,
+            created_at
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn insert_relationship(
+        tx: &mut Tx<'_>,
+        issue_id: Uuid,
+        related_issue_id: Uuid,
+        relationship_type: IssueRelationshipType,
+    ) -> Result<(), IssueTransferError> {
+        sqlx::query!(
+            r#"
+            INSERT INTO issue_relationships (issue_id, related_issue_id, relationship_type)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (issue_id, related_issue_id, relationship_type) DO NOTHING
+            "#,
+            issue_id,
+            related_issue_id,
+            relationship_type as IssueRelationshipType
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use api_types::{DeleteResponse, IssueView, MutationResponse};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::get_txid;
+
+#[derive(Debug, Error)]
+pub enum IssueViewError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+pub struct IssueViewRepository;
+
+impl IssueViewRepository {
+    pub async fn find_by_id(pool: &PgPool, id: Uuid) -> Result<Option<IssueView>, IssueViewError> {
+        let record = sqlx::query_as!(
+            IssueView,
+            r#"
+            SELECT
+                id            AS "id!: Uuid",
+                project_id    AS "project_id!: Uuid",
+                owner_user_id AS "owner_user_id?: Uuid",
+                name          AS "name!",
+                query         AS "query!",
+                sort_order    AS "sort_order!",
+                created_at    AS "created_at!: DateTime<Utc>",
+                updated_at    AS "updated_at!: DateTime<Utc>"
+            FROM issue_views
+            WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Shared views of the project plus the user's private ones.
+    pub async fn list_visible(
+        pool: &PgPool,
+        project_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<IssueView>, IssueViewError> {
+        let records = sqlx::query_as!(
+            IssueView,
+            r#"
+            SELECT
+                id            AS "id!: Uuid",
+                project_id    AS "project_id!: Uuid",
+                owner_user_id AS "owner_user_id?: Uuid",
+                name          AS "name!",
+                query         AS "query!",
+                sort_order    AS "sort_order!",
+                created_at    AS "created_at!: DateTime<Utc>",
+                updated_at    AS "updated_at!: DateTime<Utc>"
+            FROM issue_views
+            WHERE project_id = $1 AND (owner_user_id IS NULL OR owner_user_id = $2)
+            ORDER BY sort_order, name
+            "#,
+            project_id,
+            user_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        id: Option<Uuid>,
+        project_id: Uuid,
+        owner_user_id: Option<Uuid>,
+        name: String,
+        query: String,
+        sort_order: f64,
+    ) -> Result<MutationResponse<IssueView>, IssueViewError> {
+        let mut tx = pool.begin().await?;
+
+        let id = id.unwrap_or_else(Uuid::new_v4);
+        let data = sqlx::query_as!(
+            IssueView,
+            r#"
+            INSERT INTO issue_views (id, project_id, owner_user_id, name, query, sort_order)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING
+                id            AS "id!: Uuid",
+                project_id    AS "project_id!: Uuid",
+                owner_user_id AS "owner_user_id?: Uuid",
+                name          AS "name!",
+                query         AS "query!",
+                sort_order    AS "sort_order!",
+                created_at    AS "created_at!: DateTime<Utc>",
+                updated_at    AS "updated_at!: DateTime<Utc>"
+            "#,
+            id,
+            project_id,
+            owner_user_id,
+            name,
+            query,
+            sort_order
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    /// Update a view with partial fields. `owner_user_id` follows the
+    /// nullable-field convention: `Some(None)` shares the view.
+    pub async fn update(
+        pool: &PgPool,
+        id: Uuid,
+        name: Option<String>,
+        query: Option<String>,
+        owner_user_id: Option<Option<Uuid>>,
+        sort_order: Option<f64>,
+    ) -> Result<MutationResponse<IssueView>, IssueViewError> {
+        let update_owner = owner_user_id.is_some();
+        let owner_value = owner_user_id.flatten();
+
+        let mut tx = pool.begin().await?;
+
+        let data = sqlx::query_as!(
+            IssueView,
+            r#"
+            UPDATE issue_views
+            SET
+                name = COALESCE($1, name),
+                query = COALESCE($2, query),
+                owner_user_id = CASE WHEN $3 THEN $4 ELSE owner_user_id END,
+                sort_order = COALESCE($5, sort_order)
+            WHERE id = $6
+            RETURNING
+                id            AS "id!: Uuid",
+                project_id    AS "project_id!: Uuid",
+                owner_user_id AS "owner_user_id?: Uuid",
+                name          AS "name!",
+                query         AS "query!",
+                sort_order    AS "sort_order!",
+                created_at    AS "created_at!: DateTime<Utc>",
+                updated_at    AS "updated_at!: DateTime<Utc>"
+            "#,
+            name,
+            query,
+            update_owner,
+            owner_value,
+            sort_order,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, IssueViewError> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!("DELETE FROM issue_views WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(DeleteResponse { txid })
+    }
+}
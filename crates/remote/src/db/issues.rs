@@ -1,4 +1,6 @@
-use api_types::{Issue, IssuePriority, PullRequestStatus};
+use api_types::{
+    DeleteResponse, EstimateUnit, Issue, IssuePriority, MutationResponse, PullRequestStatus,
+};
 use chrono::{DateTime, Utc};
 use serde_json::Value;
 use sqlx::{Executor, PgPool, Postgres};
@@ -9,7 +11,6 @@ use super::{
     get_txid, issue_assignees::IssueAssigneeRepository, project_statuses::ProjectStatusRepository,
     pull_requests::PullRequestRepository, workspaces::WorkspaceRepository,
 };
-use api_types::{DeleteResponse, MutationResponse};
 
 #[derive(Debug, Error)]
 pub enum IssueError {
@@ -46,6 +47,8 @@ impl IssueRepository {
                 priority            AS "priority: IssuePriority",
                 start_date          AS "start_date?: DateTime<Utc>",
                 target_date         AS "target_date?: DateTime<Utc>",
+                estimate            AS "estimate?",
+                estimate_unit       AS "estimate_unit!: EstimateUnit",
                 completed_at        AS "completed_at?: DateTime<Utc>",
                 sort_order          AS "sort_order!",
                 parent_issue_id     AS "parent_issue_id?: Uuid",
@@ -102,6 +105,8 @@ impl IssueRepository {
                 priority            AS "priority: IssuePriority",
                 start_date          AS "start_date?: DateTime<Utc>",
                 target_date         AS "target_date?: DateTime<Utc>",
+                estimate            AS "estimate?",
+                estimate_unit       AS "estimate_unit!: EstimateUnit",
                 completed_at        AS "completed_at?: DateTime<Utc>",
                 sort_order          AS "sort_order!",
                 parent_issue_id     AS "parent_issue_id?: Uuid",
@@ -121,6 +126,42 @@ impl IssueRepository {
         Ok(records)
     }
 
+    pub async fn list_by_ids(pool: &PgPool, ids: &[Uuid]) -> Result<Vec<Issue>, IssueError> {
+        let records = sqlx::query_as!(
+            Issue,
+            r#"
+            SELECT
+                id                  AS "id!: Uuid",
+                project_id          AS "project_id!: Uuid",
+                issue_number        AS "issue_number!",
+                simple_id           AS "simple_id!",
+                status_id           AS "status_id!: Uuid",
+                title               AS "title!",
+                description         AS "description?",
+                priority            AS "priority: IssuePriority",
+                start_date          AS "start_date?: DateTime<Utc>",
+                target_date         AS "target_date?: DateTime<Utc>",
+                estimate            AS "estimate?",
+                estimate_unit       AS "estimate_unit!: EstimateUnit",
+                completed_at        AS "completed_at?: DateTime<Utc>",
+                sort_order          AS "sort_order!",
+                parent_issue_id     AS "parent_issue_id?: Uuid",
+                parent_issue_sort_order AS "parent_issue_sort_order?",
+                extension_metadata  AS "extension_metadata!: Value",
+                creator_user_id     AS "creator_user_id?: Uuid",
+                created_at          AS "created_at!: DateTime<Utc>",
+                updated_at          AS "updated_at!: DateTime<Utc>"
+            FROM issues
+            WHERE id = ANY($1)
+            "#,
+            ids
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(records)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn create(
         pool: &PgPool,
@@ -132,15 +173,69 @@ impl IssueRepository {
         priority: Option<IssuePriority>,
         start_date: Option<DateTime<Utc>>,
         target_date: Option<DateTime<Utc>>,
+        estimate: Option<f64>,
+        estimate_unit: Option<EstimateUnit>,
         completed_at: Option<DateTime<Utc>>,
         sort_order: f64,
         parent_issue_id: Option<Uuid>,
         parent_issue_sort_order: Option<f64>,
         extension_metadata: Value,
-        creator_user_id: Uuid,
+        creator_user_id: Option<Uuid>,
     ) -> Result<MutationResponse<Issue>, IssueError> {
         let mut tx = pool.begin().await?;
 
+        let data = Self::insert(
+            &mut *tx,
+            id,
+            project_id,
+            status_id,
+            title,
+            description,
+            priority,
+            start_date,
+            target_date,
+            estimate,
+            estimate_unit,
+            completed_at,
+            sort_order,
+            parent_issue_id,
+            parent_issue_sort_order,
+            extension_metadata,
+            creator_user_id,
+        )
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(MutationResponse { data, txid })
+    }
+
+    /// Insert an issue on `executor`, for callers that create several issues
+    /// in one transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn insert<'e, E>(
+        executor: E,
+        id: Option<Uuid>,
+        project_id: Uuid,
+        status_id: Uuid,
+        title: String,
+        description: Option<String>,
+        priority: Option<IssuePriority>,
+        start_date: Option<DateTime<Utc>>,
+        target_date: Option<DateTime<Utc>>,
+        estimate: Option<f64>,
+        estimate_unit: Option<EstimateUnit>,
+        completed_at: Option<DateTime<Utc>>,
+        sort_order: f64,
+        parent_issue_id: Option<Uuid>,
+        parent_issue_sort_order: Option<f64>,
+        extension_metadata: Value,
+        creator_user_id: Option<Uuid>,
+    ) -> Result<Issue, IssueError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
         let id = id.unwrap_or_else(Uuid::new_v4);
         // Note: issue_number and simple_id are auto-generated by the DB trigger
         let data = sqlx::query_as!(
@@ -148,11 +243,14 @@ impl IssueRepository {
             r#"
             INSERT INTO issues (
                 id, project_id, status_id, title, description, priority,
-                start_date, target_date, completed_at, sort_order,
-                parent_issue_id, parent_issue_sort_order, extension_metadata,
-                creator_user_id
+                start_date, target_date, estimate, estimate_unit, completed_at,
+                sort_order, parent_issue_id, parent_issue_sort_order,
+                extension_metadata, creator_user_id
+            )
+            VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, COALESCE($10, 'points'::estimate_unit),
+                $11, $12, $13, $14, $15, $16
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
             RETURNING
                 id                  AS "id!: Uuid",
                 project_id          AS "project_id!: Uuid",
@@ -164,6 +262,8 @@ impl IssueRepository {
                 priority            AS "priority: IssuePriority",
                 start_date          AS "start_date?: DateTime<Utc>",
                 target_date         AS "target_date?: DateTime<Utc>",
+                estimate            AS "estimate?",
+                estimate_unit       AS "estimate_unit!: EstimateUnit",
                 completed_at        AS "completed_at?: DateTime<Utc>",
                 sort_order          AS "sort_order!",
                 parent_issue_id     AS "parent_issue_id?: Uuid",
@@ -181,6 +281,8 @@ impl IssueRepository {
             priority as Option<IssuePriority>,
             start_date,
             target_date,
+            estimate,
+            estimate_unit as Option<EstimateUnit>,
             completed_at,
             sort_order,
             parent_issue_id,
@@ -188,13 +290,10 @@ impl IssueRepository {
             extension_metadata,
             creator_user_id
         )
-        .fetch_one(&mut *tx)
+        .fetch_one(executor)
         .await?;
 
-        let txid = get_txid(&mut *tx).await?;
-        tx.commit().await?;
-
-        Ok(MutationResponse { data, txid })
+        Ok(data)
     }
 
     /// Update an issue with partial fields.
@@ -214,6 +313,8 @@ impl IssueRepository {
         priority: Option<Option<IssuePriority>>,
         start_date: Option<Option<DateTime<Utc>>>,
         target_date: Option<Option<DateTime<Utc>>>,
+        estimate: Option<Option<f64>>,
+        estimate_unit: Option<EstimateUnit>,
         completed_at: Option<Option<DateTime<Utc>>>,
         sort_order: Option<f64>,
         parent_issue_id: Option<Option<Uuid>>,
@@ -233,6 +334,8 @@ impl IssueRepository {
         let start_date_value = start_date.flatten();
         let update_target_date = target_date.is_some();
         let target_date_value = target_date.flatten();
+        let update_estimate = estimate.is_some();
+        let estimate_value = estimate.flatten();
         let update_completed_at = completed_at.is_some();
         let completed_at_value = completed_at.flatten();
         let update_parent_issue_id = parent_issue_id.is_some();
@@ -251,13 +354,15 @@ impl IssueRepository {
                 priority = CASE WHEN $5 THEN $6 ELSE priority END,
                 start_date = CASE WHEN $7 THEN $8 ELSE start_date END,
                 target_date = CASE WHEN $9 THEN $10 ELSE target_date END,
-                completed_at = CASE WHEN $11 THEN $12 ELSE completed_at END,
-                sort_order = COALESCE($13, sort_order),
-                parent_issue_id = CASE WHEN $14 THEN $15 ELSE parent_issue_id END,
-                parent_issue_sort_order = CASE WHEN $16 THEN $17 ELSE parent_issue_sort_order END,
-                extension_metadata = COALESCE($18, extension_metadata),
+                estimate = CASE WHEN $11 THEN $12 ELSE estimate END,
+                estimate_unit = COALESCE($13, estimate_unit),
+                completed_at = CASE WHEN $14 THEN $15 ELSE completed_at END,
+                sort_order = COALESCE($16, sort_order),
+                parent_issue_id = CASE WHEN $17 THEN $18 ELSE parent_issue_id END,
+                parent_issue_sort_order = CASE WHEN $19 THEN $20 ELSE parent_issue_sort_order END,
+                extension_metadata = COALESCE($21, extension_metadata),
                 updated_at = NOW()
-            WHERE id = $19
+            WHERE id = $22
             RETURNING
                 id                  AS "id!: Uuid",
                 project_id          AS "project_id!: Uuid",
@@ -269,6 +374,8 @@ impl IssueRepository {
                 priority            AS "priority: IssuePriority",
                 start_date          AS "start_date?: DateTime<Utc>",
                 target_date         AS "target_date?: DateTime<Utc>",
+                estimate            AS "estimate?",
+                estimate_unit       AS "estimate_unit!: EstimateUnit",
                 completed_at        AS "completed_at?: DateTime<Utc>",
                 sort_order          AS "sort_order!",
                 parent_issue_id     AS "parent_issue_id?: Uuid",
@@ -288,6 +395,9 @@ impl IssueRepository {
             start_date_value,
             update_target_date,
             target_date_value,
+            update_estimate,
+            estimate_value,
+            estimate_unit as Option<EstimateUnit>,
             update_completed_at,
             completed_at_value,
             sort_order,
@@ -366,6 +476,8 @@ impl IssueRepository {
             None,
             None,
             None,
+            None,
+            None,
         )
         .await?;
 
@@ -401,6 +513,8 @@ impl IssueRepository {
                 None,
                 None,
                 None,
+                None,
+                None,
             )
             .await?;
         }
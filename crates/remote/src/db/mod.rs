@@ -1,15 +1,23 @@
 pub mod auth;
+pub mod custom_fields;
 pub mod github_app;
+pub mod github_issue_sync;
 pub mod identity_errors;
 pub mod invitations;
 pub mod issue_assignees;
+pub mod issue_burndown;
 pub mod issue_comment_reactions;
 pub mod issue_comments;
 pub mod issue_followers;
 pub mod issue_relationships;
 pub mod issue_tags;
+pub mod issue_templates;
+pub mod issue_time_entries;
+pub mod issue_transfer;
+pub mod issue_views;
 pub mod issues;
 pub mod migration;
+pub mod notification_digest_settings;
 pub mod notifications;
 pub mod oauth;
 pub mod oauth_accounts;
@@ -0,0 +1,126 @@
+use api_types::{DigestFrequency, NotificationDigestSettings};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::Tx;
+
+#[derive(Debug, Error)]
+pub enum NotificationDigestError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// A user whose digest is due.
+#[derive(Debug, Clone)]
+pub struct DueDigest {
+    pub user_id: Uuid,
+    pub email: String,
+    pub frequency: DigestFrequency,
+    pub last_sent_at: Option<DateTime<Utc>>,
+}
+
+pub struct NotificationDigestRepository;
+
+impl NotificationDigestRepository {
+    pub async fn find(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Option<NotificationDigestSettings>, NotificationDigestError> {
+        let record = sqlx::query_as!(
+            NotificationDigestSettings,
+            r#"
+            SELECT
+                frequency    AS "frequency!: DigestFrequency",
+                last_sent_at AS "last_sent_at?: DateTime<Utc>"
+            FROM notification_digest_settings
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Set the user's digest frequency. Turning digests on starts the
+    /// window now, so the first digest doesn't replay old notifications.
+    pub async fn upsert(
+        pool: &PgPool,
+        user_id: Uuid,
+        frequency: DigestFrequency,
+    ) -> Result<NotificationDigestSettings, NotificationDigestError> {
+        let record = sqlx::query_as!(
+            NotificationDigestSettings,
+            r#"
+            INSERT INTO notification_digest_settings (user_id, frequency, last_sent_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (user_id) DO UPDATE
+            SET frequency = EXCLUDED.frequency,
+                last_sent_at = CASE
+                    WHEN notification_digest_settings.frequency = 'off' THEN NOW()
+                    ELSE notification_digest_settings.last_sent_at
+                END
+            RETURNING
+                frequency    AS "frequency!: DigestFrequency",
+                last_sent_at AS "last_sent_at?: DateTime<Utc>"
+            "#,
+            user_id,
+            frequency as DigestFrequency
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Lock users whose daily or weekly digest is due. Rows locked by
+    /// another server instance are skipped, so each digest is sent once.
+    pub async fn lock_due(
+        tx: &mut Tx<'_>,
+        limit: i64,
+    ) -> Result<Vec<DueDigest>, NotificationDigestError> {
+        let records = sqlx::query_as!(
+            DueDigest,
+            r#"
+            SELECT
+                s.user_id      AS "user_id!: Uuid",
+                u.email        AS "email!",
+                s.frequency    AS "frequency!: DigestFrequency",
+                s.last_sent_at AS "last_sent_at?: DateTime<Utc>"
+            FROM notification_digest_settings s
+            JOIN users u ON u.id = s.user_id
+            WHERE (s.frequency = 'daily'
+                    AND (s.last_sent_at IS NULL OR s.last_sent_at <= NOW() - INTERVAL '1 day'))
+               OR (s.frequency = 'weekly'
+                    AND (s.last_sent_at IS NULL OR s.last_sent_at <= NOW() - INTERVAL '7 days'))
+            ORDER BY s.last_sent_at NULLS FIRST
+            LIMIT $1
+            FOR UPDATE OF s SKIP LOCKED
+            "#,
+            limit
+        )
+        .fetch_all(&mut **tx)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn mark_sent(
+        tx: &mut Tx<'_>,
+        user_id: Uuid,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), NotificationDigestError> {
+        sqlx::query!(
+            "UPDATE notification_digest_settings SET last_sent_at = $1 WHERE user_id = $2",
+            sent_at,
+            user_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(())
+    }
+}
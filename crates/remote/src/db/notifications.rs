@@ -11,6 +11,14 @@ pub enum NotificationError {
     Database(#[from] sqlx::Error),
 }
 
+/// Issue details copied into notification payloads.
+#[derive(Debug, Clone)]
+pub struct IssueContext {
+    pub organization_id: Uuid,
+    pub simple_id: String,
+    pub title: String,
+}
+
 pub struct NotificationRepository;
 
 impl NotificationRepository {
@@ -282,6 +290,172 @@ impl NotificationRepository {
         Ok(record)
     }
 
+    /// Create the same notification for several users at once.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_for_users<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        user_ids: &[Uuid],
+        notification_type: NotificationType,
+        payload: Value,
+        issue_id: Option<Uuid>,
+        comment_id: Option<Uuid>,
+    ) -> Result<u64, NotificationError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO notifications (organization_id, user_id, notification_type, payload, issue_id, comment_id)
+            SELECT $1, user_id, $3, $4, $5, $6 FROM UNNEST($2::uuid[]) AS t(user_id)
+            "#,
+            organization_id,
+            user_ids,
+            notification_type as NotificationType,
+            payload,
+            issue_id,
+            comment_id
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Unseen notifications created in `(after, until]`, oldest first.
+    pub async fn list_unseen_between<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        after: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Notification>, NotificationError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let records = sqlx::query_as!(
+            Notification,
+            r#"
+            SELECT
+                id                AS "id!: Uuid",
+                organization_id   AS "organization_id!: Uuid",
+                user_id           AS "user_id!: Uuid",
+                notification_type AS "notification_type!: NotificationType",
+                payload           AS "payload!: Value",
+                issue_id          AS "issue_id: Uuid",
+                comment_id        AS "comment_id: Uuid",
+                seen              AS "seen!",
+                dismissed_at      AS "dismissed_at: DateTime<Utc>",
+                created_at        AS "created_at!: DateTime<Utc>"
+            FROM notifications
+            WHERE user_id = $1
+              AND created_at > $2
+              AND created_at <= $3
+              AND seen = FALSE
+              AND dismissed_at IS NULL
+            ORDER BY created_at
+            "#,
+            user_id,
+            after,
+            until
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(records)
+    }
+
+    pub async fn find_issue_context<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<Option<IssueContext>, NotificationError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let record = sqlx::query_as!(
+            IssueContext,
+            r#"
+            SELECT
+                p.organization_id AS "organization_id!: Uuid",
+                i.simple_id       AS "simple_id!",
+                i.title           AS "title!"
+            FROM issues i
+            JOIN projects p ON p.id = i.project_id
+            WHERE i.id = $1
+            "#,
+            issue_id
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Assignees and followers of an issue.
+    pub async fn list_issue_watchers<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<Vec<Uuid>, NotificationError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let user_ids = sqlx::query_scalar!(
+            r#"
+            SELECT user_id AS "user_id!: Uuid" FROM issue_assignees WHERE issue_id = $1
+            UNION
+            SELECT user_id FROM issue_followers WHERE issue_id = $1
+            "#,
+            issue_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    pub async fn list_issue_followers<'e, E>(
+        executor: E,
+        issue_id: Uuid,
+    ) -> Result<Vec<Uuid>, NotificationError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let user_ids = sqlx::query_scalar!(
+            r#"SELECT user_id AS "user_id!: Uuid" FROM issue_followers WHERE issue_id = $1"#,
+            issue_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(user_ids)
+    }
+
+    /// Organization members whose username or email local part matches one
+    /// of the lowercased `handles`.
+    pub async fn find_members_by_handles<'e, E>(
+        executor: E,
+        organization_id: Uuid,
+        handles: &[String],
+    ) -> Result<Vec<Uuid>, NotificationError>
+    where
+        E: Executor<'e, Database = Postgres>,
+    {
+        let user_ids = sqlx::query_scalar!(
+            r#"
+            SELECT u.id AS "id!: Uuid"
+            FROM organization_member_metadata omm
+            JOIN users u ON u.id = omm.user_id
+            WHERE omm.organization_id = $1
+              AND (LOWER(u.username) = ANY($2) OR LOWER(SPLIT_PART(u.email, '@', 1)) = ANY($2))
+            "#,
+            organization_id,
+            handles
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(user_ids)
+    }
+
     pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<(), NotificationError>
     where
         E: Executor<'e, Database = Postgres>,
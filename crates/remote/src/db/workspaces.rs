@@ -1,4 +1,4 @@
-use api_types::Workspace;
+use api_types::{IssueDeliveryTimeline, Workspace};
 use chrono::{DateTime, Utc};
 use sqlx::PgPool;
 use thiserror::Error;
@@ -167,6 +167,40 @@ impl WorkspaceRepository {
         Ok(())
     }
 
+    /// Delivery timelines of every issue-linked workspace in a project,
+    /// oldest workspace first.
+    pub async fn list_delivery_timelines(
+        pool: &PgPool,
+        project_id: Uuid,
+    ) -> Result<Vec<IssueDeliveryTimeline>, WorkspaceError> {
+        let timelines = sqlx::query_as!(
+            IssueDeliveryTimeline,
+            r#"
+            SELECT
+                i.id                AS "issue_id!: Uuid",
+                w.id                AS "workspace_id!: Uuid",
+                w.local_workspace_id AS "local_workspace_id?: Uuid",
+                i.created_at        AS "issue_created_at!: DateTime<Utc>",
+                w.created_at        AS "workspace_created_at!: DateTime<Utc>",
+                pr.opened_at        AS "pull_request_opened_at?: DateTime<Utc>",
+                pr.merged_at        AS "pull_request_merged_at?: DateTime<Utc>"
+            FROM workspaces w
+            JOIN issues i ON i.id = w.issue_id
+            CROSS JOIN LATERAL (
+                SELECT MIN(p.created_at) AS opened_at, MIN(p.merged_at) AS merged_at
+                FROM pull_requests p
+                WHERE p.workspace_id = w.id
+            ) pr
+            WHERE w.project_id = $1
+            ORDER BY w.created_at
+            "#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+        Ok(timelines)
+    }
+
     pub async fn count_by_issue_id(pool: &PgPool, issue_id: Uuid) -> Result<i64, WorkspaceError> {
         let count = sqlx::query_scalar!(
             r#"SELECT COUNT(*) AS "count!" FROM workspaces WHERE issue_id = $1"#,
@@ -0,0 +1,194 @@
+//! Blocking-relationship graph of a project's issues, used for release
+//! planning: topological order, critical path and transitive dependents.
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+pub(crate) struct GraphNode {
+    pub id: Uuid,
+    pub target_date: Option<DateTime<Utc>>,
+    /// Completed issues no longer gate anything on the critical path.
+    pub open: bool,
+}
+
+pub(crate) struct DependencyGraph<'a> {
+    nodes: &'a [GraphNode],
+    index: HashMap<Uuid, usize>,
+    blockers: Vec<Vec<usize>>,
+    dependents: Vec<Vec<usize>>,
+    order: Vec<usize>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    /// Build the graph from `(blocking, blocked)` edges. Edges to issues
+    /// outside `nodes` are ignored. Ties in the topological order keep the
+    /// order of `nodes`.
+    pub fn new(nodes: &'a [GraphNode], edges: &[(Uuid, Uuid)]) -> Self {
+        let index: HashMap<Uuid, usize> = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id, i))
+            .collect();
+        let mut blockers = vec![Vec::new(); nodes.len()];
+        let mut dependents = vec![Vec::new(); nodes.len()];
+        for (blocking, blocked) in edges {
+            if let (Some(&from), Some(&to)) = (index.get(blocking), index.get(blocked)) {
+                blockers[to].push(from);
+                dependents[from].push(to);
+            }
+        }
+
+        let mut in_degree: Vec<usize> = blockers.iter().map(Vec::len).collect();
+        let mut ready: BTreeSet<usize> = (0..nodes.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(nodes.len());
+        while let Some(i) = ready.pop_first() {
+            order.push(i);
+            for &next in &dependents[i] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+        // Cycles are rejected on write, but older data may still contain
+        // them; keep those issues rather than dropping them from the order.
+        if order.len() < nodes.len() {
+            let placed: HashSet<usize> = order.iter().copied().collect();
+            order.extend((0..nodes.len()).filter(|i| !placed.contains(i)));
+        }
+
+        Self {
+            nodes,
+            index,
+            blockers,
+            dependents,
+            order,
+        }
+    }
+
+    pub fn topological_order(&self) -> Vec<Uuid> {
+        self.order.iter().map(|&i| self.nodes[i].id).collect()
+    }
+
+    /// The chain of open issues that gates the open issue with the latest
+    /// `target_date`: from that issue, repeatedly step to the open blocker
+    /// with the longest chain of open blockers behind it (later
+    /// `target_date` breaks ties). Returned first blocker first. Without any
+    /// target dates, the longest open chain is returned.
+    pub fn critical_path(&self) -> Vec<Uuid> {
+        let mut depth = vec![0usize; self.nodes.len()];
+        for &i in &self.order {
+            if !self.nodes[i].open {
+                continue;
+            }
+            depth[i] = 1 + self.blockers[i]
+                .iter()
+                .map(|&b| depth[b])
+                .max()
+                .unwrap_or(0);
+        }
+
+        let rank = |i: usize| (depth[i], self.nodes[i].target_date);
+        let end = (0..self.nodes.len())
+            .filter(|&i| self.nodes[i].open)
+            .max_by_key(|&i| (self.nodes[i].target_date, depth[i]));
+        let Some(mut current) = end else {
+            return Vec::new();
+        };
+
+        let mut visited = HashSet::from([current]);
+        let mut path = vec![self.nodes[current].id];
+        while let Some(next) = self.blockers[current]
+            .iter()
+            .copied()
+            .filter(|&b| self.nodes[b].open && !visited.contains(&b))
+            .max_by_key(|&b| rank(b))
+        {
+            visited.insert(next);
+            path.push(self.nodes[next].id);
+            current = next;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Issues transitively blocked by `issue_id`, in topological order.
+    pub fn blocked_by(&self, issue_id: Uuid) -> Vec<Uuid> {
+        let Some(&start) = self.index.get(&issue_id) else {
+            return Vec::new();
+        };
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(i) = queue.pop_front() {
+            for &next in &self.dependents[i] {
+                if next != start && seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        self.order
+            .iter()
+            .filter(|i| seen.contains(i))
+            .map(|&i| self.nodes[i].id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn node(id: u128, day: Option<u32>, open: bool) -> GraphNode {
+        GraphNode {
+            id: Uuid::from_u128(id),
+            target_date: day.map(|d| Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap()),
+            open,
+        }
+    }
+
+    fn edge(from: u128, to: u128) -> (Uuid, Uuid) {
+        (Uuid::from_u128(from), Uuid::from_u128(to))
+    }
+
+    fn ids(raw: &[u128]) -> Vec<Uuid> {
+        raw.iter().copied().map(Uuid::from_u128).collect()
+    }
+
+    #[test]
+    fn orders_blockers_first() {
+        let nodes = [
+            node(1, None, true),
+            node(2, None, true),
+            node(3, None, true),
+        ];
+        let graph = DependencyGraph::new(&nodes, &[edge(3, 1), edge(1, 2)]);
+        assert_eq!(graph.topological_order(), ids(&[3, 1, 2]));
+        assert_eq!(graph.blocked_by(Uuid::from_u128(3)), ids(&[1, 2]));
+    }
+
+    #[test]
+    fn critical_path_follows_longest_open_chain() {
+        // 1 -> 2 -> 4, 5 -> 2 and 3 -> 4; 4 has the latest target date. 1 is done.
+        let nodes = [
+            node(1, Some(1), false),
+            node(2, Some(5), true),
+            node(3, Some(8), true),
+            node(4, Some(20), true),
+            node(5, Some(10), true),
+        ];
+        let graph = DependencyGraph::new(&nodes, &[edge(1, 2), edge(2, 4), edge(3, 4), edge(5, 2)]);
+        assert_eq!(graph.critical_path(), ids(&[5, 2, 4]));
+    }
+
+    #[test]
+    fn keeps_issues_in_legacy_cycles() {
+        let nodes = [node(1, None, true), node(2, None, true)];
+        let graph = DependencyGraph::new(&nodes, &[edge(1, 2), edge(2, 1)]);
+        assert_eq!(graph.topological_order(), ids(&[1, 2]));
+        assert_eq!(graph.critical_path().len(), 2);
+    }
+}
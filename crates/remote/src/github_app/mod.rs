@@ -1,9 +1,16 @@
 mod jwt;
 mod pr_review;
+mod review_comments;
 mod service;
 mod webhook;
 
 pub use jwt::GitHubAppJwt;
 pub use pr_review::{PrReviewError, PrReviewParams, PrReviewService};
-pub use service::{GitHubAppService, InstallationInfo, PrDetails, PrRef, Repository};
+pub use review_comments::{
+    InstallationPullRequest, PublishOutcome, PullRequestReviewApi, ReviewResult, publish_review,
+};
+pub use service::{
+    GitHubAppError, GitHubAppService, GitHubIssue, GitHubIssueComment, GitHubIssueWrite,
+    GitHubLabel, GitHubUser, InstallationInfo, PrDetails, PrRef, Repository,
+};
 pub use webhook::verify_webhook_signature;
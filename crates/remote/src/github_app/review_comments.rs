@@ -0,0 +1,744 @@
+//! Publishes a finished webhook review to its pull request as a GitHub review.
+//!
+//! The review summary goes into a single PR comment and each finding becomes
+//! an inline comment on the diff line it refers to. Findings on lines outside
+//! the diff, which GitHub would reject, are listed in the summary instead.
+//!
+//! Every comment carries a hidden marker, so a re-review edits the summary in
+//! place, updates findings that are still present, resolves the threads of
+//! findings that are gone and only posts comments for new findings.
+
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use super::service::{
+    GitHubAppError, GitHubAppService, GitHubIssueComment, GitHubPullRequestFile,
+    GitHubReviewCommentWrite, GitHubReviewThread, GitHubReviewWrite,
+};
+
+const SUMMARY_MARKER: &str = "<!-- vibe-kanban-review:summary -->";
+const FINDING_MARKER_PREFIX: &str = "<!-- vibe-kanban-review:finding:";
+
+/// Review result as returned by the review worker
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReviewResult {
+    pub summary: String,
+    #[serde(default)]
+    pub comments: Vec<ReviewFinding>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReviewFinding {
+    pub comment: String,
+    #[serde(default)]
+    pub fragments: Vec<CodeFragment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CodeFragment {
+    pub file: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub message: String,
+}
+
+/// What publishing changed on the pull request
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PublishOutcome {
+    pub created: usize,
+    pub updated: usize,
+    pub resolved: usize,
+    /// Findings listed in the summary because their lines are not in the diff
+    pub outside_diff: usize,
+}
+
+/// The GitHub API calls needed to publish a review on one pull request
+#[async_trait]
+pub trait PullRequestReviewApi: Send + Sync {
+    /// Login GitHub attributes the app's comments to
+    fn bot_login(&self) -> String;
+
+    async fn head_sha(&self) -> Result<String, GitHubAppError>;
+
+    async fn changed_files(&self) -> Result<Vec<GitHubPullRequestFile>, GitHubAppError>;
+
+    async fn issue_comments(&self) -> Result<Vec<GitHubIssueComment>, GitHubAppError>;
+
+    async fn create_issue_comment(&self, body: &str) -> Result<(), GitHubAppError>;
+
+    async fn update_issue_comment(&self, comment_id: i64, body: &str)
+    -> Result<(), GitHubAppError>;
+
+    async fn review_threads(&self) -> Result<Vec<GitHubReviewThread>, GitHubAppError>;
+
+    async fn create_review(&self, review: &GitHubReviewWrite) -> Result<(), GitHubAppError>;
+
+    async fn update_review_comment(
+        &self,
+        comment_id: i64,
+        body: &str,
+    ) -> Result<(), GitHubAppError>;
+
+    async fn set_thread_resolved(
+        &self,
+        thread_id: &str,
+        resolved: bool,
+    ) -> Result<(), GitHubAppError>;
+}
+
+/// A pull request reached through the GitHub App installation
+pub struct InstallationPullRequest<'a> {
+    pub github_app: &'a GitHubAppService,
+    pub installation_id: i64,
+    pub repo_full_name: String,
+    pub number: i64,
+}
+
+#[async_trait]
+impl PullRequestReviewApi for InstallationPullRequest<'_> {
+    fn bot_login(&self) -> String {
+        self.github_app.bot_login()
+    }
+
+    async fn head_sha(&self) -> Result<String, GitHubAppError> {
+        let (owner, repo) = self
+            .repo_full_name
+            .split_once('/')
+            .unwrap_or(("", &self.repo_full_name));
+        let pr = self
+            .github_app
+            .get_pr_details(self.installation_id, owner, repo, self.number as u64)
+            .await?;
+        Ok(pr.head.sha)
+    }
+
+    async fn changed_files(&self) -> Result<Vec<GitHubPullRequestFile>, GitHubAppError> {
+        self.github_app
+            .list_pull_request_files(self.installation_id, &self.repo_full_name, self.number)
+            .await
+    }
+
+    async fn issue_comments(&self) -> Result<Vec<GitHubIssueComment>, GitHubAppError> {
+        self.github_app
+            .list_issue_comments(self.installation_id, &self.repo_full_name, self.number)
+            .await
+    }
+
+    async fn create_issue_comment(&self, body: &str) -> Result<(), GitHubAppError> {
+        self.github_app
+            .create_issue_comment(
+                self.installation_id,
+                &self.repo_full_name,
+                self.number,
+                body,
+            )
+            .await
+            .map(|_| ())
+    }
+
+    async fn update_issue_comment(
+        &self,
+        comment_id: i64,
+        body: &str,
+    ) -> Result<(), GitHubAppError> {
+        self.github_app
+            .update_issue_comment(self.installation_id, &self.repo_full_name, comment_id, body)
+            .await
+            .map(|_| ())
+    }
+
+    async fn review_threads(&self) -> Result<Vec<GitHubReviewThread>, GitHubAppError> {
+        self.github_app
+            .list_review_threads(self.installation_id, &self.repo_full_name, self.number)
+            .await
+    }
+
+    async fn create_review(&self, review: &GitHubReviewWrite) -> Result<(), GitHubAppError> {
+        self.github_app
+            .create_pull_request_review(
+                self.installation_id,
+                &self.repo_full_name,
+                self.number,
+                review,
+            )
+            .await
+    }
+
+    async fn update_review_comment(
+        &self,
+        comment_id: i64,
+        body: &str,
+    ) -> Result<(), GitHubAppError> {
+        self.github_app
+            .update_review_comment(self.installation_id, &self.repo_full_name, comment_id, body)
+            .await
+    }
+
+    async fn set_thread_resolved(
+        &self,
+        thread_id: &str,
+        resolved: bool,
+    ) -> Result<(), GitHubAppError> {
+        self.github_app
+            .set_review_thread_resolved(self.installation_id, thread_id, resolved)
+            .await
+    }
+}
+
+/// An inline comment to publish, identified across re-reviews by its
+/// fingerprint
+#[derive(Debug, Clone)]
+struct InlineFinding {
+    fingerprint: String,
+    comment: GitHubReviewCommentWrite,
+}
+
+/// Publish `result` to the pull request, replacing what earlier reviews posted
+pub async fn publish_review(
+    api: &dyn PullRequestReviewApi,
+    result: &ReviewResult,
+    review_url: &str,
+) -> Result<PublishOutcome, GitHubAppError> {
+    let bot_login = api.bot_login();
+    let head_sha = api.head_sha().await?;
+    let diff_lines = DiffLines::from_files(&api.changed_files().await?);
+
+    let (findings, outside_diff) = place_findings(result, &diff_lines);
+    let mut outcome = PublishOutcome {
+        outside_diff: outside_diff.len(),
+        ..Default::default()
+    };
+
+    // Threads started by earlier reviews, by finding fingerprint
+    let threads = api.review_threads().await?;
+    let previous: HashMap<&str, &GitHubReviewThread> = threads
+        .iter()
+        .filter_map(|thread| {
+            let comment = thread.first_comment.as_ref()?;
+            if !is_bot(comment.author_login.as_deref(), &bot_login) {
+                return None;
+            }
+            Some((finding_fingerprint(&comment.body)?, thread))
+        })
+        .collect();
+
+    let mut new_comments = Vec::new();
+    let mut current = HashSet::new();
+
+    for finding in findings {
+        current.insert(finding.fingerprint.clone());
+
+        let Some(thread) = previous.get(finding.fingerprint.as_str()) else {
+            new_comments.push(finding.comment);
+            continue;
+        };
+
+        // GitHub cannot move a comment, so a finding whose lines moved stays
+        // on its original thread. The fingerprint ignores case and spacing,
+        // which the new body may still differ in.
+        if let Some(comment) = &thread.first_comment
+            && comment.body != finding.comment.body
+        {
+            api.update_review_comment(comment.id, &finding.comment.body)
+                .await?;
+        }
+        if thread.is_resolved {
+            api.set_thread_resolved(&thread.id, false).await?;
+        }
+        outcome.updated += 1;
+    }
+
+    for (fingerprint, thread) in &previous {
+        if !current.contains(*fingerprint) && !thread.is_resolved {
+            api.set_thread_resolved(&thread.id, true).await?;
+            outcome.resolved += 1;
+        }
+    }
+
+    if !new_comments.is_empty() {
+        outcome.created = new_comments.len();
+        api.create_review(&GitHubReviewWrite {
+            commit_id: head_sha,
+            event: "COMMENT",
+            body: None,
+            comments: new_comments,
+        })
+        .await?;
+    }
+
+    let summary = summary_body(result, review_url, &outside_diff);
+    let existing_summary = api.issue_comments().await?.into_iter().find(|comment| {
+        is_bot(Some(comment.user.login.as_str()), &bot_login)
+            && comment
+                .body
+                .as_deref()
+                .is_some_and(|body| body.contains(SUMMARY_MARKER))
+    });
+
+    match existing_summary {
+        Some(comment) => api.update_issue_comment(comment.id, &summary).await?,
+        None => api.create_issue_comment(&summary).await?,
+    }
+
+    debug!(?outcome, "Published review to pull request");
+
+    Ok(outcome)
+}
+
+/// Lines on the new side of a PR diff that inline comments can be attached
+/// to, with the hunk each belongs to
+#[derive(Debug, Default)]
+struct DiffLines {
+    files: HashMap<String, HashMap<u32, usize>>,
+}
+
+impl DiffLines {
+    fn from_files(files: &[GitHubPullRequestFile]) -> Self {
+        let files = files
+            .iter()
+            .filter_map(|file| {
+                let patch = file.patch.as_deref()?;
+                Some((file.filename.clone(), commentable_lines(patch)))
+            })
+            .collect();
+
+        Self { files }
+    }
+
+    /// The range to comment on for `start..=end`: the last commentable line,
+    /// extended back to `start` when it is in the same hunk
+    fn anchor(&self, file: &str, start: u32, end: u32) -> Option<(Option<u32>, u32)> {
+        let lines = self.files.get(file)?;
+        let (start, end) = (start.min(end), start.max(end));
+
+        let line = (start..=end).rev().find(|line| lines.contains_key(line))?;
+        let hunk = lines[&line];
+        // A hunk covers a contiguous range of new-side lines
+        let start_line = (start..line).find(|candidate| lines.get(candidate) == Some(&hunk));
+
+        Some((start_line, line))
+    }
+}
+
+/// Parse unified diff hunks into the new-side line numbers they show
+fn commentable_lines(patch: &str) -> HashMap<u32, usize> {
+    let mut lines = HashMap::new();
+    let mut hunk = 0;
+    let mut next_line: Option<u32> = None;
+
+    for line in patch.lines() {
+        if let Some(header) = line.strip_prefix("@@ ") {
+            // @@ -a,b +c,d @@
+            next_line = header
+                .split_whitespace()
+                .find_map(|part| part.strip_prefix('+'))
+                .and_then(|range| range.split(',').next())
+                .and_then(|start| start.parse().ok());
+            hunk += 1;
+            continue;
+        }
+
+        let Some(current) = next_line else {
+            continue;
+        };
+
+        match line.chars().next() {
+            Some('+') | Some(' ') | None => {
+                lines.insert(current, hunk);
+                next_line = Some(current + 1);
+            }
+            // Removed lines and "\ No newline at end of file"
+            _ => {}
+        }
+    }
+
+    lines
+}
+
+/// Split the findings into inline comments and lines for the summary
+fn place_findings(
+    result: &ReviewResult,
+    diff_lines: &DiffLines,
+) -> (Vec<InlineFinding>, Vec<String>) {
+    let mut inline: Vec<InlineFinding> = Vec::new();
+    let mut outside_diff = Vec::new();
+
+    for finding in &result.comments {
+        if finding.fragments.is_empty() {
+            outside_diff.push(format!("- **{}**", finding.comment.trim()));
+            continue;
+        }
+
+        for fragment in &finding.fragments {
+            let Some((start_line, line)) =
+                diff_lines.anchor(&fragment.file, fragment.start_line, fragment.end_line)
+            else {
+                outside_diff.push(format!(
+                    "- **{}** `{}:{}-{}`: {}",
+                    finding.comment.trim(),
+                    fragment.file,
+                    fragment.start_line,
+                    fragment.end_line,
+                    fragment.message.trim()
+                ));
+                continue;
+            };
+
+            let fingerprint = fingerprint(&fragment.file, &finding.comment, &fragment.message);
+            if inline.iter().any(|f| f.fingerprint == fingerprint) {
+                continue;
+            }
+
+            inline.push(InlineFinding {
+                comment: GitHubReviewCommentWrite {
+                    path: fragment.file.clone(),
+                    line,
+                    start_line,
+                    side: "RIGHT",
+                    body: finding_body(&finding.comment, &fragment.message, &fingerprint),
+                },
+                fingerprint,
+            });
+        }
+    }
+
+    (inline, outside_diff)
+}
+
+/// Identifies a finding across re-reviews: the same text on the same file,
+/// wherever its lines moved to
+fn fingerprint(file: &str, comment: &str, message: &str) -> String {
+    let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let digest = Sha256::digest(format!(
+        "{file}\n{}\n{}",
+        normalize(comment).to_lowercase(),
+        normalize(message).to_lowercase()
+    ));
+    digest[..8]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn finding_fingerprint(body: &str) -> Option<&str> {
+    let start = body.find(FINDING_MARKER_PREFIX)? + FINDING_MARKER_PREFIX.len();
+    let rest = &body[start..];
+    rest.find(" -->").map(|end| rest[..end].trim())
+}
+
+fn finding_body(comment: &str, message: &str, fingerprint: &str) -> String {
+    let (comment, message) = (comment.trim(), message.trim());
+    let mut body = format!("**{comment}**");
+    if !message.is_empty() && message != comment {
+        body.push_str(&format!("\n\n{message}"));
+    }
+    body.push_str(&format!("\n\n{FINDING_MARKER_PREFIX}{fingerprint} -->"));
+    body
+}
+
+fn summary_body(result: &ReviewResult, review_url: &str, outside_diff: &[String]) -> String {
+    let mut body = format!(
+        "{SUMMARY_MARKER}\n## Review Complete\n\n{}\n\n**[View Story]({review_url})**\n",
+        result.summary.trim()
+    );
+
+    if !outside_diff.is_empty() {
+        body.push_str("\n### Findings outside the diff\n\n");
+        body.push_str(&outside_diff.join("\n"));
+        body.push('\n');
+    }
+
+    body.push_str("\nComment **!reviewfast** on this PR to re-generate the story.");
+    body
+}
+
+/// REST reports the app as `slug[bot]`, GraphQL as `slug`
+fn is_bot(login: Option<&str>, bot_login: &str) -> bool {
+    login
+        .is_some_and(|login| login.trim_end_matches("[bot]") == bot_login.trim_end_matches("[bot]"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use chrono::Utc;
+
+    use super::*;
+    use crate::github_app::service::{GitHubReviewThreadComment, GitHubUser};
+
+    const BOT: &str = "vibe-kanban[bot]";
+
+    /// In-memory stand-in for a pull request on GitHub
+    #[derive(Default)]
+    struct FakePullRequest {
+        files: Vec<GitHubPullRequestFile>,
+        issue_comments: Mutex<Vec<GitHubIssueComment>>,
+        threads: Mutex<Vec<GitHubReviewThread>>,
+        reviews: Mutex<Vec<GitHubReviewWrite>>,
+        updated_review_comments: Mutex<Vec<(i64, String)>>,
+    }
+
+    impl FakePullRequest {
+        fn with_patch(file: &str, patch: &str) -> Self {
+            Self {
+                files: vec![GitHubPullRequestFile {
+                    filename: file.to_string(),
+                    patch: Some(patch.to_string()),
+                }],
+                ..Default::default()
+            }
+        }
+
+        /// Turn submitted reviews into threads, as GitHub would
+        fn submit_reviews(&self) {
+            let mut threads = self.threads.lock().unwrap();
+            for review in self.reviews.lock().unwrap().drain(..) {
+                for comment in review.comments {
+                    let id = threads.len() as i64 + 1;
+                    threads.push(GitHubReviewThread {
+                        id: format!("thread-{id}"),
+                        is_resolved: false,
+                        first_comment: Some(GitHubReviewThreadComment {
+                            id,
+                            body: comment.body,
+                            author_login: Some("vibe-kanban".to_string()),
+                        }),
+                    });
+                }
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PullRequestReviewApi for FakePullRequest {
+        fn bot_login(&self) -> String {
+            BOT.to_string()
+        }
+
+        async fn head_sha(&self) -> Result<String, GitHubAppError> {
+            Ok("abc123".to_string())
+        }
+
+        async fn changed_files(&self) -> Result<Vec<GitHubPullRequestFile>, GitHubAppError> {
+            Ok(self.files.clone())
+        }
+
+        async fn issue_comments(&self) -> Result<Vec<GitHubIssueComment>, GitHubAppError> {
+            Ok(self.issue_comments.lock().unwrap().clone())
+        }
+
+        async fn create_issue_comment(&self, body: &str) -> Result<(), GitHubAppError> {
+            let mut comments = self.issue_comments.lock().unwrap();
+            let id = comments.len() as i64 + 1;
+            comments.push(GitHubIssueComment {
+                id,
+                body: Some(body.to_string()),
+                user: GitHubUser {
+                    login: BOT.to_string(),
+                    user_type: "Bot".to_string(),
+                },
+                updated_at: Utc::now(),
+            });
+            Ok(())
+        }
+
+        async fn update_issue_comment(
+            &self,
+            comment_id: i64,
+            body: &str,
+        ) -> Result<(), GitHubAppError> {
+            let mut comments = self.issue_comments.lock().unwrap();
+            if let Some(comment) = comments.iter_mut().find(|c| c.id == comment_id) {
+                comment.body = Some(body.to_string());
+            }
+            Ok(())
+        }
+
+        async fn review_threads(&self) -> Result<Vec<GitHubReviewThread>, GitHubAppError> {
+            Ok(self.threads.lock().unwrap().clone())
+        }
+
+        async fn create_review(&self, review: &GitHubReviewWrite) -> Result<(), GitHubAppError> {
+            self.reviews.lock().unwrap().push(review.clone());
+            Ok(())
+        }
+
+        async fn update_review_comment(
+            &self,
+            comment_id: i64,
+            body: &str,
+        ) -> Result<(), GitHubAppError> {
+            self.updated_review_comments
+                .lock()
+                .unwrap()
+                .push((comment_id, body.to_string()));
+            Ok(())
+        }
+
+        async fn set_thread_resolved(
+            &self,
+            thread_id: &str,
+            resolved: bool,
+        ) -> Result<(), GitHubAppError> {
+            let mut threads = self.threads.lock().unwrap();
+            if let Some(thread) = threads.iter_mut().find(|t| t.id == thread_id) {
+                thread.is_resolved = resolved;
+            }
+            Ok(())
+        }
+    }
+
+    const PATCH: &str = concat!(
+        "@@ -1,3 +1,4 @@\n",
+        " fn main() {\n",
+        "-    old();\n",
+        "+    new();\n",
+        "+    more();\n",
+        " }\n",
+        "@@ -20,2 +21,2 @@\n",
+        " fn other() {\n",
+        "-}\n",
+        "+ }",
+    );
+
+    fn finding(comment: &str, file: &str, start: u32, end: u32) -> ReviewFinding {
+        ReviewFinding {
+            comment: comment.to_string(),
+            fragments: vec![CodeFragment {
+                file: file.to_string(),
+                start_line: start,
+                end_line: end,
+                message: format!("{comment} details"),
+            }],
+        }
+    }
+
+    fn review(findings: Vec<ReviewFinding>) -> ReviewResult {
+        ReviewResult {
+            summary: "Adds a new call".to_string(),
+            comments: findings,
+        }
+    }
+
+    #[test]
+    fn test_commentable_lines() {
+        let lines = commentable_lines(PATCH);
+        let mut numbers: Vec<u32> = lines.keys().copied().collect();
+        numbers.sort();
+        assert_eq!(numbers, vec![1, 2, 3, 4, 21, 22]);
+        assert_eq!(lines[&2], lines[&3]);
+        assert_ne!(lines[&4], lines[&21]);
+    }
+
+    #[test]
+    fn test_anchor() {
+        let diff = DiffLines::from_files(&[GitHubPullRequestFile {
+            filename: "src/main.rs".to_string(),
+            patch: Some(PATCH.to_string()),
+        }]);
+
+        assert_eq!(diff.anchor("src/main.rs", 2, 3), Some((Some(2), 3)));
+        assert_eq!(diff.anchor("src/main.rs", 3, 3), Some((None, 3)));
+        // Only the part of the range inside the diff
+        assert_eq!(diff.anchor("src/main.rs", 10, 21), Some((None, 21)));
+        assert_eq!(diff.anchor("src/main.rs", 10, 15), None);
+        assert_eq!(diff.anchor("src/other.rs", 1, 1), None);
+    }
+
+    #[tokio::test]
+    async fn test_publish_review() {
+        let pr = FakePullRequest::with_patch("src/main.rs", PATCH);
+        let result = review(vec![
+            finding("Unchecked result", "src/main.rs", 2, 3),
+            finding("Stale docs", "README.md", 1, 2),
+        ]);
+
+        let outcome = publish_review(&pr, &result, "https://example.com/review/1")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            PublishOutcome {
+                created: 1,
+                updated: 0,
+                resolved: 0,
+                outside_diff: 1,
+            }
+        );
+
+        let reviews = pr.reviews.lock().unwrap();
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].commit_id, "abc123");
+        assert_eq!(reviews[0].comments[0].path, "src/main.rs");
+        assert_eq!(reviews[0].comments[0].start_line, Some(2));
+        assert_eq!(reviews[0].comments[0].line, 3);
+
+        let comments = pr.issue_comments.lock().unwrap();
+        assert_eq!(comments.len(), 1);
+        let summary = comments[0].body.as_deref().unwrap();
+        assert!(summary.contains("Adds a new call"));
+        assert!(summary.contains("`README.md:1-2`"));
+    }
+
+    #[tokio::test]
+    async fn test_republish_updates_and_resolves() {
+        let pr = FakePullRequest::with_patch("src/main.rs", PATCH);
+
+        publish_review(
+            &pr,
+            &review(vec![
+                finding("Unchecked result", "src/main.rs", 2, 3),
+                finding("Missing test", "src/main.rs", 21, 22),
+            ]),
+            "https://example.com/review/1",
+        )
+        .await
+        .unwrap();
+        pr.submit_reviews();
+
+        // The first finding is still there, on a different line; the second
+        // was fixed and a third is new
+        let outcome = publish_review(
+            &pr,
+            &review(vec![
+                finding("Unchecked result", "src/main.rs", 4, 4),
+                finding("Typo", "src/main.rs", 1, 1),
+            ]),
+            "https://example.com/review/2",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            outcome,
+            PublishOutcome {
+                created: 1,
+                updated: 1,
+                resolved: 1,
+                outside_diff: 0,
+            }
+        );
+
+        let threads = pr.threads.lock().unwrap();
+        assert!(!threads[0].is_resolved);
+        assert!(threads[1].is_resolved);
+        assert_eq!(pr.reviews.lock().unwrap()[0].comments.len(), 1);
+
+        // One summary, edited in place
+        let comments = pr.issue_comments.lock().unwrap();
+        assert_eq!(comments.len(), 1);
+        assert!(
+            comments[0]
+                .body
+                .as_deref()
+                .unwrap()
+                .contains("https://example.com/review/2")
+        );
+    }
+}
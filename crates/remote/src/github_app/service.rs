@@ -1,6 +1,7 @@
-use reqwest::Client;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, Method, RequestBuilder};
 use secrecy::SecretString;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use tempfile::TempDir;
 use thiserror::Error;
 use tokio::process::Command;
@@ -24,6 +25,8 @@ pub enum GitHubAppError {
     InstallationNotFound,
     #[error("Git operation failed: {0}")]
     GitOperation(String),
+    #[error("GitHub GraphQL error: {0}")]
+    GraphQl(String),
 }
 
 /// Information about a GitHub App installation
@@ -80,6 +83,202 @@ pub struct PrRef {
     pub ref_name: String,
 }
 
+/// A GitHub issue. The issues API also returns pull requests, which carry a
+/// `pull_request` key.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubIssue {
+    pub number: i64,
+    pub title: String,
+    pub body: Option<String>,
+    pub state: String,
+    #[serde(default)]
+    pub labels: Vec<GitHubLabel>,
+    pub user: GitHubUser,
+    pub pull_request: Option<serde_json::Value>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubLabel {
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubUser {
+    pub login: String,
+    #[serde(rename = "type")]
+    pub user_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubIssueComment {
+    pub id: i64,
+    pub body: Option<String>,
+    pub user: GitHubUser,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Fields to set when creating or editing a GitHub issue.
+#[derive(Debug, Clone, Serialize)]
+pub struct GitHubIssueWrite {
+    pub title: String,
+    pub body: String,
+    pub state: &'static str,
+    pub labels: Vec<String>,
+}
+
+/// A file changed in a pull request. `patch` is missing for binary files and
+/// diffs too large for the API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubPullRequestFile {
+    pub filename: String,
+    pub patch: Option<String>,
+}
+
+/// A pull request review to submit, with its inline comments.
+#[derive(Debug, Clone, Serialize)]
+pub struct GitHubReviewWrite {
+    pub commit_id: String,
+    /// `COMMENT`, `APPROVE` or `REQUEST_CHANGES`
+    pub event: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub comments: Vec<GitHubReviewCommentWrite>,
+}
+
+/// An inline comment on the new side of a pull request diff. Multi-line
+/// comments span `start_line..=line` within one hunk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GitHubReviewCommentWrite {
+    pub path: String,
+    pub line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<u32>,
+    pub side: &'static str,
+    pub body: String,
+}
+
+/// A review comment thread. Threads are read and resolved through the GraphQL
+/// API, as REST has no notion of resolving.
+#[derive(Debug, Clone)]
+pub struct GitHubReviewThread {
+    /// GraphQL node ID
+    pub id: String,
+    pub is_resolved: bool,
+    /// The comment that started the thread
+    pub first_comment: Option<GitHubReviewThreadComment>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GitHubReviewThreadComment {
+    /// REST ID, used to edit the comment
+    pub id: i64,
+    pub body: String,
+    /// GraphQL reports bot logins without the `[bot]` suffix
+    pub author_login: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadsData {
+    repository: Option<ReviewThreadsRepository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadsRepository {
+    pull_request: Option<ReviewThreadsPullRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadsPullRequest {
+    review_threads: ReviewThreadConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadConnection {
+    page_info: PageInfo,
+    nodes: Vec<ReviewThreadNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadNode {
+    id: String,
+    is_resolved: bool,
+    comments: ReviewThreadCommentConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewThreadCommentConnection {
+    nodes: Vec<ReviewThreadCommentNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewThreadCommentNode {
+    database_id: Option<i64>,
+    body: String,
+    author: Option<GraphQlActor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlActor {
+    login: String,
+}
+
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!, $cursor: String) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $cursor) {
+        pageInfo { hasNextPage endCursor }
+        nodes {
+          id
+          isResolved
+          comments(first: 1) {
+            nodes { databaseId body author { login } }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+const RESOLVE_REVIEW_THREAD_MUTATION: &str = r#"
+mutation($threadId: ID!) {
+  resolveReviewThread(input: { threadId: $threadId }) { thread { id } }
+}
+"#;
+
+const UNRESOLVE_REVIEW_THREAD_MUTATION: &str = r#"
+mutation($threadId: ID!) {
+  unresolveReviewThread(input: { threadId: $threadId }) { thread { id } }
+}
+"#;
+
 /// Service for interacting with the GitHub App API
 #[derive(Clone)]
 pub struct GitHubAppService {
@@ -450,4 +649,412 @@ impl GitHubAppService {
         let pr: PrDetails = response.json().await?;
         Ok(pr)
     }
+
+    /// The login GitHub attributes the app's own API writes to, used to skip
+    /// webhook events caused by them.
+    pub fn bot_login(&self) -> String {
+        format!("{}[bot]", self.app_slug)
+    }
+
+    fn installation_request(&self, method: Method, url: &str, token: &str) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", USER_AGENT)
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    async fn send_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, GitHubAppError> {
+        let response = request.send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let message = response.text().await.unwrap_or_default();
+            return Err(GitHubAppError::Api { status, message });
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// List a repository's issues (not pull requests), optionally only those
+    /// updated since the given time. Closed issues are left out unless
+    /// `include_closed` is set.
+    pub async fn list_repo_issues(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        since: Option<DateTime<Utc>>,
+        include_closed: bool,
+    ) -> Result<Vec<GitHubIssue>, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!("{}/repos/{}/issues", GITHUB_API_BASE, repo_full_name);
+        let state = if include_closed { "all" } else { "open" };
+
+        let mut all_issues = Vec::new();
+        let mut page = 1u32;
+
+        loop {
+            let mut query = vec![
+                ("state", state.to_string()),
+                ("per_page", "100".to_string()),
+                ("page", page.to_string()),
+            ];
+            if let Some(since) = since {
+                query.push(("since", since.to_rfc3339()));
+            }
+
+            let issues: Vec<GitHubIssue> = Self::send_json(
+                self.installation_request(Method::GET, &url, &token)
+                    .query(&query),
+            )
+            .await?;
+            let count = issues.len();
+            all_issues.extend(
+                issues
+                    .into_iter()
+                    .filter(|issue| issue.pull_request.is_none()),
+            );
+
+            if count < 100 {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(all_issues)
+    }
+
+    pub async fn get_issue(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        number: i64,
+    ) -> Result<GitHubIssue, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!(
+            "{}/repos/{}/issues/{}",
+            GITHUB_API_BASE, repo_full_name, number
+        );
+
+        Self::send_json(self.installation_request(Method::GET, &url, &token)).await
+    }
+
+    pub async fn create_issue(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        issue: &GitHubIssueWrite,
+    ) -> Result<GitHubIssue, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!("{}/repos/{}/issues", GITHUB_API_BASE, repo_full_name);
+
+        let created: GitHubIssue = Self::send_json(
+            self.installation_request(Method::POST, &url, &token)
+                .json(issue),
+        )
+        .await?;
+
+        // New issues always start open.
+        if issue.state == "closed" {
+            return self
+                .update_issue(installation_id, repo_full_name, created.number, issue)
+                .await;
+        }
+
+        Ok(created)
+    }
+
+    pub async fn update_issue(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        number: i64,
+        issue: &GitHubIssueWrite,
+    ) -> Result<GitHubIssue, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!(
+            "{}/repos/{}/issues/{}",
+            GITHUB_API_BASE, repo_full_name, number
+        );
+
+        Self::send_json(
+            self.installation_request(Method::PATCH, &url, &token)
+                .json(issue),
+        )
+        .await
+    }
+
+    pub async fn list_issue_comments(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        number: i64,
+    ) -> Result<Vec<GitHubIssueComment>, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!(
+            "{}/repos/{}/issues/{}/comments",
+            GITHUB_API_BASE, repo_full_name, number
+        );
+
+        let mut all_comments = Vec::new();
+        let mut page = 1u32;
+
+        loop {
+            let comments: Vec<GitHubIssueComment> = Self::send_json(
+                self.installation_request(Method::GET, &url, &token)
+                    .query(&[("per_page", "100"), ("page", &page.to_string())]),
+            )
+            .await?;
+            let count = comments.len();
+            all_comments.extend(comments);
+
+            if count < 100 {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(all_comments)
+    }
+
+    pub async fn get_issue_comment(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        comment_id: i64,
+    ) -> Result<GitHubIssueComment, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!(
+            "{}/repos/{}/issues/comments/{}",
+            GITHUB_API_BASE, repo_full_name, comment_id
+        );
+
+        Self::send_json(self.installation_request(Method::GET, &url, &token)).await
+    }
+
+    pub async fn create_issue_comment(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        number: i64,
+        body: &str,
+    ) -> Result<GitHubIssueComment, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!(
+            "{}/repos/{}/issues/{}/comments",
+            GITHUB_API_BASE, repo_full_name, number
+        );
+
+        Self::send_json(
+            self.installation_request(Method::POST, &url, &token)
+                .json(&serde_json::json!({ "body": body })),
+        )
+        .await
+    }
+
+    pub async fn update_issue_comment(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        comment_id: i64,
+        body: &str,
+    ) -> Result<GitHubIssueComment, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!(
+            "{}/repos/{}/issues/comments/{}",
+            GITHUB_API_BASE, repo_full_name, comment_id
+        );
+
+        Self::send_json(
+            self.installation_request(Method::PATCH, &url, &token)
+                .json(&serde_json::json!({ "body": body })),
+        )
+        .await
+    }
+
+    async fn graphql<T: DeserializeOwned>(
+        &self,
+        token: &str,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T, GitHubAppError> {
+        let url = format!("{}/graphql", GITHUB_API_BASE);
+
+        let response: GraphQlResponse<T> = Self::send_json(
+            self.installation_request(Method::POST, &url, token)
+                .json(&serde_json::json!({ "query": query, "variables": variables })),
+        )
+        .await?;
+
+        if !response.errors.is_empty() {
+            let messages: Vec<String> = response.errors.into_iter().map(|e| e.message).collect();
+            return Err(GitHubAppError::GraphQl(messages.join("; ")));
+        }
+
+        response
+            .data
+            .ok_or_else(|| GitHubAppError::GraphQl("response has no data".to_string()))
+    }
+
+    pub async fn list_pull_request_files(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        number: i64,
+    ) -> Result<Vec<GitHubPullRequestFile>, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!(
+            "{}/repos/{}/pulls/{}/files",
+            GITHUB_API_BASE, repo_full_name, number
+        );
+
+        let mut all_files = Vec::new();
+        let mut page = 1u32;
+
+        loop {
+            let files: Vec<GitHubPullRequestFile> = Self::send_json(
+                self.installation_request(Method::GET, &url, &token)
+                    .query(&[("per_page", "100"), ("page", &page.to_string())]),
+            )
+            .await?;
+            let count = files.len();
+            all_files.extend(files);
+
+            if count < 100 {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(all_files)
+    }
+
+    pub async fn create_pull_request_review(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        number: i64,
+        review: &GitHubReviewWrite,
+    ) -> Result<(), GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!(
+            "{}/repos/{}/pulls/{}/reviews",
+            GITHUB_API_BASE, repo_full_name, number
+        );
+
+        let _: serde_json::Value = Self::send_json(
+            self.installation_request(Method::POST, &url, &token)
+                .json(review),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn update_review_comment(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        comment_id: i64,
+        body: &str,
+    ) -> Result<(), GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let url = format!(
+            "{}/repos/{}/pulls/comments/{}",
+            GITHUB_API_BASE, repo_full_name, comment_id
+        );
+
+        let _: serde_json::Value = Self::send_json(
+            self.installation_request(Method::PATCH, &url, &token)
+                .json(&serde_json::json!({ "body": body })),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn list_review_threads(
+        &self,
+        installation_id: i64,
+        repo_full_name: &str,
+        number: i64,
+    ) -> Result<Vec<GitHubReviewThread>, GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let (owner, repo) = repo_full_name.split_once('/').ok_or_else(|| {
+            GitHubAppError::GraphQl(format!("invalid repository name: {repo_full_name}"))
+        })?;
+
+        let mut all_threads = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let data: ReviewThreadsData = self
+                .graphql(
+                    &token,
+                    REVIEW_THREADS_QUERY,
+                    serde_json::json!({
+                        "owner": owner,
+                        "repo": repo,
+                        "number": number,
+                        "cursor": cursor,
+                    }),
+                )
+                .await?;
+
+            let Some(connection) = data
+                .repository
+                .and_then(|repository| repository.pull_request)
+                .map(|pull_request| pull_request.review_threads)
+            else {
+                break;
+            };
+
+            all_threads.extend(connection.nodes.into_iter().map(|node| GitHubReviewThread {
+                id: node.id,
+                is_resolved: node.is_resolved,
+                first_comment: node.comments.nodes.into_iter().next().and_then(|comment| {
+                    Some(GitHubReviewThreadComment {
+                        id: comment.database_id?,
+                        body: comment.body,
+                        author_login: comment.author.map(|author| author.login),
+                    })
+                }),
+            }));
+
+            match connection.page_info {
+                PageInfo {
+                    has_next_page: true,
+                    end_cursor: Some(end_cursor),
+                } => cursor = Some(end_cursor),
+                _ => break,
+            }
+        }
+
+        Ok(all_threads)
+    }
+
+    pub async fn set_review_thread_resolved(
+        &self,
+        installation_id: i64,
+        thread_id: &str,
+        resolved: bool,
+    ) -> Result<(), GitHubAppError> {
+        let token = self.get_installation_token(installation_id).await?;
+        let mutation = if resolved {
+            RESOLVE_REVIEW_THREAD_MUTATION
+        } else {
+            UNRESOLVE_REVIEW_THREAD_MUTATION
+        };
+
+        let _: serde_json::Value = self
+            .graphql(
+                &token,
+                mutation,
+                serde_json::json!({ "threadId": thread_id }),
+            )
+            .await?;
+
+        Ok(())
+    }
 }
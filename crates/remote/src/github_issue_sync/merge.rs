@@ -0,0 +1,121 @@
+//! Three-way merge of an issue's synced fields.
+//!
+//! Each link remembers the fields as they were after the last sync (the
+//! base). A field changed on only one side since then takes that side's
+//! value; a field changed on both sides takes the value from whichever side
+//! was updated last. Labels merge per label, so adding a label on GitHub and
+//! another on the board keeps both.
+
+use std::collections::BTreeSet;
+
+/// The fields kept in sync between a board issue and a GitHub issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct IssueFields {
+    pub title: String,
+    pub body: String,
+    pub closed: bool,
+    pub labels: BTreeSet<String>,
+}
+
+/// Which side wins a field both sides changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Newer {
+    Local,
+    Remote,
+}
+
+pub(crate) fn merge_issue(
+    base: &IssueFields,
+    local: &IssueFields,
+    remote: &IssueFields,
+    newer: Newer,
+) -> IssueFields {
+    IssueFields {
+        title: merge_value(&base.title, &local.title, &remote.title, newer).clone(),
+        body: merge_value(&base.body, &local.body, &remote.body, newer).clone(),
+        closed: *merge_value(&base.closed, &local.closed, &remote.closed, newer),
+        labels: merge_labels(&base.labels, &local.labels, &remote.labels),
+    }
+}
+
+fn merge_value<'a, T: PartialEq>(base: &'a T, local: &'a T, remote: &'a T, newer: Newer) -> &'a T {
+    match (local != base, remote != base) {
+        (true, false) => local,
+        (false, true) => remote,
+        (false, false) => base,
+        (true, true) => match newer {
+            Newer::Local => local,
+            Newer::Remote => remote,
+        },
+    }
+}
+
+/// Labels present on both sides stay; each side's additions and removals
+/// since the base are applied to the other.
+fn merge_labels(
+    base: &BTreeSet<String>,
+    local: &BTreeSet<String>,
+    remote: &BTreeSet<String>,
+) -> BTreeSet<String> {
+    local
+        .union(remote)
+        .filter(|label| {
+            let removed = |side: &BTreeSet<String>| base.contains(*label) && !side.contains(*label);
+            !removed(local) && !removed(remote)
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(title: &str, closed: bool, labels: &[&str]) -> IssueFields {
+        IssueFields {
+            title: title.to_string(),
+            body: String::new(),
+            closed,
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn one_sided_changes_win() {
+        let base = fields("Crash", false, &["bug"]);
+        let local = fields("Crash on save", false, &["bug"]);
+        let remote = fields("Crash", true, &["bug"]);
+
+        let merged = merge_issue(&base, &local, &remote, Newer::Remote);
+        assert_eq!(merged, fields("Crash on save", true, &["bug"]));
+    }
+
+    #[test]
+    fn newer_side_wins_conflicts() {
+        let base = fields("Crash", false, &[]);
+        let local = fields("Crash on save", false, &[]);
+        let remote = fields("Crash on load", false, &[]);
+
+        assert_eq!(
+            merge_issue(&base, &local, &remote, Newer::Local).title,
+            "Crash on save"
+        );
+        assert_eq!(
+            merge_issue(&base, &local, &remote, Newer::Remote).title,
+            "Crash on load"
+        );
+    }
+
+    #[test]
+    fn labels_merge_per_label() {
+        let base = fields("", false, &["bug", "ui"]);
+        let local = fields("", false, &["bug", "ui", "p1"]);
+        let remote = fields("", false, &["bug", "regression"]);
+
+        let merged = merge_issue(&base, &local, &remote, Newer::Local);
+        assert_eq!(
+            merged.labels,
+            fields("", false, &["bug", "p1", "regression"]).labels
+        );
+    }
+}
@@ -0,0 +1,779 @@
+//! Two-way sync between a project's issues and a repository's GitHub Issues,
+//! through the organization's GitHub App installation.
+//!
+//! Titles, bodies, open/closed state, labels (as tags) and comments are
+//! mirrored both ways. `issues` and `issue_comment` webhooks apply GitHub
+//! changes as they happen; a periodic reconciliation catches up on missed
+//! webhooks, pushes board changes and, when enabled, opens GitHub issues for
+//! new board issues. Conflicting edits are merged field by field against the
+//! state of the last sync (see [`merge`]). Deleting an issue or comment on
+//! either side only unlinks it; the link stays as a tombstone so the other
+//! side isn't mirrored back. The first reconciliation only imports open
+//! GitHub issues; closed ones are picked up once they change.
+
+mod merge;
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Arc,
+    time::Duration,
+};
+
+use api_types::{Issue, Tag, User};
+use chrono::{Duration as ChronoDuration, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use self::merge::{IssueFields, Newer, merge_issue};
+use crate::{
+    db::{
+        github_issue_sync::{
+            GitHubIssueLink, GitHubIssueSync, GitHubIssueSyncError, GitHubIssueSyncRepository,
+        },
+        identity_errors::IdentityError,
+        issue_comments::{IssueCommentError, IssueCommentRepository},
+        issue_tags::{IssueTagError, IssueTagRepository},
+        issues::{IssueError, IssueRepository},
+        tags::{TagError, TagRepository},
+        users::UserRepository,
+    },
+    github_app::{GitHubAppError, GitHubAppService, GitHubIssue, GitHubIssueWrite},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(300);
+/// Reconciliation windows overlap so clock skew with GitHub can't drop
+/// changes.
+const RECONCILE_OVERLAP: ChronoDuration = ChronoDuration::minutes(5);
+const DEFAULT_TAG_COLOR: &str = "#6b7280";
+
+#[derive(Debug, Error)]
+pub enum SyncError {
+    #[error(transparent)]
+    GitHub(#[from] GitHubAppError),
+    #[error(transparent)]
+    Sync(#[from] GitHubIssueSyncError),
+    #[error(transparent)]
+    Issue(#[from] IssueError),
+    #[error(transparent)]
+    Tag(#[from] TagError),
+    #[error(transparent)]
+    IssueTag(#[from] IssueTagError),
+    #[error(transparent)]
+    Comment(#[from] IssueCommentError),
+    #[error(transparent)]
+    Identity(#[from] IdentityError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+/// What an `issues` or `issue_comment` webhook asks of the sync.
+#[derive(Debug, PartialEq, Eq)]
+enum WebhookAction {
+    /// The GitHub issue was deleted or transferred.
+    UnlinkIssue,
+    /// The GitHub comment with this ID was deleted.
+    UnlinkComment(i64),
+    /// Merge the issue, and its comments if they changed.
+    Sync { comments: bool },
+}
+
+fn webhook_action(payload: &Value) -> WebhookAction {
+    let action = payload["action"].as_str().unwrap_or("");
+    match payload.get("comment") {
+        Some(comment) => match (action, comment["id"].as_i64()) {
+            ("deleted", Some(id)) => WebhookAction::UnlinkComment(id),
+            _ => WebhookAction::Sync { comments: true },
+        },
+        None if matches!(action, "deleted" | "transferred") => WebhookAction::UnlinkIssue,
+        None => WebhookAction::Sync { comments: false },
+    }
+}
+
+/// Periodically reconcile every active sync.
+pub fn spawn_worker(
+    pool: PgPool,
+    github_app: Arc<GitHubAppService>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let syncs = match GitHubIssueSyncRepository::list_active(&pool).await {
+                Ok(syncs) => syncs,
+                Err(error) => {
+                    tracing::error!(?error, "failed to list GitHub issue syncs");
+                    continue;
+                }
+            };
+            for sync in syncs {
+                if let Err(error) = reconcile(&pool, &github_app, &sync).await {
+                    tracing::error!(?error, sync_id = %sync.id, "GitHub issue sync failed");
+                }
+            }
+        }
+    })
+}
+
+/// Reconcile one sync in the background, e.g. right after it's set up.
+pub fn spawn_reconcile(pool: PgPool, github_app: GitHubAppService, sync: GitHubIssueSync) {
+    tokio::spawn(async move {
+        if let Err(error) = reconcile(&pool, &github_app, &sync).await {
+            tracing::error!(?error, sync_id = %sync.id, "GitHub issue sync failed");
+        }
+    });
+}
+
+/// Apply an `issues` or `issue_comment` webhook event to the syncs of its
+/// repository.
+pub fn spawn_webhook_sync(pool: PgPool, github_app: GitHubAppService, payload: Value) {
+    tokio::spawn(async move {
+        if let Err(error) = apply_webhook(&pool, &github_app, &payload).await {
+            tracing::error!(?error, "failed to sync GitHub issue webhook");
+        }
+    });
+}
+
+async fn apply_webhook(
+    pool: &PgPool,
+    github_app: &GitHubAppService,
+    payload: &Value,
+) -> Result<(), SyncError> {
+    // Our own writes come back as webhooks too.
+    if payload["sender"]["login"].as_str() == Some(github_app.bot_login().as_str()) {
+        return Ok(());
+    }
+
+    let installation_id = payload["installation"]["id"].as_i64().unwrap_or(0);
+    let repo_id = payload["repository"]["id"].as_i64().unwrap_or(0);
+    let syncs =
+        GitHubIssueSyncRepository::list_active_by_repo(pool, installation_id, repo_id).await?;
+    if syncs.is_empty() {
+        return Ok(());
+    }
+
+    let Ok(remote) = serde_json::from_value::<GitHubIssue>(payload["issue"].clone()) else {
+        tracing::warn!("ignoring GitHub issue webhook without a valid issue");
+        return Ok(());
+    };
+    if remote.pull_request.is_some() {
+        return Ok(());
+    }
+
+    let action = webhook_action(payload);
+    let mut lock = pool.begin().await?;
+    GitHubIssueSyncRepository::lock_installation(&mut lock, installation_id).await?;
+
+    for sync in &syncs {
+        match action {
+            WebhookAction::UnlinkIssue => {
+                GitHubIssueSyncRepository::unlink(pool, sync.id, remote.number).await?;
+            }
+            WebhookAction::UnlinkComment(github_comment_id) => {
+                GitHubIssueSyncRepository::unlink_comment(pool, github_comment_id).await?;
+            }
+            WebhookAction::Sync { comments } => {
+                let link = sync_issue(pool, github_app, sync, remote.clone()).await?;
+                if comments && let Some(link) = link {
+                    sync_comments(pool, github_app, sync, &link).await?;
+                }
+            }
+        }
+    }
+
+    lock.commit().await?;
+    Ok(())
+}
+
+/// Pull GitHub issues updated since the last run, push board changes and
+/// export new board issues.
+async fn reconcile(
+    pool: &PgPool,
+    github_app: &GitHubAppService,
+    sync: &GitHubIssueSync,
+) -> Result<(), SyncError> {
+    let mut lock = pool.begin().await?;
+    GitHubIssueSyncRepository::lock_installation(&mut lock, sync.github_installation_id).await?;
+    let started_at = Utc::now();
+
+    // Without a previous run this is the initial import, which leaves out
+    // the repository's closed backlog.
+    let since = sync.last_reconciled_at.map(|at| at - RECONCILE_OVERLAP);
+    let remote_issues = github_app
+        .list_repo_issues(
+            sync.github_installation_id,
+            &sync.repo_full_name,
+            since,
+            since.is_some(),
+        )
+        .await?;
+    for remote in remote_issues {
+        let number = remote.number;
+        let result = async {
+            match sync_issue(pool, github_app, sync, remote).await? {
+                Some(link) => sync_comments(pool, github_app, sync, &link).await,
+                None => Ok(()),
+            }
+        }
+        .await;
+        if let Err(error) = result {
+            tracing::warn!(?error, sync_id = %sync.id, number, "failed to pull GitHub issue");
+        }
+    }
+
+    for link in GitHubIssueSyncRepository::list_locally_changed_links(pool, sync.id).await? {
+        let result = async {
+            let remote = github_app
+                .get_issue(
+                    sync.github_installation_id,
+                    &sync.repo_full_name,
+                    link.github_issue_number,
+                )
+                .await?;
+            match sync_issue(pool, github_app, sync, remote).await? {
+                Some(link) => sync_comments(pool, github_app, sync, &link).await,
+                None => Ok(()),
+            }
+        }
+        .await;
+        if let Err(error) = result {
+            tracing::warn!(?error, issue_id = ?link.issue_id, "failed to push issue to GitHub");
+        }
+    }
+
+    if sync.export_new_issues {
+        for issue_id in GitHubIssueSyncRepository::list_unlinked_issue_ids(pool, sync.id).await? {
+            if let Err(error) = export_issue(pool, github_app, sync, issue_id).await {
+                tracing::warn!(?error, %issue_id, "failed to export issue to GitHub");
+            }
+        }
+    }
+
+    GitHubIssueSyncRepository::set_last_reconciled_at(pool, sync.id, started_at).await?;
+    lock.commit().await?;
+    Ok(())
+}
+
+/// Import a GitHub issue, or merge it with the board issue it's linked to.
+/// `None` if the link is a tombstone.
+async fn sync_issue(
+    pool: &PgPool,
+    github_app: &GitHubAppService,
+    sync: &GitHubIssueSync,
+    remote: GitHubIssue,
+) -> Result<Option<GitHubIssueLink>, SyncError> {
+    let tags = TagRepository::list_by_project(pool, sync.project_id).await?;
+    let remote_fields = remote_fields(&remote);
+
+    let Some(link) =
+        GitHubIssueSyncRepository::find_link_by_number(pool, sync.id, remote.number).await?
+    else {
+        return import_issue(pool, sync, &tags, &remote_fields, remote.number)
+            .await
+            .map(Some);
+    };
+    let Some(issue_id) = link.linked_issue_id() else {
+        return Ok(None);
+    };
+    let Some(issue) = IssueRepository::find_by_id(pool, issue_id).await? else {
+        return Ok(None);
+    };
+
+    let base = IssueFields {
+        title: link.title.clone(),
+        body: link.body.clone(),
+        closed: link.closed,
+        labels: link.labels.iter().cloned().collect(),
+    };
+    let local = local_fields(pool, sync, &issue, &tags).await?;
+    let newer = if issue.updated_at > remote.updated_at {
+        Newer::Local
+    } else {
+        Newer::Remote
+    };
+    let merged = merge_issue(&base, &local, &remote_fields, newer);
+
+    if merged != local {
+        apply_local(pool, sync, &issue, &local, &merged, &tags).await?;
+    }
+    if merged != remote_fields {
+        github_app
+            .update_issue(
+                sync.github_installation_id,
+                &sync.repo_full_name,
+                remote.number,
+                &github_write(&merged),
+            )
+            .await?;
+    }
+
+    let labels: Vec<String> = merged.labels.into_iter().collect();
+    GitHubIssueSyncRepository::update_link(
+        pool,
+        link.id,
+        &merged.title,
+        &merged.body,
+        merged.closed,
+        &labels,
+    )
+    .await?;
+
+    Ok(Some(link))
+}
+
+async fn import_issue(
+    pool: &PgPool,
+    sync: &GitHubIssueSync,
+    tags: &[Tag],
+    remote: &IssueFields,
+    number: i64,
+) -> Result<GitHubIssueLink, SyncError> {
+    let (status_id, completed_at) = if remote.closed {
+        (sync.closed_status_id, Some(Utc::now()))
+    } else {
+        (sync.open_status_id, None)
+    };
+    let issue = IssueRepository::create(
+        pool,
+        None,
+        sync.project_id,
+        status_id,
+        remote.title.clone(),
+        (!remote.body.is_empty()).then(|| remote.body.clone()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        completed_at,
+        0.0,
+        None,
+        None,
+        serde_json::json!({}),
+        None,
+    )
+    .await?
+    .data;
+
+    for label in &remote.labels {
+        let tag_id = find_or_create_tag(pool, sync.project_id, tags, label).await?;
+        IssueTagRepository::create(pool, None, issue.id, tag_id).await?;
+    }
+
+    let labels: Vec<String> = remote.labels.iter().cloned().collect();
+    let link = GitHubIssueSyncRepository::create_link(
+        pool,
+        sync.id,
+        issue.id,
+        number,
+        &remote.title,
+        &remote.body,
+        remote.closed,
+        &labels,
+    )
+    .await?;
+
+    tracing::info!(issue_id = %issue.id, number, "imported GitHub issue");
+    Ok(link)
+}
+
+async fn export_issue(
+    pool: &PgPool,
+    github_app: &GitHubAppService,
+    sync: &GitHubIssueSync,
+    issue_id: Uuid,
+) -> Result<(), SyncError> {
+    let Some(issue) = IssueRepository::find_by_id(pool, issue_id).await? else {
+        return Ok(());
+    };
+    let tags = TagRepository::list_by_project(pool, sync.project_id).await?;
+    let local = local_fields(pool, sync, &issue, &tags).await?;
+
+    let created = github_app
+        .create_issue(
+            sync.github_installation_id,
+            &sync.repo_full_name,
+            &github_write(&local),
+        )
+        .await?;
+
+    let labels: Vec<String> = local.labels.into_iter().collect();
+    let link = GitHubIssueSyncRepository::create_link(
+        pool,
+        sync.id,
+        issue.id,
+        created.number,
+        &local.title,
+        &local.body,
+        local.closed,
+        &labels,
+    )
+    .await?;
+    sync_comments(pool, github_app, sync, &link).await?;
+
+    tracing::info!(%issue_id, number = created.number, "exported issue to GitHub");
+    Ok(())
+}
+
+/// Mirror new comments both ways and merge edits to mirrored ones.
+async fn sync_comments(
+    pool: &PgPool,
+    github_app: &GitHubAppService,
+    sync: &GitHubIssueSync,
+    link: &GitHubIssueLink,
+) -> Result<(), SyncError> {
+    let Some(issue_id) = link.linked_issue_id() else {
+        return Ok(());
+    };
+    let remote_comments = github_app
+        .list_issue_comments(
+            sync.github_installation_id,
+            &sync.repo_full_name,
+            link.github_issue_number,
+        )
+        .await?;
+    let local_comments: HashMap<_, _> = IssueCommentRepository::list_by_issue(pool, issue_id)
+        .await?
+        .into_iter()
+        .map(|comment| (comment.id, comment))
+        .collect();
+    let comment_links = GitHubIssueSyncRepository::list_comment_links(pool, link.id).await?;
+    let bot_login = github_app.bot_login();
+
+    for remote in &remote_comments {
+        let remote_body = remote.body.clone().unwrap_or_default();
+        let Some(comment_link) = comment_links
+            .iter()
+            .find(|comment_link| comment_link.github_comment_id == remote.id)
+        else {
+            // Unlinked bot comments are ones we posted but failed to link.
+            if remote.user.login != bot_login {
+                let message = incoming_comment(&remote.user.login, &remote_body);
+                let comment =
+                    GitHubIssueSyncRepository::create_mirrored_comment(pool, issue_id, &message)
+                        .await?;
+                GitHubIssueSyncRepository::create_comment_link(
+                    pool,
+                    link.id,
+                    comment.id,
+                    remote.id,
+                    &message,
+                    &remote_body,
+                )
+                .await?;
+            }
+            continue;
+        };
+        let Some(local) = comment_link
+            .linked_comment_id()
+            .and_then(|comment_id| local_comments.get(&comment_id))
+        else {
+            continue;
+        };
+
+        let local_changed = local.message != comment_link.message;
+        let remote_changed = remote_body != comment_link.github_body;
+        if !local_changed && !remote_changed {
+            continue;
+        }
+        // Same rule as issue fields: a one-sided edit wins, otherwise the
+        // later one does.
+        let take_local = match (local_changed, remote_changed) {
+            (true, false) => true,
+            (false, true) => false,
+            _ => local.updated_at > remote.updated_at,
+        };
+        let (message, github_body) = if take_local {
+            let body = outgoing_comment(pool, local.author_id, &local.message).await?;
+            github_app
+                .update_issue_comment(
+                    sync.github_installation_id,
+                    &sync.repo_full_name,
+                    remote.id,
+                    &body,
+                )
+                .await?;
+            (local.message.clone(), body)
+        } else {
+            let message = incoming_comment(&remote.user.login, &remote_body);
+            IssueCommentRepository::update(pool, local.id, Some(message.clone())).await?;
+            (message, remote_body)
+        };
+        GitHubIssueSyncRepository::update_comment_link(
+            pool,
+            comment_link.id,
+            &message,
+            &github_body,
+        )
+        .await?;
+    }
+
+    for local in local_comments.values() {
+        let is_linked = comment_links
+            .iter()
+            .any(|comment_link| comment_link.comment_id == Some(local.id));
+        // Comments without an author were mirrored from GitHub.
+        if is_linked || local.author_id.is_none() {
+            continue;
+        }
+        let body = outgoing_comment(pool, local.author_id, &local.message).await?;
+        let remote = github_app
+            .create_issue_comment(
+                sync.github_installation_id,
+                &sync.repo_full_name,
+                link.github_issue_number,
+                &body,
+            )
+            .await?;
+        GitHubIssueSyncRepository::create_comment_link(
+            pool,
+            link.id,
+            local.id,
+            remote.id,
+            &local.message,
+            &body,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn remote_fields(remote: &GitHubIssue) -> IssueFields {
+    IssueFields {
+        title: remote.title.clone(),
+        body: remote.body.clone().unwrap_or_default(),
+        closed: remote.state == "closed",
+        labels: remote
+            .labels
+            .iter()
+            .map(|label| label.name.clone())
+            .collect(),
+    }
+}
+
+/// An issue counts as closed while it's in the sync's closed status.
+async fn local_fields(
+    pool: &PgPool,
+    sync: &GitHubIssueSync,
+    issue: &Issue,
+    tags: &[Tag],
+) -> Result<IssueFields, SyncError> {
+    let labels = IssueTagRepository::list_by_issue(pool, issue.id)
+        .await?
+        .into_iter()
+        .filter_map(|issue_tag| {
+            tags.iter()
+                .find(|tag| tag.id == issue_tag.tag_id)
+                .map(|tag| tag.name.clone())
+        })
+        .collect();
+
+    Ok(IssueFields {
+        title: issue.title.clone(),
+        body: issue.description.clone().unwrap_or_default(),
+        closed: issue.status_id == sync.closed_status_id,
+        labels,
+    })
+}
+
+async fn apply_local(
+    pool: &PgPool,
+    sync: &GitHubIssueSync,
+    issue: &Issue,
+    local: &IssueFields,
+    merged: &IssueFields,
+    tags: &[Tag],
+) -> Result<(), SyncError> {
+    let closed_changed = merged.closed != local.closed;
+    let (status_id, completed_at) = match (closed_changed, merged.closed) {
+        (false, _) => (None, None),
+        (true, true) => (Some(sync.closed_status_id), Some(Some(Utc::now()))),
+        (true, false) => (Some(sync.open_status_id), Some(None)),
+    };
+    if merged.title != local.title || merged.body != local.body || closed_changed {
+        IssueRepository::update(
+            pool,
+            issue.id,
+            status_id,
+            (merged.title != local.title).then(|| merged.title.clone()),
+            (merged.body != local.body)
+                .then(|| (!merged.body.is_empty()).then(|| merged.body.clone())),
+            None,
+            None,
+            None,
+            None,
+            None,
+            completed_at,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await?;
+    }
+
+    if merged.labels != local.labels {
+        for label in merged.labels.difference(&local.labels) {
+            let tag_id = find_or_create_tag(pool, sync.project_id, tags, label).await?;
+            IssueTagRepository::create(pool, None, issue.id, tag_id).await?;
+        }
+        let removed: BTreeSet<_> = local.labels.difference(&merged.labels).collect();
+        for issue_tag in IssueTagRepository::list_by_issue(pool, issue.id).await? {
+            let is_removed = tags
+                .iter()
+                .any(|tag| tag.id == issue_tag.tag_id && removed.contains(&tag.name));
+            if is_removed {
+                IssueTagRepository::delete(pool, issue_tag.id).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_or_create_tag(
+    pool: &PgPool,
+    project_id: Uuid,
+    tags: &[Tag],
+    name: &str,
+) -> Result<Uuid, SyncError> {
+    if let Some(tag) = tags.iter().find(|tag| tag.name == name) {
+        return Ok(tag.id);
+    }
+    let tag = TagRepository::create(
+        pool,
+        None,
+        project_id,
+        name.to_string(),
+        DEFAULT_TAG_COLOR.to_string(),
+    )
+    .await?
+    .data;
+    Ok(tag.id)
+}
+
+fn github_write(fields: &IssueFields) -> GitHubIssueWrite {
+    GitHubIssueWrite {
+        title: fields.title.clone(),
+        body: fields.body.clone(),
+        state: if fields.closed { "closed" } else { "open" },
+        labels: fields.labels.iter().cloned().collect(),
+    }
+}
+
+fn incoming_comment(login: &str, body: &str) -> String {
+    format!("**@{login}** commented on GitHub:\n\n{body}")
+}
+
+/// GitHub shows the app as the author, so name the board user in the body.
+async fn outgoing_comment(
+    pool: &PgPool,
+    author_id: Option<Uuid>,
+    message: &str,
+) -> Result<String, SyncError> {
+    let author = match author_id {
+        Some(author_id) => Some(UserRepository::new(pool).fetch_user(author_id).await?),
+        None => None,
+    };
+    let name = author
+        .as_ref()
+        .map(display_name)
+        .unwrap_or_else(|| "Someone".to_string());
+    Ok(format!("**{name}** commented on the board:\n\n{message}"))
+}
+
+fn display_name(user: &User) -> String {
+    match (&user.first_name, &user.last_name) {
+        (Some(first), Some(last)) => format!("{first} {last}"),
+        (Some(first), None) => first.clone(),
+        _ => user
+            .username
+            .clone()
+            .unwrap_or_else(|| "Someone".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::db::github_issue_sync::GitHubCommentLink;
+
+    fn link(issue_id: Option<Uuid>, unlinked: bool) -> GitHubIssueLink {
+        GitHubIssueLink {
+            id: Uuid::new_v4(),
+            sync_id: Uuid::new_v4(),
+            issue_id,
+            github_issue_number: 7,
+            title: "Title".to_string(),
+            body: String::new(),
+            closed: false,
+            labels: Vec::new(),
+            unlinked,
+            synced_at: Utc::now(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn routes_webhook_actions() {
+        let issue = json!({ "number": 7 });
+        assert_eq!(
+            webhook_action(&json!({ "action": "edited", "issue": issue })),
+            WebhookAction::Sync { comments: false }
+        );
+        assert_eq!(
+            webhook_action(&json!({ "action": "transferred", "issue": issue })),
+            WebhookAction::UnlinkIssue
+        );
+        assert_eq!(
+            webhook_action(&json!({ "action": "deleted", "issue": issue })),
+            WebhookAction::UnlinkIssue
+        );
+        assert_eq!(
+            webhook_action(&json!({ "action": "created", "issue": issue, "comment": { "id": 3 } })),
+            WebhookAction::Sync { comments: true }
+        );
+        assert_eq!(
+            webhook_action(&json!({ "action": "deleted", "issue": issue, "comment": { "id": 3 } })),
+            WebhookAction::UnlinkComment(3)
+        );
+    }
+
+    #[test]
+    fn tombstone_links_are_not_synced() {
+        let issue_id = Uuid::new_v4();
+        assert_eq!(
+            link(Some(issue_id), false).linked_issue_id(),
+            Some(issue_id)
+        );
+        // Board issue deleted: the FK was set to NULL.
+        assert_eq!(link(None, false).linked_issue_id(), None);
+        // GitHub issue deleted or transferred.
+        assert_eq!(link(Some(issue_id), true).linked_issue_id(), None);
+
+        let comment_id = Uuid::new_v4();
+        let comment_link = |comment_id, unlinked| GitHubCommentLink {
+            id: Uuid::new_v4(),
+            link_id: Uuid::new_v4(),
+            comment_id,
+            github_comment_id: 3,
+            message: String::new(),
+            github_body: String::new(),
+            unlinked,
+            created_at: Utc::now(),
+        };
+        assert_eq!(
+            comment_link(Some(comment_id), false).linked_comment_id(),
+            Some(comment_id)
+        );
+        assert_eq!(comment_link(None, false).linked_comment_id(), None);
+        assert_eq!(
+            comment_link(Some(comment_id), true).linked_comment_id(),
+            None
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! Issue query language: filters such as
+//! `status:"In Review" priority>=high assignee:me tag:backend updated:<7d has:pr`
+//! or `field.severity:high` on custom fields are parsed into an [`IssueQuery`]
+//! and compiled into parameterised SQL over the issue tables.
+
+mod parser;
+
+use std::collections::HashMap;
+
+use api_types::{CUSTOM_FIELDS_METADATA_KEY, Issue, IssuePriority};
+use chrono::{DateTime, NaiveDate, Utc};
+pub(crate) use parser::parse;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::db::issues::{IssueError, IssueRepository};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct IssueQuery {
+    pub terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Term {
+    pub negated: bool,
+    pub filter: Filter,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Filter {
+    /// Lowercased status names.
+    Status(Vec<String>),
+    Priority(Vec<IssuePriority>),
+    Assignee(Vec<UserRef>),
+    /// Lowercased tag names.
+    Tag(Vec<String>),
+    /// Half-open range `[from, to)`.
+    Date {
+        field: DateField,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    },
+    Has(HasField),
+    State(IssueState),
+    /// Uppercased simple ID of the parent issue.
+    Parent(String),
+    /// A value in the issue's custom fields, by lowercased field key.
+    CustomField {
+        key: String,
+        condition: CustomFieldCondition,
+    },
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CustomFieldCondition {
+    /// Any non-empty value.
+    Set,
+    /// The value, or for multi-select fields any of its values, is one of
+    /// these lowercased strings.
+    AnyOf(Vec<String>),
+    Number(Comparison, f64),
+    Date(Comparison, NaiveDate),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    fn as_sql(self) -> &'static str {
+        match self {
+            Comparison::Lt => " < ",
+            Comparison::Le => " <= ",
+            Comparison::Gt => " > ",
+            Comparison::Ge => " >= ",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum UserRef {
+    Me,
+    /// Lowercased email or username.
+    Name(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DateField {
+    Created,
+    Updated,
+    Due,
+    Completed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HasField {
+    PullRequest,
+    Workspace,
+    Assignee,
+    Tag,
+    Priority,
+    Parent,
+    Description,
+    Due,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum IssueState {
+    Open,
+    Closed,
+    Blocked,
+}
+
+impl IssueQuery {
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+/// Issues of a project matching `query`, most recently updated first.
+/// `user_id` is who `assignee:me` refers to.
+pub(crate) async fn search(
+    pool: &PgPool,
+    project_id: Uuid,
+    user_id: Uuid,
+    query: &IssueQuery,
+) -> Result<Vec<Issue>, IssueError> {
+    let mut builder =
+        QueryBuilder::<Postgres>::new("SELECT i.id FROM issues i WHERE i.project_id = ");
+    builder.push_bind(project_id);
+    for term in &query.terms {
+        if term.negated {
+            // A negated condition that evaluates to NULL (e.g. a missing
+            // priority) should still match.
+            builder.push(" AND NOT COALESCE((");
+            push_filter(&mut builder, &term.filter, user_id);
+            builder.push("), FALSE)");
+        } else {
+            builder.push(" AND (");
+            push_filter(&mut builder, &term.filter, user_id);
+            builder.push(")");
+        }
+    }
+    builder.push(" ORDER BY i.updated_at DESC");
+
+    let ids: Vec<Uuid> = builder.build_query_scalar().fetch_all(pool).await?;
+    let positions: HashMap<Uuid, usize> = ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let mut issues = IssueRepository::list_by_ids(pool, &ids).await?;
+    issues.sort_by_key(|issue| positions.get(&issue.id).copied().unwrap_or(usize::MAX));
+    Ok(issues)
+}
+
+fn push_filter(builder: &mut QueryBuilder<'_, Postgres>, filter: &Filter, user_id: Uuid) {
+    match filter {
+        Filter::Status(names) => {
+            builder.push(
+                "i.status_id IN (SELECT ps.id FROM project_statuses ps \
+                 WHERE ps.project_id = i.project_id AND LOWER(ps.name) = ANY(",
+            );
+            builder.push_bind(names.clone());
+            builder.push("))");
+        }
+        Filter::Priority(priorities) => {
+            let names: Vec<String> = priorities
+                .iter()
+                .map(|priority| priority_name(*priority).to_string())
+                .collect();
+            builder.push("i.priority::text = ANY(");
+            builder.push_bind(names);
+            builder.push(")");
+        }
+        Filter::Assignee(users) => {
+            let ids: Vec<Uuid> = users
+                .iter()
+                .filter(|user| **user == UserRef::Me)
+                .map(|_| user_id)
+                .collect();
+            let names: Vec<String> = users
+                .iter()
+                .filter_map(|user| match user {
+                    UserRef::Name(name) => Some(name.clone()),
+                    UserRef::Me => None,
+                })
+                .collect();
+            builder.push(
+                "EXISTS (SELECT 1 FROM issue_assignees ia JOIN users u ON u.id = ia.user_id \
+                 WHERE ia.issue_id = i.id AND (ia.user_id = ANY(",
+            );
+            builder.push_bind(ids);
+            builder.push(") OR LOWER(u.email) = ANY(");
+            builder.push_bind(names.clone());
+            builder.push(") OR LOWER(u.username) = ANY(");
+            builder.push_bind(names);
+            builder.push(")))");
+        }
+        Filter::Tag(names) => {
+            builder.push(
+                "EXISTS (SELECT 1 FROM issue_tags it JOIN tags t ON t.id = it.tag_id \
+                 WHERE it.issue_id = i.id AND LOWER(t.name) = ANY(",
+            );
+            builder.push_bind(names.clone());
+            builder.push("))");
+        }
+        Filter::Date { field, from, to } => {
+            let column = match field {
+                DateField::Created => "i.created_at",
+                DateField::Updated => "i.updated_at",
+                DateField::Due => "i.target_date",
+                DateField::Completed => "i.completed_at",
+            };
+            builder.push(column);
+            builder.push(" IS NOT NULL");
+            if let Some(from) = from {
+                builder.push(" AND ");
+                builder.push(column);
+                builder.push(" >= ");
+                builder.push_bind(*from);
+            }
+            if let Some(to) = to {
+                builder.push(" AND ");
+                builder.push(column);
+                builder.push(" < ");
+                builder.push_bind(*to);
+            }
+        }
+        Filter::Has(field) => {
+            builder.push(match field {
+                HasField::PullRequest => {
+                    "EXISTS (SELECT 1 FROM pull_requests pr WHERE pr.issue_id = i.id)"
+                }
+                HasField::Workspace => {
+                    "EXISTS (SELECT 1 FROM workspaces w WHERE w.issue_id = i.id AND NOT w.archived)"
+                }
+                HasField::Assignee => {
+                    "EXISTS (SELECT 1 FROM issue_assignees ia WHERE ia.issue_id = i.id)"
+                }
+                HasField::Tag => "EXISTS (SELECT 1 FROM issue_tags it WHERE it.issue_id = i.id)",
+                HasField::Priority => "i.priority IS NOT NULL",
+                HasField::Parent => "i.parent_issue_id IS NOT NULL",
+                HasField::Description => "COALESCE(i.description, '') <> ''",
+                HasField::Due => "i.target_date IS NOT NULL",
+            });
+        }
+        Filter::State(state) => {
+            builder.push(match state {
+                IssueState::Open => "i.completed_at IS NULL",
+                IssueState::Closed => "i.completed_at IS NOT NULL",
+                IssueState::Blocked => {
+                    "EXISTS (SELECT 1 FROM issue_relationships r \
+                     JOIN issues blocker ON blocker.id = r.issue_id \
+                     WHERE r.related_issue_id = i.id AND r.relationship_type = 'blocking' \
+                     AND blocker.completed_at IS NULL)"
+                }
+            });
+        }
+        Filter::Parent(simple_id) => {
+            builder.push(
+                "i.parent_issue_id IN (SELECT p.id FROM issues p \
+                 WHERE p.project_id = i.project_id AND UPPER(p.simple_id) = ",
+            );
+            builder.push_bind(simple_id.clone());
+            builder.push(")");
+        }
+        Filter::CustomField { key, condition } => {
+            push_custom_field(builder, key, condition);
+        }
+        Filter::Text(text) => {
+            let pattern = format!("%{}%", escape_like(text));
+            builder.push("i.title ILIKE ");
+            builder.push_bind(pattern.clone());
+            builder.push(" OR i.description ILIKE ");
+            builder.push_bind(pattern);
+            builder.push(" OR UPPER(i.simple_id) = ");
+            builder.push_bind(text.to_uppercase());
+        }
+    }
+}
+
+fn push_custom_field(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    key: &str,
+    condition: &CustomFieldCondition,
+) {
+    let push_value = |builder: &mut QueryBuilder<'_, Postgres>| {
+        builder.push("(i.extension_metadata -> ");
+        builder.push_bind(CUSTOM_FIELDS_METADATA_KEY);
+        builder.push(" -> ");
+        builder.push_bind(key.to_string());
+        builder.push(")");
+    };
+
+    match condition {
+        CustomFieldCondition::Set => {
+            builder.push("COALESCE(");
+            push_value(builder);
+            builder.push(", 'null'::jsonb) NOT IN ('null'::jsonb, '[]'::jsonb, '\"\"'::jsonb)");
+        }
+        CustomFieldCondition::AnyOf(values) => {
+            // Scalars are wrapped so single and multi-select values match
+            // the same way.
+            builder.push("EXISTS (SELECT 1 FROM jsonb_array_elements_text(CASE jsonb_typeof(");
+            push_value(builder);
+            builder.push(") WHEN 'array' THEN ");
+            push_value(builder);
+            builder.push(" ELSE jsonb_build_array(");
+            push_value(builder);
+            builder.push(") END) AS cf(value) WHERE LOWER(cf.value) = ANY(");
+            builder.push_bind(values.clone());
+            builder.push("))");
+        }
+        CustomFieldCondition::Number(comparison, number) => {
+            builder.push("CASE WHEN jsonb_typeof(");
+            push_value(builder);
+            builder.push(") = 'number' THEN ");
+            push_value(builder);
+            builder.push("::float8");
+            builder.push(comparison.as_sql());
+            builder.push_bind(*number);
+            builder.push(" END");
+        }
+        CustomFieldCondition::Date(comparison, date) => {
+            // Dates are stored as `YYYY-MM-DD`, which sorts chronologically.
+            builder.push("CASE WHEN jsonb_typeof(");
+            push_value(builder);
+            builder.push(") = 'string' THEN ");
+            push_value(builder);
+            builder.push(" #>> '{}'");
+            builder.push(comparison.as_sql());
+            builder.push_bind(date.format("%Y-%m-%d").to_string());
+            builder.push(" END");
+        }
+    }
+}
+
+fn priority_name(priority: IssuePriority) -> &'static str {
+    match priority {
+        IssuePriority::Urgent => "urgent",
+        IssuePriority::High => "high",
+        IssuePriority::Medium => "medium",
+        IssuePriority::Low => "low",
+    }
+}
+
+fn escape_like(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -0,0 +1,648 @@
+//! Parser for the issue query language.
+//!
+//! A query is a whitespace-separated list of terms, all of which must match:
+//!
+//! - `field:value`, `field>=value` (also `>`, `<`, `<=`, `=`); a colon may
+//!   precede the comparison, as in `updated:<7d`
+//! - values may be quoted (`status:"In Review"`) and `:` filters accept a
+//!   comma-separated list (`tag:backend,infra`)
+//! - a leading `-` negates a term (`-tag:wontfix`)
+//! - `field.<key>` filters on a custom field (`field.severity:high`,
+//!   `field.points>=3`, `field.launch<2026-06-01`, `field.team:none`)
+//! - anything else is free text matched against title, description and
+//!   simple ID
+
+use api_types::IssuePriority;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use super::{
+    Comparison, CustomFieldCondition, DateField, Filter, HasField, IssueQuery, IssueState, Term,
+    UserRef,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub(crate) enum QueryError {
+    #[error("unterminated quote at position {0}")]
+    UnterminatedQuote(usize),
+    #[error("`{0}` needs a value")]
+    MissingValue(String),
+    #[error("`{field}` does not support `{op}`")]
+    UnsupportedOperator { field: String, op: &'static str },
+    #[error("`{field}` takes a single value")]
+    SingleValue { field: String },
+    #[error("invalid value `{value}` for `{field}`: {reason}")]
+    InvalidValue {
+        field: String,
+        value: String,
+        reason: &'static str,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn as_str(self) -> &'static str {
+        match self {
+            Op::Eq => ":",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Gt => ">",
+            Op::Ge => ">=",
+        }
+    }
+}
+
+/// Prefix of custom field filters, followed by the field key.
+const CUSTOM_FIELD_PREFIX: &str = "field.";
+
+const FIELDS: &[&str] = &[
+    "status",
+    "priority",
+    "assignee",
+    "tag",
+    "label",
+    "created",
+    "updated",
+    "due",
+    "completed",
+    "has",
+    "no",
+    "is",
+    "parent",
+];
+
+/// A lexed term before its field-specific interpretation.
+struct RawTerm {
+    negated: bool,
+    field: Option<String>,
+    op: Op,
+    values: Vec<String>,
+}
+
+pub(crate) fn parse(input: &str, now: DateTime<Utc>) -> Result<IssueQuery, QueryError> {
+    let terms = lex(input)?
+        .into_iter()
+        .map(|raw| interpret(raw, now))
+        .collect::<Result<_, _>>()?;
+    Ok(IssueQuery { terms })
+}
+
+fn lex(input: &str) -> Result<Vec<RawTerm>, QueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut pos = 0;
+    let mut terms = Vec::new();
+
+    while pos < chars.len() {
+        if chars[pos].is_whitespace() {
+            pos += 1;
+            continue;
+        }
+
+        let negated = chars[pos] == '-' && chars.get(pos + 1).is_some_and(|c| !c.is_whitespace());
+        if negated {
+            pos += 1;
+        }
+
+        if chars[pos] == '"' {
+            let (text, end) = read_quoted(&chars, pos)?;
+            pos = end;
+            terms.push(RawTerm {
+                negated,
+                field: None,
+                op: Op::Eq,
+                values: vec![text],
+            });
+            continue;
+        }
+
+        let start = pos;
+        while pos < chars.len() && chars[pos].is_alphabetic() {
+            pos += 1;
+        }
+        let mut word: String = chars[start..pos].iter().collect::<String>().to_lowercase();
+        if word == "field" && chars.get(pos) == Some(&'.') {
+            pos += 1;
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            word = chars[start..pos].iter().collect::<String>().to_lowercase();
+        }
+        let is_field = FIELDS.contains(&word.as_str())
+            || (word.len() > CUSTOM_FIELD_PREFIX.len() && word.starts_with(CUSTOM_FIELD_PREFIX));
+        let is_filter = is_field && matches!(chars.get(pos), Some(':' | '<' | '>' | '='));
+
+        if !is_filter {
+            // Free text runs to the next whitespace.
+            while pos < chars.len() && !chars[pos].is_whitespace() {
+                pos += 1;
+            }
+            terms.push(RawTerm {
+                negated,
+                field: None,
+                op: Op::Eq,
+                values: vec![chars[start..pos].iter().collect()],
+            });
+            continue;
+        }
+
+        if chars[pos] == ':' {
+            pos += 1;
+        }
+        let (op, width) = match (chars.get(pos), chars.get(pos + 1)) {
+            (Some('<'), Some('=')) => (Op::Le, 2),
+            (Some('>'), Some('=')) => (Op::Ge, 2),
+            (Some('<'), _) => (Op::Lt, 1),
+            (Some('>'), _) => (Op::Gt, 1),
+            (Some('='), _) => (Op::Eq, 1),
+            _ => (Op::Eq, 0),
+        };
+        pos += width;
+
+        let mut values = Vec::new();
+        loop {
+            match chars.get(pos) {
+                Some('"') => {
+                    let (value, end) = read_quoted(&chars, pos)?;
+                    values.push(value);
+                    pos = end;
+                }
+                Some(c) if !c.is_whitespace() => {
+                    let value_start = pos;
+                    while pos < chars.len() && !chars[pos].is_whitespace() && chars[pos] != ',' {
+                        pos += 1;
+                    }
+                    values.push(chars[value_start..pos].iter().collect());
+                }
+                _ => break,
+            }
+            if chars.get(pos) == Some(&',') {
+                pos += 1;
+            } else {
+                break;
+            }
+        }
+        values.retain(|value: &String| !value.is_empty());
+        if values.is_empty() {
+            return Err(QueryError::MissingValue(word));
+        }
+
+        terms.push(RawTerm {
+            negated,
+            field: Some(word),
+            op,
+            values,
+        });
+    }
+
+    Ok(terms)
+}
+
+/// Read a double-quoted string starting at `start`; returns its contents
+/// and the position after the closing quote. `\"` escapes a quote.
+fn read_quoted(chars: &[char], start: usize) -> Result<(String, usize), QueryError> {
+    let mut value = String::new();
+    let mut pos = start + 1;
+    loop {
+        match chars.get(pos) {
+            Some('\\') if chars.get(pos + 1) == Some(&'"') => {
+                value.push('"');
+                pos += 2;
+            }
+            Some('"') => return Ok((value, pos + 1)),
+            Some(&c) => {
+                value.push(c);
+                pos += 1;
+            }
+            None => return Err(QueryError::UnterminatedQuote(start)),
+        }
+    }
+}
+
+fn interpret(raw: RawTerm, now: DateTime<Utc>) -> Result<Term, QueryError> {
+    let RawTerm {
+        mut negated,
+        field,
+        op,
+        values,
+    } = raw;
+
+    let Some(field) = field else {
+        return Ok(Term {
+            negated,
+            filter: Filter::Text(values.into_iter().next().unwrap_or_default()),
+        });
+    };
+
+    let invalid = |value: &str, reason: &'static str| QueryError::InvalidValue {
+        field: field.clone(),
+        value: value.to_string(),
+        reason,
+    };
+    let require_eq = || {
+        if op == Op::Eq {
+            Ok(())
+        } else {
+            Err(QueryError::UnsupportedOperator {
+                field: field.clone(),
+                op: op.as_str(),
+            })
+        }
+    };
+    let single = || {
+        if values.len() == 1 {
+            Ok(values[0].as_str())
+        } else {
+            Err(QueryError::SingleValue {
+                field: field.clone(),
+            })
+        }
+    };
+    let is_none = values.len() == 1 && values[0].eq_ignore_ascii_case("none");
+
+    let filter = match field.as_str() {
+        "status" => {
+            require_eq()?;
+            Filter::Status(values.iter().map(|v| v.to_lowercase()).collect())
+        }
+        "priority" if is_none => {
+            require_eq()?;
+            negated = !negated;
+            Filter::Has(HasField::Priority)
+        }
+        "priority" => {
+            if op != Op::Eq {
+                let value = single()?;
+                let rank =
+                    priority_rank(parse_priority(value).ok_or_else(|| {
+                        invalid(value, "expected urgent, high, medium, low or none")
+                    })?);
+                let allowed = ALL_PRIORITIES
+                    .into_iter()
+                    .filter(|p| {
+                        let r = priority_rank(*p);
+                        match op {
+                            Op::Lt => r < rank,
+                            Op::Le => r <= rank,
+                            Op::Gt => r > rank,
+                            Op::Ge => r >= rank,
+                            Op::Eq => r == rank,
+                        }
+                    })
+                    .collect();
+                Filter::Priority(allowed)
+            } else {
+                let priorities = values
+                    .iter()
+                    .map(|value| {
+                        parse_priority(value).ok_or_else(|| {
+                            invalid(value, "expected urgent, high, medium, low or none")
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Filter::Priority(priorities)
+            }
+        }
+        "assignee" if is_none => {
+            require_eq()?;
+            negated = !negated;
+            Filter::Has(HasField::Assignee)
+        }
+        "assignee" => {
+            require_eq()?;
+            Filter::Assignee(
+                values
+                    .iter()
+                    .map(|value| {
+                        if value.eq_ignore_ascii_case("me") {
+                            UserRef::Me
+                        } else {
+                            UserRef::Name(value.trim_start_matches('@').to_lowercase())
+                        }
+                    })
+                    .collect(),
+            )
+        }
+        "tag" | "label" if is_none => {
+            require_eq()?;
+            negated = !negated;
+            Filter::Has(HasField::Tag)
+        }
+        "tag" | "label" => {
+            require_eq()?;
+            Filter::Tag(values.iter().map(|v| v.to_lowercase()).collect())
+        }
+        "created" | "updated" | "due" | "completed" => {
+            let date_field = match field.as_str() {
+                "created" => DateField::Created,
+                "updated" => DateField::Updated,
+                "due" => DateField::Due,
+                _ => DateField::Completed,
+            };
+            let value = single()?;
+            let (from, to) = date_range(value, op, now).ok_or_else(|| {
+                invalid(
+                    value,
+                    "expected a date (YYYY-MM-DD, today, yesterday) or an age such as 12h, 7d or 2w",
+                )
+            })?;
+            Filter::Date {
+                field: date_field,
+                from,
+                to,
+            }
+        }
+        "has" | "no" => {
+            require_eq()?;
+            let value = single()?;
+            if field == "no" {
+                negated = !negated;
+            }
+            Filter::Has(parse_has(value).ok_or_else(|| {
+                invalid(
+                    value,
+                    "expected pr, workspace, assignee, tag, priority, parent, description or due",
+                )
+            })?)
+        }
+        "is" => {
+            require_eq()?;
+            let value = single()?;
+            Filter::State(match value.to_lowercase().as_str() {
+                "open" => IssueState::Open,
+                "closed" | "done" | "completed" => IssueState::Closed,
+                "blocked" => IssueState::Blocked,
+                _ => return Err(invalid(value, "expected open, closed or blocked")),
+            })
+        }
+        "parent" => {
+            require_eq()?;
+            Filter::Parent(single()?.to_uppercase())
+        }
+        _ if field.starts_with(CUSTOM_FIELD_PREFIX) => {
+            let key = field[CUSTOM_FIELD_PREFIX.len()..].to_string();
+            let condition = if is_none {
+                require_eq()?;
+                negated = !negated;
+                CustomFieldCondition::Set
+            } else if op == Op::Eq {
+                CustomFieldCondition::AnyOf(values.iter().map(|v| v.to_lowercase()).collect())
+            } else {
+                let value = single()?;
+                let comparison = match op {
+                    Op::Lt => Comparison::Lt,
+                    Op::Le => Comparison::Le,
+                    Op::Gt => Comparison::Gt,
+                    _ => Comparison::Ge,
+                };
+                if let Ok(number) = value.parse::<f64>() {
+                    CustomFieldCondition::Number(comparison, number)
+                } else if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+                    CustomFieldCondition::Date(comparison, date)
+                } else {
+                    return Err(invalid(value, "expected a number or a date (YYYY-MM-DD)"));
+                }
+            };
+            Filter::CustomField { key, condition }
+        }
+        _ => unreachable!("lexer only yields known fields"),
+    };
+
+    Ok(Term { negated, filter })
+}
+
+const ALL_PRIORITIES: [IssuePriority; 4] = [
+    IssuePriority::Urgent,
+    IssuePriority::High,
+    IssuePriority::Medium,
+    IssuePriority::Low,
+];
+
+fn priority_rank(priority: IssuePriority) -> u8 {
+    match priority {
+        IssuePriority::Urgent => 3,
+        IssuePriority::High => 2,
+        IssuePriority::Medium => 1,
+        IssuePriority::Low => 0,
+    }
+}
+
+fn parse_priority(value: &str) -> Option<IssuePriority> {
+    match value.to_lowercase().as_str() {
+        "urgent" => Some(IssuePriority::Urgent),
+        "high" => Some(IssuePriority::High),
+        "medium" => Some(IssuePriority::Medium),
+        "low" => Some(IssuePriority::Low),
+        _ => None,
+    }
+}
+
+fn parse_has(value: &str) -> Option<HasField> {
+    Some(match value.to_lowercase().as_str() {
+        "pr" | "pull_request" => HasField::PullRequest,
+        "workspace" => HasField::Workspace,
+        "assignee" => HasField::Assignee,
+        "tag" | "label" => HasField::Tag,
+        "priority" => HasField::Priority,
+        "parent" => HasField::Parent,
+        "description" => HasField::Description,
+        "due" => HasField::Due,
+        _ => return None,
+    })
+}
+
+/// Resolve a date value and comparison into a half-open `[from, to)` range.
+///
+/// Ages compare by how long ago something happened, so `updated:<7d` means
+/// "updated less than seven days ago". Dates compare chronologically and
+/// `:` matches the whole day.
+fn date_range(
+    value: &str,
+    op: Op,
+    now: DateTime<Utc>,
+) -> Option<(Option<DateTime<Utc>>, Option<DateTime<Utc>>)> {
+    let value = value.to_lowercase();
+    let today = now.date_naive();
+    let day = match value.as_str() {
+        "today" => Some(today),
+        "yesterday" => today.pred_opt(),
+        _ => NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok(),
+    };
+
+    if let Some(day) = day {
+        let start = day.and_hms_opt(0, 0, 0)?.and_utc();
+        let end = start + Duration::days(1);
+        return Some(match op {
+            Op::Eq => (Some(start), Some(end)),
+            Op::Lt => (None, Some(start)),
+            Op::Le => (None, Some(end)),
+            Op::Gt => (Some(end), None),
+            Op::Ge => (Some(start), None),
+        });
+    }
+
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let amount: i64 = value[..split].parse().ok()?;
+    let age = match &value[split..] {
+        "h" => Duration::try_hours(amount)?,
+        "d" => Duration::try_days(amount)?,
+        "w" => Duration::try_weeks(amount)?,
+        _ => return None,
+    };
+    let instant = now.checked_sub_signed(age)?;
+    Some(match op {
+        Op::Eq | Op::Lt | Op::Le => (Some(instant), None),
+        Op::Gt | Op::Ge => (None, Some(instant)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn parses_example_query() {
+        let query = parse(
+            r#"status:"In Review" priority>=high assignee:me tag:backend updated:<7d has:pr"#,
+            now(),
+        )
+        .unwrap();
+
+        let filters: Vec<_> = query.terms.iter().map(|t| &t.filter).collect();
+        assert_eq!(
+            filters,
+            vec![
+                &Filter::Status(vec!["in review".into()]),
+                &Filter::Priority(vec![IssuePriority::Urgent, IssuePriority::High]),
+                &Filter::Assignee(vec![UserRef::Me]),
+                &Filter::Tag(vec!["backend".into()]),
+                &Filter::Date {
+                    field: DateField::Updated,
+                    from: Some(Utc.with_ymd_and_hms(2026, 3, 3, 12, 0, 0).unwrap()),
+                    to: None,
+                },
+                &Filter::Has(HasField::PullRequest),
+            ]
+        );
+        assert!(query.terms.iter().all(|t| !t.negated));
+    }
+
+    #[test]
+    fn handles_negation_lists_none_and_free_text() {
+        let query = parse(
+            r#"-tag:wontfix,Later assignee:none flaky "login page""#,
+            now(),
+        )
+        .unwrap();
+        let terms: Vec<_> = query.terms.iter().map(|t| (t.negated, &t.filter)).collect();
+        assert_eq!(
+            terms,
+            vec![
+                (true, &Filter::Tag(vec!["wontfix".into(), "later".into()])),
+                (true, &Filter::Has(HasField::Assignee)),
+                (false, &Filter::Text("flaky".into())),
+                (false, &Filter::Text("login page".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_custom_field_filters() {
+        let query = parse(
+            "field.severity:High,low field.story_points>=3 -field.launch<2026-06-01 field.team:none",
+            now(),
+        )
+        .unwrap();
+        let terms: Vec<_> = query.terms.iter().map(|t| (t.negated, &t.filter)).collect();
+        let custom = |key: &str, condition| Filter::CustomField {
+            key: key.into(),
+            condition,
+        };
+        assert_eq!(
+            terms,
+            vec![
+                (
+                    false,
+                    &custom(
+                        "severity",
+                        CustomFieldCondition::AnyOf(vec!["high".into(), "low".into()])
+                    )
+                ),
+                (
+                    false,
+                    &custom(
+                        "story_points",
+                        CustomFieldCondition::Number(Comparison::Ge, 3.0)
+                    )
+                ),
+                (
+                    true,
+                    &custom(
+                        "launch",
+                        CustomFieldCondition::Date(
+                            Comparison::Lt,
+                            NaiveDate::from_ymd_opt(2026, 6, 1).unwrap()
+                        )
+                    )
+                ),
+                (true, &custom("team", CustomFieldCondition::Set)),
+            ]
+        );
+        assert!(matches!(
+            parse("field.points>lots", now()),
+            Err(QueryError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn resolves_calendar_dates() {
+        let day = |d| Some(Utc.with_ymd_and_hms(2026, 3, d, 0, 0, 0).unwrap());
+        assert_eq!(
+            date_range("2026-03-05", Op::Eq, now()),
+            Some((day(5), day(6)))
+        );
+        assert_eq!(date_range("today", Op::Lt, now()), Some((None, day(10))));
+        assert_eq!(
+            date_range("yesterday", Op::Gt, now()),
+            Some((day(10), None))
+        );
+        assert_eq!(date_range("3x", Op::Eq, now()), None);
+    }
+
+    #[test]
+    fn rejects_invalid_terms() {
+        assert_eq!(
+            parse(r#"status:"open"#, now()).unwrap_err(),
+            QueryError::UnterminatedQuote(7)
+        );
+        assert!(matches!(
+            parse("tag>backend", now()),
+            Err(QueryError::UnsupportedOperator { .. })
+        ));
+        assert!(matches!(
+            parse("priority:critical", now()),
+            Err(QueryError::InvalidValue { .. })
+        ));
+        assert_eq!(
+            parse("status:", now()).unwrap_err(),
+            QueryError::MissingValue("status".into())
+        );
+        // Unknown fields are plain text rather than errors.
+        assert_eq!(
+            parse("foo:bar", now()).unwrap().terms[0].filter,
+            Filter::Text("foo:bar".into())
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Minimal RFC 4180 reader and writer: quoted fields may contain commas,
+//! doubled quotes and line breaks.
+
+#[derive(Debug, thiserror::Error)]
+#[error("unterminated quoted field starting on line {line}")]
+pub(crate) struct CsvError {
+    pub line: usize,
+}
+
+pub(crate) fn parse(content: &str) -> Result<Vec<Vec<String>>, CsvError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut chars = content.chars().peekable();
+    let mut line = 1;
+    let mut at_field_start = true;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if at_field_start => {
+                let start_line = line;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => return Err(CsvError { line: start_line }),
+                    }
+                }
+                at_field_start = false;
+            }
+            ',' => {
+                record.push(std::mem::take(&mut field));
+                at_field_start = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                line += 1;
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                at_field_start = true;
+            }
+            c => {
+                field.push(c);
+                at_field_start = false;
+            }
+        }
+    }
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    // Blank lines carry no data.
+    records.retain(|record| !(record.len() == 1 && record[0].is_empty()));
+    Ok(records)
+}
+
+pub(crate) fn write_record<S: AsRef<str>>(out: &mut String, fields: &[S]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let field = field.as_ref();
+        if field.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&field.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(field);
+        }
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_quoted_fields() {
+        let rows = vec![
+            vec!["title", "description"],
+            vec!["Audit deps", "Run \"cargo audit\", then\nfile issues"],
+            vec!["", "plain"],
+        ];
+        let mut out = String::new();
+        for row in &rows {
+            write_record(&mut out, row);
+        }
+        assert_eq!(parse(&out).unwrap(), rows);
+    }
+
+    #[test]
+    fn skips_bom_and_blank_lines() {
+        let parsed = parse("\u{feff}a,b\n\n1,2\n").unwrap();
+        assert_eq!(parsed, vec![vec!["a", "b"], vec!["1", "2"]]);
+    }
+
+    #[test]
+    fn reports_unterminated_quote() {
+        assert_eq!(parse("a\n\"b,c\n").unwrap_err().line, 2);
+    }
+}
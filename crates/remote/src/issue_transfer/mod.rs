@@ -0,0 +1,738 @@
+//! Bulk import of issues from CSV, JSON, Linear and Jira exports, and export
+//! of a project's issues with their comments and relationships.
+//!
+//! An import is planned against a snapshot of the project first: statuses,
+//! tags and users are resolved by name, and every problem is reported per
+//! row. A dry run returns that plan; a real run writes it in one
+//! transaction.
+
+mod csv;
+mod sources;
+
+use std::collections::{HashMap, HashSet};
+
+use api_types::{
+    ExportedIssue, ExportedIssueComment, ExportedIssueRelationship, ImportIssuePreview,
+    ImportIssuesRequest, ImportIssuesResponse, ImportRowError, Issue, IssueExport,
+    IssueImportAction, IssueImportFormat, IssuePriority, IssueRelationshipType, ProjectStatus, Tag,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::{Value, json};
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use self::sources::{SourceIssue, SourceRelationship};
+use crate::db::{
+    issue_relationships::{IssueRelationshipError, IssueRelationshipRepository},
+    issue_transfer::{
+        IssueTransferError, IssueTransferRepository, MemberIdentity, NewImportedIssue,
+    },
+    issues::{IssueError, IssueRepository},
+    project_statuses::{ProjectStatusError, ProjectStatusRepository},
+    tags::{TagError, TagRepository},
+};
+
+/// Key in `Issue::extension_metadata` recording where an imported issue
+/// came from, so importing the same file twice skips what already landed.
+const IMPORT_METADATA_KEY: &str = "import";
+const NEW_TAG_COLOR: &str = "0 0% 50%";
+const MAX_TITLE_LEN: usize = 255;
+const MAX_TAG_LEN: usize = 50;
+
+#[derive(Debug, Error)]
+pub enum TransferError {
+    #[error("invalid CSV: {0}")]
+    Csv(#[from] csv::CsvError),
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("{0}")]
+    Format(String),
+    #[error("project has no statuses")]
+    NoStatuses,
+    #[error("{0} rows have errors; fix or remove them before importing")]
+    RowErrors(usize),
+    #[error(transparent)]
+    Transfer(#[from] IssueTransferError),
+    #[error(transparent)]
+    Relationship(#[from] IssueRelationshipError),
+    #[error(transparent)]
+    Issue(#[from] IssueError),
+    #[error(transparent)]
+    ProjectStatus(#[from] ProjectStatusError),
+    #[error(transparent)]
+    Tag(#[from] TagError),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+}
+
+impl TransferError {
+    /// Whether the error is caused by the uploaded file rather than the
+    /// server.
+    pub fn is_invalid_input(&self) -> bool {
+        matches!(
+            self,
+            Self::Csv(_) | Self::Json(_) | Self::Format(_) | Self::NoStatuses | Self::RowErrors(_)
+        )
+    }
+}
+
+/// The parts of a project an import is resolved against.
+pub struct ProjectSnapshot {
+    statuses: Vec<ProjectStatus>,
+    tags: Vec<Tag>,
+    members: Vec<MemberIdentity>,
+    issues: Vec<Issue>,
+}
+
+impl ProjectSnapshot {
+    pub async fn load(pool: &PgPool, project_id: Uuid) -> Result<Self, TransferError> {
+        let mut statuses = ProjectStatusRepository::list_by_project(pool, project_id).await?;
+        statuses.sort_by_key(|status| status.sort_order);
+        Ok(Self {
+            statuses,
+            tags: TagRepository::list_by_project(pool, project_id).await?,
+            members: IssueTransferRepository::list_members(pool, project_id).await?,
+            issues: IssueRepository::list_by_project(pool, project_id).await?,
+        })
+    }
+
+    fn find_member(&self, name: &str) -> Option<&MemberIdentity> {
+        self.members.iter().find(|member| {
+            member.email.eq_ignore_ascii_case(name)
+                || member
+                    .username
+                    .as_deref()
+                    .is_some_and(|username| username.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// Source ids that already exist in the project: issue simple ids and
+    /// the ids recorded by earlier imports.
+    fn existing_ids(&self) -> HashMap<String, Uuid> {
+        let mut ids = HashMap::new();
+        for issue in &self.issues {
+            ids.insert(issue.simple_id.clone(), issue.id);
+            if let Some(external_id) = issue
+                .extension_metadata
+                .pointer(&format!("/{IMPORT_METADATA_KEY}/external_id"))
+                .and_then(Value::as_str)
+            {
+                ids.insert(external_id.to_string(), issue.id);
+            }
+        }
+        ids
+    }
+}
+
+pub fn read_sources(request: &ImportIssuesRequest) -> Result<Vec<SourceIssue>, TransferError> {
+    match request.format {
+        IssueImportFormat::Csv => sources::parse_csv(&request.content, &request.column_mapping),
+        IssueImportFormat::Json => sources::parse_json(&request.content, &request.column_mapping),
+        IssueImportFormat::Linear => sources::parse_linear(&request.content),
+        IssueImportFormat::Jira => sources::parse_jira(&request.content),
+    }
+}
+
+struct PlannedIssue {
+    preview: ImportIssuePreview,
+    issue: NewImportedIssue,
+    assignee_ids: Vec<Uuid>,
+    comments: Vec<PlannedComment>,
+    relationships: Vec<SourceRelationship>,
+}
+
+struct PlannedComment {
+    author_id: Option<Uuid>,
+    message: String,
+    created_at: Option<DateTime<Utc>>,
+}
+
+pub struct ImportPlan {
+    issues: Vec<PlannedIssue>,
+    report: ImportIssuesResponse,
+}
+
+impl ImportPlan {
+    pub fn into_report(self) -> ImportIssuesResponse {
+        self.report
+    }
+}
+
+pub fn plan_import(
+    sources: Vec<SourceIssue>,
+    snapshot: &ProjectSnapshot,
+    request: &ImportIssuesRequest,
+) -> Result<ImportPlan, TransferError> {
+    let default_status = snapshot
+        .statuses
+        .iter()
+        .find(|status| !status.hidden)
+        .or_else(|| snapshot.statuses.first())
+        .ok_or(TransferError::NoStatuses)?;
+    let existing_ids = snapshot.existing_ids();
+    let source_name = match request.format {
+        IssueImportFormat::Csv => "csv",
+        IssueImportFormat::Json => "json",
+        IssueImportFormat::Linear => "linear",
+        IssueImportFormat::Jira => "jira",
+    };
+    let sort_base = snapshot
+        .issues
+        .iter()
+        .map(|issue| issue.sort_order)
+        .fold(0.0, f64::max)
+        + 1.0;
+
+    let mut report = ImportIssuesResponse {
+        dry_run: request.dry_run,
+        issues: Vec::new(),
+        new_tags: Vec::new(),
+        unmapped_statuses: Vec::new(),
+        unknown_users: Vec::new(),
+        errors: Vec::new(),
+        skipped_relationships: Vec::new(),
+        created_issue_ids: Vec::new(),
+    };
+    let mut seen_ids = HashSet::new();
+    let mut issues = Vec::new();
+
+    for source in sources {
+        let row = source.row;
+        let mut row_errors = Vec::new();
+
+        let title = source.title.unwrap_or_default();
+        if title.is_empty() {
+            row_errors.push("missing title".to_string());
+        } else if title.chars().count() > MAX_TITLE_LEN {
+            row_errors.push(format!("title is longer than {MAX_TITLE_LEN} characters"));
+        }
+        if let Some(external_id) = &source.external_id
+            && !seen_ids.insert(external_id.clone())
+        {
+            row_errors.push(format!("duplicate id {external_id}"));
+        }
+        let priority = match source.priority.as_deref().map(parse_priority) {
+            Some(Ok(priority)) => priority,
+            Some(Err(())) => {
+                row_errors.push(format!(
+                    "unknown priority {}",
+                    source.priority.as_deref().unwrap_or_default()
+                ));
+                None
+            }
+            None => None,
+        };
+        let mut date = |name: &str, value: Option<String>| {
+            let value = value?;
+            let parsed = parse_date(&value);
+            if parsed.is_none() {
+                row_errors.push(format!("invalid {name} {value}"));
+            }
+            parsed
+        };
+        let start_date = date("start date", source.start_date);
+        let target_date = date("target date", source.target_date);
+        let completed_at = date("completion date", source.completed_at);
+
+        let status = match &source.status {
+            Some(name) => {
+                let mapped = request
+                    .status_mapping
+                    .iter()
+                    .find(|(from, _)| from.eq_ignore_ascii_case(name))
+                    .map_or(name.as_str(), |(_, to)| to.as_str());
+                snapshot
+                    .statuses
+                    .iter()
+                    .find(|status| status.name.eq_ignore_ascii_case(mapped))
+                    .unwrap_or_else(|| {
+                        push_unique(&mut report.unmapped_statuses, name);
+                        default_status
+                    })
+            }
+            None => default_status,
+        };
+
+        let mut tags: Vec<String> = Vec::new();
+        for name in &source.tags {
+            if name.chars().count() > MAX_TAG_LEN {
+                row_errors.push(format!(
+                    "tag {name} is longer than {MAX_TAG_LEN} characters"
+                ));
+                continue;
+            }
+            let name = snapshot
+                .tags
+                .iter()
+                .find(|tag| tag.name.eq_ignore_ascii_case(name))
+                .map_or(name.as_str(), |tag| tag.name.as_str());
+            if !tags.iter().any(|tag| tag.eq_ignore_ascii_case(name)) {
+                tags.push(name.to_string());
+            }
+        }
+
+        if !row_errors.is_empty() {
+            report.errors.extend(
+                row_errors
+                    .into_iter()
+                    .map(|message| ImportRowError { row, message }),
+            );
+            continue;
+        }
+
+        let action = match &source.external_id {
+            Some(external_id) if existing_ids.contains_key(external_id) => {
+                IssueImportAction::SkipExisting
+            }
+            _ => IssueImportAction::Create,
+        };
+        if action == IssueImportAction::Create {
+            for tag in &tags {
+                if !snapshot
+                    .tags
+                    .iter()
+                    .any(|existing| existing.name.eq_ignore_ascii_case(tag))
+                {
+                    push_unique(&mut report.new_tags, tag);
+                }
+            }
+        }
+
+        let mut assignee_emails = Vec::new();
+        let mut assignee_ids = Vec::new();
+        for name in &source.assignees {
+            match snapshot.find_member(name) {
+                Some(member) if !assignee_ids.contains(&member.user_id) => {
+                    assignee_ids.push(member.user_id);
+                    assignee_emails.push(member.email.clone());
+                }
+                Some(_) => {}
+                None => push_unique(&mut report.unknown_users, name),
+            }
+        }
+
+        let comments = source
+            .comments
+            .into_iter()
+            .map(|comment| {
+                let author_id = comment.author.as_deref().and_then(|author| {
+                    let member = snapshot.find_member(author);
+                    if member.is_none() {
+                        push_unique(&mut report.unknown_users, author);
+                    }
+                    member.map(|member| member.user_id)
+                });
+                PlannedComment {
+                    author_id,
+                    message: comment.message,
+                    created_at: comment.created_at.as_deref().and_then(parse_date),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut extension_metadata = json!({ IMPORT_METADATA_KEY: { "source": source_name } });
+        if let Some(external_id) = &source.external_id {
+            extension_metadata[IMPORT_METADATA_KEY]["external_id"] = json!(external_id);
+        }
+
+        report.issues.push(ImportIssuePreview {
+            row,
+            external_id: source.external_id,
+            action,
+            title: title.clone(),
+            status: status.name.clone(),
+            priority,
+            tags,
+            assignees: assignee_emails,
+            parent_external_id: source.parent,
+            comment_count: comments.len(),
+            relationship_count: source.relationships.len(),
+        });
+        issues.push(PlannedIssue {
+            preview: report
+                .issues
+                .last()
+                .cloned()
+                .expect("preview was just pushed"),
+            issue: NewImportedIssue {
+                status_id: status.id,
+                title,
+                description: source.description,
+                priority,
+                start_date,
+                target_date,
+                completed_at,
+                sort_order: sort_base + issues.len() as f64,
+                extension_metadata,
+            },
+            assignee_ids,
+            comments,
+            relationships: source.relationships,
+        });
+    }
+
+    Ok(ImportPlan { issues, report })
+}
+
+/// Write a plan in one transaction. Plans with row errors are refused rather
+/// than imported without those rows. Parents are created before their
+/// sub-issues; relationships are added once every issue exists, skipping
+/// (and reporting) blocking edges that would close a cycle.
+pub async fn execute_import(
+    pool: &PgPool,
+    project_id: Uuid,
+    snapshot: &ProjectSnapshot,
+    plan: ImportPlan,
+    importer_id: Uuid,
+) -> Result<ImportIssuesResponse, TransferError> {
+    let ImportPlan { issues, mut report } = plan;
+    if !report.errors.is_empty() {
+        return Err(TransferError::RowErrors(report.errors.len()));
+    }
+    let mut tx = pool.begin().await?;
+    IssueTransferRepository::lock_project(&mut tx, project_id).await?;
+
+    let mut tag_ids: HashMap<String, Uuid> = snapshot
+        .tags
+        .iter()
+        .map(|tag| (tag.name.to_lowercase(), tag.id))
+        .collect();
+    for name in &report.new_tags {
+        let id =
+            IssueTransferRepository::insert_tag(&mut tx, project_id, name, NEW_TAG_COLOR).await?;
+        tag_ids.insert(name.to_lowercase(), id);
+    }
+
+    let mut ids = snapshot.existing_ids();
+    let mut created = HashMap::new();
+    for index in creation_order(&issues) {
+        let planned = &issues[index];
+        if planned.preview.action != IssueImportAction::Create {
+            continue;
+        }
+        let issue = planned.issue.clone();
+        let parent_issue_id = planned
+            .preview
+            .parent_external_id
+            .as_ref()
+            .and_then(|parent| ids.get(parent).copied());
+        let issue_id = IssueRepository::insert(
+            &mut *tx,
+            None,
+            project_id,
+            issue.status_id,
+            issue.title,
+            issue.description,
+            issue.priority,
+            issue.start_date,
+            issue.target_date,
+            None,
+            None,
+            issue.completed_at,
+            issue.sort_order,
+            parent_issue_id,
+            None,
+            issue.extension_metadata,
+            Some(importer_id),
+        )
+        .await?
+        .id;
+        if let Some(external_id) = &planned.preview.external_id {
+            ids.insert(external_id.clone(), issue_id);
+        }
+        created.insert(index, issue_id);
+
+        let issue_tag_ids: Vec<Uuid> = planned
+            .preview
+            .tags
+            .iter()
+            .filter_map(|name| tag_ids.get(&name.to_lowercase()).copied())
+            .collect();
+        IssueTransferRepository::insert_issue_tags(&mut tx, issue_id, &issue_tag_ids).await?;
+        IssueTransferRepository::insert_issue_assignees(&mut tx, issue_id, &planned.assignee_ids)
+            .await?;
+        for comment in &planned.comments {
+            IssueTransferRepository::insert_comment(
+                &mut tx,
+                issue_id,
+                comment.author_id.unwrap_or(importer_id),
+                &comment.message,
+                comment.created_at.unwrap_or_else(Utc::now),
+            )
+            .await?;
+        }
+    }
+
+    for (index, planned) in issues.iter().enumerate() {
+        let Some(&issue_id) = created.get(&index) else {
+            continue;
+        };
+        for relationship in &planned.relationships {
+            let Some(&related_id) = ids.get(&relationship.related) else {
+                continue;
+            };
+            let (from, to) = if relationship.inverse {
+                (related_id, issue_id)
+            } else {
+                (issue_id, related_id)
+            };
+            if from == to {
+                continue;
+            }
+            if relationship.relationship_type == IssueRelationshipType::Blocking
+                && IssueRelationshipRepository::blocks_transitively(&mut *tx, to, from).await?
+            {
+                report.skipped_relationships.push(ImportRowError {
+                    row: planned.preview.row,
+                    message: format!(
+                        "blocking relationship with {} would create a cycle",
+                        relationship.related
+                    ),
+                });
+                continue;
+            }
+            IssueTransferRepository::insert_relationship(
+                &mut tx,
+                from,
+                to,
+                relationship.relationship_type,
+            )
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    let mut created: Vec<(usize, Uuid)> = created.into_iter().collect();
+    created.sort_unstable();
+    report.created_issue_ids = created.into_iter().map(|(_, id)| id).collect();
+    Ok(report)
+}
+
+/// Indices of `issues` ordered so a parent from the same file comes before
+/// its sub-issues. Parent cycles are broken arbitrarily.
+fn creation_order(issues: &[PlannedIssue]) -> Vec<usize> {
+    fn visit(
+        index: usize,
+        issues: &[PlannedIssue],
+        by_id: &HashMap<&str, usize>,
+        visited: &mut [bool],
+        order: &mut Vec<usize>,
+    ) {
+        if visited[index] {
+            return;
+        }
+        visited[index] = true;
+        if let Some(&parent) = issues[index]
+            .preview
+            .parent_external_id
+            .as_deref()
+            .and_then(|parent| by_id.get(parent))
+        {
+            visit(parent, issues, by_id, visited, order);
+        }
+        order.push(index);
+    }
+
+    let by_id: HashMap<&str, usize> = issues
+        .iter()
+        .enumerate()
+        .filter_map(|(index, issue)| Some((issue.preview.external_id.as_deref()?, index)))
+        .collect();
+    let mut visited = vec![false; issues.len()];
+    let mut order = Vec::with_capacity(issues.len());
+    for index in 0..issues.len() {
+        visit(index, issues, &by_id, &mut visited, &mut order);
+    }
+    order
+}
+
+pub async fn export(pool: &PgPool, project_id: Uuid) -> Result<IssueExport, TransferError> {
+    let mut issues = IssueRepository::list_by_project(pool, project_id).await?;
+    issues.sort_by_key(|issue| issue.issue_number);
+    let statuses: HashMap<Uuid, String> =
+        ProjectStatusRepository::list_by_project(pool, project_id)
+            .await?
+            .into_iter()
+            .map(|status| (status.id, status.name))
+            .collect();
+    let simple_ids: HashMap<Uuid, String> = issues
+        .iter()
+        .map(|issue| (issue.id, issue.simple_id.clone()))
+        .collect();
+
+    let mut tags: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for label in IssueTransferRepository::list_issue_tag_names(pool, project_id).await? {
+        tags.entry(label.issue_id).or_default().push(label.value);
+    }
+    let mut assignees: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for label in IssueTransferRepository::list_issue_assignee_emails(pool, project_id).await? {
+        assignees
+            .entry(label.issue_id)
+            .or_default()
+            .push(label.value);
+    }
+    let mut comments: HashMap<Uuid, Vec<ExportedIssueComment>> = HashMap::new();
+    for comment in IssueTransferRepository::list_comments(pool, project_id).await? {
+        comments
+            .entry(comment.issue_id)
+            .or_default()
+            .push(ExportedIssueComment {
+                author: comment.author_email,
+                message: comment.message,
+                created_at: comment.created_at,
+            });
+    }
+    let mut relationships: HashMap<Uuid, Vec<ExportedIssueRelationship>> = HashMap::new();
+    for relationship in IssueTransferRepository::list_relationships(pool, project_id).await? {
+        relationships
+            .entry(relationship.issue_id)
+            .or_default()
+            .push(ExportedIssueRelationship {
+                relationship_type: relationship.relationship_type,
+                related: relationship.related_simple_id,
+            });
+    }
+
+    let issues = issues
+        .into_iter()
+        .map(|issue| ExportedIssue {
+            status: statuses.get(&issue.status_id).cloned().unwrap_or_default(),
+            parent: issue
+                .parent_issue_id
+                .and_then(|parent| simple_ids.get(&parent).cloned()),
+            tags: tags.remove(&issue.id).unwrap_or_default(),
+            assignees: assignees.remove(&issue.id).unwrap_or_default(),
+            comments: comments.remove(&issue.id).unwrap_or_default(),
+            relationships: relationships.remove(&issue.id).unwrap_or_default(),
+            id: issue.simple_id,
+            title: issue.title,
+            description: issue.description,
+            priority: issue.priority,
+            start_date: issue.start_date,
+            target_date: issue.target_date,
+            completed_at: issue.completed_at,
+        })
+        .collect();
+
+    Ok(IssueExport {
+        project_id,
+        exported_at: Utc::now(),
+        issues,
+    })
+}
+
+/// One row per issue with the same column names the CSV importer reads.
+pub fn export_csv(export: &IssueExport) -> String {
+    let mut out = String::new();
+    csv::write_record(
+        &mut out,
+        &[
+            "id",
+            "title",
+            "description",
+            "status",
+            "priority",
+            "tags",
+            "assignees",
+            "start_date",
+            "target_date",
+            "completed_at",
+            "parent",
+        ],
+    );
+    let date = |date: Option<DateTime<Utc>>| date.map(|date| date.to_rfc3339()).unwrap_or_default();
+    for issue in &export.issues {
+        csv::write_record(
+            &mut out,
+            &[
+                issue.id.clone(),
+                issue.title.clone(),
+                issue.description.clone().unwrap_or_default(),
+                issue.status.clone(),
+                issue
+                    .priority
+                    .map(priority_name)
+                    .unwrap_or_default()
+                    .to_string(),
+                issue.tags.join(", "),
+                issue.assignees.join(", "),
+                date(issue.start_date),
+                date(issue.target_date),
+                date(issue.completed_at),
+                issue.parent.clone().unwrap_or_default(),
+            ],
+        );
+    }
+    out
+}
+
+fn push_unique(values: &mut Vec<String>, value: &str) {
+    if !values.iter().any(|existing| existing == value) {
+        values.push(value.to_string());
+    }
+}
+
+fn priority_name(priority: IssuePriority) -> &'static str {
+    match priority {
+        IssuePriority::Urgent => "urgent",
+        IssuePriority::High => "high",
+        IssuePriority::Medium => "medium",
+        IssuePriority::Low => "low",
+    }
+}
+
+/// Priority names used by this app, Jira and Linear, plus `p0`-`p3`.
+fn parse_priority(value: &str) -> Result<Option<IssuePriority>, ()> {
+    let priority = match value.trim().to_lowercase().as_str() {
+        "" | "none" | "no priority" => return Ok(None),
+        "urgent" | "highest" | "critical" | "blocker" | "p0" => IssuePriority::Urgent,
+        "high" | "major" | "p1" => IssuePriority::High,
+        "medium" | "normal" | "p2" => IssuePriority::Medium,
+        "low" | "lowest" | "minor" | "trivial" | "p3" => IssuePriority::Low,
+        _ => return Err(()),
+    };
+    Ok(Some(priority))
+}
+
+/// RFC 3339, Jira's `2024-01-15T10:30:00.000+0000`, or a bare date taken
+/// as midnight UTC.
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date| date.and_utc())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_source_dates() {
+        let expected = "2024-01-15T10:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(parse_date("2024-01-15T10:30:00Z"), Some(expected));
+        assert_eq!(parse_date("2024-01-15T10:30:00.000+0000"), Some(expected));
+        assert_eq!(
+            parse_date("2024-01-15"),
+            "2024-01-15T00:00:00Z".parse::<DateTime<Utc>>().ok()
+        );
+        assert_eq!(parse_date("next week"), None);
+    }
+
+    #[test]
+    fn maps_foreign_priorities() {
+        assert_eq!(parse_priority("Highest"), Ok(Some(IssuePriority::Urgent)));
+        assert_eq!(parse_priority("Normal"), Ok(Some(IssuePriority::Medium)));
+        assert_eq!(parse_priority("No priority"), Ok(None));
+        assert_eq!(parse_priority("soon"), Err(()));
+    }
+}
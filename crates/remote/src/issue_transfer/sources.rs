@@ -0,0 +1,623 @@
+//! Readers that turn each supported import file into [`SourceIssue`]s.
+//! Values are kept as text here; validation happens when the import is
+//! planned so every problem can be reported against its row.
+
+use std::collections::HashMap;
+
+use api_types::{IssueImportField, IssueRelationshipType};
+use serde_json::Value;
+
+use super::{TransferError, csv};
+
+static NULL: Value = Value::Null;
+
+#[derive(Debug, Default)]
+pub(crate) struct SourceIssue {
+    /// 1-based position in the source file.
+    pub row: usize,
+    pub external_id: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub tags: Vec<String>,
+    pub assignees: Vec<String>,
+    pub start_date: Option<String>,
+    pub target_date: Option<String>,
+    pub completed_at: Option<String>,
+    pub parent: Option<String>,
+    pub comments: Vec<SourceComment>,
+    pub relationships: Vec<SourceRelationship>,
+}
+
+#[derive(Debug)]
+pub(crate) struct SourceComment {
+    pub author: Option<String>,
+    pub message: String,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct SourceRelationship {
+    pub relationship_type: IssueRelationshipType,
+    /// Source id of the other issue.
+    pub related: String,
+    /// The edge points from `related` to this issue.
+    pub inverse: bool,
+}
+
+impl SourceIssue {
+    fn set(&mut self, field: IssueImportField, values: Vec<String>) {
+        let scalar = || {
+            values
+                .iter()
+                .map(|value| value.trim())
+                .find(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let list = || {
+            values
+                .iter()
+                .flat_map(|value| value.split([',', ';']))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        match field {
+            IssueImportField::Id => self.external_id = scalar(),
+            IssueImportField::Title => self.title = scalar(),
+            // Keep the description's own whitespace.
+            IssueImportField::Description => {
+                self.description = values.into_iter().find(|value| !value.trim().is_empty())
+            }
+            IssueImportField::Status => self.status = scalar(),
+            IssueImportField::Priority => self.priority = scalar(),
+            IssueImportField::Tags => self.tags.extend(list()),
+            IssueImportField::Assignees => self.assignees.extend(list()),
+            IssueImportField::StartDate => self.start_date = scalar(),
+            IssueImportField::TargetDate => self.target_date = scalar(),
+            IssueImportField::CompletedAt => self.completed_at = scalar(),
+            IssueImportField::Parent => self.parent = scalar(),
+            IssueImportField::Ignore => {}
+        }
+    }
+}
+
+/// Field a CSV header or JSON key maps to, honouring the request's
+/// overrides before the built-in names and aliases.
+fn resolve_field(
+    key: &str,
+    mapping: &HashMap<String, IssueImportField>,
+) -> Option<IssueImportField> {
+    let normalize = |key: &str| key.trim().to_lowercase().replace(['_', '-'], " ");
+    let key = normalize(key);
+    if let Some(field) = mapping
+        .iter()
+        .find(|(name, _)| normalize(name) == key)
+        .map(|(_, field)| *field)
+    {
+        return Some(field);
+    }
+    let field = match key.as_str() {
+        "id" | "key" | "issue key" | "issue id" | "identifier" | "simple id" => {
+            IssueImportField::Id
+        }
+        "title" | "summary" | "name" => IssueImportField::Title,
+        "description" | "body" => IssueImportField::Description,
+        "status" | "state" => IssueImportField::Status,
+        "priority" => IssueImportField::Priority,
+        "tags" | "tag" | "labels" | "label" => IssueImportField::Tags,
+        "assignees" | "assignee" => IssueImportField::Assignees,
+        "start date" | "started" | "started at" => IssueImportField::StartDate,
+        "target date" | "due date" | "duedate" | "due" => IssueImportField::TargetDate,
+        "completed at" | "completed" | "resolved" | "resolution date" => {
+            IssueImportField::CompletedAt
+        }
+        "parent" | "parent issue" | "parent id" => IssueImportField::Parent,
+        _ => return None,
+    };
+    Some(field)
+}
+
+pub(crate) fn parse_csv(
+    content: &str,
+    mapping: &HashMap<String, IssueImportField>,
+) -> Result<Vec<SourceIssue>, TransferError> {
+    let mut records = csv::parse(content)?.into_iter();
+    let Some(headers) = records.next() else {
+        return Ok(Vec::new());
+    };
+    let fields: Vec<Option<IssueImportField>> = headers
+        .iter()
+        .map(|header| resolve_field(header, mapping))
+        .collect();
+    if !fields.contains(&Some(IssueImportField::Title)) {
+        return Err(TransferError::Format(
+            "no column maps to the issue title".to_string(),
+        ));
+    }
+
+    Ok(records
+        .enumerate()
+        .map(|(i, record)| {
+            let mut issue = SourceIssue {
+                row: i + 1,
+                ..Default::default()
+            };
+            for (field, value) in fields.iter().zip(record) {
+                if let Some(field) = field {
+                    issue.set(*field, vec![value]);
+                }
+            }
+            issue
+        })
+        .collect())
+}
+
+/// Generic JSON: an array of flat issue objects, or an object holding one
+/// under `issues` (the export format).
+pub(crate) fn parse_json(
+    content: &str,
+    mapping: &HashMap<String, IssueImportField>,
+) -> Result<Vec<SourceIssue>, TransferError> {
+    let value: Value = serde_json::from_str(content)?;
+    let items = match &value {
+        Value::Array(items) => items,
+        Value::Object(object) => match object.get("issues") {
+            Some(Value::Array(items)) => items,
+            _ => {
+                return Err(TransferError::Format(
+                    "expected an array of issues or an object with an `issues` array".to_string(),
+                ));
+            }
+        },
+        _ => {
+            return Err(TransferError::Format(
+                "expected an array of issues".to_string(),
+            ));
+        }
+    };
+
+    Ok(items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let mut issue = SourceIssue {
+                row: i + 1,
+                ..Default::default()
+            };
+            let Some(object) = item.as_object() else {
+                return issue;
+            };
+            for (key, value) in object {
+                match key.as_str() {
+                    "comments" => {
+                        issue.comments = array(value)
+                            .filter_map(|comment| {
+                                Some(SourceComment {
+                                    author: text(comment.get("author"))
+                                        .or_else(|| text(comment.get("email"))),
+                                    message: text(comment.get("message"))
+                                        .or_else(|| text(comment.get("body")))?,
+                                    created_at: text(comment.get("created_at")),
+                                })
+                            })
+                            .collect();
+                    }
+                    "relationships" => {
+                        issue.relationships = array(value)
+                            .filter_map(|relationship| {
+                                Some(SourceRelationship {
+                                    relationship_type: serde_json::from_value(
+                                        relationship.get("relationship_type")?.clone(),
+                                    )
+                                    .ok()?,
+                                    related: text(relationship.get("related"))?,
+                                    inverse: false,
+                                })
+                            })
+                            .collect();
+                    }
+                    _ => {
+                        if let Some(field) = resolve_field(key, mapping) {
+                            issue.set(field, texts(value));
+                        }
+                    }
+                }
+            }
+            issue
+        })
+        .collect())
+}
+
+/// Linear's GraphQL shape: `{ data: { issues: { nodes: [...] } } }`, with or
+/// without the `data` wrapper, or a bare array of issue nodes.
+pub(crate) fn parse_linear(content: &str) -> Result<Vec<SourceIssue>, TransferError> {
+    let value: Value = serde_json::from_str(content)?;
+    let root = value.get("data").unwrap_or(&value);
+    let nodes = match root.get("issues").unwrap_or(root) {
+        Value::Array(nodes) => nodes,
+        issues => issues
+            .get("nodes")
+            .and_then(Value::as_array)
+            .ok_or_else(|| TransferError::Format("expected `issues.nodes`".to_string()))?,
+    };
+
+    Ok(nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| {
+            let priority = match node.get("priority").and_then(Value::as_u64) {
+                Some(1) => Some("urgent".to_string()),
+                Some(2) => Some("high".to_string()),
+                Some(3) => Some("medium".to_string()),
+                Some(4) => Some("low".to_string()),
+                _ => text(node.get("priorityLabel")),
+            };
+            SourceIssue {
+                row: i + 1,
+                external_id: text(node.get("identifier")),
+                title: text(node.get("title")),
+                description: text(node.get("description")),
+                status: text(node.pointer("/state/name")),
+                priority,
+                tags: connection(node.get("labels"))
+                    .filter_map(|label| text(label.get("name")))
+                    .collect(),
+                assignees: node
+                    .get("assignee")
+                    .and_then(|assignee| {
+                        text(assignee.get("email")).or_else(|| text(assignee.get("name")))
+                    })
+                    .into_iter()
+                    .collect(),
+                start_date: text(node.get("startedAt")),
+                target_date: text(node.get("dueDate")),
+                completed_at: text(node.get("completedAt")),
+                parent: text(node.pointer("/parent/identifier")),
+                comments: connection(node.get("comments"))
+                    .filter_map(|comment| {
+                        Some(SourceComment {
+                            author: text(comment.pointer("/user/email")),
+                            message: text(comment.get("body"))?,
+                            created_at: text(comment.get("createdAt")),
+                        })
+                    })
+                    .collect(),
+                relationships: connection(node.get("relations"))
+                    .filter_map(|relation| {
+                        let related = text(relation.pointer("/relatedIssue/identifier"))?;
+                        // "duplicate" marks this issue as the duplicate.
+                        let (relationship_type, inverse) =
+                            match relation.get("type").and_then(Value::as_str) {
+                                Some("blocks") => (IssueRelationshipType::Blocking, false),
+                                Some("duplicate") => (IssueRelationshipType::HasDuplicate, true),
+                                _ => (IssueRelationshipType::Related, false),
+                            };
+                        Some(SourceRelationship {
+                            relationship_type,
+                            related,
+                            inverse,
+                        })
+                    })
+                    .collect(),
+            }
+        })
+        .collect())
+}
+
+/// Jira's REST search result: `{ issues: [{ key, fields: {...} }] }`.
+pub(crate) fn parse_jira(content: &str) -> Result<Vec<SourceIssue>, TransferError> {
+    let value: Value = serde_json::from_str(content)?;
+    let issues = match &value {
+        Value::Array(issues) => issues,
+        value => value
+            .get("issues")
+            .and_then(Value::as_array)
+            .ok_or_else(|| TransferError::Format("expected an `issues` array".to_string()))?,
+    };
+
+    Ok(issues
+        .iter()
+        .enumerate()
+        .map(|(i, issue)| {
+            let fields = issue.get("fields").unwrap_or(&NULL);
+            let user = |user: Option<&Value>| {
+                user.and_then(|user| {
+                    text(user.get("emailAddress")).or_else(|| text(user.get("displayName")))
+                })
+            };
+            SourceIssue {
+                row: i + 1,
+                external_id: text(issue.get("key")),
+                title: text(fields.get("summary")),
+                description: fields.get("description").and_then(rich_text),
+                status: text(fields.pointer("/status/name")),
+                priority: text(fields.pointer("/priority/name")),
+                tags: array(fields.get("labels").unwrap_or(&NULL))
+                    .filter_map(|label| text(Some(label)))
+                    .collect(),
+                assignees: user(fields.get("assignee")).into_iter().collect(),
+                start_date: None,
+                target_date: text(fields.get("duedate")),
+                completed_at: text(fields.get("resolutiondate")),
+                parent: text(fields.pointer("/parent/key")),
+                comments: array(fields.pointer("/comment/comments").unwrap_or(&NULL))
+                    .filter_map(|comment| {
+                        Some(SourceComment {
+                            author: user(comment.get("author")),
+                            message: comment.get("body").and_then(rich_text)?,
+                            created_at: text(comment.get("created")),
+                        })
+                    })
+                    .collect(),
+                relationships: array(fields.get("issuelinks").unwrap_or(&NULL))
+                    .filter_map(jira_link)
+                    .collect(),
+            }
+        })
+        .collect())
+}
+
+fn jira_link(link: &Value) -> Option<SourceRelationship> {
+    let kind = text(link.pointer("/type/name"))?.to_lowercase();
+    let (related, outward) = match text(link.pointer("/outwardIssue/key")) {
+        Some(key) => (key, true),
+        None => (text(link.pointer("/inwardIssue/key"))?, false),
+    };
+    // Outward "blocks" means this issue blocks the other one; outward
+    // "duplicates" means this issue is the duplicate.
+    let (relationship_type, inverse) = match kind.as_str() {
+        "blocks" => (IssueRelationshipType::Blocking, !outward),
+        "duplicate" => (IssueRelationshipType::HasDuplicate, outward),
+        _ => (IssueRelationshipType::Related, false),
+    };
+    Some(SourceRelationship {
+        relationship_type,
+        related,
+        inverse,
+    })
+}
+
+/// Plain text of a Jira field that is either a string or an Atlassian
+/// Document Format tree.
+fn rich_text(value: &Value) -> Option<String> {
+    fn walk(node: &Value, out: &mut String) {
+        match node.get("type").and_then(Value::as_str) {
+            Some("text") => out.push_str(node.get("text").and_then(Value::as_str).unwrap_or("")),
+            Some("hardBreak") => out.push('\n'),
+            kind => {
+                if kind == Some("listItem") {
+                    out.push_str("- ");
+                }
+                for child in array(node.get("content").unwrap_or(&NULL)) {
+                    walk(child, out);
+                }
+                if matches!(
+                    kind,
+                    Some("paragraph" | "heading" | "codeBlock" | "blockquote")
+                ) {
+                    out.push('\n');
+                }
+            }
+        }
+    }
+
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Object(_) => {
+            let mut out = String::new();
+            walk(value, &mut out);
+            let out = out.trim_end().to_string();
+            (!out.is_empty()).then_some(out)
+        }
+        _ => None,
+    }
+}
+
+fn array(value: &Value) -> impl Iterator<Item = &Value> {
+    value.as_array().into_iter().flatten()
+}
+
+/// Items of a GraphQL connection (`{ nodes: [...] }`) or a plain array.
+fn connection(value: Option<&Value>) -> impl Iterator<Item = &Value> {
+    let value = value.unwrap_or(&NULL);
+    array(value.get("nodes").unwrap_or(value))
+}
+
+fn text(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(text) if !text.trim().is_empty() => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+/// Every scalar in a JSON value, taking `name`/`email` out of objects, so
+/// `"tags": ["a", {"name": "b"}]` and `"tags": "a, b"` read the same.
+fn texts(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items.iter().flat_map(texts).collect(),
+        Value::Object(object) => text(object.get("email"))
+            .or_else(|| text(object.get("name")))
+            .into_iter()
+            .collect(),
+        Value::Bool(_) | Value::Null => Vec::new(),
+        value => text(Some(value)).into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_fixture() {
+        let issues = parse_csv(include_str!("fixtures/issues.csv"), &HashMap::new()).unwrap();
+        assert_eq!(issues.len(), 3);
+
+        let rotate = &issues[0];
+        assert_eq!(rotate.row, 1);
+        assert_eq!(rotate.external_id.as_deref(), Some("OPS-1"));
+        assert_eq!(
+            rotate.description.as_deref(),
+            Some("Rotate the primary and replica passwords.\nUpdate the vault entries afterwards.")
+        );
+        assert_eq!(rotate.status.as_deref(), Some("In Progress"));
+        assert_eq!(rotate.priority.as_deref(), Some("High"));
+        assert_eq!(rotate.tags, vec!["infra", "security"]);
+        assert_eq!(rotate.assignees, vec!["ops@example.com"]);
+        assert_eq!(rotate.target_date.as_deref(), Some("2024-03-01"));
+        assert_eq!(rotate.parent, None);
+
+        let docs = &issues[1];
+        assert_eq!(
+            docs.title.as_deref(),
+            Some("Document the rotation, step by step")
+        );
+        assert_eq!(docs.description, None);
+        assert!(docs.assignees.is_empty());
+        assert_eq!(docs.parent.as_deref(), Some("OPS-1"));
+
+        assert_eq!(issues[2].title, None);
+    }
+
+    #[test]
+    fn linear_fixture() {
+        let issues = parse_linear(include_str!("fixtures/linear.json")).unwrap();
+        assert_eq!(issues.len(), 2);
+
+        let auth = &issues[0];
+        assert_eq!(auth.external_id.as_deref(), Some("ENG-1"));
+        assert_eq!(auth.priority.as_deref(), Some("high"));
+        assert_eq!(auth.tags, vec!["backend", "auth"]);
+        assert_eq!(auth.assignees, vec!["dev1@example.com"]);
+        assert_eq!(auth.start_date.as_deref(), Some("2024-02-01T09:00:00.000Z"));
+        assert_eq!(auth.target_date.as_deref(), Some("2024-02-29"));
+        assert_eq!(auth.completed_at, None);
+        assert_eq!(auth.comments.len(), 1);
+        assert_eq!(auth.comments[0].author.as_deref(), Some("dev2@example.com"));
+        assert_eq!(auth.comments[0].message, "Blocked on the schema change.");
+        assert_eq!(auth.relationships.len(), 1);
+        assert_eq!(
+            auth.relationships[0].relationship_type,
+            IssueRelationshipType::Blocking
+        );
+        assert!(!auth.relationships[0].inverse);
+
+        let legacy = &issues[1];
+        assert_eq!(legacy.priority.as_deref(), Some("No priority"));
+        assert_eq!(legacy.description, None);
+        assert!(legacy.assignees.is_empty());
+        assert_eq!(legacy.parent.as_deref(), Some("ENG-1"));
+        let duplicate = &legacy.relationships[0];
+        assert_eq!(
+            duplicate.relationship_type,
+            IssueRelationshipType::HasDuplicate
+        );
+        assert_eq!(duplicate.related, "ENG-9");
+        assert!(duplicate.inverse);
+    }
+
+    #[test]
+    fn jira_fixture() {
+        let issues = parse_jira(include_str!("fixtures/jira.json")).unwrap();
+        assert_eq!(issues.len(), 2);
+
+        let checkout = &issues[0];
+        assert_eq!(checkout.external_id.as_deref(), Some("PROJ-1"));
+        assert_eq!(
+            checkout.description.as_deref(),
+            Some("Steps to reproduce:\n- Pick a saved card")
+        );
+        assert_eq!(checkout.status.as_deref(), Some("To Do"));
+        assert_eq!(checkout.priority.as_deref(), Some("Highest"));
+        assert_eq!(checkout.tags, vec!["payments", "regression"]);
+        assert_eq!(checkout.assignees, vec!["pat@example.com"]);
+        assert_eq!(checkout.target_date.as_deref(), Some("2024-04-10"));
+        assert_eq!(checkout.comments[0].author.as_deref(), Some("Sam Roe"));
+        let links: Vec<_> = checkout
+            .relationships
+            .iter()
+            .map(|r| (r.relationship_type, r.related.as_str(), r.inverse))
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                (IssueRelationshipType::HasDuplicate, "PROJ-7", true),
+                (IssueRelationshipType::Related, "PROJ-3", false),
+            ]
+        );
+
+        let retry = &issues[1];
+        assert_eq!(retry.description.as_deref(), Some("Plain text description"));
+        assert_eq!(
+            retry.completed_at.as_deref(),
+            Some("2024-04-05T16:00:00.000+0000")
+        );
+        assert_eq!(retry.parent.as_deref(), Some("PROJ-1"));
+        assert!(retry.relationships.is_empty());
+    }
+
+    #[test]
+    fn csv_uses_aliases_and_overrides() {
+        let mapping = HashMap::from([("Owner".to_string(), IssueImportField::Assignees)]);
+        let issues = parse_csv(
+            "Key,Summary,Labels,Owner,Notes\nOPS-1,Rotate keys,\"infra; security\",a@x.io,skip\n",
+            &mapping,
+        )
+        .unwrap();
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.external_id.as_deref(), Some("OPS-1"));
+        assert_eq!(issue.title.as_deref(), Some("Rotate keys"));
+        assert_eq!(issue.tags, vec!["infra", "security"]);
+        assert_eq!(issue.assignees, vec!["a@x.io"]);
+    }
+
+    #[test]
+    fn jira_links_point_the_right_way() {
+        let issues = parse_jira(
+            r#"{"issues": [{"key": "J-2", "fields": {
+                "summary": "Ship",
+                "description": {"type": "doc", "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "Line one"}]},
+                    {"type": "paragraph", "content": [{"type": "text", "text": "Line two"}]}
+                ]},
+                "issuelinks": [
+                    {"type": {"name": "Blocks"}, "inwardIssue": {"key": "J-1"}},
+                    {"type": {"name": "Blocks"}, "outwardIssue": {"key": "J-3"}}
+                ]
+            }}]}"#,
+        )
+        .unwrap();
+        let issue = &issues[0];
+        assert_eq!(issue.description.as_deref(), Some("Line one\nLine two"));
+        let edges: Vec<_> = issue
+            .relationships
+            .iter()
+            .map(|r| (r.related.as_str(), r.inverse))
+            .collect();
+        assert_eq!(edges, vec![("J-1", true), ("J-3", false)]);
+    }
+
+    #[test]
+    fn linear_reads_graphql_connections() {
+        let issues = parse_linear(
+            r#"{"data": {"issues": {"nodes": [{
+                "identifier": "ENG-7", "title": "Fix login", "priority": 1,
+                "state": {"name": "Todo"},
+                "labels": {"nodes": [{"name": "bug"}]},
+                "assignee": {"email": "dev@x.io"},
+                "parent": {"identifier": "ENG-1"}
+            }]}}}"#,
+        )
+        .unwrap();
+        let issue = &issues[0];
+        assert_eq!(issue.priority.as_deref(), Some("urgent"));
+        assert_eq!(issue.status.as_deref(), Some("Todo"));
+        assert_eq!(issue.tags, vec!["bug"]);
+        assert_eq!(issue.parent.as_deref(), Some("ENG-1"));
+    }
+}
@@ -2,13 +2,20 @@ mod analytics;
 mod app;
 mod auth;
 mod billing;
+mod custom_fields;
 pub mod config;
 pub mod db;
+mod dependency_graph;
+mod issue_query;
+mod issue_transfer;
 pub mod mutation_definition;
 pub mod github_app;
+mod github_issue_sync;
 pub mod mail;
 mod middleware;
+mod notifications;
 pub mod r2;
+mod recurrence;
 pub mod routes;
 pub mod shape_definition;
 pub mod shapes;
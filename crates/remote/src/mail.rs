@@ -3,12 +3,40 @@ use std::time::Duration;
 use async_trait::async_trait;
 use serde_json::json;
 
-use api_types::MemberRole;
+use api_types::{DigestFrequency, MemberRole};
 
 const LOOPS_INVITE_TEMPLATE_ID: &str = "cmhvy2wgs3s13z70i1pxakij9";
 const LOOPS_REVIEW_READY_TEMPLATE_ID: &str = "cmj47k5ge16990iylued9by17";
 const LOOPS_REVIEW_FAILED_TEMPLATE_ID: &str = "cmj49ougk1c8s0iznavijdqpo";
 
+/// Unseen notifications collected for one digest email.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationDigest {
+    pub frequency: DigestFrequency,
+    pub items: Vec<DigestItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestItem {
+    /// `SIMPLE-ID Title` of the issue the notification is about.
+    pub issue: Option<String>,
+    pub summary: String,
+}
+
+impl NotificationDigest {
+    /// Plain-text body, one notification per line.
+    pub fn to_text(&self) -> String {
+        self.items
+            .iter()
+            .map(|item| match &item.issue {
+                Some(issue) => format!("{issue}: {}", item.summary),
+                None => item.summary.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 #[async_trait]
 pub trait Mailer: Send + Sync {
     async fn send_org_invitation(
@@ -23,11 +51,14 @@ pub trait Mailer: Send + Sync {
     async fn send_review_ready(&self, email: &str, review_url: &str, pr_name: &str);
 
     async fn send_review_failed(&self, email: &str, pr_name: &str, review_id: &str);
+
+    async fn send_notification_digest(&self, email: &str, digest: &NotificationDigest);
 }
 
 pub struct LoopsMailer {
     client: reqwest::Client,
     api_key: String,
+    digest_template_id: Option<String>,
 }
 
 impl LoopsMailer {
@@ -37,7 +68,18 @@ impl LoopsMailer {
             .build()
             .expect("failed to build reqwest client");
 
-        Self { client, api_key }
+        Self {
+            client,
+            api_key,
+            digest_template_id: None,
+        }
+    }
+
+    /// Loops template for notification digests. Digests are skipped
+    /// without one.
+    pub fn with_digest_template(mut self, template_id: String) -> Self {
+        self.digest_template_id = Some(template_id);
+        self
     }
 }
 
@@ -181,4 +223,57 @@ impl Mailer for LoopsMailer {
             }
         }
     }
+
+    async fn send_notification_digest(&self, email: &str, digest: &NotificationDigest) {
+        let Some(template_id) = &self.digest_template_id else {
+            tracing::debug!("No digest template configured, skipping digest for {email}");
+            return;
+        };
+
+        let period = match digest.frequency {
+            DigestFrequency::Daily => "daily",
+            DigestFrequency::Weekly => "weekly",
+            DigestFrequency::Off => return,
+        };
+        let summary = digest.to_text();
+
+        if cfg!(debug_assertions) {
+            tracing::info!(
+                "Sending {period} notification digest to {email}\n\
+                 {summary}"
+            );
+        }
+
+        let payload = json!({
+            "transactionalId": template_id,
+            "email": email,
+            "dataVariables": {
+                "period": period,
+                "count": digest.items.len(),
+                "summary": summary,
+            }
+        });
+
+        let res = self
+            .client
+            .post("https://app.loops.so/api/v1/transactional")
+            .bearer_auth(&self.api_key)
+            .json(&payload)
+            .send()
+            .await;
+
+        match res {
+            Ok(resp) if resp.status().is_success() => {
+                tracing::debug!("Notification digest sent via Loops to {email}");
+            }
+            Ok(resp) => {
+                let status = resp.status();
+                let body = resp.text().await.unwrap_or_default();
+                tracing::warn!(status = %status, body = %body, "Loops send failed for notification digest");
+            }
+            Err(err) => {
+                tracing::error!(error = ?err, "Loops request error for notification digest");
+            }
+        }
+    }
 }
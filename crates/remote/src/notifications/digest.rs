@@ -0,0 +1,274 @@
+//! Daily and weekly emails summarising a user's unseen notifications.
+
+use std::{sync::Arc, time::Duration};
+
+use api_types::{DigestFrequency, Notification, NotificationType};
+use chrono::{Duration as ChronoDuration, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::{
+    db::{
+        notification_digest_settings::{NotificationDigestError, NotificationDigestRepository},
+        notifications::{NotificationError, NotificationRepository},
+    },
+    mail::{DigestItem, Mailer, NotificationDigest},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+const DUE_BATCH_SIZE: i64 = 100;
+/// Longer digests end with a count of what was left out.
+const MAX_DIGEST_ITEMS: usize = 50;
+
+#[derive(Debug, Error)]
+pub enum DigestError {
+    #[error(transparent)]
+    Settings(#[from] NotificationDigestError),
+    #[error(transparent)]
+    Notification(#[from] NotificationError),
+}
+
+/// Start the background worker that emails digests as they come due.
+pub fn spawn_worker(pool: PgPool, mailer: Arc<dyn Mailer>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(error) = send_due(&pool, mailer.as_ref()).await {
+                tracing::error!(?error, "failed to send notification digests");
+            }
+        }
+    })
+}
+
+async fn send_due(pool: &PgPool, mailer: &dyn Mailer) -> Result<(), DigestError> {
+    let now = Utc::now();
+
+    // Mark every claimed digest as sent before mailing, so a crash skips a
+    // digest rather than sending it twice.
+    let mut tx = pool.begin().await.map_err(NotificationDigestError::from)?;
+    let due = NotificationDigestRepository::lock_due(&mut tx, DUE_BATCH_SIZE).await?;
+    for user in &due {
+        NotificationDigestRepository::mark_sent(&mut tx, user.user_id, now).await?;
+    }
+    tx.commit().await.map_err(NotificationDigestError::from)?;
+
+    for user in due {
+        let after = user
+            .last_sent_at
+            .unwrap_or_else(|| now - period(user.frequency));
+        let notifications =
+            NotificationRepository::list_unseen_between(pool, user.user_id, after, now).await?;
+        deliver(mailer, &user.email, user.frequency, &notifications).await;
+    }
+
+    Ok(())
+}
+
+fn period(frequency: DigestFrequency) -> ChronoDuration {
+    match frequency {
+        DigestFrequency::Weekly => ChronoDuration::weeks(1),
+        DigestFrequency::Daily | DigestFrequency::Off => ChronoDuration::days(1),
+    }
+}
+
+/// Email `notifications` as one digest. Nothing is sent when there is
+/// nothing to report.
+async fn deliver(
+    mailer: &dyn Mailer,
+    email: &str,
+    frequency: DigestFrequency,
+    notifications: &[Notification],
+) -> Option<NotificationDigest> {
+    let digest = build_digest(frequency, notifications)?;
+    mailer.send_notification_digest(email, &digest).await;
+    Some(digest)
+}
+
+fn build_digest(
+    frequency: DigestFrequency,
+    notifications: &[Notification],
+) -> Option<NotificationDigest> {
+    if frequency == DigestFrequency::Off || notifications.is_empty() {
+        return None;
+    }
+
+    let mut items: Vec<DigestItem> = notifications
+        .iter()
+        .take(MAX_DIGEST_ITEMS)
+        .map(|notification| DigestItem {
+            issue: issue_label(&notification.payload),
+            summary: summarize(notification),
+        })
+        .collect();
+    if notifications.len() > MAX_DIGEST_ITEMS {
+        items.push(DigestItem {
+            issue: None,
+            summary: format!(
+                "…and {} more notifications",
+                notifications.len() - MAX_DIGEST_ITEMS
+            ),
+        });
+    }
+
+    Some(NotificationDigest { frequency, items })
+}
+
+fn issue_label(payload: &Value) -> Option<String> {
+    let simple_id = payload.get("issue_simple_id").and_then(Value::as_str)?;
+    Some(match payload.get("issue_title").and_then(Value::as_str) {
+        Some(title) => format!("{simple_id} {title}"),
+        None => simple_id.to_string(),
+    })
+}
+
+fn summarize(notification: &Notification) -> String {
+    let payload = &notification.payload;
+    let pr_number = payload
+        .get("pull_request_number")
+        .and_then(Value::as_i64)
+        .map(|number| format!(" #{number}"))
+        .unwrap_or_default();
+
+    match notification.notification_type {
+        NotificationType::IssueCommentAdded => "New comment".to_string(),
+        NotificationType::IssueStatusChanged => "Status changed".to_string(),
+        NotificationType::IssueAssigneeChanged => "Assignees changed".to_string(),
+        NotificationType::IssueDeleted => "Issue deleted".to_string(),
+        NotificationType::IssueMentioned => "You were mentioned in a comment".to_string(),
+        NotificationType::IssueWorkspaceFinished => {
+            if payload.get("success").and_then(Value::as_bool) == Some(false) {
+                "Workspace run failed".to_string()
+            } else {
+                "Workspace run finished".to_string()
+            }
+        }
+        NotificationType::IssuePullRequestOpened => format!("Pull request{pr_number} opened"),
+        NotificationType::IssuePullRequestMerged => format!("Pull request{pr_number} merged"),
+        NotificationType::IssueBlocked => {
+            match payload.get("blocker_simple_id").and_then(Value::as_str) {
+                Some(blocker) => format!("Blocked by {blocker}"),
+                None => "Blocked".to_string(),
+            }
+        }
+        NotificationType::IssueUnblocked => "No longer blocked".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use api_types::MemberRole;
+    use async_trait::async_trait;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeMailer {
+        digests: Mutex<Vec<(String, NotificationDigest)>>,
+    }
+
+    #[async_trait]
+    impl Mailer for FakeMailer {
+        async fn send_org_invitation(
+            &self,
+            _org_name: &str,
+            _email: &str,
+            _accept_url: &str,
+            _role: MemberRole,
+            _invited_by: Option<&str>,
+        ) {
+        }
+
+        async fn send_review_ready(&self, _email: &str, _review_url: &str, _pr_name: &str) {}
+
+        async fn send_review_failed(&self, _email: &str, _pr_name: &str, _review_id: &str) {}
+
+        async fn send_notification_digest(&self, email: &str, digest: &NotificationDigest) {
+            self.digests
+                .lock()
+                .unwrap()
+                .push((email.to_string(), digest.clone()));
+        }
+    }
+
+    fn notification(notification_type: NotificationType, payload: Value) -> Notification {
+        Notification {
+            id: Uuid::new_v4(),
+            organization_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            notification_type,
+            payload,
+            issue_id: None,
+            comment_id: None,
+            seen: false,
+            dismissed_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_one_digest_per_user() {
+        let mailer = FakeMailer::default();
+        let notifications = vec![
+            notification(
+                NotificationType::IssueMentioned,
+                json!({ "issue_simple_id": "VK-7", "issue_title": "Fix login" }),
+            ),
+            notification(
+                NotificationType::IssuePullRequestMerged,
+                json!({ "issue_simple_id": "VK-9", "pull_request_number": 42 }),
+            ),
+            notification(
+                NotificationType::IssueBlocked,
+                json!({ "issue_simple_id": "VK-9", "blocker_simple_id": "VK-3" }),
+            ),
+        ];
+
+        deliver(
+            &mailer,
+            "dev@example.com",
+            DigestFrequency::Daily,
+            &notifications,
+        )
+        .await;
+
+        let sent = mailer.digests.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "dev@example.com");
+        assert_eq!(
+            sent[0].1.to_text(),
+            "VK-7 Fix login: You were mentioned in a comment\n\
+             VK-9: Pull request #42 merged\n\
+             VK-9: Blocked by VK-3"
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_empty_digests() {
+        let mailer = FakeMailer::default();
+        assert!(
+            deliver(&mailer, "dev@example.com", DigestFrequency::Weekly, &[])
+                .await
+                .is_none()
+        );
+        assert!(mailer.digests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn truncates_long_digests() {
+        let notifications: Vec<_> = (0..MAX_DIGEST_ITEMS + 3)
+            .map(|_| notification(NotificationType::IssueCommentAdded, json!({})))
+            .collect();
+        let digest = build_digest(DigestFrequency::Weekly, &notifications).unwrap();
+        assert_eq!(digest.items.len(), MAX_DIGEST_ITEMS + 1);
+        assert_eq!(
+            digest.items.last().unwrap().summary,
+            "…and 3 more notifications"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! `@handle` mentions in comment markdown.
+
+/// Lowercased, de-duplicated handles mentioned in `message`, in order of
+/// first appearance. Email addresses and code spans are not mentions.
+pub(crate) fn parse_mentions(message: &str) -> Vec<String> {
+    let mut handles: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    for line in message.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut in_code = false;
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c == '`' {
+                in_code = !in_code;
+            } else if c == '@'
+                && !in_code
+                && (i == 0 || !(chars[i - 1].is_alphanumeric() || chars[i - 1] == '@'))
+            {
+                let start = i + 1;
+                let mut end = start;
+                while end < chars.len() && is_handle_char(chars[end]) {
+                    end += 1;
+                }
+                let handle: String = chars[start..end].iter().collect();
+                // Sentence punctuation after a mention isn't part of it.
+                let handle = handle.trim_end_matches(['.', '-']).to_lowercase();
+                if !handle.is_empty() && !handles.contains(&handle) {
+                    handles.push(handle);
+                }
+                i = end;
+                continue;
+            }
+            i += 1;
+        }
+    }
+
+    handles
+}
+
+fn is_handle_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_mentions_once_in_order() {
+        assert_eq!(
+            parse_mentions("@Alice can you pair with @bob.smith? cc @alice."),
+            vec!["alice", "bob.smith"]
+        );
+    }
+
+    #[test]
+    fn ignores_emails_and_code() {
+        let message = "mail ops@example.com\n`@not_me` and\n```\n@nor_me\n```\n(@carol)";
+        assert_eq!(parse_mentions(message), vec!["carol"]);
+    }
+}
@@ -0,0 +1,334 @@
+//! Creating notifications for issue events, and emailing digests of them.
+//!
+//! Notifications are a side effect of the request that caused them: the
+//! functions here log failures instead of returning them, so a missed
+//! notification never fails a comment, pull request or issue update.
+
+pub mod digest;
+mod mentions;
+
+use api_types::{
+    Issue, IssueComment, IssueRelationship, IssueRelationshipType, NotificationType, PullRequest,
+    PullRequestStatus, Workspace,
+};
+use serde_json::{Map, Value, json};
+use sqlx::PgPool;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::db::{
+    issue_relationships::{IssueRelationshipError, IssueRelationshipRepository},
+    issues::{IssueError, IssueRepository},
+    notifications::{NotificationError, NotificationRepository},
+};
+
+#[derive(Debug, Error)]
+enum NotifyError {
+    #[error(transparent)]
+    Notification(#[from] NotificationError),
+    #[error(transparent)]
+    Issue(#[from] IssueError),
+    #[error(transparent)]
+    IssueRelationship(#[from] IssueRelationshipError),
+}
+
+/// Who hears about an event on an issue.
+#[derive(Debug, Clone, Copy)]
+enum Audience {
+    /// Assignees and followers.
+    Watchers,
+    Followers,
+}
+
+/// Notify the audience of `issue_id`, leaving out whoever caused the event.
+/// `details` is merged into the payload next to the issue's simple ID and
+/// title.
+async fn notify_issue(
+    pool: &PgPool,
+    issue_id: Uuid,
+    actor_id: Option<Uuid>,
+    audience: Audience,
+    notification_type: NotificationType,
+    details: Value,
+) -> Result<(), NotifyError> {
+    let Some(issue) = NotificationRepository::find_issue_context(pool, issue_id).await? else {
+        return Ok(());
+    };
+
+    let mut user_ids = match audience {
+        Audience::Watchers => NotificationRepository::list_issue_watchers(pool, issue_id).await?,
+        Audience::Followers => NotificationRepository::list_issue_followers(pool, issue_id).await?,
+    };
+    user_ids.retain(|user_id| Some(*user_id) != actor_id);
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut payload = Map::new();
+    payload.insert("issue_simple_id".into(), issue.simple_id.into());
+    payload.insert("issue_title".into(), issue.title.into());
+    payload.insert("actor_user_id".into(), json!(actor_id));
+    if let Value::Object(details) = details {
+        payload.extend(details);
+    }
+
+    NotificationRepository::create_for_users(
+        pool,
+        issue.organization_id,
+        &user_ids,
+        notification_type,
+        Value::Object(payload),
+        Some(issue_id),
+        None,
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Notify organization members mentioned as `@handle` in a new comment.
+/// Handles match usernames and the local part of email addresses.
+pub(crate) async fn comment_created(pool: &PgPool, comment: &IssueComment) {
+    if let Err(error) = notify_mentions(pool, comment).await {
+        tracing::warn!(?error, comment_id = %comment.id, "failed to notify mentioned users");
+    }
+}
+
+async fn notify_mentions(pool: &PgPool, comment: &IssueComment) -> Result<(), NotifyError> {
+    let handles = mentions::parse_mentions(&comment.message);
+    if handles.is_empty() {
+        return Ok(());
+    }
+    let Some(issue) = NotificationRepository::find_issue_context(pool, comment.issue_id).await?
+    else {
+        return Ok(());
+    };
+
+    let mut user_ids =
+        NotificationRepository::find_members_by_handles(pool, issue.organization_id, &handles)
+            .await?;
+    user_ids.retain(|user_id| Some(*user_id) != comment.author_id);
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+
+    let excerpt: String = comment.message.chars().take(140).collect();
+    NotificationRepository::create_for_users(
+        pool,
+        issue.organization_id,
+        &user_ids,
+        NotificationType::IssueMentioned,
+        json!({
+            "issue_simple_id": issue.simple_id,
+            "issue_title": issue.title,
+            "actor_user_id": comment.author_id,
+            "excerpt": excerpt,
+        }),
+        Some(comment.issue_id),
+        Some(comment.id),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Notify issue watchers that a pull request was opened or merged.
+/// `previous` is the pull request's status before this change, if it
+/// already existed.
+pub(crate) async fn pull_request_changed(
+    pool: &PgPool,
+    previous: Option<PullRequestStatus>,
+    pr: &PullRequest,
+    actor_id: Uuid,
+) {
+    let notification_type = match (previous, pr.status) {
+        (None, PullRequestStatus::Open) => NotificationType::IssuePullRequestOpened,
+        (previous, PullRequestStatus::Merged) if previous != Some(PullRequestStatus::Merged) => {
+            NotificationType::IssuePullRequestMerged
+        }
+        _ => return,
+    };
+
+    let details = json!({
+        "pull_request_url": pr.url,
+        "pull_request_number": pr.number,
+    });
+    if let Err(error) = notify_issue(
+        pool,
+        pr.issue_id,
+        Some(actor_id),
+        Audience::Watchers,
+        notification_type,
+        details,
+    )
+    .await
+    {
+        tracing::warn!(?error, pull_request_id = %pr.id, "failed to notify pull request change");
+    }
+}
+
+/// Notify issue watchers that a coding agent run in a linked workspace
+/// ended.
+pub(crate) async fn workspace_finished(
+    pool: &PgPool,
+    workspace: &Workspace,
+    success: bool,
+    actor_id: Uuid,
+) {
+    let Some(issue_id) = workspace.issue_id else {
+        return;
+    };
+
+    let details = json!({
+        "workspace_id": workspace.id,
+        "workspace_name": workspace.name,
+        "success": success,
+    });
+    if let Err(error) = notify_issue(
+        pool,
+        issue_id,
+        Some(actor_id),
+        Audience::Watchers,
+        NotificationType::IssueWorkspaceFinished,
+        details,
+    )
+    .await
+    {
+        tracing::warn!(?error, workspace_id = %workspace.id, "failed to notify workspace finished");
+    }
+}
+
+/// Notify followers of the blocked issue when a new blocking relationship
+/// makes it blocked.
+pub(crate) async fn blocking_added(
+    pool: &PgPool,
+    relationship: &IssueRelationship,
+    actor_id: Uuid,
+) {
+    if relationship.relationship_type != IssueRelationshipType::Blocking {
+        return;
+    }
+    if let Err(error) = notify_blocking_added(pool, relationship, actor_id).await {
+        tracing::warn!(?error, relationship_id = %relationship.id, "failed to notify blocked issue");
+    }
+}
+
+async fn notify_blocking_added(
+    pool: &PgPool,
+    relationship: &IssueRelationship,
+    actor_id: Uuid,
+) -> Result<(), NotifyError> {
+    let blocker_open = IssueRepository::find_by_id(pool, relationship.issue_id)
+        .await?
+        .is_some_and(|blocker| blocker.completed_at.is_none());
+    // Already blocked by something else: nothing changes for followers.
+    if !blocker_open
+        || IssueRelationshipRepository::count_open_blockers(pool, relationship.related_issue_id)
+            .await?
+            != 1
+    {
+        return Ok(());
+    }
+
+    notify_blocked_state(
+        pool,
+        relationship.related_issue_id,
+        relationship.issue_id,
+        true,
+        actor_id,
+    )
+    .await
+}
+
+/// Notify followers of the formerly blocked issue when removing a blocking
+/// relationship leaves it with no unfinished blockers.
+pub(crate) async fn blocking_removed(
+    pool: &PgPool,
+    relationship: &IssueRelationship,
+    actor_id: Uuid,
+) {
+    if relationship.relationship_type != IssueRelationshipType::Blocking {
+        return;
+    }
+    if let Err(error) = notify_blocking_removed(pool, relationship, actor_id).await {
+        tracing::warn!(?error, relationship_id = %relationship.id, "failed to notify unblocked issue");
+    }
+}
+
+async fn notify_blocking_removed(
+    pool: &PgPool,
+    relationship: &IssueRelationship,
+    actor_id: Uuid,
+) -> Result<(), NotifyError> {
+    let blocker_open = IssueRepository::find_by_id(pool, relationship.issue_id)
+        .await?
+        .is_some_and(|blocker| blocker.completed_at.is_none());
+    if !blocker_open
+        || IssueRelationshipRepository::count_open_blockers(pool, relationship.related_issue_id)
+            .await?
+            != 0
+    {
+        return Ok(());
+    }
+
+    notify_blocked_state(
+        pool,
+        relationship.related_issue_id,
+        relationship.issue_id,
+        false,
+        actor_id,
+    )
+    .await
+}
+
+/// Notify followers of issues this one blocks when completing or reopening
+/// it unblocks or blocks them.
+pub(crate) async fn completion_changed(pool: &PgPool, issue: &Issue, actor_id: Uuid) {
+    if let Err(error) = notify_completion_changed(pool, issue, actor_id).await {
+        tracing::warn!(?error, issue_id = %issue.id, "failed to notify dependent issues");
+    }
+}
+
+async fn notify_completion_changed(
+    pool: &PgPool,
+    issue: &Issue,
+    actor_id: Uuid,
+) -> Result<(), NotifyError> {
+    let blocked = issue.completed_at.is_none();
+    let dependents =
+        IssueRelationshipRepository::list_dependents_blocked_only_by(pool, issue.id).await?;
+    for dependent_id in dependents {
+        notify_blocked_state(pool, dependent_id, issue.id, blocked, actor_id).await?;
+    }
+    Ok(())
+}
+
+async fn notify_blocked_state(
+    pool: &PgPool,
+    issue_id: Uuid,
+    blocker_id: Uuid,
+    blocked: bool,
+    actor_id: Uuid,
+) -> Result<(), NotifyError> {
+    let blocker_simple_id = NotificationRepository::find_issue_context(pool, blocker_id)
+        .await?
+        .map(|blocker| blocker.simple_id);
+    let notification_type = if blocked {
+        NotificationType::IssueBlocked
+    } else {
+        NotificationType::IssueUnblocked
+    };
+
+    notify_issue(
+        pool,
+        issue_id,
+        Some(actor_id),
+        Audience::Followers,
+        notification_type,
+        json!({
+            "blocker_issue_id": blocker_id,
+            "blocker_simple_id": blocker_simple_id,
+        }),
+    )
+    .await
+}
@@ -0,0 +1,366 @@
+//! Issue templates: placeholder rendering, cron-like recurrence rules and the
+//! background worker that files recurring issues when they come due.
+
+use std::{str::FromStr, time::Duration};
+
+use api_types::{AUTO_START_METADATA_KEY, Issue, IssueTemplate};
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, NaiveDate, Timelike, Utc};
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::db::{
+    issue_assignees::{IssueAssigneeError, IssueAssigneeRepository},
+    issue_tags::{IssueTagError, IssueTagRepository},
+    issue_templates::{IssueTemplateError, IssueTemplateRepository},
+    issues::{IssueError, IssueRepository},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+const DUE_BATCH_SIZE: i64 = 50;
+/// Give up looking for the next occurrence of rules that can never match,
+/// e.g. `0 0 31 2 *`.
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 5;
+
+#[derive(Debug, Error)]
+pub enum RecurrenceError {
+    #[error("invalid recurrence rule: {0}")]
+    InvalidRule(String),
+    #[error(transparent)]
+    Template(#[from] IssueTemplateError),
+    #[error(transparent)]
+    Issue(#[from] IssueError),
+    #[error(transparent)]
+    Tag(#[from] IssueTagError),
+    #[error(transparent)]
+    Assignee(#[from] IssueAssigneeError),
+}
+
+/// A five-field cron expression (`minute hour day-of-month month
+/// day-of-week`) evaluated in UTC. Fields accept `*`, values, ranges
+/// (`1-5`), lists (`1,15`) and steps (`*/2`, `0-30/10`); day-of-week is
+/// 0-7 with both 0 and 7 meaning Sunday. The shortcuts `@hourly`, `@daily`,
+/// `@weekly`, `@monthly` and `@yearly` are also accepted. As in cron, when
+/// both day fields are restricted a day matches if either does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl FromStr for CronSchedule {
+    type Err = RecurrenceError;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let expanded = match rule.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            return Err(RecurrenceError::InvalidRule(format!(
+                "expected 5 fields, got {}",
+                fields.len()
+            )));
+        };
+
+        let mut days_of_week = parse_field(dow, 0, 7)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59)?,
+            hours: parse_field(hour, 0, 23)?,
+            days_of_month: parse_field(dom, 1, 31)?,
+            months: parse_field(month, 1, 12)?,
+            days_of_week,
+            any_day_of_month: dom == "*",
+            any_day_of_week: dow == "*",
+        })
+    }
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, RecurrenceError> {
+    let invalid = || RecurrenceError::InvalidRule(format!("invalid field '{field}'"));
+    let parse = |value: &str| -> Result<u32, RecurrenceError> {
+        value
+            .parse::<u32>()
+            .ok()
+            .filter(|v| (min..=max).contains(v))
+            .ok_or_else(invalid)
+    };
+
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (parse(start)?, parse(end)?),
+                // `5/15` means every 15 starting at 5.
+                None if part.contains('/') => (parse(range)?, max),
+                None => {
+                    let value = parse(range)?;
+                    (value, value)
+                }
+            },
+        };
+        if start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+impl CronSchedule {
+    /// The first matching minute strictly after `after`.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let limit = after + ChronoDuration::days(MAX_LOOKAHEAD_DAYS);
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + ChronoDuration::minutes(1);
+
+        while t <= limit {
+            if self.months & (1 << t.month()) == 0 {
+                let (year, month) = if t.month() == 12 {
+                    (t.year() + 1, 1)
+                } else {
+                    (t.year(), t.month() + 1)
+                };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+                continue;
+            }
+            if !self.day_matches(t.date_naive()) {
+                t = (t.date_naive() + ChronoDuration::days(1))
+                    .and_hms_opt(0, 0, 0)?
+                    .and_utc();
+                continue;
+            }
+            if self.hours & (1 << t.hour()) == 0 {
+                t = t.with_minute(0)? + ChronoDuration::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << t.minute()) == 0 {
+                t += ChronoDuration::minutes(1);
+                continue;
+            }
+            return Some(t);
+        }
+        None
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let dom = self.days_of_month & (1 << date.day()) != 0;
+        let dow = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day_of_month, self.any_day_of_week) {
+            (true, true) => true,
+            (true, false) => dow,
+            (false, true) => dom,
+            (false, false) => dom || dow,
+        }
+    }
+}
+
+/// Validate `rule` and compute its first run after `now`.
+pub fn first_run(
+    rule: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<Option<DateTime<Utc>>, RecurrenceError> {
+    let Some(rule) = rule else {
+        return Ok(None);
+    };
+    let schedule: CronSchedule = rule.parse()?;
+    schedule
+        .next_after(now)
+        .map(Some)
+        .ok_or_else(|| RecurrenceError::InvalidRule(format!("'{rule}' never matches")))
+}
+
+/// Fill in the `{{date}}`, `{{week}}`, `{{month}}` and `{{year}}`
+/// placeholders for an issue filed at `at`.
+pub fn render_placeholders(text: &str, at: DateTime<Utc>) -> String {
+    let week = at.iso_week();
+    text.replace("{{date}}", &at.format("%Y-%m-%d").to_string())
+        .replace("{{week}}", &format!("{}-W{:02}", week.year(), week.week()))
+        .replace("{{month}}", &at.format("%B %Y").to_string())
+        .replace("{{year}}", &at.format("%Y").to_string())
+}
+
+/// Create an issue from `template` as of `at`, with the template's tags and
+/// assignees. Auto-start templates mark the issue for the local scheduler
+/// through its extension metadata.
+pub async fn instantiate_template(
+    pool: &PgPool,
+    template: &IssueTemplate,
+    at: DateTime<Utc>,
+    creator_user_id: Option<uuid::Uuid>,
+) -> Result<Issue, RecurrenceError> {
+    let mut extension_metadata = serde_json::json!({});
+    if let Some(profile) = &template.auto_start_executor_profile {
+        extension_metadata[AUTO_START_METADATA_KEY] = serde_json::json!(profile.0);
+    }
+
+    let issue = IssueRepository::create(
+        pool,
+        None,
+        template.project_id,
+        template.status_id,
+        render_placeholders(&template.title, at),
+        template
+            .description
+            .as_deref()
+            .map(|description| render_placeholders(description, at)),
+        template.priority,
+        None,
+        None,
+        None,
+        None,
+        None,
+        0.0,
+        None,
+        None,
+        extension_metadata,
+        creator_user_id,
+    )
+    .await?
+    .data;
+
+    for &tag_id in &template.tag_ids {
+        IssueTagRepository::create(pool, None, issue.id, tag_id).await?;
+    }
+    for &user_id in &template.assignee_ids {
+        IssueAssigneeRepository::create(pool, None, issue.id, user_id).await?;
+    }
+
+    Ok(issue)
+}
+
+/// Poll for due recurring templates and file their issues.
+pub fn spawn_worker(pool: PgPool) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(error) = run_due(&pool).await {
+                tracing::error!(?error, "failed to run recurring issue templates");
+            }
+        }
+    })
+}
+
+async fn run_due(pool: &PgPool) -> Result<(), RecurrenceError> {
+    let now = Utc::now();
+
+    // Advance every claimed template before creating any issue, so a crash
+    // skips a run rather than filing it twice. Missed runs (e.g. while the
+    // server was down) collapse into one issue.
+    let mut tx = pool.begin().await.map_err(IssueTemplateError::from)?;
+    let due = IssueTemplateRepository::lock_due(&mut tx, DUE_BATCH_SIZE).await?;
+    for template in &due {
+        let next = template
+            .recurrence
+            .as_deref()
+            .and_then(|rule| rule.parse::<CronSchedule>().ok())
+            .and_then(|schedule| schedule.next_after(now));
+        IssueTemplateRepository::set_next_run_at(&mut tx, template.id, next).await?;
+    }
+    tx.commit().await.map_err(IssueTemplateError::from)?;
+
+    for template in &due {
+        let at = template.next_run_at.unwrap_or(now);
+        match instantiate_template(pool, template, at, template.creator_user_id).await {
+            Ok(issue) => tracing::info!(
+                template_id = %template.id,
+                issue_id = %issue.id,
+                "created recurring issue"
+            ),
+            Err(error) => tracing::error!(
+                ?error,
+                template_id = %template.id,
+                "failed to create recurring issue"
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn weekly_rule_finds_next_monday() {
+        // 2026-02-11 is a Wednesday.
+        let schedule: CronSchedule = "0 9 * * 1".parse().unwrap();
+        assert_eq!(
+            schedule.next_after(at(2026, 2, 11, 12, 30)),
+            Some(at(2026, 2, 16, 9, 0))
+        );
+        // Strictly after: a run exactly on the boundary moves to next week.
+        assert_eq!(
+            schedule.next_after(at(2026, 2, 16, 9, 0)),
+            Some(at(2026, 2, 23, 9, 0))
+        );
+    }
+
+    #[test]
+    fn steps_ranges_and_shortcuts() {
+        let schedule: CronSchedule = "*/15 8-10 * * *".parse().unwrap();
+        assert_eq!(
+            schedule.next_after(at(2026, 2, 11, 10, 50)),
+            Some(at(2026, 2, 12, 8, 0))
+        );
+        let monthly: CronSchedule = "@monthly".parse().unwrap();
+        assert_eq!(
+            monthly.next_after(at(2026, 12, 15, 0, 0)),
+            Some(at(2027, 1, 1, 0, 0))
+        );
+        let sunday: CronSchedule = "0 0 * * 7".parse().unwrap();
+        assert_eq!(
+            sunday.next_after(at(2026, 2, 11, 0, 0)),
+            Some(at(2026, 2, 15, 0, 0))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        assert!("0 9 * *".parse::<CronSchedule>().is_err());
+        assert!("60 * * * *".parse::<CronSchedule>().is_err());
+        assert!("*/0 * * * *".parse::<CronSchedule>().is_err());
+        assert!(first_run(Some("0 0 31 2 *"), at(2026, 1, 1, 0, 0)).is_err());
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let title = render_placeholders("Changelog {{week}} ({{date}})", at(2026, 2, 11, 9, 0));
+        assert_eq!(title, "Changelog 2026-W07 (2026-02-11)");
+        assert_eq!(
+            render_placeholders("{{month}}", at(2026, 2, 11, 9, 0)),
+            "February 2026"
+        );
+    }
+}
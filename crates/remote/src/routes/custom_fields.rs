@@ -0,0 +1,195 @@
+use api_types::{
+    CreateCustomFieldRequest, CustomField, DeleteResponse, ListCustomFieldsQuery,
+    ListCustomFieldsResponse, MutationResponse, UpdateCustomFieldRequest,
+};
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{
+    error::{ErrorResponse, db_error},
+    organization_members::{ensure_admin_access, ensure_member_access},
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    custom_fields::{is_valid_key, validate_options},
+    db::custom_fields::CustomFieldRepository,
+    mutation_definition::MutationBuilder,
+};
+
+/// Mutation definition for CustomField - provides both router and TypeScript metadata.
+pub fn mutation() -> MutationBuilder<CustomField, CreateCustomFieldRequest, UpdateCustomFieldRequest>
+{
+    MutationBuilder::new("custom_fields")
+        .list(list_custom_fields)
+        .get(get_custom_field)
+        .create(create_custom_field)
+        .update(update_custom_field)
+        .delete(delete_custom_field)
+}
+
+pub fn router() -> axum::Router<AppState> {
+    mutation().router()
+}
+
+#[instrument(
+    name = "custom_fields.list_custom_fields",
+    skip(state, ctx),
+    fields(organization_id = %query.organization_id, user_id = %ctx.user.id)
+)]
+async fn list_custom_fields(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<ListCustomFieldsQuery>,
+) -> Result<Json<ListCustomFieldsResponse>, ErrorResponse> {
+    ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
+
+    let custom_fields =
+        CustomFieldRepository::list_by_organization(state.pool(), query.organization_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, organization_id = %query.organization_id, "failed to list custom fields");
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to list custom fields",
+                )
+            })?;
+
+    Ok(Json(ListCustomFieldsResponse { custom_fields }))
+}
+
+#[instrument(
+    name = "custom_fields.get_custom_field",
+    skip(state, ctx),
+    fields(custom_field_id = %custom_field_id, user_id = %ctx.user.id)
+)]
+async fn get_custom_field(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(custom_field_id): Path<Uuid>,
+) -> Result<Json<CustomField>, ErrorResponse> {
+    let field = load_custom_field(&state, custom_field_id).await?;
+
+    ensure_member_access(state.pool(), field.organization_id, ctx.user.id).await?;
+
+    Ok(Json(field))
+}
+
+#[instrument(
+    name = "custom_fields.create_custom_field",
+    skip(state, ctx, payload),
+    fields(organization_id = %payload.organization_id, user_id = %ctx.user.id)
+)]
+async fn create_custom_field(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<CreateCustomFieldRequest>,
+) -> Result<Json<MutationResponse<CustomField>>, ErrorResponse> {
+    ensure_admin_access(state.pool(), payload.organization_id, ctx.user.id).await?;
+
+    if !is_valid_key(&payload.key) {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "key must start with a lowercase letter and contain only lowercase letters, digits and underscores",
+        ));
+    }
+    validate_options(payload.field_type, &payload.options)
+        .map_err(|message| ErrorResponse::new(StatusCode::BAD_REQUEST, message))?;
+
+    let response = CustomFieldRepository::create(
+        state.pool(),
+        payload.id,
+        payload.organization_id,
+        payload.key,
+        payload.name,
+        payload.field_type,
+        payload.options,
+        payload.sort_order,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to create custom field");
+        db_error(error, "failed to create custom field")
+    })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "custom_fields.update_custom_field",
+    skip(state, ctx, payload),
+    fields(custom_field_id = %custom_field_id, user_id = %ctx.user.id)
+)]
+async fn update_custom_field(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(custom_field_id): Path<Uuid>,
+    Json(payload): Json<UpdateCustomFieldRequest>,
+) -> Result<Json<MutationResponse<CustomField>>, ErrorResponse> {
+    let field = load_custom_field(&state, custom_field_id).await?;
+
+    ensure_admin_access(state.pool(), field.organization_id, ctx.user.id).await?;
+
+    // Removed options stay on existing issues until they are next edited.
+    if let Some(options) = &payload.options {
+        validate_options(field.field_type, options)
+            .map_err(|message| ErrorResponse::new(StatusCode::BAD_REQUEST, message))?;
+    }
+
+    let response = CustomFieldRepository::update(
+        state.pool(),
+        custom_field_id,
+        payload.name,
+        payload.options,
+        payload.sort_order,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to update custom field");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+    })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "custom_fields.delete_custom_field",
+    skip(state, ctx),
+    fields(custom_field_id = %custom_field_id, user_id = %ctx.user.id)
+)]
+async fn delete_custom_field(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(custom_field_id): Path<Uuid>,
+) -> Result<Json<DeleteResponse>, ErrorResponse> {
+    let field = load_custom_field(&state, custom_field_id).await?;
+
+    ensure_admin_access(state.pool(), field.organization_id, ctx.user.id).await?;
+
+    let response = CustomFieldRepository::delete(state.pool(), custom_field_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to delete custom field");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(response))
+}
+
+async fn load_custom_field(state: &AppState, id: Uuid) -> Result<CustomField, ErrorResponse> {
+    CustomFieldRepository::find_by_id(state.pool(), id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %id, "failed to load custom field");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load custom field",
+            )
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "custom field not found"))
+}
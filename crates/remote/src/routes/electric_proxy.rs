@@ -41,6 +41,10 @@ pub fn router() -> Router<AppState> {
             get(proxy_organization_members),
         )
         .route(shapes::USERS_SHAPE.url, get(proxy_users))
+        .route(
+            shapes::ORGANIZATION_CUSTOM_FIELDS_SHAPE.url,
+            get(proxy_custom_fields),
+        )
         // Project-scoped
         .route(shapes::USER_WORKSPACES_SHAPE.url, get(proxy_workspaces))
         .route(
@@ -153,6 +157,24 @@ async fn proxy_users(
     .await
 }
 
+async fn proxy_custom_fields(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<OrgShapeQuery>,
+) -> Result<Response, ProxyError> {
+    organization_members::assert_membership(state.pool(), query.organization_id, ctx.user.id)
+        .await
+        .map_err(|e| ProxyError::Authorization(e.to_string()))?;
+
+    proxy_table(
+        &state,
+        &shapes::ORGANIZATION_CUSTOM_FIELDS_SHAPE,
+        &query.params,
+        &[query.organization_id.to_string()],
+    )
+    .await
+}
+
 async fn proxy_workspaces(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
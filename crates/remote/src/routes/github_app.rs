@@ -21,6 +21,7 @@ use crate::{
         organizations::OrganizationRepository, reviews::ReviewRepository,
     },
     github_app::{PrReviewParams, PrReviewService, verify_webhook_signature},
+    github_issue_sync,
 };
 
 // ========== Public Routes ==========
@@ -53,6 +54,10 @@ pub fn protected_router() -> Router<AppState> {
             "/organizations/{org_id}/github-app/repositories/{repo_id}/review-enabled",
             patch(update_repo_review_enabled),
         )
+        .route(
+            "/organizations/{org_id}/github-app/repositories/{repo_id}/review-inline-comments",
+            patch(update_repo_review_inline_comments),
+        )
         .route("/debug/pr-review/trigger", post(trigger_pr_review))
 }
 
@@ -88,6 +93,7 @@ pub struct RepositoryDetails {
     pub github_repo_id: i64,
     pub repo_full_name: String,
     pub review_enabled: bool,
+    pub review_inline_comments: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -236,6 +242,7 @@ pub async fn get_status(
                         github_repo_id: r.github_repo_id,
                         repo_full_name: r.repo_full_name,
                         review_enabled: r.review_enabled,
+                        review_inline_comments: r.review_inline_comments,
                     })
                     .collect(),
             }))
@@ -337,6 +344,66 @@ pub async fn update_repo_review_enabled(
         github_repo_id: updated.github_repo_id,
         repo_full_name: updated.repo_full_name,
         review_enabled: updated.review_enabled,
+        review_inline_comments: updated.review_inline_comments,
+    }))
+}
+
+/// PATCH /v1/organizations/:org_id/github-app/repositories/:repo_id/review-inline-comments
+/// Toggle whether review findings are posted to the PR as inline comments
+pub async fn update_repo_review_inline_comments(
+    State(state): State<AppState>,
+    axum::extract::Extension(ctx): axum::extract::Extension<RequestContext>,
+    Path((org_id, repo_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateRepoReviewEnabledRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    // Check user is admin of organization
+    let org_repo = OrganizationRepository::new(state.pool());
+    org_repo
+        .assert_admin(org_id, ctx.user.id)
+        .await
+        .map_err(|e| match e {
+            IdentityError::PermissionDenied => {
+                ErrorResponse::new(StatusCode::FORBIDDEN, "Admin access required")
+            }
+            IdentityError::NotFound => {
+                ErrorResponse::new(StatusCode::NOT_FOUND, "Organization not found")
+            }
+            _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
+        })?;
+
+    let gh_repo = GitHubAppRepository2::new(state.pool());
+    let installation = gh_repo
+        .get_by_organization(org_id)
+        .await
+        .map_err(|_| ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"))?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "GitHub App not installed"))?;
+
+    let updated = gh_repo
+        .update_repository_review_inline_comments(repo_id, installation.id, payload.enabled)
+        .await
+        .map_err(|e| {
+            error!(?e, "Failed to update repository review_inline_comments");
+            match e {
+                crate::db::github_app::GitHubAppDbError::NotFound => {
+                    ErrorResponse::new(StatusCode::NOT_FOUND, "Repository not found")
+                }
+                _ => ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error"),
+            }
+        })?;
+
+    info!(
+        org_id = %org_id,
+        repo_id = %repo_id,
+        review_inline_comments = payload.enabled,
+        "Repository review_inline_comments updated"
+    );
+
+    Ok(Json(RepositoryDetails {
+        id: updated.id.to_string(),
+        github_repo_id: updated.github_repo_id,
+        repo_full_name: updated.repo_full_name,
+        review_enabled: updated.review_enabled,
+        review_inline_comments: updated.review_inline_comments,
     }))
 }
 
@@ -406,6 +473,7 @@ pub async fn fetch_repositories(
                 github_repo_id: r.github_repo_id,
                 repo_full_name: r.repo_full_name,
                 review_enabled: r.review_enabled,
+                review_inline_comments: r.review_inline_comments,
             })
             .collect::<Vec<_>>(),
     ))
@@ -654,6 +722,7 @@ pub async fn handle_webhook(
         "installation" => handle_installation_event(&state, &payload).await,
         "installation_repositories" => handle_installation_repos_event(&state, &payload).await,
         "pull_request" => handle_pull_request_event(&state, github_app, &payload).await,
+        "issues" => handle_issues_event(&state, github_app, payload),
         "issue_comment" => handle_issue_comment_event(&state, github_app, &payload).await,
         _ => {
             info!(event_type, "Ignoring unhandled webhook event");
@@ -990,6 +1059,16 @@ async fn handle_pull_request_event(
     StatusCode::OK.into_response()
 }
 
+/// Issue changes are applied to any project syncing the repository.
+fn handle_issues_event(
+    state: &AppState,
+    github_app: &crate::github_app::GitHubAppService,
+    payload: serde_json::Value,
+) -> Response {
+    github_issue_sync::spawn_webhook_sync(state.pool.clone(), github_app.clone(), payload);
+    StatusCode::OK.into_response()
+}
+
 async fn handle_issue_comment_event(
     state: &AppState,
     github_app: &crate::github_app::GitHubAppService,
@@ -997,13 +1076,25 @@ async fn handle_issue_comment_event(
 ) -> Response {
     let action = payload["action"].as_str().unwrap_or("");
 
-    // Only handle new comments
+    // Only handle new comments, and edits to comments on synced issues
     if action != "created" {
+        if action == "edited" && payload["issue"]["pull_request"].is_null() {
+            github_issue_sync::spawn_webhook_sync(
+                state.pool.clone(),
+                github_app.clone(),
+                payload.clone(),
+            );
+        }
         return StatusCode::OK.into_response();
     }
 
-    // Check if comment is on a PR (issues don't have pull_request field)
+    // Comments on issues (which have no pull_request field) may need syncing
     if payload["issue"]["pull_request"].is_null() {
+        github_issue_sync::spawn_webhook_sync(
+            state.pool.clone(),
+            github_app.clone(),
+            payload.clone(),
+        );
         return StatusCode::OK.into_response();
     }
 
@@ -0,0 +1,257 @@
+use axum::{
+    Json, Router,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::{
+    error::ErrorResponse,
+    organization_members::{ensure_admin_access, ensure_project_access},
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{
+        github_app::GitHubAppRepository2,
+        github_issue_sync::{GitHubIssueSync, GitHubIssueSyncRepository},
+        project_statuses::ProjectStatusRepository,
+    },
+    github_issue_sync,
+};
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        "/projects/{project_id}/github-issue-sync",
+        get(get_sync).put(upsert_sync).delete(delete_sync),
+    )
+}
+
+// ========== Types ==========
+
+#[derive(Debug, Deserialize)]
+pub struct UpsertGitHubIssueSyncRequest {
+    pub github_repo_id: i64,
+    pub open_status_id: Uuid,
+    pub closed_status_id: Uuid,
+    #[serde(default)]
+    pub export_new_issues: bool,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitHubIssueSyncDetails {
+    pub id: String,
+    pub project_id: String,
+    pub github_repo_id: i64,
+    pub repo_full_name: String,
+    pub open_status_id: String,
+    pub closed_status_id: String,
+    pub export_new_issues: bool,
+    pub enabled: bool,
+    pub last_reconciled_at: Option<String>,
+    pub created_at: String,
+}
+
+impl From<GitHubIssueSync> for GitHubIssueSyncDetails {
+    fn from(sync: GitHubIssueSync) -> Self {
+        Self {
+            id: sync.id.to_string(),
+            project_id: sync.project_id.to_string(),
+            github_repo_id: sync.github_repo_id,
+            repo_full_name: sync.repo_full_name,
+            open_status_id: sync.open_status_id.to_string(),
+            closed_status_id: sync.closed_status_id.to_string(),
+            export_new_issues: sync.export_new_issues,
+            enabled: sync.enabled,
+            last_reconciled_at: sync.last_reconciled_at.map(|t| t.to_rfc3339()),
+            created_at: sync.created_at.to_rfc3339(),
+        }
+    }
+}
+
+// ========== Handlers ==========
+
+/// GET /v1/projects/:project_id/github-issue-sync
+/// Returns the project's GitHub Issues sync settings
+pub async fn get_sync(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+
+    let sync = GitHubIssueSyncRepository::find_by_project(state.pool(), project_id)
+        .await
+        .map_err(|e| {
+            error!(?e, "Failed to get GitHub issue sync");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "GitHub issue sync not set up"))?;
+
+    Ok(Json(GitHubIssueSyncDetails::from(sync)))
+}
+
+/// PUT /v1/projects/:project_id/github-issue-sync
+/// Sets up or changes two-way sync with a repository's GitHub Issues.
+/// Switching to another repository drops the existing issue links.
+pub async fn upsert_sync(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpsertGitHubIssueSyncRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let org_id = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let Some(github_app) = state.github_app() else {
+        return Err(ErrorResponse::new(
+            StatusCode::NOT_IMPLEMENTED,
+            "GitHub App not configured",
+        ));
+    };
+
+    for status_id in [payload.open_status_id, payload.closed_status_id] {
+        let status = ProjectStatusRepository::find_by_id(state.pool(), status_id)
+            .await
+            .map_err(|e| {
+                error!(?e, "Failed to get project status");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+            })?;
+        if status.is_none_or(|status| status.project_id != project_id) {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "Statuses must belong to the project",
+            ));
+        }
+    }
+
+    let installation = GitHubAppRepository2::new(state.pool())
+        .get_by_organization(org_id)
+        .await
+        .map_err(|e| {
+            error!(?e, "Failed to get GitHub App installation");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?
+        .ok_or_else(|| {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, "GitHub App is not installed")
+        })?;
+
+    // Ask GitHub rather than the cached list, which is empty for "all
+    // repositories" installations
+    let repos = github_app
+        .list_installation_repos(installation.github_installation_id)
+        .await
+        .map_err(|e| {
+            error!(?e, "Failed to fetch repositories from GitHub");
+            ErrorResponse::new(StatusCode::BAD_GATEWAY, "Failed to fetch repositories")
+        })?;
+    let repo = repos
+        .into_iter()
+        .find(|repo| repo.id == payload.github_repo_id)
+        .ok_or_else(|| {
+            ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "Repository is not accessible to the GitHub App",
+            )
+        })?;
+
+    let db_error = |e| {
+        error!(?e, "Failed to save GitHub issue sync");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+    };
+    let existing = GitHubIssueSyncRepository::find_by_project(state.pool(), project_id)
+        .await
+        .map_err(db_error)?;
+    let sync = match existing {
+        Some(existing)
+            if existing.installation_id == installation.id
+                && existing.github_repo_id == repo.id =>
+        {
+            GitHubIssueSyncRepository::update_settings(
+                state.pool(),
+                existing.id,
+                payload.open_status_id,
+                payload.closed_status_id,
+                payload.export_new_issues,
+                payload.enabled,
+            )
+            .await
+            .map_err(db_error)?;
+            GitHubIssueSyncRepository::find_by_project(state.pool(), project_id)
+                .await
+                .map_err(db_error)?
+                .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "Sync not found"))?
+        }
+        existing => {
+            if existing.is_some() {
+                GitHubIssueSyncRepository::delete_by_project(state.pool(), project_id)
+                    .await
+                    .map_err(db_error)?;
+            }
+            GitHubIssueSyncRepository::create(
+                state.pool(),
+                project_id,
+                installation.id,
+                repo.id,
+                &repo.full_name,
+                payload.open_status_id,
+                payload.closed_status_id,
+                payload.export_new_issues,
+                payload.enabled,
+                ctx.user.id,
+            )
+            .await
+            .map_err(db_error)?
+        }
+    };
+
+    info!(
+        %project_id,
+        repo = %sync.repo_full_name,
+        user_id = %ctx.user.id,
+        "GitHub issue sync saved"
+    );
+
+    if sync.enabled {
+        github_issue_sync::spawn_reconcile(state.pool.clone(), github_app.clone(), sync.clone());
+    }
+
+    Ok(Json(GitHubIssueSyncDetails::from(sync)))
+}
+
+/// DELETE /v1/projects/:project_id/github-issue-sync
+/// Stops syncing. Issues and comments already mirrored stay on both sides.
+pub async fn delete_sync(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let org_id = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    ensure_admin_access(state.pool(), org_id, ctx.user.id).await?;
+
+    let deleted = GitHubIssueSyncRepository::delete_by_project(state.pool(), project_id)
+        .await
+        .map_err(|e| {
+            error!(?e, "Failed to delete GitHub issue sync");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "Database error")
+        })?;
+    if !deleted {
+        return Err(ErrorResponse::new(
+            StatusCode::NOT_FOUND,
+            "GitHub issue sync not set up",
+        ));
+    }
+
+    info!(%project_id, user_id = %ctx.user.id, "GitHub issue sync removed");
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -20,6 +20,7 @@ use crate::{
     auth::RequestContext,
     db::{issue_comments::IssueCommentRepository, organization_members::check_user_role},
     mutation_definition::MutationBuilder,
+    notifications,
 };
 
 /// Mutation definition for IssueComment - provides both router and TypeScript metadata.
@@ -115,6 +116,8 @@ async fn create_issue_comment(
         db_error(error, "failed to create issue comment")
     })?;
 
+    notifications::comment_created(state.pool(), &response.data).await;
+
     if let Some(analytics) = state.analytics() {
         analytics.track(
             ctx.user.id,
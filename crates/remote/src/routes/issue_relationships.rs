@@ -2,23 +2,30 @@ use axum::{
     Json,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
+    routing::get,
 };
 use tracing::instrument;
 use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
-    organization_members::ensure_issue_access,
+    organization_members::{ensure_issue_access, ensure_project_access},
 };
 use api_types::{DeleteResponse, MutationResponse};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::issue_relationships::IssueRelationshipRepository,
+    db::{
+        issue_relationships::{IssueRelationshipError, IssueRelationshipRepository},
+        issues::IssueRepository,
+    },
+    dependency_graph::{DependencyGraph, GraphNode},
     mutation_definition::{MutationBuilder, NoUpdate},
+    notifications,
 };
 use api_types::{
-    CreateIssueRelationshipRequest, IssueRelationship, ListIssueRelationshipsQuery,
+    CreateIssueRelationshipRequest, IssueDependencyEdge, IssueDependencyGraphQuery,
+    IssueDependencyGraphResponse, IssueRelationship, ListIssueRelationshipsQuery,
     ListIssueRelationshipsResponse,
 };
 
@@ -33,7 +40,9 @@ pub fn mutation(
 }
 
 pub fn router() -> axum::Router<AppState> {
-    mutation().router()
+    mutation()
+        .router()
+        .route("/issue_relationships/graph", get(get_dependency_graph))
 }
 
 #[instrument(
@@ -112,11 +121,19 @@ async fn create_issue_relationship(
         payload.relationship_type,
     )
     .await
-    .map_err(|error| {
-        tracing::error!(?error, "failed to create issue relationship");
-        db_error(error, "failed to create issue relationship")
+    .map_err(|error| match error {
+        IssueRelationshipError::Cycle => ErrorResponse::new(
+            StatusCode::CONFLICT,
+            "blocking relationship would create a cycle",
+        ),
+        error => {
+            tracing::error!(?error, "failed to create issue relationship");
+            db_error(error, "failed to create issue relationship")
+        }
     })?;
 
+    notifications::blocking_added(state.pool(), &response.data, ctx.user.id).await;
+
     Ok(Json(response))
 }
 
@@ -150,5 +167,66 @@ async fn delete_issue_relationship(
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
 
+    notifications::blocking_removed(state.pool(), &relationship, ctx.user.id).await;
+
     Ok(Json(response))
 }
+
+#[instrument(
+    name = "issue_relationships.get_dependency_graph",
+    skip(state, ctx),
+    fields(project_id = %query.project_id, user_id = %ctx.user.id)
+)]
+async fn get_dependency_graph(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<IssueDependencyGraphQuery>,
+) -> Result<Json<IssueDependencyGraphResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+
+    let mut issues = IssueRepository::list_by_project(state.pool(), query.project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, project_id = %query.project_id, "failed to list issues");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list issues")
+        })?;
+    issues.sort_by(|a, b| a.sort_order.total_cmp(&b.sort_order));
+
+    let edges = IssueRelationshipRepository::list_blocking_by_project(
+        state.pool(),
+        query.project_id,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, project_id = %query.project_id, "failed to list issue relationships");
+        ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to list issue relationships",
+        )
+    })?;
+
+    let nodes: Vec<GraphNode> = issues
+        .iter()
+        .map(|issue| GraphNode {
+            id: issue.id,
+            target_date: issue.target_date,
+            open: issue.completed_at.is_none(),
+        })
+        .collect();
+    let graph = DependencyGraph::new(&nodes, &edges);
+
+    Ok(Json(IssueDependencyGraphResponse {
+        topological_order: graph.topological_order(),
+        edges: edges
+            .iter()
+            .map(
+                |&(blocking_issue_id, blocked_issue_id)| IssueDependencyEdge {
+                    blocking_issue_id,
+                    blocked_issue_id,
+                },
+            )
+            .collect(),
+        critical_path: graph.critical_path(),
+        blocked_issue_ids: query.issue_id.map(|issue_id| graph.blocked_by(issue_id)),
+    }))
+}
@@ -0,0 +1,242 @@
+use api_types::{
+    CreateIssueTemplateRequest, DeleteResponse, Issue, IssueTemplate, ListIssueTemplatesQuery,
+    ListIssueTemplatesResponse, MutationResponse, UpdateIssueTemplateRequest,
+};
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    routing::post,
+};
+use chrono::Utc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{
+    error::{ErrorResponse, db_error},
+    organization_members::ensure_project_access,
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::issue_templates::IssueTemplateRepository,
+    mutation_definition::MutationBuilder,
+    recurrence::{self, RecurrenceError},
+};
+
+/// Mutation definition for IssueTemplates - provides both router and TypeScript metadata.
+pub fn mutation()
+-> MutationBuilder<IssueTemplate, CreateIssueTemplateRequest, UpdateIssueTemplateRequest> {
+    MutationBuilder::new("issue_templates")
+        .list(list_issue_templates)
+        .get(get_issue_template)
+        .create(create_issue_template)
+        .update(update_issue_template)
+        .delete(delete_issue_template)
+}
+
+pub fn router() -> axum::Router<AppState> {
+    mutation().router().route(
+        "/issue_templates/{issue_template_id}/instantiate",
+        post(instantiate_issue_template),
+    )
+}
+
+fn recurrence_error(error: RecurrenceError) -> ErrorResponse {
+    match error {
+        RecurrenceError::InvalidRule(_) => {
+            ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string())
+        }
+        error => {
+            tracing::error!(?error, "issue template error");
+            db_error(error, "failed to create issue from template")
+        }
+    }
+}
+
+async fn load_template(
+    state: &AppState,
+    user_id: Uuid,
+    issue_template_id: Uuid,
+) -> Result<IssueTemplate, ErrorResponse> {
+    let template = IssueTemplateRepository::find_by_id(state.pool(), issue_template_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %issue_template_id, "failed to load issue template");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load issue template",
+            )
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue template not found"))?;
+
+    ensure_project_access(state.pool(), user_id, template.project_id).await?;
+
+    Ok(template)
+}
+
+#[instrument(
+    name = "issue_templates.list_issue_templates",
+    skip(state, ctx),
+    fields(project_id = %query.project_id, user_id = %ctx.user.id)
+)]
+async fn list_issue_templates(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<ListIssueTemplatesQuery>,
+) -> Result<Json<ListIssueTemplatesResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+
+    let issue_templates = IssueTemplateRepository::list_by_project(state.pool(), query.project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, project_id = %query.project_id, "failed to list issue templates");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to list issue templates",
+            )
+        })?;
+
+    Ok(Json(ListIssueTemplatesResponse { issue_templates }))
+}
+
+#[instrument(
+    name = "issue_templates.get_issue_template",
+    skip(state, ctx),
+    fields(issue_template_id = %issue_template_id, user_id = %ctx.user.id)
+)]
+async fn get_issue_template(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_template_id): Path<Uuid>,
+) -> Result<Json<IssueTemplate>, ErrorResponse> {
+    let template = load_template(&state, ctx.user.id, issue_template_id).await?;
+    Ok(Json(template))
+}
+
+#[instrument(
+    name = "issue_templates.create_issue_template",
+    skip(state, ctx, payload),
+    fields(project_id = %payload.project_id, user_id = %ctx.user.id)
+)]
+async fn create_issue_template(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<CreateIssueTemplateRequest>,
+) -> Result<Json<MutationResponse<IssueTemplate>>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+
+    let next_run_at = recurrence::first_run(payload.recurrence.as_deref(), Utc::now())
+        .map_err(recurrence_error)?;
+
+    let response = IssueTemplateRepository::create(
+        state.pool(),
+        payload.id,
+        payload.project_id,
+        payload.name,
+        payload.title,
+        payload.description,
+        payload.status_id,
+        payload.priority,
+        payload.tag_ids,
+        payload.assignee_ids,
+        payload.recurrence,
+        next_run_at,
+        payload.auto_start_executor_profile,
+        ctx.user.id,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to create issue template");
+        db_error(error, "failed to create issue template")
+    })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "issue_templates.update_issue_template",
+    skip(state, ctx, payload),
+    fields(issue_template_id = %issue_template_id, user_id = %ctx.user.id)
+)]
+async fn update_issue_template(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_template_id): Path<Uuid>,
+    Json(payload): Json<UpdateIssueTemplateRequest>,
+) -> Result<Json<MutationResponse<IssueTemplate>>, ErrorResponse> {
+    let template = load_template(&state, ctx.user.id, issue_template_id).await?;
+
+    // Changing the rule reschedules from now; otherwise keep the pending run.
+    let next_run_at = match &payload.recurrence {
+        Some(rule) => {
+            recurrence::first_run(rule.as_deref(), Utc::now()).map_err(recurrence_error)?
+        }
+        None => template.next_run_at,
+    };
+
+    let response = IssueTemplateRepository::update(
+        state.pool(),
+        issue_template_id,
+        payload.name,
+        payload.title,
+        payload.description,
+        payload.status_id,
+        payload.priority,
+        payload.tag_ids,
+        payload.assignee_ids,
+        payload.recurrence,
+        next_run_at,
+        payload.auto_start_executor_profile,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to update issue template");
+        db_error(error, "failed to update issue template")
+    })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "issue_templates.delete_issue_template",
+    skip(state, ctx),
+    fields(issue_template_id = %issue_template_id, user_id = %ctx.user.id)
+)]
+async fn delete_issue_template(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_template_id): Path<Uuid>,
+) -> Result<Json<DeleteResponse>, ErrorResponse> {
+    load_template(&state, ctx.user.id, issue_template_id).await?;
+
+    let response = IssueTemplateRepository::delete(state.pool(), issue_template_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to delete issue template");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(response))
+}
+
+/// File an issue from the template now, independent of its recurrence.
+#[instrument(
+    name = "issue_templates.instantiate_issue_template",
+    skip(state, ctx),
+    fields(issue_template_id = %issue_template_id, user_id = %ctx.user.id)
+)]
+async fn instantiate_issue_template(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_template_id): Path<Uuid>,
+) -> Result<Json<Issue>, ErrorResponse> {
+    let template = load_template(&state, ctx.user.id, issue_template_id).await?;
+
+    let issue =
+        recurrence::instantiate_template(state.pool(), &template, Utc::now(), Some(ctx.user.id))
+            .await
+            .map_err(recurrence_error)?;
+
+    Ok(Json(issue))
+}
@@ -0,0 +1,189 @@
+use api_types::{
+    CreateIssueTimeEntryRequest, DeleteResponse, IssueTimeEntry, ListIssueTimeEntriesQuery,
+    ListIssueTimeEntriesResponse, MutationResponse,
+};
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{
+    error::{ErrorResponse, db_error},
+    organization_members::ensure_issue_access,
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    db::{issue_time_entries::IssueTimeEntryRepository, workspaces::WorkspaceRepository},
+    mutation_definition::{MutationBuilder, NoUpdate},
+};
+
+/// Mutation definition for IssueTimeEntry - provides both router and TypeScript metadata.
+pub fn mutation() -> MutationBuilder<IssueTimeEntry, CreateIssueTimeEntryRequest, NoUpdate> {
+    MutationBuilder::new("issue_time_entries")
+        .list(list_issue_time_entries)
+        .get(get_issue_time_entry)
+        .create(create_issue_time_entry)
+        .delete(delete_issue_time_entry)
+}
+
+pub fn router() -> axum::Router<AppState> {
+    mutation().router()
+}
+
+#[instrument(
+    name = "issue_time_entries.list_issue_time_entries",
+    skip(state, ctx),
+    fields(issue_id = %query.issue_id, user_id = %ctx.user.id)
+)]
+async fn list_issue_time_entries(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<ListIssueTimeEntriesQuery>,
+) -> Result<Json<ListIssueTimeEntriesResponse>, ErrorResponse> {
+    ensure_issue_access(state.pool(), ctx.user.id, query.issue_id).await?;
+
+    let issue_time_entries = IssueTimeEntryRepository::list_by_issue(state.pool(), query.issue_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, issue_id = %query.issue_id, "failed to list issue time entries");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to list issue time entries",
+            )
+        })?;
+
+    Ok(Json(ListIssueTimeEntriesResponse { issue_time_entries }))
+}
+
+#[instrument(
+    name = "issue_time_entries.get_issue_time_entry",
+    skip(state, ctx),
+    fields(issue_time_entry_id = %issue_time_entry_id, user_id = %ctx.user.id)
+)]
+async fn get_issue_time_entry(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_time_entry_id): Path<Uuid>,
+) -> Result<Json<IssueTimeEntry>, ErrorResponse> {
+    let entry = load_time_entry(&state, issue_time_entry_id).await?;
+
+    ensure_issue_access(state.pool(), ctx.user.id, entry.issue_id).await?;
+
+    Ok(Json(entry))
+}
+
+#[instrument(
+    name = "issue_time_entries.create_issue_time_entry",
+    skip(state, ctx, payload),
+    fields(issue_id = %payload.issue_id, user_id = %ctx.user.id)
+)]
+async fn create_issue_time_entry(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<CreateIssueTimeEntryRequest>,
+) -> Result<Json<MutationResponse<IssueTimeEntry>>, ErrorResponse> {
+    ensure_issue_access(state.pool(), ctx.user.id, payload.issue_id).await?;
+
+    if payload.duration_seconds < 0 {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            "duration_seconds must not be negative",
+        ));
+    }
+
+    if let Some(workspace_id) = payload.workspace_id {
+        let workspace = WorkspaceRepository::find_by_id(state.pool(), workspace_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %workspace_id, "failed to load workspace");
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to load workspace",
+                )
+            })?;
+        if workspace.and_then(|workspace| workspace.issue_id) != Some(payload.issue_id) {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "workspace is not linked to this issue",
+            ));
+        }
+    }
+
+    // Entries can only be re-reported by their author, for the same issue.
+    if let Some(id) = payload.id
+        && let Some(existing) = IssueTimeEntryRepository::find_by_id(state.pool(), id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %id, "failed to load issue time entry");
+                ErrorResponse::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to load issue time entry",
+                )
+            })?
+        && (existing.user_id != ctx.user.id || existing.issue_id != payload.issue_id)
+    {
+        return Err(ErrorResponse::new(
+            StatusCode::CONFLICT,
+            "issue time entry already exists",
+        ));
+    }
+
+    let response = IssueTimeEntryRepository::upsert(
+        state.pool(),
+        payload.id,
+        payload.issue_id,
+        payload.workspace_id,
+        ctx.user.id,
+        payload.source,
+        payload.started_at,
+        payload.duration_seconds,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to create issue time entry");
+        db_error(error, "failed to create issue time entry")
+    })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "issue_time_entries.delete_issue_time_entry",
+    skip(state, ctx),
+    fields(issue_time_entry_id = %issue_time_entry_id, user_id = %ctx.user.id)
+)]
+async fn delete_issue_time_entry(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_time_entry_id): Path<Uuid>,
+) -> Result<Json<DeleteResponse>, ErrorResponse> {
+    let entry = load_time_entry(&state, issue_time_entry_id).await?;
+
+    ensure_issue_access(state.pool(), ctx.user.id, entry.issue_id).await?;
+
+    let response = IssueTimeEntryRepository::delete(state.pool(), issue_time_entry_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to delete issue time entry");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(response))
+}
+
+async fn load_time_entry(state: &AppState, id: Uuid) -> Result<IssueTimeEntry, ErrorResponse> {
+    IssueTimeEntryRepository::find_by_id(state.pool(), id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %id, "failed to load issue time entry");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load issue time entry",
+            )
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue time entry not found"))
+}
@@ -0,0 +1,108 @@
+use api_types::{ExportIssuesQuery, ImportIssuesRequest, ImportIssuesResponse, IssueExportFormat};
+use axum::{
+    Json, Router,
+    extract::{DefaultBodyLimit, Extension, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use tracing::instrument;
+
+use super::{
+    error::{ErrorResponse, db_error},
+    organization_members::ensure_project_access,
+};
+use crate::{
+    AppState,
+    auth::RequestContext,
+    issue_transfer::{self, ProjectSnapshot, TransferError},
+};
+
+/// Export files from other trackers easily exceed axum's 2 MB default.
+const MAX_IMPORT_BYTES: usize = 20 * 1024 * 1024;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/issues/import",
+            post(import_issues).layer(DefaultBodyLimit::max(MAX_IMPORT_BYTES)),
+        )
+        .route("/issues/export", get(export_issues))
+}
+
+fn transfer_error(error: TransferError, message: &str) -> ErrorResponse {
+    if error.is_invalid_input() {
+        return ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string());
+    }
+    tracing::error!(?error, "{message}");
+    db_error(error, message)
+}
+
+#[instrument(
+    name = "issue_transfer.import_issues",
+    skip(state, ctx, payload),
+    fields(project_id = %payload.project_id, user_id = %ctx.user.id, dry_run = payload.dry_run)
+)]
+async fn import_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<ImportIssuesRequest>,
+) -> Result<Json<ImportIssuesResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+
+    let sources = issue_transfer::read_sources(&payload)
+        .map_err(|error| transfer_error(error, "failed to read import file"))?;
+    let snapshot = ProjectSnapshot::load(state.pool(), payload.project_id)
+        .await
+        .map_err(|error| transfer_error(error, "failed to load project"))?;
+    let plan = issue_transfer::plan_import(sources, &snapshot, &payload)
+        .map_err(|error| transfer_error(error, "failed to plan import"))?;
+
+    if payload.dry_run {
+        return Ok(Json(plan.into_report()));
+    }
+
+    let report = issue_transfer::execute_import(
+        state.pool(),
+        payload.project_id,
+        &snapshot,
+        plan,
+        ctx.user.id,
+    )
+    .await
+    .map_err(|error| transfer_error(error, "failed to import issues"))?;
+
+    Ok(Json(report))
+}
+
+#[instrument(
+    name = "issue_transfer.export_issues",
+    skip(state, ctx),
+    fields(project_id = %query.project_id, user_id = %ctx.user.id)
+)]
+async fn export_issues(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<ExportIssuesQuery>,
+) -> Result<Response, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+
+    let export = issue_transfer::export(state.pool(), query.project_id)
+        .await
+        .map_err(|error| transfer_error(error, "failed to export issues"))?;
+
+    Ok(match query.format {
+        IssueExportFormat::Json => Json(export).into_response(),
+        IssueExportFormat::Csv => (
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"issues.csv\"",
+                ),
+            ],
+            issue_transfer::export_csv(&export),
+        )
+            .into_response(),
+    })
+}
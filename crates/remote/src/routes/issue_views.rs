@@ -0,0 +1,203 @@
+use api_types::{
+    CreateIssueViewRequest, DeleteResponse, IssueView, ListIssueViewsQuery, ListIssueViewsResponse,
+    MutationResponse, UpdateIssueViewRequest,
+};
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use super::{
+    error::{ErrorResponse, db_error},
+    organization_members::ensure_project_access,
+};
+use crate::{
+    AppState, auth::RequestContext, db::issue_views::IssueViewRepository, issue_query,
+    mutation_definition::MutationBuilder,
+};
+
+/// Mutation definition for IssueViews - provides both router and TypeScript metadata.
+pub fn mutation() -> MutationBuilder<IssueView, CreateIssueViewRequest, UpdateIssueViewRequest> {
+    MutationBuilder::new("issue_views")
+        .list(list_issue_views)
+        .get(get_issue_view)
+        .create(create_issue_view)
+        .update(update_issue_view)
+        .delete(delete_issue_view)
+}
+
+pub fn router() -> axum::Router<AppState> {
+    mutation().router()
+}
+
+fn validate_query(query: &str) -> Result<(), ErrorResponse> {
+    issue_query::parse(query, Utc::now())
+        .map(|_| ())
+        .map_err(|error| ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string()))
+}
+
+/// Load a view the user may see: a shared view of a project they can
+/// access, or one of their own. Other users' private views are reported as
+/// missing.
+pub(super) async fn load_visible_view(
+    pool: &PgPool,
+    user_id: Uuid,
+    view_id: Uuid,
+) -> Result<IssueView, ErrorResponse> {
+    let view = IssueViewRepository::find_by_id(pool, view_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %view_id, "failed to load issue view");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load issue view",
+            )
+        })?
+        .filter(|view| view.owner_user_id.is_none_or(|owner| owner == user_id))
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue view not found"))?;
+
+    ensure_project_access(pool, user_id, view.project_id).await?;
+
+    Ok(view)
+}
+
+#[instrument(
+    name = "issue_views.list_issue_views",
+    skip(state, ctx),
+    fields(project_id = %query.project_id, user_id = %ctx.user.id)
+)]
+async fn list_issue_views(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<ListIssueViewsQuery>,
+) -> Result<Json<ListIssueViewsResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+
+    let issue_views = IssueViewRepository::list_visible(
+        state.pool(),
+        query.project_id,
+        ctx.user.id,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, project_id = %query.project_id, "failed to list issue views");
+        ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to list issue views",
+        )
+    })?;
+
+    Ok(Json(ListIssueViewsResponse { issue_views }))
+}
+
+#[instrument(
+    name = "issue_views.get_issue_view",
+    skip(state, ctx),
+    fields(issue_view_id = %issue_view_id, user_id = %ctx.user.id)
+)]
+async fn get_issue_view(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_view_id): Path<Uuid>,
+) -> Result<Json<IssueView>, ErrorResponse> {
+    let view = load_visible_view(state.pool(), ctx.user.id, issue_view_id).await?;
+    Ok(Json(view))
+}
+
+#[instrument(
+    name = "issue_views.create_issue_view",
+    skip(state, ctx, payload),
+    fields(project_id = %payload.project_id, user_id = %ctx.user.id)
+)]
+async fn create_issue_view(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<CreateIssueViewRequest>,
+) -> Result<Json<MutationResponse<IssueView>>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+    validate_query(&payload.query)?;
+
+    let owner_user_id = (!payload.shared).then_some(ctx.user.id);
+    let response = IssueViewRepository::create(
+        state.pool(),
+        payload.id,
+        payload.project_id,
+        owner_user_id,
+        payload.name,
+        payload.query,
+        payload.sort_order,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to create issue view");
+        db_error(error, "failed to create issue view")
+    })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "issue_views.update_issue_view",
+    skip(state, ctx, payload),
+    fields(issue_view_id = %issue_view_id, user_id = %ctx.user.id)
+)]
+async fn update_issue_view(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_view_id): Path<Uuid>,
+    Json(payload): Json<UpdateIssueViewRequest>,
+) -> Result<Json<MutationResponse<IssueView>>, ErrorResponse> {
+    load_visible_view(state.pool(), ctx.user.id, issue_view_id).await?;
+
+    if let Some(ref query) = payload.query {
+        validate_query(query)?;
+    }
+
+    // Making a shared view private hands it to whoever unshares it.
+    let owner_user_id = payload
+        .shared
+        .map(|shared| (!shared).then_some(ctx.user.id));
+
+    let response = IssueViewRepository::update(
+        state.pool(),
+        issue_view_id,
+        payload.name,
+        payload.query,
+        owner_user_id,
+        payload.sort_order,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to update issue view");
+        db_error(error, "failed to update issue view")
+    })?;
+
+    Ok(Json(response))
+}
+
+#[instrument(
+    name = "issue_views.delete_issue_view",
+    skip(state, ctx),
+    fields(issue_view_id = %issue_view_id, user_id = %ctx.user.id)
+)]
+async fn delete_issue_view(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(issue_view_id): Path<Uuid>,
+) -> Result<Json<DeleteResponse>, ErrorResponse> {
+    load_visible_view(state.pool(), ctx.user.id, issue_view_id).await?;
+
+    let response = IssueViewRepository::delete(state.pool(), issue_view_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to delete issue view");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(response))
+}
@@ -1,26 +1,36 @@
+use api_types::{
+    BurnupPoint, CreateIssueRequest, DeleteResponse, Issue, IssueBurndownQuery,
+    IssueBurndownResponse, ListIssuesQuery, ListIssuesResponse, MutationResponse,
+    UpdateIssueRequest, protect_auto_start_metadata,
+};
 use axum::{
     Json,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
-    routing::post,
+    routing::{get, post},
 };
+use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use tracing::instrument;
-use api_types::{
-    CreateIssueRequest, Issue, ListIssuesQuery, ListIssuesResponse, UpdateIssueRequest,
-};
 use uuid::Uuid;
 
 use super::{
     error::{ErrorResponse, db_error},
+    issue_views::load_visible_view,
     organization_members::ensure_project_access,
 };
-use api_types::{DeleteResponse, MutationResponse};
 use crate::{
     AppState,
     auth::RequestContext,
-    db::{get_txid, issues::IssueRepository},
+    custom_fields,
+    db::{
+        custom_fields::CustomFieldRepository, get_txid, issue_burndown::IssueBurndownRepository,
+        issues::IssueRepository, organization_members,
+    },
+    issue_query,
     mutation_definition::MutationBuilder,
+    notifications,
 };
 
 /// Mutation definition for Issue - provides both router and TypeScript metadata.
@@ -38,8 +48,12 @@ pub fn router() -> axum::Router<AppState> {
     mutation()
         .router()
         .route("/issues/bulk", post(bulk_update_issues))
+        .route("/issues/burndown", get(issue_burndown))
 }
 
+/// Longest range a burndown can cover, in days.
+const MAX_BURNDOWN_DAYS: i64 = 366;
+
 #[instrument(
     name = "issues.list_issues",
     skip(state, ctx),
@@ -52,12 +66,34 @@ async fn list_issues(
 ) -> Result<Json<ListIssuesResponse>, ErrorResponse> {
     ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
 
-    let issues = IssueRepository::list_by_project(state.pool(), query.project_id)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, project_id = %query.project_id, "failed to list issues");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list issues")
-        })?;
+    // A saved view's query is combined with any ad-hoc query.
+    let mut text = String::new();
+    if let Some(view_id) = query.view_id {
+        let view = load_visible_view(state.pool(), ctx.user.id, view_id).await?;
+        if view.project_id != query.project_id {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                "issue view belongs to a different project",
+            ));
+        }
+        text.push_str(&view.query);
+    }
+    if let Some(q) = &query.q {
+        text.push(' ');
+        text.push_str(q);
+    }
+    let filter = issue_query::parse(&text, Utc::now())
+        .map_err(|error| ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string()))?;
+
+    let issues = if filter.is_empty() {
+        IssueRepository::list_by_project(state.pool(), query.project_id).await
+    } else {
+        issue_query::search(state.pool(), query.project_id, ctx.user.id, &filter).await
+    }
+    .map_err(|error| {
+        tracing::error!(?error, project_id = %query.project_id, "failed to list issues");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list issues")
+    })?;
 
     Ok(Json(ListIssuesResponse { issues }))
 }
@@ -93,10 +129,12 @@ async fn get_issue(
 async fn create_issue(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
-    Json(payload): Json<CreateIssueRequest>,
+    Json(mut payload): Json<CreateIssueRequest>,
 ) -> Result<Json<MutationResponse<Issue>>, ErrorResponse> {
     let organization_id =
         ensure_project_access(state.pool(), ctx.user.id, payload.project_id).await?;
+    validate_custom_fields(&state, organization_id, &payload.extension_metadata).await?;
+    protect_auto_start_metadata(&mut payload.extension_metadata, None);
 
     let has_parent = payload.parent_issue_id.is_some();
     let has_description = payload.description.is_some();
@@ -113,12 +151,14 @@ async fn create_issue(
         payload.priority,
         payload.start_date,
         payload.target_date,
+        payload.estimate,
+        payload.estimate_unit,
         payload.completed_at,
         payload.sort_order,
         payload.parent_issue_id,
         payload.parent_issue_sort_order,
         payload.extension_metadata,
-        ctx.user.id,
+        Some(ctx.user.id),
     )
     .await
     .map_err(|error| {
@@ -166,7 +206,7 @@ async fn update_issue(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
     Path(issue_id): Path<Uuid>,
-    Json(payload): Json<UpdateIssueRequest>,
+    Json(mut payload): Json<UpdateIssueRequest>,
 ) -> Result<Json<MutationResponse<Issue>>, ErrorResponse> {
     let issue = IssueRepository::find_by_id(state.pool(), issue_id)
         .await
@@ -176,7 +216,12 @@ async fn update_issue(
         })?
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
 
-    ensure_project_access(state.pool(), ctx.user.id, issue.project_id).await?;
+    let organization_id =
+        ensure_project_access(state.pool(), ctx.user.id, issue.project_id).await?;
+    if let Some(extension_metadata) = &mut payload.extension_metadata {
+        validate_custom_fields(&state, organization_id, extension_metadata).await?;
+        protect_auto_start_metadata(extension_metadata, Some(&issue.extension_metadata));
+    }
 
     let mut tx = state.pool().begin().await.map_err(|error| {
         tracing::error!(?error, "failed to begin transaction");
@@ -192,6 +237,8 @@ async fn update_issue(
         payload.priority,
         payload.start_date,
         payload.target_date,
+        payload.estimate,
+        payload.estimate_unit,
         payload.completed_at,
         payload.sort_order,
         payload.parent_issue_id,
@@ -214,6 +261,10 @@ async fn update_issue(
         ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
     })?;
 
+    if issue.completed_at.is_none() != data.completed_at.is_none() {
+        notifications::completion_changed(state.pool(), &data, ctx.user.id).await;
+    }
+
     Ok(Json(MutationResponse { data, txid }))
 }
 
@@ -296,7 +347,12 @@ async fn bulk_update_issues(
         .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "issue not found"))?;
 
     let project_id = first_issue.project_id;
-    ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    let organization_id = ensure_project_access(state.pool(), ctx.user.id, project_id).await?;
+    for item in &payload.updates {
+        if let Some(extension_metadata) = &item.changes.extension_metadata {
+            validate_custom_fields(&state, organization_id, extension_metadata).await?;
+        }
+    }
 
     let mut tx = state.pool().begin().await.map_err(|error| {
         tracing::error!(?error, "failed to begin transaction");
@@ -304,8 +360,9 @@ async fn bulk_update_issues(
     })?;
 
     let mut results = Vec::with_capacity(payload.updates.len());
+    let mut completion_changed = Vec::new();
 
-    for item in payload.updates {
+    for mut item in payload.updates {
         // Verify issue belongs to the same project
         let issue = IssueRepository::find_by_id(&mut *tx, item.id)
             .await
@@ -321,6 +378,9 @@ async fn bulk_update_issues(
                 "all issues must belong to the same project",
             ));
         }
+        if let Some(extension_metadata) = &mut item.changes.extension_metadata {
+            protect_auto_start_metadata(extension_metadata, Some(&issue.extension_metadata));
+        }
 
         // Update the issue
         let updated = IssueRepository::update(
@@ -332,6 +392,8 @@ async fn bulk_update_issues(
             item.changes.priority,
             item.changes.start_date,
             item.changes.target_date,
+            item.changes.estimate,
+            item.changes.estimate_unit,
             item.changes.completed_at,
             item.changes.sort_order,
             item.changes.parent_issue_id,
@@ -344,6 +406,9 @@ async fn bulk_update_issues(
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to update issue")
         })?;
 
+        if issue.completed_at.is_none() != updated.completed_at.is_none() {
+            completion_changed.push(updated.clone());
+        }
         results.push(updated);
     }
 
@@ -356,8 +421,110 @@ async fn bulk_update_issues(
         ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
     })?;
 
+    for issue in &completion_changed {
+        notifications::completion_changed(state.pool(), issue, ctx.user.id).await;
+    }
+
     Ok(Json(BulkUpdateIssuesResponse {
         data: results,
         txid,
     }))
 }
+
+/// Reject custom field values that don't match the organization's field
+/// definitions, including user fields naming non-members.
+async fn validate_custom_fields(
+    state: &AppState,
+    organization_id: Uuid,
+    extension_metadata: &Value,
+) -> Result<(), ErrorResponse> {
+    if extension_metadata
+        .get(api_types::CUSTOM_FIELDS_METADATA_KEY)
+        .is_none()
+    {
+        return Ok(());
+    }
+
+    let fields = CustomFieldRepository::list_by_organization(state.pool(), organization_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %organization_id, "failed to load custom fields");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    let users = custom_fields::validate_values(&fields, extension_metadata)
+        .map_err(|error| ErrorResponse::new(StatusCode::BAD_REQUEST, error.to_string()))?;
+
+    for user_id in users {
+        let is_member = organization_members::is_member(state.pool(), organization_id, user_id)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, %user_id, "failed to check membership");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+        if !is_member {
+            return Err(ErrorResponse::new(
+                StatusCode::BAD_REQUEST,
+                format!("user {user_id} is not a member of this organization"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[instrument(
+    name = "issues.issue_burndown",
+    skip(state, ctx),
+    fields(project_id = %query.project_id, user_id = %ctx.user.id)
+)]
+async fn issue_burndown(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<IssueBurndownQuery>,
+) -> Result<Json<IssueBurndownResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query.from.unwrap_or(to - Duration::days(30));
+    if from > to || (to - from).num_days() >= MAX_BURNDOWN_DAYS {
+        return Err(ErrorResponse::new(
+            StatusCode::BAD_REQUEST,
+            format!("burndown range must be between 1 and {MAX_BURNDOWN_DAYS} days"),
+        ));
+    }
+
+    let statuses = IssueBurndownRepository::status_points(
+        state.pool(),
+        query.project_id,
+        query.unit,
+        from,
+        to,
+    )
+    .await
+    .map_err(|error| {
+        tracing::error!(?error, "failed to load status burndown");
+        ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load burndown")
+    })?;
+    let totals =
+        IssueBurndownRepository::burnup(state.pool(), query.project_id, query.unit, from, to)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to load burnup");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load burndown")
+            })?
+            .into_iter()
+            .map(|row| BurnupPoint {
+                day: row.day,
+                scope: row.scope,
+                completed: row.completed,
+                remaining: row.scope - row.completed,
+            })
+            .collect();
+
+    Ok(Json(IssueBurndownResponse {
+        unit: query.unit,
+        statuses,
+        totals,
+    }))
+}
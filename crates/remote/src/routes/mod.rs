@@ -29,9 +29,11 @@ mod billing {
         Router::new()
     }
 }
+pub mod custom_fields;
 mod electric_proxy;
 pub(crate) mod error;
 mod github_app;
+mod github_issue_sync;
 mod identity;
 pub mod issue_assignees;
 pub mod issue_comment_reactions;
@@ -39,6 +41,10 @@ pub mod issue_comments;
 pub mod issue_followers;
 pub mod issue_relationships;
 pub mod issue_tags;
+pub mod issue_templates;
+pub mod issue_time_entries;
+pub mod issue_transfer;
+pub mod issue_views;
 pub mod issues;
 mod migration;
 pub mod notifications;
@@ -116,8 +122,10 @@ pub fn router(state: AppState) -> Router {
         .merge(oauth::protected_router())
         .merge(electric_proxy::router())
         .merge(github_app::protected_router())
+        .merge(github_issue_sync::router())
         .merge(project_statuses::router())
         .merge(tags::router())
+        .merge(custom_fields::router())
         .merge(issue_comments::router())
         .merge(issue_comment_reactions::router())
         .merge(issues::router())
@@ -125,6 +133,10 @@ pub fn router(state: AppState) -> Router {
         .merge(issue_followers::router())
         .merge(issue_tags::router())
         .merge(issue_relationships::router())
+        .merge(issue_templates::router())
+        .merge(issue_time_entries::router())
+        .merge(issue_transfer::router())
+        .merge(issue_views::router())
         .merge(pull_requests::router())
         .merge(notifications::router())
         .merge(workspaces::router())
@@ -183,12 +195,16 @@ pub fn all_mutation_definitions() -> Vec<crate::mutation_definition::MutationDef
         projects::mutation().definition(),
         notifications::mutation().definition(),
         tags::mutation().definition(),
+        custom_fields::mutation().definition(),
         project_statuses::mutation().definition(),
         issues::mutation().definition(),
         issue_assignees::mutation().definition(),
         issue_followers::mutation().definition(),
         issue_tags::mutation().definition(),
         issue_relationships::mutation().definition(),
+        issue_templates::mutation().definition(),
+        issue_time_entries::mutation().definition(),
+        issue_views::mutation().definition(),
         issue_comments::mutation().definition(),
         issue_comment_reactions::mutation().definition(),
     ]
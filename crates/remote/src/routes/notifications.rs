@@ -10,10 +10,17 @@ use uuid::Uuid;
 
 use super::error::ErrorResponse;
 use crate::{
-    AppState, auth::RequestContext, db::notifications::NotificationRepository,
+    AppState, auth::RequestContext,
+    db::{
+        notification_digest_settings::NotificationDigestRepository,
+        notifications::NotificationRepository,
+    },
     mutation_definition::{MutationBuilder, NoCreate},
 };
-use api_types::{Notification, UpdateNotificationRequest};
+use api_types::{
+    Notification, NotificationDigestSettings, UpdateNotificationDigestSettingsRequest,
+    UpdateNotificationRequest,
+};
 
 #[derive(Debug, Serialize)]
 pub struct ListNotificationsResponse {
@@ -49,6 +56,10 @@ pub fn router() -> Router<AppState> {
         .router()
         .route("/notifications/unread-count", get(unread_count))
         .route("/notifications/mark-all-seen", post(mark_all_seen))
+        .route(
+            "/notifications/digest-settings",
+            get(get_digest_settings).put(update_digest_settings),
+        )
 }
 
 #[instrument(
@@ -220,3 +231,44 @@ async fn unread_count(
 
     Ok(Json(UnreadCountResponse { count }))
 }
+
+#[instrument(
+    name = "notifications.get_digest_settings",
+    skip(state, ctx),
+    fields(user_id = %ctx.user.id)
+)]
+async fn get_digest_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+) -> Result<Json<NotificationDigestSettings>, ErrorResponse> {
+    let settings = NotificationDigestRepository::find(state.pool(), ctx.user.id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to load notification digest settings");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?
+        .unwrap_or_default();
+
+    Ok(Json(settings))
+}
+
+#[instrument(
+    name = "notifications.update_digest_settings",
+    skip(state, ctx, payload),
+    fields(user_id = %ctx.user.id)
+)]
+async fn update_digest_settings(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<UpdateNotificationDigestSettingsRequest>,
+) -> Result<Json<NotificationDigestSettings>, ErrorResponse> {
+    let settings =
+        NotificationDigestRepository::upsert(state.pool(), ctx.user.id, payload.frequency)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, "failed to update notification digest settings");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            })?;
+
+    Ok(Json(settings))
+}
@@ -17,6 +17,7 @@ use crate::{
     AppState,
     auth::RequestContext,
     db::{issues::IssueRepository, pull_requests::PullRequestRepository, workspaces::WorkspaceRepository},
+    notifications,
 };
 use api_types::{PullRequest, PullRequestStatus, UpsertPullRequestRequest};
 
@@ -106,6 +107,8 @@ async fn create_pull_request(
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
 
+    notifications::pull_request_changed(state.pool(), None, &pr, ctx.user.id).await;
+
     Ok(Json(pr))
 }
 
@@ -152,6 +155,9 @@ async fn update_pull_request(
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
 
+    notifications::pull_request_changed(state.pool(), Some(pull_request.status), &pr, ctx.user.id)
+        .await;
+
     Ok(Json(pr))
 }
 
@@ -194,6 +200,7 @@ async fn upsert_pull_request(
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
 
+    let previous_status = existing_pr.as_ref().map(|existing| existing.status);
     let pr = if let Some(existing) = existing_pr {
         if existing.issue_id != issue_id {
             return Err(ErrorResponse::new(
@@ -240,5 +247,7 @@ async fn upsert_pull_request(
             ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
         })?;
 
+    notifications::pull_request_changed(state.pool(), previous_status, &pr, ctx.user.id).await;
+
     Ok(Json(pr))
 }
@@ -14,7 +14,11 @@ use uuid::Uuid;
 
 use crate::{
     AppState,
-    db::reviews::{CreateReviewParams, ReviewRepository},
+    db::{
+        github_app::GitHubAppRepository2,
+        reviews::{CreateReviewParams, Review, ReviewRepository},
+    },
+    github_app::{GitHubAppService, InstallationPullRequest, ReviewResult, publish_review},
     r2::R2Error,
 };
 
@@ -413,6 +417,10 @@ pub async fn review_success(
     if review.is_webhook_review() {
         // Post PR comment instead of sending email
         if let Some(github_app) = state.github_app() {
+            if publish_inline_review(&state, github_app, &review, &review_url).await {
+                return Ok(StatusCode::OK);
+            }
+
             let comment = format!(
                 "## Review Complete\n\n\
                 Your review story is ready!\n\n\
@@ -448,6 +456,105 @@ pub async fn review_success(
     Ok(StatusCode::OK)
 }
 
+/// Publish a webhook review's findings to its PR as a GitHub review with
+/// inline comments, if the repository opted in. Returns false when the plain
+/// completion comment should be posted instead.
+async fn publish_inline_review(
+    state: &AppState,
+    github_app: &GitHubAppService,
+    review: &Review,
+    review_url: &str,
+) -> bool {
+    let installation_id = review.github_installation_id.unwrap_or(0);
+    let repo_full_name = format!(
+        "{}/{}",
+        review.pr_owner.as_deref().unwrap_or(""),
+        review.pr_repo.as_deref().unwrap_or("")
+    );
+
+    let gh_repo = GitHubAppRepository2::new(state.pool());
+    match gh_repo
+        .is_repository_review_inline_comments_enabled(installation_id, &repo_full_name)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => return false,
+        Err(e) => {
+            tracing::error!(
+                ?e,
+                review_id = %review.id,
+                "Failed to check inline comment setting"
+            );
+            return false;
+        }
+    }
+
+    let result = match fetch_review_result(state, review.id).await {
+        Ok(result) => result,
+        Err(e) => {
+            tracing::error!(
+                ?e,
+                review_id = %review.id,
+                "Failed to fetch review result from worker"
+            );
+            return false;
+        }
+    };
+
+    let pr = InstallationPullRequest {
+        github_app,
+        installation_id,
+        repo_full_name,
+        number: review.pr_number.unwrap_or(0) as i64,
+    };
+
+    match publish_review(&pr, &result, review_url).await {
+        Ok(outcome) => {
+            tracing::info!(
+                review_id = %review.id,
+                created = outcome.created,
+                updated = outcome.updated,
+                resolved = outcome.resolved,
+                "Published review comments to PR"
+            );
+            true
+        }
+        Err(e) => {
+            tracing::error!(
+                ?e,
+                review_id = %review.id,
+                "Failed to publish review comments to PR"
+            );
+            false
+        }
+    }
+}
+
+/// Fetch a completed review's summary and findings from the worker
+async fn fetch_review_result(
+    state: &AppState,
+    review_id: Uuid,
+) -> Result<ReviewResult, ReviewError> {
+    let base_url = state
+        .config
+        .review_worker_base_url
+        .as_ref()
+        .ok_or(ReviewError::WorkerNotConfigured)?;
+
+    let url = format!("{}/review/{}", base_url.trim_end_matches('/'), review_id);
+
+    let result = state
+        .http_client
+        .get(&url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(result)
+}
+
 /// POST /review/:id/failed - Called by worker when review fails
 /// Sends failure notification email to the user, or posts PR comment for webhook reviews
 pub async fn review_failed(
@@ -1,6 +1,6 @@
 use axum::{
     Json, Router,
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     routing::{delete, get, head, post},
 };
@@ -12,11 +12,15 @@ use super::{
     error::{ErrorResponse, db_error},
     organization_members::ensure_project_access,
 };
-use api_types::{DeleteWorkspaceRequest, UpdateWorkspaceRequest, Workspace};
+use api_types::{
+    DeleteWorkspaceRequest, ListIssueDeliveryTimelinesQuery, ListIssueDeliveryTimelinesResponse,
+    UpdateWorkspaceRequest, Workspace, WorkspaceFinishedRequest,
+};
 use crate::{
     AppState,
     auth::RequestContext,
     db::{issues::IssueRepository, workspaces::{CreateWorkspaceParams, WorkspaceRepository}},
+    notifications,
 };
 
 #[derive(Debug, Deserialize)]
@@ -48,6 +52,8 @@ pub fn router() -> Router<AppState> {
             "/workspaces/exists/{local_workspace_id}",
             head(workspace_exists),
         )
+        .route("/workspaces/finished", post(workspace_finished))
+        .route("/workspaces/delivery-timelines", get(list_delivery_timelines))
 }
 
 #[instrument(
@@ -274,3 +280,57 @@ async fn workspace_exists(
         ))
     }
 }
+
+#[instrument(
+    name = "workspaces.workspace_finished",
+    skip(state, ctx, payload),
+    fields(local_workspace_id = %payload.local_workspace_id, user_id = %ctx.user.id)
+)]
+async fn workspace_finished(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Json(payload): Json<WorkspaceFinishedRequest>,
+) -> Result<StatusCode, ErrorResponse> {
+    let workspace = WorkspaceRepository::find_by_local_id(state.pool(), payload.local_workspace_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to find workspace");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to find workspace",
+            )
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "workspace not found"))?;
+
+    ensure_project_access(state.pool(), ctx.user.id, workspace.project_id).await?;
+
+    notifications::workspace_finished(state.pool(), &workspace, payload.success, ctx.user.id)
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(
+    name = "workspaces.list_delivery_timelines",
+    skip(state, ctx),
+    fields(project_id = %query.project_id, user_id = %ctx.user.id)
+)]
+async fn list_delivery_timelines(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Query(query): Query<ListIssueDeliveryTimelinesQuery>,
+) -> Result<Json<ListIssueDeliveryTimelinesResponse>, ErrorResponse> {
+    ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
+
+    let timelines = WorkspaceRepository::list_delivery_timelines(state.pool(), query.project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, "failed to list delivery timelines");
+            ErrorResponse::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to list delivery timelines",
+            )
+        })?;
+
+    Ok(Json(ListIssueDeliveryTimelinesResponse { timelines }))
+}
@@ -2,9 +2,9 @@
 
 use crate::shape_definition::{ShapeDefinition, ShapeExport};
 use api_types::{
-    Issue, IssueAssignee, IssueComment, IssueCommentReaction, IssueFollower, IssueRelationship,
-    IssueTag, Notification, OrganizationMember, Project, ProjectStatus, PullRequest, Tag, User,
-    Workspace,
+    CustomField, Issue, IssueAssignee, IssueComment, IssueCommentReaction, IssueFollower,
+    IssueRelationship, IssueTag, Notification, OrganizationMember, Project, ProjectStatus,
+    PullRequest, Tag, User, Workspace,
 };
 
 // =============================================================================
@@ -39,6 +39,13 @@ pub const USERS_SHAPE: ShapeDefinition<User> = crate::define_shape!(
     params: ["organization_id"],
 );
 
+pub const ORGANIZATION_CUSTOM_FIELDS_SHAPE: ShapeDefinition<CustomField> = crate::define_shape!(
+    table: "custom_fields",
+    where_clause: r#""organization_id" = $1"#,
+    url: "/shape/custom_fields",
+    params: ["organization_id"],
+);
+
 // =============================================================================
 // Project-scoped shapes
 // =============================================================================
@@ -151,6 +158,7 @@ pub fn all_shapes() -> Vec<(&'static str, &'static dyn ShapeExport)> {
         NOTIFICATIONS_SHAPE,
         ORGANIZATION_MEMBERS_SHAPE,
         USERS_SHAPE,
+        ORGANIZATION_CUSTOM_FIELDS_SHAPE,
         PROJECT_TAGS_SHAPE,
         PROJECT_PROJECT_STATUSES_SHAPE,
         PROJECT_ISSUES_SHAPE,
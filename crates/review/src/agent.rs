@@ -0,0 +1,233 @@
+use std::{collections::BTreeMap, path::Path, sync::Arc, time::Duration};
+
+use executors::{
+    actions::{
+        Executable,
+        review::{RepoReviewContext, ReviewRequest},
+    },
+    approvals::NoopExecutorApprovalService,
+    env::{ExecutionEnv, RepoContext},
+    executors::{ExecutorExitResult, StandardCodingAgentExecutor, build_review_prompt},
+    logs::{
+        NormalizedEntry, NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch,
+    },
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use futures::{TryStreamExt, stream::select};
+use tokio_util::io::ReaderStream;
+use tracing::debug;
+use uuid::Uuid;
+use workspace_utils::{log_msg::LogMsg, msg_store::MsgStore, process::kill_process_group};
+
+use crate::{error::ReviewError, source::Checkout};
+
+/// Name of the file agent conversation history is written to in the checkout
+pub const AGENT_MESSAGES_FILE: &str = ".agent-messages.json";
+
+/// Instructions that turn a plain code review into the story-style summary
+/// the hosted service produces
+const STORY_PROMPT: &str = r#"You are writing a review for a human who has to approve these changes. Do not modify any files.
+
+Instead of a wall of diffs, tell the story of the change. Write your final answer as Markdown with exactly these sections:
+
+## Overview
+Two or three sentences on what the change does and why.
+
+## The story
+The change as a sequence of short chapters, in the order a reader should understand them rather than file order. Give each chapter a `###` heading and explain what changed and why, citing code as `path/to/file.rs:123`.
+
+## Key decisions
+Important design decisions and trade-offs made along the way, including alternatives that were rejected.
+
+## Needs attention
+A list ordered by importance of bugs, risky changes, missing tests and anything else the reviewer should look at closely. Write "Nothing stood out." if there is nothing.
+
+Your final message must contain only the review.
+"#;
+
+/// Build the prompt for reviewing `checkout`
+pub fn review_prompt(
+    checkout: &Checkout,
+    has_agent_messages: bool,
+) -> (Vec<RepoReviewContext>, String) {
+    let context = vec![RepoReviewContext {
+        repo_id: Uuid::new_v4(),
+        repo_name: checkout.repo_name.clone(),
+        base_commit: checkout.base_commit.clone(),
+        head_commit: Some(checkout.head_commit.clone()),
+    }];
+
+    let mut additional = format!("Title: {}\n", checkout.title);
+    if !checkout.description.trim().is_empty() {
+        additional.push_str(&format!("Description:\n{}\n", checkout.description.trim()));
+    }
+    if has_agent_messages {
+        additional.push_str(&format!(
+            "\nThe conversation with the coding agent that wrote these changes is in `{AGENT_MESSAGES_FILE}`. Use it to explain why things were done, but review the code itself.\n"
+        ));
+    }
+    additional.push('\n');
+    additional.push_str(STORY_PROMPT);
+
+    let prompt = build_review_prompt(Some(&context), Some(&additional));
+    (context, prompt)
+}
+
+/// Run a review with a locally installed coding agent and return its final
+/// message
+pub async fn run_review(
+    profile: &ExecutorProfileId,
+    dir: &Path,
+    context: Vec<RepoReviewContext>,
+    prompt: String,
+    timeout: Duration,
+) -> Result<String, ReviewError> {
+    let request = ReviewRequest {
+        executor_profile_id: profile.clone(),
+        context: Some(context),
+        prompt,
+        session_id: None,
+        working_dir: None,
+    };
+
+    let env = ExecutionEnv::new(
+        RepoContext::new(dir.to_path_buf(), Vec::new()),
+        false,
+        String::new(),
+    );
+
+    // Nobody is around to answer approval prompts, and the checkout is thrown
+    // away afterwards
+    let mut spawned = request
+        .spawn(dir, Arc::new(NoopExecutorApprovalService), &env)
+        .await
+        .map_err(|e| ReviewError::AgentFailed(e.to_string()))?;
+
+    let store = Arc::new(MsgStore::new());
+
+    let out = spawned
+        .child
+        .inner()
+        .stdout
+        .take()
+        .ok_or_else(|| ReviewError::AgentFailed("agent has no stdout".to_string()))?;
+    let err = spawned
+        .child
+        .inner()
+        .stderr
+        .take()
+        .ok_or_else(|| ReviewError::AgentFailed("agent has no stderr".to_string()))?;
+    let out = ReaderStream::new(out)
+        .map_ok(|chunk| LogMsg::Stdout(String::from_utf8_lossy(&chunk).into_owned()));
+    let err = ReaderStream::new(err)
+        .map_ok(|chunk| LogMsg::Stderr(String::from_utf8_lossy(&chunk).into_owned()));
+    let forwarder = store.clone().spawn_forwarder(select(out, err));
+
+    ExecutorConfigs::get_cached()
+        .get_coding_agent_or_default(profile)
+        .normalize_logs(store.clone(), dir);
+
+    let exit_signal = spawned.exit_signal.take();
+    let exit_signal = async move {
+        match exit_signal {
+            Some(exit_signal) => exit_signal.await.ok(),
+            None => std::future::pending().await,
+        }
+    };
+
+    let child = &mut spawned.child;
+    let succeeded = tokio::select! {
+        status = child.wait() => {
+            let status = status.map_err(|e| ReviewError::AgentFailed(e.to_string()))?;
+            debug!("Agent exited with {status}");
+            status.success()
+        }
+        result = exit_signal => {
+            debug!("Agent signalled exit: {result:?}");
+            let _ = kill_process_group(child).await;
+            !matches!(result, Some(ExecutorExitResult::Failure))
+        }
+        _ = tokio::time::sleep(timeout) => {
+            let _ = kill_process_group(child).await;
+            return Err(ReviewError::AgentFailed(format!(
+                "timed out after {} minutes",
+                timeout.as_secs() / 60
+            )));
+        }
+    };
+
+    let _ = forwarder.await;
+    store.push_finished();
+    wait_for_normalizers(&store).await;
+
+    match last_assistant_message(&store) {
+        Some(message) => Ok(message),
+        None if succeeded => Err(ReviewError::AgentFailed(
+            "the agent finished without writing a review".to_string(),
+        )),
+        None => Err(ReviewError::AgentFailed(last_stderr(&store))),
+    }
+}
+
+/// Normalizers run on their own tasks without a completion signal, so wait
+/// until they stop adding to the store
+async fn wait_for_normalizers(store: &MsgStore) {
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+    const MAX_POLLS: usize = 50;
+
+    let mut last_seq = store.next_seq();
+    let mut quiet_polls = 0;
+
+    for _ in 0..MAX_POLLS {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let seq = store.next_seq();
+        if seq == last_seq {
+            quiet_polls += 1;
+            if quiet_polls == 3 {
+                return;
+            }
+        } else {
+            last_seq = seq;
+            quiet_polls = 0;
+        }
+    }
+}
+
+/// Replay the normalized conversation and return its last assistant message
+fn last_assistant_message(store: &MsgStore) -> Option<String> {
+    let mut entries: BTreeMap<usize, NormalizedEntry> = BTreeMap::new();
+
+    for msg in store.get_history() {
+        if let LogMsg::JsonPatch(patch) = msg
+            && let Some((index, entry)) = extract_normalized_entry_from_patch(&patch)
+        {
+            entries.insert(index, entry);
+        }
+    }
+
+    entries
+        .into_values()
+        .rev()
+        .filter(|entry| matches!(entry.entry_type, NormalizedEntryType::AssistantMessage))
+        .map(|entry| entry.content.trim().to_string())
+        .find(|content| !content.is_empty())
+}
+
+fn last_stderr(store: &MsgStore) -> String {
+    let stderr: String = store
+        .get_history()
+        .into_iter()
+        .filter_map(|msg| match msg {
+            LogMsg::Stderr(chunk) => Some(chunk),
+            _ => None,
+        })
+        .collect();
+
+    let lines: Vec<&str> = stderr.lines().rev().take(10).collect();
+    if lines.is_empty() {
+        return "the agent exited without output".to_string();
+    }
+
+    lines.into_iter().rev().collect::<Vec<_>>().join("\n")
+}
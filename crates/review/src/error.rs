@@ -8,6 +8,12 @@ pub enum ReviewError {
     #[error("GitHub CLI is not authenticated. Run 'gh auth login' first.")]
     GhNotAuthenticated,
 
+    #[error("Azure CLI (az) is not installed. Install it from https://aka.ms/azure-cli")]
+    AzNotInstalled,
+
+    #[error("Azure CLI is not authenticated. Run 'az login' first.")]
+    AzNotAuthenticated,
+
     #[error("Invalid GitHub PR URL format. Expected: https://github.com/owner/repo/pull/123")]
     InvalidPrUrl,
 
@@ -35,9 +41,26 @@ pub enum ReviewError {
     #[error("Review timed out after 10 minutes")]
     Timeout,
 
-    #[error("Failed to discover Claude Code sessions: {0}")]
+    #[error("Failed to discover agent sessions: {0}")]
     SessionDiscoveryFailed(String),
 
-    #[error("Failed to parse JSONL file: {0}")]
-    JsonlParseFailed(String),
+    #[error("Failed to parse session file: {0}")]
+    SessionParseFailed(String),
+
+    #[error(
+        "Expected a pull request URL. Use --local to review branches, commit ranges and patches."
+    )]
+    PrUrlRequired,
+
+    #[error("Invalid review target: {0}")]
+    InvalidTarget(String),
+
+    #[error("Git command failed: {0}")]
+    GitFailed(String),
+
+    #[error("Coding agent failed: {0}")]
+    AgentFailed(String),
+
+    #[error("Failed to write review report: {0}")]
+    ReportFailed(String),
 }
@@ -225,11 +225,14 @@ pub fn clone_repo(owner: &str, repo: &str, target_dir: &Path) -> Result<(), Revi
 /// This is more reliable than `gh pr checkout` because it works even when
 /// the PR's branch has been deleted (common for merged PRs).
 pub fn checkout_commit(commit_sha: &str, repo_dir: &Path) -> Result<(), ReviewError> {
-    debug!("Fetching commit {commit_sha} in {}", repo_dir.display());
-
     // First, fetch the specific commit
+    fetch_commit(commit_sha, repo_dir)?;
+
+    debug!("Checking out commit {commit_sha}");
+
+    // Then checkout the commit
     let output = Command::new("git")
-        .args(["fetch", "origin", commit_sha])
+        .args(["checkout", commit_sha])
         .current_dir(repo_dir)
         .output()
         .map_err(|e| ReviewError::CheckoutFailed(e.to_string()))?;
@@ -237,15 +240,22 @@ pub fn checkout_commit(commit_sha: &str, repo_dir: &Path) -> Result<(), ReviewEr
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(ReviewError::CheckoutFailed(format!(
-            "Failed to fetch commit: {stderr}"
+            "Failed to checkout commit: {stderr}"
         )));
     }
 
-    debug!("Checking out commit {commit_sha}");
+    Ok(())
+}
+
+/// Fetch a specific commit from `origin`
+///
+/// Fresh clones usually have the PR base already, but it may live on a
+/// branch that was not cloned or has since been force-pushed.
+pub fn fetch_commit(commit_sha: &str, repo_dir: &Path) -> Result<(), ReviewError> {
+    debug!("Fetching commit {commit_sha} in {}", repo_dir.display());
 
-    // Then checkout the commit
     let output = Command::new("git")
-        .args(["checkout", commit_sha])
+        .args(["fetch", "origin", commit_sha])
         .current_dir(repo_dir)
         .output()
         .map_err(|e| ReviewError::CheckoutFailed(e.to_string()))?;
@@ -253,7 +263,7 @@ pub fn checkout_commit(commit_sha: &str, repo_dir: &Path) -> Result<(), ReviewEr
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(ReviewError::CheckoutFailed(format!(
-            "Failed to checkout commit: {stderr}"
+            "Failed to fetch commit: {stderr}"
         )));
     }
 
@@ -0,0 +1,158 @@
+//! Plumbing for reviewing code from a local repository.
+//!
+//! Branches, commit ranges and patches are reviewed from a throwaway clone so
+//! the user's working copy is left alone.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tempfile::TempDir;
+use tracing::debug;
+
+use crate::error::ReviewError;
+
+/// The code under review, cloned into a temporary directory that is removed
+/// when the checkout is dropped
+pub struct Checkout {
+    pub dir: PathBuf,
+    /// Owner or organization the repository belongs to
+    pub owner: String,
+    pub repo_name: String,
+    pub title: String,
+    pub description: String,
+    pub base_commit: String,
+    pub head_commit: String,
+    /// Branch used to find matching coding agent sessions
+    pub branch: Option<String>,
+    /// Short name for the review, used for the default report file name
+    pub slug: String,
+    /// Owns the clone; dropping it removes the directory
+    pub(crate) temp_dir: TempDir,
+}
+
+/// Run git in `dir` and return its trimmed stdout
+pub(crate) fn git(dir: &Path, args: &[&str]) -> Result<String, ReviewError> {
+    debug!("git {}", args.join(" "));
+
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| ReviewError::GitFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ReviewError::GitFailed(format!(
+            "git {}: {}",
+            args.join(" "),
+            stderr.trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Top level of the repository containing `path`
+pub(crate) fn repo_root(path: &Path) -> Result<PathBuf, ReviewError> {
+    git(path, &["rev-parse", "--show-toplevel"])
+        .map(PathBuf::from)
+        .map_err(|_| {
+            ReviewError::InvalidTarget(format!("{} is not inside a git repository", path.display()))
+        })
+}
+
+pub(crate) fn rev_parse(repo_root: &Path, rev: &str) -> Result<String, ReviewError> {
+    git(
+        repo_root,
+        &["rev-parse", "--verify", &format!("{rev}^{{commit}}")],
+    )
+    .map_err(|_| ReviewError::InvalidTarget(format!("'{rev}' is not a branch or commit")))
+}
+
+pub(crate) fn repo_name(repo_root: &Path) -> String {
+    repo_root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "repo".to_string())
+}
+
+/// Clone a local repository into a temporary directory and check out `commit`
+///
+/// A plain local clone hardlinks the object store, so it is cheap, leaves the
+/// user's working copy alone and can be archived on its own.
+pub(crate) fn clone_local(
+    repo_root: &Path,
+    commit: &str,
+) -> Result<(TempDir, PathBuf), ReviewError> {
+    let temp_dir = TempDir::new().map_err(|e| ReviewError::CloneFailed(e.to_string()))?;
+    let dir = temp_dir.path().join(repo_name(repo_root));
+
+    let source = repo_root
+        .to_str()
+        .ok_or_else(|| ReviewError::CloneFailed("Invalid repository path".to_string()))?;
+    let target = dir
+        .to_str()
+        .ok_or_else(|| ReviewError::CloneFailed("Invalid target path".to_string()))?;
+
+    git(
+        temp_dir.path(),
+        &["clone", "--quiet", "--no-checkout", source, target],
+    )
+    .map_err(|e| ReviewError::CloneFailed(e.to_string()))?;
+    git(&dir, &["checkout", "--quiet", "--detach", commit])
+        .map_err(|e| ReviewError::CheckoutFailed(e.to_string()))?;
+
+    Ok((temp_dir, dir))
+}
+
+/// Subjects of the commits in `base..head`, standing in for a PR description
+pub(crate) fn commit_subjects(dir: &Path, base: &str, head: &str) -> Result<String, ReviewError> {
+    git(
+        dir,
+        &[
+            "log",
+            "--reverse",
+            "--format=- %s",
+            &format!("{base}..{head}"),
+        ],
+    )
+}
+
+pub(crate) fn slugify(value: &str) -> String {
+    let slug: String = value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// The branch `origin/HEAD` points at, falling back to `main`
+pub(crate) fn default_branch(repo_root: &Path) -> String {
+    git(
+        repo_root,
+        &[
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            "refs/remotes/origin/HEAD",
+        ],
+    )
+    .unwrap_or_else(|_| "main".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("repo-feature/login"), "repo-feature-login");
+        assert_eq!(slugify("owner/repo#42"), "owner-repo-42");
+    }
+}
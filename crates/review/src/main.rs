@@ -1,26 +1,32 @@
+mod agent;
 mod api;
 mod archive;
-mod claude_session;
 mod config;
 mod error;
 mod github;
+mod local;
+mod report;
 mod session_selector;
+mod sessions;
+mod source;
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use anyhow::Result;
 use api::{ReviewApiClient, ReviewStatus, StartRequest};
 use clap::Parser;
 use error::ReviewError;
-use github::{checkout_commit, clone_repo, get_pr_info, parse_pr_url};
+use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use indicatif::{ProgressBar, ProgressStyle};
-use tempfile::TempDir;
+use sessions::AgentSession;
+use source::{ReviewSource, Source, SourceOptions};
 use tracing::debug;
 use tracing_subscriber::EnvFilter;
 
 const DEFAULT_API_URL: &str = "https://api.vibekanban.com";
 const POLL_INTERVAL: Duration = Duration::from_secs(10);
 const TIMEOUT: Duration = Duration::from_secs(600); // 10 minutes
+const LOCAL_TIMEOUT: Duration = Duration::from_secs(1800); // 30 minutes
 
 const BANNER: &str = r#"
 ██████╗ ███████╗██╗   ██╗██╗███████╗██╗    ██╗   ███████╗ █████╗ ███████╗████████╗
@@ -35,13 +41,40 @@ const BANNER: &str = r#"
 #[derive(Parser, Debug)]
 #[command(name = "review")]
 #[command(
-    about = "Vibe-Kanban Review helps you review GitHub pull requests by turning them into a clear, story-driven summary instead of a wall of diffs. You provide a pull request URL, optionally link a Claude Code project for additional context, and it builds a narrative that highlights key events and important decisions, helping you prioritise what actually needs attention. It's particularly useful when reviewing large amounts of AI-generated code. Note that code is uploaded to and processed on Vibe-Kanban servers using AI."
+    about = "Vibe-Kanban Review helps you review GitHub and Azure DevOps pull requests by turning them into a clear, story-driven summary instead of a wall of diffs. You provide a pull request URL, optionally link a Claude Code, Codex, Opencode or Gemini project for additional context, and it builds a narrative that highlights key events and important decisions, helping you prioritise what actually needs attention. It's particularly useful when reviewing large amounts of AI-generated code. Note that code is uploaded to and processed on Vibe-Kanban servers using AI, unless --local is used to run the review with a coding agent installed on this machine."
 )]
 #[command(version)]
 struct Args {
-    /// GitHub PR URL (e.g., https://github.com/owner/repo/pull/123)
+    /// GitHub or Azure DevOps PR URL (e.g., https://github.com/owner/repo/pull/123). With
+    /// --local, this can also be a branch, a commit range (e.g., main..HEAD) or a patch file
+    #[arg(value_name = "TARGET")]
     pr_url: String,
 
+    /// Review with a locally installed coding agent instead of uploading the code
+    #[arg(long, default_value_t = false)]
+    local: bool,
+
+    /// Coding agent used for --local reviews (e.g., CLAUDE_CODE, CODEX, GEMINI)
+    #[arg(long, default_value = "CLAUDE_CODE", value_parser = parse_agent, requires = "local")]
+    agent: BaseCodingAgent,
+
+    /// Profile variant of the coding agent (e.g., PLAN)
+    #[arg(long, requires = "local")]
+    variant: Option<String>,
+
+    /// Repository that branches, commit ranges and patches are read from
+    #[arg(long, requires = "local")]
+    repo: Option<PathBuf>,
+
+    /// Branch that branches are compared against (defaults to origin's default branch), or the
+    /// commit a patch applies to (defaults to HEAD)
+    #[arg(long, requires = "local")]
+    base: Option<String>,
+
+    /// Where to write a --local review. A .html extension writes HTML, anything else Markdown
+    #[arg(short, long, requires = "local")]
+    output: Option<PathBuf>,
+
     /// Enable verbose output
     #[arg(short, long, default_value_t = false)]
     verbose: bool,
@@ -51,6 +84,40 @@ struct Args {
     api_url: String,
 }
 
+/// Accept agent names in any case, with dashes or underscores
+fn parse_agent(value: &str) -> Result<BaseCodingAgent, String> {
+    let normalized = value.replace('-', "_").to_ascii_uppercase();
+    normalized
+        .parse()
+        .map_err(|_| format!("unknown coding agent '{value}'"))
+}
+
+fn source_options(args: &Args) -> SourceOptions {
+    SourceOptions {
+        repo: args.repo.clone(),
+        base: args.base.clone(),
+    }
+}
+
+/// Offer to attach coding agent sessions for `branch`, if there is one
+fn select_sessions(branch: Option<&str>) -> Option<Vec<AgentSession>> {
+    match session_selector::select_session(branch?) {
+        Ok(session_selector::SessionSelection::Selected(sessions)) => {
+            println!("  Selected {} session(s)", sessions.len());
+            Some(sessions)
+        }
+        Ok(session_selector::SessionSelection::Skipped) => {
+            println!("  Skipping project attachment");
+            None
+        }
+        Err(e) => {
+            debug!("Session selection error: {}", e);
+            println!("  No sessions found");
+            None
+        }
+    }
+}
+
 fn show_disclaimer() {
     println!();
     println!(
@@ -117,12 +184,20 @@ async fn main() -> Result<()> {
 
     println!("{}", BANNER);
 
-    show_disclaimer();
+    // Nothing leaves the machine in local mode
+    if !args.local {
+        show_disclaimer();
+    }
 
     debug!("Args: {:?}", args);
 
     // Run the main flow and handle errors
-    if let Err(e) = run(args).await {
+    let result = if args.local {
+        run_local(args).await
+    } else {
+        run(args).await
+    };
+    if let Err(e) = result {
         eprintln!("Error: {e}");
         std::process::exit(1);
     }
@@ -137,87 +212,61 @@ async fn run(args: Args) -> Result<(), ReviewError> {
 
     // 2. Parse PR URL
     let spinner = create_spinner("Parsing PR URL...");
-    let (owner, repo, pr_number) = parse_pr_url(&args.pr_url)?;
-    spinner.finish_with_message(format!("PR: {owner}/{repo}#{pr_number}"));
-
-    // 3. Get PR info
-    let spinner = create_spinner("Fetching PR information...");
-    let pr_info = get_pr_info(&owner, &repo, pr_number)?;
-    spinner.finish_with_message(format!("PR: {}", pr_info.title));
-
-    // 4. Select Claude Code session (optional)
-    let session_files = match session_selector::select_session(&pr_info.head_ref_name) {
-        Ok(session_selector::SessionSelection::Selected(files)) => {
-            println!("  Selected {} session file(s)", files.len());
-            Some(files)
-        }
-        Ok(session_selector::SessionSelection::Skipped) => {
-            println!("  Skipping project attachment");
-            None
-        }
-        Err(e) => {
-            debug!("Session selection error: {}", e);
-            println!("  No sessions found");
-            None
-        }
-    };
+    let source = Source::parse(&args.pr_url, &source_options(&args))?;
+    let pr_url = source.url().ok_or(ReviewError::PrUrlRequired)?;
+    spinner.finish_with_message(format!("PR: {}", source.label()));
 
-    // 5. Clone repository to temp directory
-    let temp_dir = TempDir::new().map_err(|e| ReviewError::CloneFailed(e.to_string()))?;
-    let repo_dir = temp_dir.path().join(&repo);
+    // 3. Clone repository and check out the PR head commit
+    let spinner = create_spinner("Fetching PR and cloning repository...");
+    let checkout = source.checkout()?;
+    spinner.finish_with_message(format!("PR: {}", checkout.title));
 
-    let spinner = create_spinner("Cloning repository...");
-    clone_repo(&owner, &repo, &repo_dir)?;
-    spinner.finish_with_message("Repository cloned");
+    // 4. Select coding agent sessions (optional)
+    let selected_sessions = select_sessions(checkout.branch.as_deref());
 
-    // 6. Checkout PR head commit
-    let spinner = create_spinner("Checking out PR...");
-    checkout_commit(&pr_info.head_commit, &repo_dir)?;
-    spinner.finish_with_message("PR checked out");
-
-    // 7. Create tarball (with optional session data)
+    // 5. Create tarball (with optional session data)
     let spinner = create_spinner("Creating archive...");
 
     // If sessions were selected, write .agent-messages.json to repo root
-    if let Some(ref files) = session_files {
-        let json_content = claude_session::concatenate_sessions_to_json(files)?;
-        let agent_messages_path = repo_dir.join(".agent-messages.json");
+    if let Some(ref sessions) = selected_sessions {
+        let json_content = sessions::concatenate_sessions_to_json(sessions)?;
+        let agent_messages_path = checkout.dir.join(agent::AGENT_MESSAGES_FILE);
         std::fs::write(&agent_messages_path, json_content)
             .map_err(|e| ReviewError::ArchiveFailed(e.to_string()))?;
     }
 
-    let payload = archive::create_tarball(&repo_dir)?;
+    let payload = source.archive(&checkout)?;
     let size_mb = payload.len() as f64 / 1_048_576.0;
     spinner.finish_with_message(format!("Archive created ({size_mb:.2} MB)"));
 
-    // 8. Initialize review
+    // 6. Initialize review
     let client = ReviewApiClient::new(args.api_url.clone());
     let spinner = create_spinner("Initializing review...");
-    let init_response = client.init(&args.pr_url, &email, &pr_info.title).await?;
+    let init_response = client.init(&pr_url, &email, &checkout.title).await?;
     spinner.finish_with_message(format!("Review ID: {}", init_response.review_id));
 
-    // 9. Upload archive
+    // 7. Upload archive
     let spinner = create_spinner("Uploading archive...");
     client.upload(&init_response.upload_url, payload).await?;
     spinner.finish_with_message("Upload complete");
 
-    // 10. Start review
+    // 8. Start review
     let spinner = create_spinner("Starting review...");
     let codebase_url = format!("r2://{}", init_response.object_key);
     client
         .start(StartRequest {
             id: init_response.review_id.to_string(),
-            title: pr_info.title,
-            description: pr_info.description,
-            org: pr_info.owner,
-            repo: pr_info.repo,
+            title: checkout.title.clone(),
+            description: checkout.description.clone(),
+            org: checkout.owner.clone(),
+            repo: checkout.repo_name.clone(),
             codebase_url,
-            base_commit: pr_info.base_commit,
+            base_commit: checkout.base_commit.clone(),
         })
         .await?;
     spinner.finish_with_message(format!("Review started, we'll send you an email at {} when the review is ready. This can take a few minutes, you may now close the terminal", email));
 
-    // 11. Poll for completion
+    // 9. Poll for completion
     let spinner = create_spinner("Review in progress...");
     let start_time = std::time::Instant::now();
 
@@ -251,10 +300,60 @@ async fn run(args: Args) -> Result<(), ReviewError> {
         }
     }
 
-    // 12. Print result URL
+    // 10. Print result URL
     let review_url = client.review_url(&init_response.review_id.to_string());
     println!("\nReview available at:");
     println!("  {review_url}");
 
     Ok(())
 }
+
+async fn run_local(args: Args) -> Result<(), ReviewError> {
+    // 1. Resolve what to review
+    let spinner = create_spinner("Resolving review target...");
+    let source = Source::parse(&args.pr_url, &source_options(&args))?;
+    spinner.finish_with_message(format!("Target: {}", source.label()));
+
+    // 2. Check out the code under review
+    let spinner = create_spinner("Preparing checkout...");
+    let checkout = source.checkout()?;
+    spinner.finish_with_message(format!(
+        "Reviewing {} ({}..{})",
+        checkout.title,
+        report::short_sha(&checkout.base_commit),
+        report::short_sha(&checkout.head_commit),
+    ));
+
+    // 3. Select coding agent sessions (optional)
+    let selected_sessions = select_sessions(checkout.branch.as_deref());
+
+    if let Some(ref sessions) = selected_sessions {
+        let json_content = sessions::concatenate_sessions_to_json(sessions)?;
+        std::fs::write(checkout.dir.join(agent::AGENT_MESSAGES_FILE), json_content)
+            .map_err(|e| ReviewError::AgentFailed(e.to_string()))?;
+    }
+
+    // 4. Run the review with the local coding agent
+    let profile = ExecutorProfileId {
+        executor: args.agent,
+        variant: args.variant.clone(),
+    };
+    let (context, prompt) = agent::review_prompt(&checkout, selected_sessions.is_some());
+
+    let spinner = create_spinner(&format!("Reviewing with {profile}..."));
+    let review = agent::run_review(&profile, &checkout.dir, context, prompt, LOCAL_TIMEOUT)
+        .await
+        .inspect_err(|_| spinner.finish_with_message("Review failed"))?;
+    spinner.finish_with_message("Review completed!");
+
+    // 5. Write the report
+    let output = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("review-{}.md", checkout.slug)));
+    report::write_report(&output, &checkout, &profile.to_string(), &review)?;
+
+    println!("\nReview written to:");
+    println!("  {}", output.display());
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use pulldown_cmark::{Options, Parser, html};
+
+use crate::{error::ReviewError, source::Checkout};
+
+/// Write a finished review to `path`
+///
+/// Paths ending in `.html` or `.htm` get a standalone HTML page, anything
+/// else is written as Markdown.
+pub fn write_report(
+    path: &Path,
+    checkout: &Checkout,
+    agent: &str,
+    review: &str,
+) -> Result<(), ReviewError> {
+    let markdown = render_markdown(checkout, agent, review);
+
+    let is_html = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+
+    let contents = if is_html {
+        render_html(&checkout.title, &markdown)
+    } else {
+        markdown
+    };
+
+    std::fs::write(path, contents).map_err(|e| ReviewError::ReportFailed(e.to_string()))
+}
+
+fn render_markdown(checkout: &Checkout, agent: &str, review: &str) -> String {
+    format!(
+        "# Review: {title}\n\n\
+         | | |\n\
+         |---|---|\n\
+         | Repository | {repo} |\n\
+         | Changes | `{base}..{head}` |\n\
+         | Reviewed by | {agent} |\n\n\
+         {review}\n",
+        title = checkout.title,
+        repo = checkout.repo_name,
+        base = short_sha(&checkout.base_commit),
+        head = short_sha(&checkout.head_commit),
+        review = review.trim(),
+    )
+}
+
+fn render_html(title: &str, markdown: &str) -> String {
+    let parser = Parser::new_ext(markdown, Options::ENABLE_TABLES);
+    let mut body = String::new();
+    html::push_html(&mut body, parser);
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Review: {title}</title>
+<style>
+body {{ max-width: 52rem; margin: 2rem auto; padding: 0 1rem; font: 16px/1.6 system-ui, sans-serif; color: #1f2328; }}
+code, pre {{ font-family: ui-monospace, monospace; font-size: 0.9em; background: #f6f8fa; border-radius: 4px; }}
+code {{ padding: 0.1em 0.3em; }}
+pre {{ padding: 1rem; overflow-x: auto; }}
+pre code {{ padding: 0; }}
+table {{ border-collapse: collapse; }}
+td, th {{ border: 1px solid #d0d7de; padding: 0.3rem 0.7rem; text-align: left; }}
+h2 {{ border-bottom: 1px solid #d0d7de; padding-bottom: 0.3rem; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        title = escape_html(title),
+    )
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
@@ -1,24 +1,24 @@
-use std::{path::PathBuf, time::SystemTime};
+use std::time::SystemTime;
 
 use dialoguer::{Select, theme::ColorfulTheme};
 use tracing::debug;
 
 use crate::{
-    claude_session::{
-        ClaudeProject, discover_projects, discover_sessions, find_projects_by_branch,
-    },
     error::ReviewError,
+    sessions::{
+        AgentProject, AgentSession, agent_label, discover_projects, find_projects_by_branch,
+    },
 };
 
 /// Result of session selection process
 pub enum SessionSelection {
-    /// User selected session files to include (all sessions from a project)
-    Selected(Vec<PathBuf>),
+    /// User selected sessions to include (all sessions from a project)
+    Selected(Vec<AgentSession>),
     /// User chose to skip session attachment
     Skipped,
 }
 
-/// Prompt user to select a Claude Code project
+/// Prompt user to select a coding agent project
 ///
 /// Flow:
 /// 1. Try auto-match by branch name
@@ -29,31 +29,30 @@ pub enum SessionSelection {
 /// When a project is selected, ALL sessions from that project are included.
 pub fn select_session(pr_branch: &str) -> Result<SessionSelection, ReviewError> {
     debug!(
-        "Looking for Claude Code projects matching branch: {}",
+        "Looking for coding agent projects matching branch: {}",
         pr_branch
     );
 
-    let projects = discover_projects()?;
+    let projects = discover_projects();
 
     if projects.is_empty() {
-        debug!("No Claude Code projects found");
+        debug!("No coding agent projects found");
         return Ok(SessionSelection::Skipped);
     }
 
     // Try auto-match by branch
-    let matches = find_projects_by_branch(&projects, pr_branch)?;
+    let matches = find_projects_by_branch(&projects, pr_branch);
 
-    if !matches.is_empty() {
+    if let Some(project) = matches.first() {
         // Found a matching project, ask for confirmation
-        let (project, sessions) = &matches[0];
-
         println!();
         println!();
         println!(
-            "Found matching Claude Code project for branch '{}'",
+            "Found matching {} project for branch '{}'",
+            agent_label(project.agent),
             pr_branch
         );
-        println!("  Project: {}", project.name);
+        println!("  Project: {} ({})", project.name, project.path.display());
         if let Some(ref prompt) = project.first_prompt {
             println!("  \"{}\"", prompt);
         }
@@ -79,8 +78,7 @@ pub fn select_session(pr_branch: &str) -> Result<SessionSelection, ReviewError>
         match selection {
             0 => {
                 // Yes, use all sessions from this project
-                let paths: Vec<PathBuf> = sessions.iter().map(|s| s.path.clone()).collect();
-                return Ok(SessionSelection::Selected(paths));
+                return Ok(SessionSelection::Selected(project.sessions.clone()));
             }
             2 => {
                 // Skip
@@ -97,7 +95,7 @@ pub fn select_session(pr_branch: &str) -> Result<SessionSelection, ReviewError>
 }
 
 /// Manual project selection - returns all sessions from selected project
-fn select_project(projects: &[ClaudeProject]) -> Result<SessionSelection, ReviewError> {
+fn select_project(projects: &[AgentProject]) -> Result<SessionSelection, ReviewError> {
     // Build project list with rich metadata
     let mut items: Vec<String> = Vec::new();
     items.push("Skip (no project)\n".to_string());
@@ -107,7 +105,7 @@ fn select_project(projects: &[ClaudeProject]) -> Result<SessionSelection, Review
     println!();
     println!();
     let selection = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Select a Claude Code project to improve review quality")
+        .with_prompt("Select a coding agent project to improve review quality")
         .items(&items)
         .default(0)
         .max_length(5)
@@ -119,16 +117,13 @@ fn select_project(projects: &[ClaudeProject]) -> Result<SessionSelection, Review
         return Ok(SessionSelection::Skipped);
     }
 
-    let project = &projects[selection];
-    let sessions = discover_sessions(project)?;
-
-    // Return all session paths from this project
-    let paths: Vec<PathBuf> = sessions.iter().map(|s| s.path.clone()).collect();
-    Ok(SessionSelection::Selected(paths))
+    // Return all sessions from this project
+    let project = &projects[selection - 1];
+    Ok(SessionSelection::Selected(project.sessions.clone()))
 }
 
 /// Format a project item for display in the selection list
-fn format_project_item(project: &ClaudeProject) -> String {
+fn format_project_item(project: &AgentProject) -> String {
     let prompt_line = project
         .first_prompt
         .as_ref()
@@ -142,8 +137,9 @@ fn format_project_item(project: &ClaudeProject) -> String {
         .unwrap_or_else(|| "no branch".to_string());
 
     format!(
-        "{}{}\n  {} · {} session{} · {}\n",
+        "{} ({}){}\n  {} · {} session{} · {}\n",
         project.name,
+        agent_label(project.agent),
         prompt_line,
         branch,
         project.session_count,
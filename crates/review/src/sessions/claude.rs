@@ -0,0 +1,329 @@
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use executors::executors::BaseCodingAgent;
+use serde::Deserialize;
+use tracing::debug;
+
+use super::{
+    AgentMessage, AgentProject, AgentSession, MessageRole, SessionSource, modified_at,
+    parse_timestamp, truncate_string,
+};
+use crate::error::ReviewError;
+
+/// Claude Code sessions, stored as JSONL files under `~/.claude/projects/<encoded-path>/`
+pub struct ClaudeSessions;
+
+/// A JSONL record for metadata extraction
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonlRecord {
+    git_branch: Option<String>,
+    message: Option<JsonlMessage>,
+}
+
+/// Message within a JSONL record
+#[derive(Debug, Deserialize)]
+struct JsonlMessage {
+    role: Option<String>,
+    content: Option<serde_json::Value>,
+}
+
+/// Get the Claude projects directory path (~/.claude/projects)
+pub fn get_claude_projects_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".claude").join("projects"))
+}
+
+impl SessionSource for ClaudeSessions {
+    fn agent(&self) -> BaseCodingAgent {
+        BaseCodingAgent::ClaudeCode
+    }
+
+    fn discover_projects(&self) -> Result<Vec<AgentProject>, ReviewError> {
+        let projects_dir = get_claude_projects_dir().ok_or_else(|| {
+            ReviewError::SessionDiscoveryFailed("Could not find home directory".into())
+        })?;
+
+        if !projects_dir.exists() {
+            debug!(
+                "Claude projects directory does not exist: {:?}",
+                projects_dir
+            );
+            return Ok(Vec::new());
+        }
+
+        let mut projects = Vec::new();
+
+        let entries = fs::read_dir(&projects_dir)
+            .map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+            let path = entry.path();
+
+            if !path.is_dir() {
+                continue;
+            }
+
+            // Extract a friendly name from the directory name
+            // e.g., "-private-var-...-worktrees-a04a-store-payloads-i" -> "store-payloads-i"
+            let dir_name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+
+            let name = extract_project_name(dir_name);
+            let sessions = discover_sessions_in_dir(&path)?;
+
+            // Skips projects with no sessions
+            if let Some(project) = AgentProject::from_sessions(self.agent(), path, name, sessions) {
+                projects.push(project);
+            }
+        }
+
+        // Sort by modification time, most recent first
+        projects.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+        Ok(projects)
+    }
+
+    /// Filters to include only:
+    /// - User messages (role = "user") with string content or text blocks
+    /// - Assistant messages with text content (role = "assistant" with content[].type = "text")
+    ///
+    /// Other content blocks (tool_use, tool_result, etc.) are filtered out.
+    fn read_messages(&self, session: &AgentSession) -> Result<Vec<AgentMessage>, ReviewError> {
+        let path = &session.path;
+        let file = File::open(path)
+            .map_err(|e| ReviewError::SessionParseFailed(format!("{}: {}", path.display(), e)))?;
+        let reader = BufReader::new(file);
+        let mut messages = Vec::new();
+
+        for (line_num, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| {
+                ReviewError::SessionParseFailed(format!(
+                    "{}:{}: {}",
+                    path.display(),
+                    line_num + 1,
+                    e
+                ))
+            })?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record: serde_json::Value = serde_json::from_str(&line).map_err(|e| {
+                ReviewError::SessionParseFailed(format!(
+                    "{}:{}: {}",
+                    path.display(),
+                    line_num + 1,
+                    e
+                ))
+            })?;
+
+            if let Some(message) = extract_filtered_message(&record) {
+                messages.push(message);
+            }
+        }
+
+        Ok(messages)
+    }
+}
+
+/// Extract a friendly project name from the Claude directory name
+fn extract_project_name(dir_name: &str) -> String {
+    // Directory names look like:
+    // "-private-var-folders-m1-9q-ct1913z10v6wbnv54j25r0000gn-T-vibe-kanban-worktrees-a04a-store-payloads-i"
+    // We want to extract the meaningful part after "worktrees-"
+    if let Some(idx) = dir_name.find("worktrees-") {
+        let after_worktrees = &dir_name[idx + "worktrees-".len()..];
+        // Skip the short hash prefix (e.g., "a04a-")
+        if let Some(dash_idx) = after_worktrees.find('-') {
+            return after_worktrees[dash_idx + 1..].to_string();
+        }
+        return after_worktrees.to_string();
+    }
+
+    // Fallback: use last segment after the final dash
+    dir_name.rsplit('-').next().unwrap_or(dir_name).to_string()
+}
+
+/// Discover sessions in a directory, excluding agent-* files
+fn discover_sessions_in_dir(dir_path: &Path) -> Result<Vec<AgentSession>, ReviewError> {
+    let mut sessions = Vec::new();
+
+    let entries =
+        fs::read_dir(dir_path).map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+        let path = entry.path();
+
+        // Only process .jsonl files
+        if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+            continue;
+        }
+
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+        // Skip agent-* files
+        if file_name.starts_with("agent-") {
+            continue;
+        }
+
+        // Extract metadata from the JSONL file
+        let (git_branch, first_prompt) = extract_session_metadata(&path);
+
+        sessions.push(AgentSession {
+            agent: BaseCodingAgent::ClaudeCode,
+            modified_at: modified_at(&path),
+            path,
+            git_branch,
+            first_prompt,
+        });
+    }
+
+    Ok(sessions)
+}
+
+/// Extract session metadata from a JSONL file
+/// Returns: (git_branch, first_prompt)
+fn extract_session_metadata(path: &Path) -> (Option<String>, Option<String>) {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(_) => return (None, None),
+    };
+    let reader = BufReader::new(file);
+
+    let mut git_branch: Option<String> = None;
+    let mut first_prompt: Option<String> = None;
+
+    // Check first 50 lines for metadata
+    for line in reader.lines().take(50) {
+        let line = match line {
+            Ok(l) => l,
+            Err(_) => continue,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Ok(record) = serde_json::from_str::<JsonlRecord>(&line) {
+            // Extract git branch if not already found
+            if git_branch.is_none() && record.git_branch.is_some() {
+                git_branch = record.git_branch;
+            }
+
+            // Extract first user prompt if not already found
+            if first_prompt.is_none()
+                && let Some(ref message) = record.message
+                && message.role.as_deref() == Some("user")
+                && let Some(ref content) = message.content
+            {
+                // Content can be a string or an array
+                if let Some(text) = content.as_str() {
+                    first_prompt = Some(truncate_string(text, 60));
+                }
+            }
+
+            // Stop early if we have both
+            if git_branch.is_some() && first_prompt.is_some() {
+                break;
+            }
+        }
+    }
+
+    (git_branch, first_prompt)
+}
+
+/// Extract and filter a message from a JSONL record.
+///
+/// Returns Some(message) if the record should be included, None otherwise.
+/// - User messages: include if content is a string, or if content array has text blocks
+/// - Assistant messages: include if content array has text blocks (filter out tool_use, etc.)
+fn extract_filtered_message(record: &serde_json::Value) -> Option<AgentMessage> {
+    let message = record.get("message")?;
+    let content = message.get("content")?;
+    let role = match message.get("role")?.as_str()? {
+        "user" => MessageRole::User,
+        "assistant" => MessageRole::Assistant,
+        _ => return None,
+    };
+
+    let texts: Vec<String> = match content {
+        // Plain string content is only sent by the user
+        serde_json::Value::String(text) if role == MessageRole::User => vec![text.clone()],
+        serde_json::Value::Array(blocks) => blocks
+            .iter()
+            .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("text"))
+            .filter_map(|block| block.get("text").and_then(|t| t.as_str()))
+            .map(str::to_string)
+            .collect(),
+        _ => return None,
+    };
+
+    let timestamp = record
+        .get("timestamp")
+        .and_then(|v| v.as_str())
+        .and_then(parse_timestamp);
+
+    AgentMessage::from_texts(BaseCodingAgent::ClaudeCode, role, texts, timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::ContentBlock;
+
+    #[test]
+    fn test_extract_project_name() {
+        assert_eq!(
+            extract_project_name(
+                "-private-var-folders-m1-9q-ct1913z10v6wbnv54j25r0000gn-T-vibe-kanban-worktrees-a04a-store-payloads-i"
+            ),
+            "store-payloads-i"
+        );
+
+        assert_eq!(
+            extract_project_name(
+                "-private-var-folders-m1-9q-ct1913z10v6wbnv54j25r0000gn-T-vibe-kanban-worktrees-1ff1-new-rust-binary"
+            ),
+            "new-rust-binary"
+        );
+    }
+
+    #[test]
+    fn test_extract_filtered_message() {
+        let record = serde_json::json!({
+            "timestamp": "2025-01-01T10:00:00.000Z",
+            "message": {
+                "role": "assistant",
+                "content": [
+                    {"type": "text", "text": "Adding the form"},
+                    {"type": "tool_use", "name": "Edit", "input": {}}
+                ]
+            }
+        });
+        let message = extract_filtered_message(&record).unwrap();
+        assert_eq!(message.role, MessageRole::Assistant);
+        assert_eq!(
+            message.content,
+            vec![ContentBlock::Text {
+                text: "Adding the form".to_string()
+            }]
+        );
+
+        // Tool results carry no text
+        let record = serde_json::json!({
+            "message": {
+                "role": "user",
+                "content": [{"type": "tool_result", "content": "ok"}]
+            }
+        });
+        assert!(extract_filtered_message(&record).is_none());
+    }
+}
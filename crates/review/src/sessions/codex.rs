@@ -0,0 +1,290 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use executors::executors::{BaseCodingAgent, codex::codex_home};
+use serde_json::Value;
+use tracing::debug;
+
+use super::{
+    AgentMessage, AgentProject, AgentSession, ContentBlock, MessageRole, SessionSource,
+    modified_at, parse_timestamp, project_name_from_path, truncate_string,
+};
+use crate::error::ReviewError;
+
+/// Codex sessions, stored as rollout JSONL files under
+/// `$CODEX_HOME/sessions/YYYY/MM/DD/`
+///
+/// Sessions are grouped into projects by the working directory recorded in
+/// their `session_meta` line.
+pub struct CodexSessions;
+
+/// Text Codex adds to the conversation itself rather than the user typing it
+const CONTEXT_PREFIXES: &[&str] = &[
+    "<environment_context>",
+    "<user_instructions>",
+    "# AGENTS.md instructions",
+];
+
+impl SessionSource for CodexSessions {
+    fn agent(&self) -> BaseCodingAgent {
+        BaseCodingAgent::Codex
+    }
+
+    fn discover_projects(&self) -> Result<Vec<AgentProject>, ReviewError> {
+        let sessions_dir = codex_home()
+            .ok_or_else(|| {
+                ReviewError::SessionDiscoveryFailed("Could not find home directory".into())
+            })?
+            .join("sessions");
+
+        if !sessions_dir.exists() {
+            debug!(
+                "Codex sessions directory does not exist: {:?}",
+                sessions_dir
+            );
+            return Ok(Vec::new());
+        }
+
+        let mut rollouts = Vec::new();
+        collect_rollouts(&sessions_dir, &mut rollouts)?;
+
+        let mut by_cwd: HashMap<PathBuf, Vec<AgentSession>> = HashMap::new();
+        for path in rollouts {
+            let Some(metadata) = read_session_metadata(&path) else {
+                continue;
+            };
+
+            by_cwd.entry(metadata.cwd).or_default().push(AgentSession {
+                agent: BaseCodingAgent::Codex,
+                modified_at: modified_at(&path),
+                path,
+                git_branch: metadata.git_branch,
+                first_prompt: metadata.first_prompt,
+            });
+        }
+
+        let mut projects: Vec<AgentProject> = by_cwd
+            .into_iter()
+            .filter_map(|(cwd, sessions)| {
+                let name = project_name_from_path(&cwd);
+                AgentProject::from_sessions(self.agent(), cwd, name, sessions)
+            })
+            .collect();
+
+        projects.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+        Ok(projects)
+    }
+
+    fn read_messages(&self, session: &AgentSession) -> Result<Vec<AgentMessage>, ReviewError> {
+        let path = &session.path;
+        let file = File::open(path)
+            .map_err(|e| ReviewError::SessionParseFailed(format!("{}: {}", path.display(), e)))?;
+        let reader = BufReader::new(file);
+        let mut messages = Vec::new();
+
+        for (line_num, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| {
+                ReviewError::SessionParseFailed(format!(
+                    "{}:{}: {}",
+                    path.display(),
+                    line_num + 1,
+                    e
+                ))
+            })?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let record: Value = serde_json::from_str(&line).map_err(|e| {
+                ReviewError::SessionParseFailed(format!(
+                    "{}:{}: {}",
+                    path.display(),
+                    line_num + 1,
+                    e
+                ))
+            })?;
+
+            if let Some(message) = extract_message(&record) {
+                messages.push(message);
+            }
+        }
+
+        Ok(messages)
+    }
+}
+
+/// Recursively collect `rollout-*.jsonl` files
+fn collect_rollouts(dir: &Path, rollouts: &mut Vec<PathBuf>) -> Result<(), ReviewError> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_rollouts(&path, rollouts)?;
+            continue;
+        }
+
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if file_name.starts_with("rollout-") && file_name.ends_with(".jsonl") {
+            rollouts.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+struct SessionMetadata {
+    cwd: PathBuf,
+    git_branch: Option<String>,
+    first_prompt: Option<String>,
+}
+
+/// Read the working directory and branch from the `session_meta` line, and the
+/// first prompt the user typed. Returns `None` for rollouts without a
+/// working directory, which cannot be matched to a project.
+fn read_session_metadata(path: &Path) -> Option<SessionMetadata> {
+    let reader = BufReader::new(File::open(path).ok()?);
+
+    let mut cwd: Option<PathBuf> = None;
+    let mut git_branch: Option<String> = None;
+    let mut first_prompt: Option<String> = None;
+
+    // The prompt follows the injected instructions and environment context
+    for line in reader.lines().take(50) {
+        let Ok(line) = line else {
+            continue;
+        };
+        let Ok(record) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+
+        if record.get("type").and_then(Value::as_str) == Some("session_meta")
+            && let Some(payload) = record.get("payload")
+        {
+            cwd = payload
+                .get("cwd")
+                .and_then(Value::as_str)
+                .map(PathBuf::from);
+            git_branch = payload
+                .pointer("/git/branch")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+        }
+
+        if first_prompt.is_none()
+            && let Some(message) = extract_message(&record)
+            && message.role == MessageRole::User
+            && let Some(ContentBlock::Text { text }) = message.content.first()
+        {
+            first_prompt = Some(truncate_string(text, 60));
+        }
+
+        if cwd.is_some() && first_prompt.is_some() {
+            break;
+        }
+    }
+
+    Some(SessionMetadata {
+        cwd: cwd?,
+        git_branch,
+        first_prompt,
+    })
+}
+
+/// Extract a user or assistant message from a rollout line, keeping only the
+/// text the user typed and the text the agent replied with
+fn extract_message(record: &Value) -> Option<AgentMessage> {
+    // Rollouts wrap items as `{"type": "response_item", "payload": {...}}`;
+    // older ones wrote the item directly
+    let item = match record.get("type").and_then(Value::as_str)? {
+        "response_item" => record.get("payload")?,
+        "message" => record,
+        _ => return None,
+    };
+
+    if item.get("type").and_then(Value::as_str) != Some("message") {
+        return None;
+    }
+
+    let role = match item.get("role").and_then(Value::as_str)? {
+        "user" => MessageRole::User,
+        "assistant" => MessageRole::Assistant,
+        _ => return None,
+    };
+
+    let texts: Vec<String> = item
+        .get("content")?
+        .as_array()?
+        .iter()
+        .filter(|block| {
+            matches!(
+                block.get("type").and_then(Value::as_str),
+                Some("input_text" | "output_text")
+            )
+        })
+        .filter_map(|block| block.get("text").and_then(Value::as_str))
+        .filter(|text| {
+            let text = text.trim_start();
+            !CONTEXT_PREFIXES
+                .iter()
+                .any(|prefix| text.starts_with(prefix))
+        })
+        .map(str::to_string)
+        .collect();
+
+    let timestamp = record
+        .get("timestamp")
+        .and_then(Value::as_str)
+        .and_then(parse_timestamp);
+
+    AgentMessage::from_texts(BaseCodingAgent::Codex, role, texts, timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_message() {
+        let record = serde_json::json!({
+            "timestamp": "2025-09-10T12:00:00.000Z",
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": "user",
+                "content": [{"type": "input_text", "text": "Add a login form"}]
+            }
+        });
+        let message = extract_message(&record).unwrap();
+        assert_eq!(message.role, MessageRole::User);
+        assert_eq!(message.agent, BaseCodingAgent::Codex);
+        assert!(message.timestamp.is_some());
+
+        // Injected context is not part of the conversation
+        let record = serde_json::json!({
+            "type": "response_item",
+            "payload": {
+                "type": "message",
+                "role": "user",
+                "content": [{"type": "input_text", "text": "<environment_context>\n  <cwd>/repo</cwd>\n</environment_context>"}]
+            }
+        });
+        assert!(extract_message(&record).is_none());
+
+        // Tool calls are not messages
+        let record = serde_json::json!({
+            "type": "response_item",
+            "payload": {"type": "function_call", "name": "shell", "arguments": "{}"}
+        });
+        assert!(extract_message(&record).is_none());
+    }
+}
@@ -0,0 +1,265 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use executors::executors::BaseCodingAgent;
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use super::{
+    AgentMessage, AgentProject, AgentSession, MessageRole, SessionSource, current_branch,
+    modified_at, parse_timestamp, project_name_from_path, truncate_string,
+};
+use crate::error::ReviewError;
+
+/// Gemini CLI sessions, stored as JSON files under
+/// `~/.gemini/tmp/<project-hash>/chats/`
+///
+/// The project hash is the SHA-256 of the project's root path, so it can only
+/// be mapped back to a directory by hashing candidate paths: the current
+/// repository and its worktrees. Sessions from other directories are still
+/// listed, named after their hash.
+pub struct GeminiSessions;
+
+#[derive(Debug, Deserialize)]
+struct ConversationRecord {
+    #[serde(default)]
+    messages: Vec<MessageRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageRecord {
+    #[serde(rename = "type")]
+    kind: String,
+    timestamp: Option<String>,
+    /// A string, or a list of parts with `text`
+    content: Option<Value>,
+}
+
+impl MessageRecord {
+    fn texts(&self) -> Vec<String> {
+        match &self.content {
+            Some(Value::String(text)) => vec![text.clone()],
+            Some(Value::Array(parts)) => parts
+                .iter()
+                .filter_map(|part| part.get("text").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn role(&self) -> Option<MessageRole> {
+        match self.kind.as_str() {
+            "user" => Some(MessageRole::User),
+            "gemini" => Some(MessageRole::Assistant),
+            _ => None,
+        }
+    }
+}
+
+impl SessionSource for GeminiSessions {
+    fn agent(&self) -> BaseCodingAgent {
+        BaseCodingAgent::Gemini
+    }
+
+    fn discover_projects(&self) -> Result<Vec<AgentProject>, ReviewError> {
+        let tmp_dir = dirs::home_dir()
+            .ok_or_else(|| {
+                ReviewError::SessionDiscoveryFailed("Could not find home directory".into())
+            })?
+            .join(".gemini")
+            .join("tmp");
+
+        if !tmp_dir.exists() {
+            debug!("Gemini tmp directory does not exist: {:?}", tmp_dir);
+            return Ok(Vec::new());
+        }
+
+        let known_roots = candidate_project_roots();
+        let mut projects = Vec::new();
+
+        let entries = fs::read_dir(&tmp_dir)
+            .map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+            let chats_dir = entry.path().join("chats");
+
+            if !chats_dir.is_dir() {
+                continue;
+            }
+
+            let hash = entry.file_name().to_string_lossy().to_string();
+            let root = known_roots
+                .iter()
+                .find(|root| project_hash(root) == hash)
+                .cloned();
+            let git_branch = root.as_deref().and_then(current_branch);
+
+            let sessions = discover_sessions_in_dir(&chats_dir, git_branch)?;
+
+            let (path, name) = match root {
+                Some(root) => {
+                    let name = project_name_from_path(&root);
+                    (root, name)
+                }
+                None => (
+                    entry.path(),
+                    format!("gemini-{}", hash.get(..8).unwrap_or(&hash)),
+                ),
+            };
+
+            if let Some(project) = AgentProject::from_sessions(self.agent(), path, name, sessions) {
+                projects.push(project);
+            }
+        }
+
+        projects.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+        Ok(projects)
+    }
+
+    fn read_messages(&self, session: &AgentSession) -> Result<Vec<AgentMessage>, ReviewError> {
+        let record = read_conversation(&session.path)?;
+
+        Ok(record
+            .messages
+            .iter()
+            .filter_map(|message| {
+                AgentMessage::from_texts(
+                    BaseCodingAgent::Gemini,
+                    message.role()?,
+                    message.texts(),
+                    message.timestamp.as_deref().and_then(parse_timestamp),
+                )
+            })
+            .collect())
+    }
+}
+
+/// Discover `session-*.json` files in a project's chats directory
+fn discover_sessions_in_dir(
+    chats_dir: &Path,
+    git_branch: Option<String>,
+) -> Result<Vec<AgentSession>, ReviewError> {
+    let mut sessions = Vec::new();
+
+    let entries =
+        fs::read_dir(chats_dir).map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+        let path = entry.path();
+
+        let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if !file_name.starts_with("session-") || !file_name.ends_with(".json") {
+            continue;
+        }
+
+        let first_prompt = read_conversation(&path).ok().and_then(|record| {
+            record
+                .messages
+                .iter()
+                .filter(|message| message.role() == Some(MessageRole::User))
+                .find_map(|message| message.texts().into_iter().next())
+                .map(|text| truncate_string(&text, 60))
+        });
+
+        sessions.push(AgentSession {
+            agent: BaseCodingAgent::Gemini,
+            modified_at: modified_at(&path),
+            path,
+            git_branch: git_branch.clone(),
+            first_prompt,
+        });
+    }
+
+    Ok(sessions)
+}
+
+fn read_conversation(path: &Path) -> Result<ConversationRecord, ReviewError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| ReviewError::SessionParseFailed(format!("{}: {}", path.display(), e)))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| ReviewError::SessionParseFailed(format!("{}: {}", path.display(), e)))
+}
+
+/// Gemini's name for a project's tmp directory
+fn project_hash(root: &Path) -> String {
+    let digest = Sha256::digest(root.to_string_lossy().as_bytes());
+    digest.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Directories Gemini may have been started in: the current directory, the
+/// repository containing it and all of that repository's worktrees
+fn candidate_project_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = std::env::current_dir().into_iter().collect();
+
+    let worktrees = Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+        .unwrap_or_default();
+
+    roots.extend(
+        worktrees
+            .lines()
+            .filter_map(|line| line.strip_prefix("worktree "))
+            .map(PathBuf::from),
+    );
+
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session-2025-09-10T12-00-abc.json");
+        fs::write(
+            &path,
+            serde_json::json!({
+                "sessionId": "abc",
+                "messages": [
+                    {"type": "user", "timestamp": "2025-09-10T12:00:00.000Z", "content": "Add a login form"},
+                    {"type": "info", "content": "Switched model"},
+                    {"type": "gemini", "timestamp": "2025-09-10T12:00:05.000Z", "content": [{"text": "Done"}]}
+                ]
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let session = AgentSession {
+            agent: BaseCodingAgent::Gemini,
+            path,
+            git_branch: None,
+            first_prompt: None,
+            modified_at: std::time::SystemTime::UNIX_EPOCH,
+        };
+        let messages = GeminiSessions.read_messages(&session).unwrap();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, MessageRole::User);
+        assert_eq!(messages[1].role, MessageRole::Assistant);
+    }
+
+    #[test]
+    fn test_project_hash() {
+        assert_eq!(
+            project_hash(Path::new("/repo")),
+            "816fc349d3faebf805d1bed70fce7e14754cad5251c77dda31c414ee961a0bdd"
+        );
+    }
+}
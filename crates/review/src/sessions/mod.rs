@@ -0,0 +1,420 @@
+//! Discovery of coding agent session logs that can give the review context.
+//!
+//! Each supported agent stores its sessions in its own on-disk format. A
+//! [`SessionSource`] finds the projects an agent has worked in and reads a
+//! session back as [`AgentMessage`]s, so sessions from different agents can be
+//! matched to a branch and attached to a review in one schema.
+
+mod claude;
+mod codex;
+mod gemini;
+mod opencode;
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Utc};
+use claude::ClaudeSessions;
+use codex::CodexSessions;
+use executors::executors::BaseCodingAgent;
+use gemini::GeminiSessions;
+use opencode::OpencodeSessions;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::error::ReviewError;
+
+pub trait SessionSource {
+    fn agent(&self) -> BaseCodingAgent;
+
+    /// Discover projects with at least one session, most recent first
+    fn discover_projects(&self) -> Result<Vec<AgentProject>, ReviewError>;
+
+    /// Read the user and assistant text messages of a session
+    fn read_messages(&self, session: &AgentSession) -> Result<Vec<AgentMessage>, ReviewError>;
+}
+
+/// All session sources, in the order their projects are listed
+fn sources() -> Vec<Box<dyn SessionSource>> {
+    vec![
+        Box::new(ClaudeSessions),
+        Box::new(CodexSessions),
+        Box::new(OpencodeSessions),
+        Box::new(GeminiSessions),
+    ]
+}
+
+/// A directory a coding agent has sessions for
+#[derive(Debug, Clone)]
+pub struct AgentProject {
+    pub agent: BaseCodingAgent,
+    pub path: PathBuf,
+    pub name: String,
+    pub git_branch: Option<String>,
+    pub first_prompt: Option<String>,
+    pub session_count: usize,
+    pub modified_at: SystemTime,
+    /// Sessions in the project, most recent first
+    pub sessions: Vec<AgentSession>,
+}
+
+/// A single session of a coding agent
+#[derive(Debug, Clone)]
+pub struct AgentSession {
+    pub agent: BaseCodingAgent,
+    /// File or directory the session is stored in
+    pub path: PathBuf,
+    pub git_branch: Option<String>,
+    pub first_prompt: Option<String>,
+    pub modified_at: SystemTime,
+}
+
+impl AgentProject {
+    /// Build a project from its sessions, taking the branch and prompt from
+    /// the most recent one. Returns `None` when there are no sessions.
+    fn from_sessions(
+        agent: BaseCodingAgent,
+        path: PathBuf,
+        name: String,
+        mut sessions: Vec<AgentSession>,
+    ) -> Option<Self> {
+        sessions.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+        let most_recent = sessions.first()?;
+
+        Some(Self {
+            agent,
+            path,
+            name,
+            git_branch: most_recent.git_branch.clone(),
+            first_prompt: most_recent.first_prompt.clone(),
+            session_count: sessions.len(),
+            modified_at: most_recent.modified_at,
+            sessions,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ContentBlock {
+    Text { text: String },
+}
+
+/// A message in the normalized schema written to `.agent-messages.json`
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentMessage {
+    pub agent: BaseCodingAgent,
+    pub role: MessageRole,
+    pub content: Vec<ContentBlock>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl AgentMessage {
+    /// Build a message from text blocks, skipping blank ones. Returns `None`
+    /// when no text is left, e.g. for messages that only carry tool calls.
+    fn from_texts(
+        agent: BaseCodingAgent,
+        role: MessageRole,
+        texts: impl IntoIterator<Item = String>,
+        timestamp: Option<DateTime<Utc>>,
+    ) -> Option<Self> {
+        let content: Vec<ContentBlock> = texts
+            .into_iter()
+            .filter(|text| !text.trim().is_empty())
+            .map(|text| ContentBlock::Text { text })
+            .collect();
+
+        if content.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            agent,
+            role,
+            content,
+            timestamp,
+        })
+    }
+}
+
+/// Name shown for an agent in the session selector
+pub fn agent_label(agent: BaseCodingAgent) -> &'static str {
+    match agent {
+        BaseCodingAgent::ClaudeCode => "Claude Code",
+        BaseCodingAgent::Codex => "Codex",
+        BaseCodingAgent::Opencode => "Opencode",
+        BaseCodingAgent::Gemini => "Gemini",
+        _ => "Agent",
+    }
+}
+
+/// Discover projects from every agent, most recent first
+///
+/// A source that fails is logged and skipped, so one unreadable session store
+/// does not hide the others.
+pub fn discover_projects() -> Vec<AgentProject> {
+    let mut projects: Vec<AgentProject> = sources()
+        .iter()
+        .flat_map(|source| match source.discover_projects() {
+            Ok(projects) => projects,
+            Err(e) => {
+                debug!(
+                    "Failed to discover {} sessions: {}",
+                    agent_label(source.agent()),
+                    e
+                );
+                Vec::new()
+            }
+        })
+        .collect();
+
+    projects.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    projects
+}
+
+/// Find projects matching a specific git branch using fuzzy matching
+pub fn find_projects_by_branch(
+    projects: &[AgentProject],
+    target_branch: &str,
+) -> Vec<AgentProject> {
+    projects
+        .iter()
+        .filter(|project| {
+            project
+                .git_branch
+                .as_deref()
+                .is_some_and(|branch| branches_match(target_branch, branch))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Check if two branch names match using fuzzy matching
+fn branches_match(target: &str, session_branch: &str) -> bool {
+    let target_normalized = normalize_branch(target);
+    let session_normalized = normalize_branch(session_branch);
+
+    // Exact match after normalization
+    if target_normalized == session_normalized {
+        return true;
+    }
+
+    // Check if the slug portions match (e.g., "feature-auth" matches "vk/feature-auth")
+    let target_slug = extract_branch_slug(&target_normalized);
+    let session_slug = extract_branch_slug(&session_normalized);
+
+    target_slug == session_slug && !target_slug.is_empty()
+}
+
+/// Normalize a branch name by stripping common prefixes
+fn normalize_branch(branch: &str) -> String {
+    let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+
+    branch.to_lowercase()
+}
+
+/// Extract the "slug" portion of a branch name
+/// e.g., "vk/a04a-store-payloads-i" -> "a04a-store-payloads-i"
+fn extract_branch_slug(branch: &str) -> String {
+    // Split by '/' and take the last part
+    branch.rsplit('/').next().unwrap_or(branch).to_string()
+}
+
+/// Read the selected sessions and concatenate their messages into a single
+/// JSON array, ordered by timestamp
+///
+/// Only user and assistant text is kept; tool calls, tool results and
+/// reasoning are dropped. Every message has the same shape regardless of the
+/// agent it came from:
+///
+/// ```json
+/// {"agent": "CODEX", "role": "user", "content": [{"type": "text", "text": "..."}], "timestamp": "..."}
+/// ```
+pub fn concatenate_sessions_to_json(sessions: &[AgentSession]) -> Result<String, ReviewError> {
+    let sources = sources();
+    let mut messages = Vec::new();
+
+    for session in sessions {
+        let source = sources
+            .iter()
+            .find(|source| source.agent() == session.agent)
+            .ok_or_else(|| {
+                ReviewError::SessionParseFailed(format!("No session source for {}", session.agent))
+            })?;
+        messages.extend(source.read_messages(session)?);
+    }
+
+    // Stable sort keeps file order for messages without a timestamp
+    messages.sort_by_key(|message| message.timestamp);
+
+    serde_json::to_string(&messages).map_err(|e| ReviewError::SessionParseFailed(e.to_string()))
+}
+
+/// Truncate a string to max length, adding "..." if truncated
+fn truncate_string(s: &str, max_len: usize) -> String {
+    // Replace newlines with spaces for display
+    let s = s.replace('\n', " ");
+    if s.chars().count() <= max_len {
+        s
+    } else {
+        let truncated: String = s.chars().take(max_len - 3).collect();
+        format!("{truncated}...")
+    }
+}
+
+/// Friendly name for a project directory
+///
+/// Worktrees are named after the part following `worktrees/`, without the
+/// short hash prefix, e.g. `.../worktrees/a04a-store-payloads-i/repo` ->
+/// `store-payloads-i`.
+fn project_name_from_path(path: &Path) -> String {
+    let components: Vec<String> = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    if let Some(idx) = components.iter().position(|c| c == "worktrees")
+        && let Some(worktree) = components.get(idx + 1)
+    {
+        return worktree
+            .split_once('-')
+            .map_or(worktree.as_str(), |(_, rest)| rest)
+            .to_string();
+    }
+
+    components
+        .last()
+        .cloned()
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Branch currently checked out in `dir`, for agents that do not record one
+fn current_branch(dir: &Path) -> Option<String> {
+    if !dir.is_dir() {
+        return None;
+    }
+
+    let output = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !branch.is_empty() && branch != "HEAD").then_some(branch)
+}
+
+/// Modification time of a file or directory, or the epoch if unavailable
+fn modified_at(path: &Path) -> SystemTime {
+    path.metadata()
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// Parse an RFC 3339 timestamp as written by most agents
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_branches_match() {
+        // Exact match
+        assert!(branches_match("feature-auth", "feature-auth"));
+
+        // With prefix
+        assert!(branches_match("feature-auth", "vk/feature-auth"));
+        assert!(branches_match("vk/feature-auth", "feature-auth"));
+
+        // Slug matching
+        assert!(branches_match(
+            "a04a-store-payloads-i",
+            "vk/a04a-store-payloads-i"
+        ));
+
+        // Case insensitive
+        assert!(branches_match("Feature-Auth", "feature-auth"));
+
+        // Non-matches
+        assert!(!branches_match("feature-auth", "feature-other"));
+        assert!(!branches_match("main", "feature-auth"));
+
+        // Regression tests: substring matches should NOT match
+        // (these were incorrectly matching before the fix)
+        assert!(!branches_match("vk/d13f-remove-compare-c", "c"));
+        assert!(!branches_match("vk/d13f-remove-compare-c", "compare"));
+        assert!(!branches_match("feature-auth", "auth"));
+        assert!(!branches_match("feature-auth", "feature"));
+    }
+
+    #[test]
+    fn test_normalize_branch() {
+        assert_eq!(normalize_branch("refs/heads/main"), "main");
+        assert_eq!(normalize_branch("Feature-Auth"), "feature-auth");
+        assert_eq!(normalize_branch("vk/feature-auth"), "vk/feature-auth");
+    }
+
+    #[test]
+    fn test_extract_branch_slug() {
+        assert_eq!(extract_branch_slug("vk/feature-auth"), "feature-auth");
+        assert_eq!(extract_branch_slug("feature-auth"), "feature-auth");
+        assert_eq!(
+            extract_branch_slug("user/prefix/feature-auth"),
+            "feature-auth"
+        );
+    }
+
+    #[test]
+    fn test_project_name_from_path() {
+        assert_eq!(
+            project_name_from_path(Path::new(
+                "/private/var/folders/T/vibe-kanban/worktrees/a04a-store-payloads-i/repo"
+            )),
+            "store-payloads-i"
+        );
+        assert_eq!(
+            project_name_from_path(Path::new("/home/me/my-project")),
+            "my-project"
+        );
+    }
+
+    #[test]
+    fn test_normalized_message_schema() {
+        let message = AgentMessage::from_texts(
+            BaseCodingAgent::Codex,
+            MessageRole::User,
+            ["Add a login form".to_string(), " ".to_string()],
+            parse_timestamp("2025-09-10T12:00:00.000Z"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            serde_json::json!({
+                "agent": "CODEX",
+                "role": "user",
+                "content": [{"type": "text", "text": "Add a login form"}],
+                "timestamp": "2025-09-10T12:00:00Z",
+            })
+        );
+        assert!(
+            AgentMessage::from_texts(BaseCodingAgent::Codex, MessageRole::User, [], None).is_none()
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use executors::executors::BaseCodingAgent;
+use serde::{Deserialize, de::DeserializeOwned};
+use tracing::debug;
+
+use super::{
+    AgentMessage, AgentProject, AgentSession, MessageRole, SessionSource, current_branch,
+    modified_at, project_name_from_path, truncate_string,
+};
+use crate::error::ReviewError;
+
+/// Opencode sessions, stored as one JSON file per session, message and
+/// message part under `$XDG_DATA_HOME/opencode/storage/`:
+///
+/// - `session/<project-id>/<session-id>.json`
+/// - `message/<session-id>/<message-id>.json`
+/// - `part/<message-id>/<part-id>.json`
+///
+/// Opencode does not record the branch, so it is read from the session's
+/// directory if that still exists.
+pub struct OpencodeSessions;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionInfo {
+    directory: PathBuf,
+    title: Option<String>,
+    /// Set for sessions spawned by another session's subagents
+    #[serde(rename = "parentID")]
+    parent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageInfo {
+    id: String,
+    role: String,
+    time: MessageTime,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageTime {
+    /// Milliseconds since the epoch
+    created: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PartInfo {
+    #[serde(rename = "type")]
+    kind: String,
+    text: Option<String>,
+    /// Text opencode generated itself, e.g. file contents attached to a prompt
+    #[serde(default)]
+    synthetic: bool,
+}
+
+/// Opencode's storage directory (`$XDG_DATA_HOME/opencode/storage`)
+fn storage_dir() -> Option<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("share")))?;
+
+    Some(data_home.join("opencode").join("storage"))
+}
+
+impl SessionSource for OpencodeSessions {
+    fn agent(&self) -> BaseCodingAgent {
+        BaseCodingAgent::Opencode
+    }
+
+    fn discover_projects(&self) -> Result<Vec<AgentProject>, ReviewError> {
+        let storage = storage_dir().ok_or_else(|| {
+            ReviewError::SessionDiscoveryFailed("Could not find home directory".into())
+        })?;
+        let sessions_dir = storage.join("session");
+
+        if !sessions_dir.exists() {
+            debug!(
+                "Opencode sessions directory does not exist: {:?}",
+                sessions_dir
+            );
+            return Ok(Vec::new());
+        }
+
+        let mut by_directory: HashMap<PathBuf, Vec<AgentSession>> = HashMap::new();
+
+        for project_dir in read_dir_paths(&sessions_dir)? {
+            if !project_dir.is_dir() {
+                continue;
+            }
+
+            for path in read_dir_paths(&project_dir)? {
+                let Ok(info) = read_json::<SessionInfo>(&path) else {
+                    continue;
+                };
+
+                if info.parent_id.is_some() {
+                    continue;
+                }
+
+                // Messages are written as the session goes on, so the
+                // message directory tracks activity better than the session file
+                let session_id = path.file_stem().unwrap_or_default();
+                let messages_dir = storage.join("message").join(session_id);
+
+                by_directory
+                    .entry(info.directory)
+                    .or_default()
+                    .push(AgentSession {
+                        agent: BaseCodingAgent::Opencode,
+                        modified_at: modified_at(&messages_dir).max(modified_at(&path)),
+                        path,
+                        git_branch: None,
+                        first_prompt: info.title.map(|title| truncate_string(&title, 60)),
+                    });
+            }
+        }
+
+        let mut projects: Vec<AgentProject> = by_directory
+            .into_iter()
+            .filter_map(|(directory, mut sessions)| {
+                let git_branch = current_branch(&directory);
+                for session in &mut sessions {
+                    session.git_branch = git_branch.clone();
+                }
+
+                let name = project_name_from_path(&directory);
+                AgentProject::from_sessions(self.agent(), directory, name, sessions)
+            })
+            .collect();
+
+        projects.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+        Ok(projects)
+    }
+
+    fn read_messages(&self, session: &AgentSession) -> Result<Vec<AgentMessage>, ReviewError> {
+        let storage = session
+            .path
+            .ancestors()
+            .nth(3)
+            .ok_or_else(|| {
+                ReviewError::SessionParseFailed(format!(
+                    "{}: not in opencode storage",
+                    session.path.display()
+                ))
+            })?
+            .to_path_buf();
+        let session_id = session.path.file_stem().unwrap_or_default();
+        let messages_dir = storage.join("message").join(session_id);
+
+        if !messages_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut messages = Vec::new();
+
+        for path in read_dir_paths(&messages_dir)? {
+            let info: MessageInfo = read_json(&path)?;
+
+            let role = match info.role.as_str() {
+                "user" => MessageRole::User,
+                "assistant" => MessageRole::Assistant,
+                _ => continue,
+            };
+
+            let parts_dir = storage.join("part").join(&info.id);
+            let mut part_paths = if parts_dir.exists() {
+                read_dir_paths(&parts_dir)?
+            } else {
+                Vec::new()
+            };
+            // Part IDs sort in creation order
+            part_paths.sort();
+
+            let mut texts = Vec::new();
+            for part_path in part_paths {
+                let part: PartInfo = read_json(&part_path)?;
+                if part.kind == "text"
+                    && !part.synthetic
+                    && let Some(text) = part.text
+                {
+                    texts.push(text);
+                }
+            }
+
+            let timestamp = DateTime::<Utc>::from_timestamp_millis(info.time.created);
+
+            if let Some(message) =
+                AgentMessage::from_texts(BaseCodingAgent::Opencode, role, texts, timestamp)
+            {
+                messages.push(message);
+            }
+        }
+
+        Ok(messages)
+    }
+}
+
+/// Paths of the JSON files and directories in `dir`
+fn read_dir_paths(dir: &Path) -> Result<Vec<PathBuf>, ReviewError> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| ReviewError::SessionDiscoveryFailed(e.to_string()))?;
+        let path = entry.path();
+
+        if path.is_dir() || path.extension().and_then(|e| e.to_str()) == Some("json") {
+            paths.push(path);
+        }
+    }
+
+    Ok(paths)
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, ReviewError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| ReviewError::SessionParseFailed(format!("{}: {}", path.display(), e)))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| ReviewError::SessionParseFailed(format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::ContentBlock;
+
+    #[test]
+    fn test_read_messages() {
+        let storage = tempfile::tempdir().unwrap();
+        let write = |path: &str, value: serde_json::Value| {
+            let path = storage.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, value.to_string()).unwrap();
+        };
+
+        write(
+            "session/prj_1/ses_1.json",
+            serde_json::json!({"id": "ses_1", "directory": "/repo", "title": "Login form"}),
+        );
+        write(
+            "message/ses_1/msg_1.json",
+            serde_json::json!({"id": "msg_1", "role": "user", "time": {"created": 1757505600000i64}}),
+        );
+        write(
+            "part/msg_1/prt_1.json",
+            serde_json::json!({"id": "prt_1", "type": "text", "text": "Add a login form"}),
+        );
+        write(
+            "part/msg_1/prt_2.json",
+            serde_json::json!({"id": "prt_2", "type": "text", "text": "<file>...</file>", "synthetic": true}),
+        );
+        write(
+            "message/ses_1/msg_2.json",
+            serde_json::json!({"id": "msg_2", "role": "assistant", "time": {"created": 1757505660000i64}}),
+        );
+        write(
+            "part/msg_2/prt_3.json",
+            serde_json::json!({"id": "prt_3", "type": "tool", "tool": "edit"}),
+        );
+
+        let session = AgentSession {
+            agent: BaseCodingAgent::Opencode,
+            path: storage.path().join("session/prj_1/ses_1.json"),
+            git_branch: None,
+            first_prompt: None,
+            modified_at: std::time::SystemTime::UNIX_EPOCH,
+        };
+        let messages = OpencodeSessions.read_messages(&session).unwrap();
+
+        // The assistant message only called a tool
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].role, MessageRole::User);
+        assert_eq!(
+            messages[0].content,
+            vec![ContentBlock::Text {
+                text: "Add a login form".to_string()
+            }]
+        );
+    }
+}
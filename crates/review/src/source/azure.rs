@@ -0,0 +1,153 @@
+use services::services::git_host::azure::{AzCli, AzCliError, AzurePrDetails};
+use tempfile::TempDir;
+
+use super::{Checkout, ReviewSource};
+use crate::{
+    error::ReviewError,
+    local::{git, slugify},
+};
+
+/// An Azure DevOps pull request, fetched with the Azure CLI (`az repos`)
+#[derive(Debug, PartialEq, Eq)]
+pub struct AzurePullRequest {
+    pub url: String,
+    pub organization: String,
+    pub id: i64,
+}
+
+impl AzurePullRequest {
+    /// Parse an Azure DevOps PR URL
+    ///
+    /// Expected formats:
+    /// - `https://dev.azure.com/{org}/{project}/_git/{repo}/pullrequest/{id}`
+    /// - `https://{org}.visualstudio.com/{project}/_git/{repo}/pullrequest/{id}`
+    pub fn parse(url: &str) -> Result<Self, ReviewError> {
+        let url = url.trim().trim_end_matches('/');
+
+        let (organization, id) = AzCli::parse_pr_url(url)
+            .filter(|(organization, id)| !organization.is_empty() && *id > 0)
+            .ok_or_else(|| {
+                ReviewError::InvalidTarget(format!(
+                    "'{url}' is not an Azure DevOps pull request URL. Expected: https://dev.azure.com/org/project/_git/repo/pullrequest/123"
+                ))
+            })?;
+
+        Ok(Self {
+            url: url.to_string(),
+            organization,
+            id,
+        })
+    }
+
+    fn details(&self) -> Result<AzurePrDetails, ReviewError> {
+        AzCli::new()
+            .view_pr_details(&self.url)
+            .map_err(|e| match e {
+                AzCliError::NotAvailable => ReviewError::AzNotInstalled,
+                AzCliError::AuthFailed(_) => ReviewError::AzNotAuthenticated,
+                e => ReviewError::PrInfoFailed(e.to_string()),
+            })
+    }
+}
+
+impl ReviewSource for AzurePullRequest {
+    fn label(&self) -> String {
+        format!("{}!{}", self.organization, self.id)
+    }
+
+    fn url(&self) -> Option<String> {
+        Some(self.url.clone())
+    }
+
+    fn checkout(&self) -> Result<Checkout, ReviewError> {
+        let pr = self.details()?;
+
+        let temp_dir = TempDir::new().map_err(|e| ReviewError::CloneFailed(e.to_string()))?;
+        let dir = temp_dir.path().join(&pr.repo_name);
+        let target = dir
+            .to_str()
+            .ok_or_else(|| ReviewError::CloneFailed("Invalid target path".to_string()))?;
+
+        // Cloning goes through git's credential helpers, as Azure Repos has no
+        // equivalent of `gh repo clone`
+        git(
+            temp_dir.path(),
+            &["clone", "--quiet", &pr.remote_url, target],
+        )
+        .map_err(|e| ReviewError::CloneFailed(e.to_string()))?;
+
+        // Fetch both refs explicitly: the target branch is not the default
+        // branch for every PR, and Azure Repos does not serve arbitrary SHAs
+        git(
+            &dir,
+            &[
+                "fetch",
+                "--quiet",
+                "origin",
+                &pr.source_ref_name,
+                &pr.target_ref_name,
+            ],
+        )
+        .map_err(|e| ReviewError::CheckoutFailed(e.to_string()))?;
+
+        let head_commit = pr.source_commit;
+        git(&dir, &["checkout", "--quiet", "--detach", &head_commit])
+            .map_err(|e| ReviewError::CheckoutFailed(e.to_string()))?;
+
+        Ok(Checkout {
+            dir,
+            slug: slugify(&format!("{}-{}-{}", pr.project, pr.repo_name, self.id)),
+            owner: self.organization.clone(),
+            repo_name: pr.repo_name,
+            title: pr.title,
+            description: pr.description,
+            base_commit: pr.target_commit,
+            head_commit,
+            branch: Some(
+                pr.source_ref_name
+                    .trim_start_matches("refs/heads/")
+                    .to_string(),
+            ),
+            temp_dir,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_azure_pr_url() {
+        let pr = AzurePullRequest::parse(
+            "https://dev.azure.com/myorg/myproject/_git/myrepo/pullrequest/123",
+        )
+        .expect("Should parse valid URL");
+        assert_eq!(pr.organization, "myorg");
+        assert_eq!(pr.id, 123);
+        assert_eq!(pr.label(), "myorg!123");
+    }
+
+    #[test]
+    fn test_parse_azure_pr_url_visualstudio() {
+        let pr = AzurePullRequest::parse(
+            "https://myorg.visualstudio.com/myproject/_git/myrepo/pullrequest/456/",
+        )
+        .expect("Should parse legacy URL");
+        assert_eq!(pr.organization, "myorg");
+        assert_eq!(pr.id, 456);
+    }
+
+    #[test]
+    fn test_parse_azure_pr_url_invalid() {
+        assert!(
+            AzurePullRequest::parse("https://dev.azure.com/myorg/myproject/_git/myrepo").is_err()
+        );
+        assert!(
+            AzurePullRequest::parse(
+                "https://dev.azure.com/myorg/myproject/_git/myrepo/pullrequest/abc"
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use super::{Checkout, ReviewSource};
+use crate::{
+    error::ReviewError,
+    local::{
+        clone_local, commit_subjects, default_branch, git, repo_name, repo_root, rev_parse, slugify,
+    },
+};
+
+/// A branch or commit range in a local repository
+#[derive(Debug, PartialEq, Eq)]
+pub struct GitRange {
+    pub repo: PathBuf,
+    /// Start of the range; `None` means the repository's default branch
+    pub base: Option<String>,
+    pub head: String,
+    /// Review from the merge base of `base` and `head`, like `git diff base...head`
+    pub merge_base: bool,
+    /// Set when reviewing a branch, used to find matching coding agent sessions
+    pub branch: Option<String>,
+}
+
+impl GitRange {
+    /// Parse `base..head`, `base...head` or a branch name
+    ///
+    /// Branches are compared from their merge base with `default_base`.
+    pub fn parse(
+        target: &str,
+        repo: PathBuf,
+        default_base: Option<String>,
+    ) -> Result<Self, ReviewError> {
+        let range = target
+            .split_once("...")
+            .map(|(base, head)| (base, head, true))
+            .or_else(|| {
+                target
+                    .split_once("..")
+                    .map(|(base, head)| (base, head, false))
+            });
+
+        let Some((base, head, merge_base)) = range else {
+            return Ok(Self {
+                repo,
+                base: default_base,
+                head: target.to_string(),
+                merge_base: true,
+                branch: Some(target.to_string()),
+            });
+        };
+
+        if base.is_empty() && head.is_empty() {
+            return Err(ReviewError::InvalidTarget(format!(
+                "'{target}' is not a commit range"
+            )));
+        }
+
+        // Like git, an empty side of the range means HEAD
+        let or_head = |rev: &str| {
+            if rev.is_empty() {
+                "HEAD".to_string()
+            } else {
+                rev.to_string()
+            }
+        };
+
+        Ok(Self {
+            repo,
+            base: Some(or_head(base)),
+            head: or_head(head),
+            merge_base,
+            branch: None,
+        })
+    }
+}
+
+impl ReviewSource for GitRange {
+    fn label(&self) -> String {
+        match (&self.branch, &self.base) {
+            (Some(branch), _) => branch.clone(),
+            (None, Some(base)) => {
+                let dots = if self.merge_base { "..." } else { ".." };
+                format!("{base}{dots}{}", self.head)
+            }
+            (None, None) => self.head.clone(),
+        }
+    }
+
+    fn url(&self) -> Option<String> {
+        None
+    }
+
+    fn checkout(&self) -> Result<Checkout, ReviewError> {
+        let repo_root = repo_root(&self.repo)?;
+
+        let base = match &self.base {
+            Some(base) => base.clone(),
+            None => default_branch(&repo_root),
+        };
+        let head_commit = rev_parse(&repo_root, &self.head)?;
+        let base_commit = if self.merge_base {
+            git(&repo_root, &["merge-base", &base, &head_commit])?
+        } else {
+            rev_parse(&repo_root, &base)?
+        };
+
+        let (temp_dir, dir) = clone_local(&repo_root, &head_commit)?;
+        let description = commit_subjects(&dir, &base_commit, &head_commit)?;
+        let repo_name = repo_name(&repo_root);
+        let title = self.label();
+
+        Ok(Checkout {
+            dir,
+            owner: "local".to_string(),
+            slug: slugify(&format!("{repo_name}-{title}")),
+            repo_name,
+            title,
+            description,
+            base_commit,
+            head_commit,
+            branch: self.branch.clone(),
+            temp_dir,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ranges() {
+        let range = GitRange::parse("main..feature", PathBuf::from("."), None).unwrap();
+        assert_eq!(range.base.as_deref(), Some("main"));
+        assert_eq!(range.head, "feature");
+        assert!(!range.merge_base);
+
+        let range = GitRange::parse("origin/main...", PathBuf::from("."), None).unwrap();
+        assert_eq!(range.base.as_deref(), Some("origin/main"));
+        assert_eq!(range.head, "HEAD");
+        assert!(range.merge_base);
+
+        assert!(GitRange::parse("..", PathBuf::from("."), None).is_err());
+    }
+
+    #[test]
+    fn test_parse_branch() {
+        let range = GitRange::parse(
+            "feature/login",
+            PathBuf::from("."),
+            Some("develop".to_string()),
+        )
+        .unwrap();
+        assert_eq!(range.base.as_deref(), Some("develop"));
+        assert_eq!(range.branch.as_deref(), Some("feature/login"));
+        assert_eq!(range.label(), "feature/login");
+    }
+}
@@ -0,0 +1,63 @@
+use tempfile::TempDir;
+
+use super::{Checkout, ReviewSource};
+use crate::{
+    error::ReviewError,
+    github::{checkout_commit, clone_repo, fetch_commit, get_pr_info, parse_pr_url},
+    local::slugify,
+};
+
+/// A GitHub pull request, fetched and cloned with `gh`
+#[derive(Debug, PartialEq, Eq)]
+pub struct GitHubPullRequest {
+    pub url: String,
+    pub owner: String,
+    pub repo: String,
+    pub number: i64,
+}
+
+impl GitHubPullRequest {
+    pub fn parse(url: &str) -> Result<Self, ReviewError> {
+        let (owner, repo, number) = parse_pr_url(url)?;
+        Ok(Self {
+            url: url.trim().to_string(),
+            owner,
+            repo,
+            number,
+        })
+    }
+}
+
+impl ReviewSource for GitHubPullRequest {
+    fn label(&self) -> String {
+        format!("{}/{}#{}", self.owner, self.repo, self.number)
+    }
+
+    fn url(&self) -> Option<String> {
+        Some(self.url.clone())
+    }
+
+    fn checkout(&self) -> Result<Checkout, ReviewError> {
+        let pr_info = get_pr_info(&self.owner, &self.repo, self.number)?;
+
+        let temp_dir = TempDir::new().map_err(|e| ReviewError::CloneFailed(e.to_string()))?;
+        let dir = temp_dir.path().join(&self.repo);
+
+        clone_repo(&self.owner, &self.repo, &dir)?;
+        checkout_commit(&pr_info.head_commit, &dir)?;
+        fetch_commit(&pr_info.base_commit, &dir)?;
+
+        Ok(Checkout {
+            dir,
+            owner: pr_info.owner,
+            repo_name: pr_info.repo,
+            title: pr_info.title,
+            description: pr_info.description,
+            base_commit: pr_info.base_commit,
+            head_commit: pr_info.head_commit,
+            branch: Some(pr_info.head_ref_name),
+            slug: slugify(&format!("{}-{}-{}", self.owner, self.repo, self.number)),
+            temp_dir,
+        })
+    }
+}
@@ -0,0 +1,150 @@
+//! Where the code under review comes from.
+//!
+//! Every source resolves to a [`Checkout`]: a self-contained clone with the
+//! head of the change checked out and the base commit available, which both
+//! the hosted upload and `--local` reviews work from.
+
+mod azure;
+mod git_range;
+mod github;
+mod patch;
+
+use std::path::{Path, PathBuf};
+
+pub use azure::AzurePullRequest;
+use enum_dispatch::enum_dispatch;
+pub use git_range::GitRange;
+pub use github::GitHubPullRequest;
+pub use patch::PatchFile;
+
+pub use crate::local::Checkout;
+use crate::{archive, error::ReviewError};
+
+#[enum_dispatch]
+pub trait ReviewSource {
+    /// Short description shown while preparing, e.g. `owner/repo#123`
+    fn label(&self) -> String;
+
+    /// Link to the change on its host, if it has one
+    fn url(&self) -> Option<String>;
+
+    /// Check out the change and resolve its base and head commits
+    fn checkout(&self) -> Result<Checkout, ReviewError>;
+
+    /// Package a checkout for upload to the hosted review service
+    fn archive(&self, checkout: &Checkout) -> Result<Vec<u8>, ReviewError> {
+        archive::create_tarball(&checkout.dir)
+    }
+}
+
+#[enum_dispatch(ReviewSource)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Source {
+    GitHub(GitHubPullRequest),
+    AzureDevOps(AzurePullRequest),
+    GitRange(GitRange),
+    Patch(PatchFile),
+}
+
+/// Options that apply to sources read from a local repository
+#[derive(Debug, Default)]
+pub struct SourceOptions {
+    /// Repository to read branches, ranges and patch bases from
+    pub repo: Option<PathBuf>,
+    /// Branch that branches are compared against, or the commit a patch applies to
+    pub base: Option<String>,
+}
+
+impl Source {
+    /// Parse a PR URL, patch file, commit range or branch name
+    pub fn parse(target: &str, options: &SourceOptions) -> Result<Self, ReviewError> {
+        let target = target.trim();
+
+        if target.is_empty() {
+            return Err(ReviewError::InvalidTarget("target is empty".to_string()));
+        }
+
+        if target.contains("://")
+            || target.contains("github.com/")
+            || target.contains("dev.azure.com/")
+        {
+            return Self::parse_pr_url(target);
+        }
+
+        let repo = options.repo.clone().unwrap_or_else(|| PathBuf::from("."));
+
+        if Path::new(target).is_file() {
+            return Ok(Self::Patch(PatchFile {
+                path: PathBuf::from(target),
+                repo,
+                base: options.base.clone(),
+            }));
+        }
+
+        Ok(Self::GitRange(GitRange::parse(
+            target,
+            repo,
+            options.base.clone(),
+        )?))
+    }
+
+    fn parse_pr_url(url: &str) -> Result<Self, ReviewError> {
+        let lower = url.to_lowercase();
+
+        // Same host detection as the server's git_host providers
+        if lower.contains("dev.azure.com")
+            || lower.contains(".visualstudio.com")
+            || lower.contains("/_git/")
+        {
+            return AzurePullRequest::parse(url).map(Self::AzureDevOps);
+        }
+
+        if lower.contains("github.com") {
+            return GitHubPullRequest::parse(url).map(Self::GitHub);
+        }
+
+        Err(ReviewError::InvalidTarget(format!(
+            "'{url}' is not a GitHub or Azure DevOps pull request URL"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pr_urls() {
+        let options = SourceOptions::default();
+
+        assert!(matches!(
+            Source::parse("https://github.com/owner/repo/pull/42", &options).unwrap(),
+            Source::GitHub(_)
+        ));
+        assert!(matches!(
+            Source::parse(
+                "https://dev.azure.com/org/project/_git/repo/pullrequest/7",
+                &options
+            )
+            .unwrap(),
+            Source::AzureDevOps(_)
+        ));
+        assert!(
+            Source::parse("https://gitlab.com/owner/repo/-/merge_requests/1", &options).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_local_targets() {
+        let options = SourceOptions::default();
+
+        assert!(matches!(
+            Source::parse("main..feature", &options).unwrap(),
+            Source::GitRange(_)
+        ));
+        assert!(matches!(
+            Source::parse("Cargo.toml", &options).unwrap(),
+            Source::Patch(_)
+        ));
+    }
+}
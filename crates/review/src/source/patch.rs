@@ -0,0 +1,119 @@
+use std::path::PathBuf;
+
+use super::{Checkout, ReviewSource};
+use crate::{
+    error::ReviewError,
+    local::{clone_local, git, repo_name, repo_root, rev_parse, slugify},
+    report::short_sha,
+};
+
+/// A patch or diff file, applied on top of a commit in a local repository
+#[derive(Debug, PartialEq, Eq)]
+pub struct PatchFile {
+    pub path: PathBuf,
+    pub repo: PathBuf,
+    /// Commit the patch applies to; `None` means `HEAD`
+    pub base: Option<String>,
+}
+
+impl ReviewSource for PatchFile {
+    fn label(&self) -> String {
+        self.path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.path.display().to_string())
+    }
+
+    fn url(&self) -> Option<String> {
+        None
+    }
+
+    fn checkout(&self) -> Result<Checkout, ReviewError> {
+        let patch_path = self
+            .path
+            .canonicalize()
+            .map_err(|e| ReviewError::InvalidTarget(format!("{}: {e}", self.path.display())))?;
+        let patch = std::fs::read_to_string(&patch_path)
+            .map_err(|e| ReviewError::InvalidTarget(format!("{}: {e}", self.path.display())))?;
+
+        let repo_root = repo_root(&self.repo)?;
+        let base_commit = rev_parse(&repo_root, self.base.as_deref().unwrap_or("HEAD"))?;
+        let (temp_dir, dir) = clone_local(&repo_root, &base_commit)?;
+
+        let patch_arg = patch_path
+            .to_str()
+            .ok_or_else(|| ReviewError::InvalidTarget("Invalid patch path".to_string()))?;
+        git(&dir, &["apply", "--index", patch_arg]).map_err(|e| {
+            ReviewError::InvalidTarget(format!(
+                "patch does not apply to {}: {e}",
+                short_sha(&base_commit)
+            ))
+        })?;
+
+        // Commit the patch so the review sees an ordinary base..HEAD diff
+        let title = self.label();
+        git(
+            &dir,
+            &[
+                "-c",
+                "user.name=review",
+                "-c",
+                "user.email=review@localhost",
+                "commit",
+                "--quiet",
+                "--no-verify",
+                "-m",
+                &format!("Apply {title}"),
+            ],
+        )?;
+        let head_commit = git(&dir, &["rev-parse", "HEAD"])?;
+
+        let repo_name = repo_name(&repo_root);
+
+        Ok(Checkout {
+            dir,
+            owner: "local".to_string(),
+            slug: slugify(&format!("{repo_name}-{title}")),
+            repo_name,
+            title,
+            description: patch_subjects(&patch),
+            base_commit,
+            head_commit,
+            branch: None,
+            temp_dir,
+        })
+    }
+}
+
+/// Commit subjects from `git format-patch` output, standing in for a PR
+/// description
+fn patch_subjects(patch: &str) -> String {
+    patch
+        .lines()
+        .filter_map(|line| line.strip_prefix("Subject: "))
+        .map(|subject| {
+            // Drop the `[PATCH 1/3]` prefix
+            let subject = match subject.strip_prefix('[') {
+                Some(rest) => rest.split_once("] ").map_or(subject, |(_, s)| s),
+                None => subject,
+            };
+            format!("- {subject}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patch_subjects() {
+        let patch = "From abc Mon Sep 17 00:00:00 2001\n\
+                     Subject: [PATCH 1/2] Add login form\n\
+                     ---\n\
+                     Subject: Fix typo\n";
+        assert_eq!(patch_subjects(patch), "- Add login form\n- Fix typo");
+        assert_eq!(patch_subjects("diff --git a/x b/x\n"), "");
+    }
+}
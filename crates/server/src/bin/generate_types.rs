@@ -14,15 +14,26 @@ fn generate_types_content() -> String {
         db::models::project::Project::decl(),
         db::models::project::CreateProject::decl(),
         db::models::project::UpdateProject::decl(),
+        executors::sandbox::SandboxConfig::decl(),
+        executors::egress::EgressMode::decl(),
+        executors::egress::EgressPolicy::decl(),
         db::models::project::SearchResult::decl(),
         db::models::project::SearchMatchType::decl(),
         db::models::repo::Repo::decl(),
         db::models::repo::UpdateRepo::decl(),
+        db::models::repo::PrReviewGate::decl(),
+        executors::oci::ContainerRuntime::decl(),
+        executors::oci::ContainerConfig::decl(),
         db::models::project_repo::ProjectRepo::decl(),
         db::models::project_repo::CreateProjectRepo::decl(),
+        db::models::project_secret::ProjectSecret::decl(),
+        db::models::project_secret::UpsertProjectSecret::decl(),
         db::models::workspace_repo::WorkspaceRepo::decl(),
         db::models::workspace_repo::CreateWorkspaceRepo::decl(),
         db::models::workspace_repo::RepoWithTargetBranch::decl(),
+        db::models::issue_scheduler::SchedulerRepo::decl(),
+        db::models::issue_scheduler::IssueScheduler::decl(),
+        db::models::issue_scheduler::UpsertIssueScheduler::decl(),
         db::models::tag::Tag::decl(),
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
@@ -123,6 +134,7 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::OpenEditorResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
         server::routes::task_attempts::pr::CreatePrApiRequest::decl(),
+        server::routes::task_attempts::pr::CreatePrResponse::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
@@ -156,6 +168,7 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::workspace_summary::WorkspaceSummary::decl(),
         server::routes::task_attempts::workspace_summary::WorkspaceSummaryResponse::decl(),
         server::routes::task_attempts::workspace_summary::DiffStats::decl(),
+        server::routes::terminal::TerminalSessionInfo::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
         services::services::file_search::SearchMode::decl(),
@@ -170,7 +183,11 @@ fn generate_types_content() -> String {
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
         services::services::config::SendMessageShortcut::decl(),
+        utils::redaction::LogRedactionConfig::decl(),
+        utils::redaction::RedactionRule::decl(),
         git::GitBranch::decl(),
+        services::services::project_analytics::ProjectAnalytics::decl(),
+        services::services::project_analytics::AgentWeekAnalytics::decl(),
         services::services::queued_message::QueuedMessage::decl(),
         services::services::queued_message::QueueStatus::decl(),
         git::ConflictOp::decl(),
@@ -210,6 +227,8 @@ fn generate_types_content() -> String {
         executors::actions::coding_agent_follow_up::CodingAgentFollowUpRequest::decl(),
         executors::actions::review::ReviewRequest::decl(),
         executors::actions::review::RepoReviewContext::decl(),
+        executors::actions::review::FindingSeverity::decl(),
+        executors::actions::review::ReviewFinding::decl(),
         executors::logs::CommandExitStatus::decl(),
         executors::logs::CommandRunResult::decl(),
         executors::logs::NormalizedEntry::decl(),
@@ -17,12 +17,14 @@ use local_deployment::pty::PtyError;
 use services::services::{
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
+    events::EventError,
     git_host::GitHostError,
     image::ImageError,
     migration::MigrationError,
     project::ProjectServiceError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
+    secrets::SecretError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
@@ -52,6 +54,8 @@ pub enum ApiError {
     #[error(transparent)]
     Container(#[from] ContainerError),
     #[error(transparent)]
+    Events(#[from] EventError),
+    #[error(transparent)]
     Executor(#[from] ExecutorError),
     #[error(transparent)]
     Database(#[from] sqlx::Error),
@@ -83,6 +87,8 @@ pub enum ApiError {
     Pty(#[from] PtyError),
     #[error(transparent)]
     Migration(#[from] MigrationError),
+    #[error(transparent)]
+    Secret(#[from] SecretError),
 }
 
 impl From<&'static str> for ApiError {
@@ -397,6 +403,11 @@ impl IntoResponse for ApiError {
             ApiError::Pty(PtyError::SessionClosed) => {
                 ErrorInfo::with_status(StatusCode::GONE, "PtyError", "PTY session closed.")
             }
+            ApiError::Pty(PtyError::ReadOnly) => ErrorInfo::with_status(
+                StatusCode::FORBIDDEN,
+                "PtyError",
+                "PTY session is attached read-only.",
+            ),
             ApiError::Pty(_) => ErrorInfo::internal("PtyError"),
 
             ApiError::Unauthorized => ErrorInfo::with_status(
@@ -414,8 +425,19 @@ impl IntoResponse for ApiError {
                 "Failed to upload file. Please ensure the file is valid and try again.",
             ),
 
+            ApiError::Secret(SecretError::Database(_)) => ErrorInfo::internal("DatabaseError"),
+            ApiError::Secret(SecretError::InvalidName(_)) => {
+                ErrorInfo::bad_request("SecretError", format!("{}", self))
+            }
+            ApiError::Secret(_) => ErrorInfo::with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "SecretError",
+                format!("{}", self),
+            ),
+
             ApiError::Deployment(_) => ErrorInfo::internal("DeploymentError"),
             ApiError::Container(_) => ErrorInfo::internal("ContainerError"),
+            ApiError::Events(_) => ErrorInfo::internal("EventError"),
             ApiError::Executor(_) => ErrorInfo::internal("ExecutorError"),
             ApiError::CommandBuilder(_) => ErrorInfo::internal("CommandBuildError"),
             ApiError::Database(_) => ErrorInfo::internal("DatabaseError"),
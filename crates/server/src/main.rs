@@ -70,6 +70,18 @@ async fn main() -> Result<(), VibeKanbanError> {
     deployment
         .track_if_analytics_allowed("session_start", serde_json::json!({}))
         .await;
+    // Redact stored logs again whenever the redaction rules have changed
+    let stored_logs_scrubbed = {
+        let config = deployment.config().read().await;
+        config.log_redaction.stored_logs_scrubbed_with.as_deref()
+            == Some(config.log_redaction.rules_hash().as_str())
+    };
+    if !stored_logs_scrubbed {
+        let deployment_for_scrub = deployment.clone();
+        tokio::spawn(async move {
+            deployment_for_scrub.scrub_stored_logs().await;
+        });
+    }
     // Pre-warm file search cache for most active projects
     let deployment_for_cache = deployment.clone();
     tokio::spawn(async move {
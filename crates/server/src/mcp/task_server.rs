@@ -1,10 +1,11 @@
 use std::str::FromStr;
 
 use api_types::{
-    Issue, ListIssuesResponse, ListOrganizationsResponse, ListProjectStatusesResponse,
-    MutationResponse, ProjectStatus,
+    CustomField, Issue, ListCustomFieldsResponse, ListIssuesResponse, ListOrganizationsResponse,
+    ListProjectStatusesResponse, MutationResponse, ProjectStatus, custom_field_values,
 };
 use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
     project::Project,
     repo::Repo,
     tag::Tag,
@@ -26,7 +27,10 @@ use serde_json;
 use uuid::Uuid;
 
 use crate::routes::{
-    containers::ContainerQuery, task_attempts::WorkspaceRepoInput, tasks::CreateAndStartTaskRequest,
+    containers::ContainerQuery,
+    task_attempts::{WorkspaceRepoInput, dev_server::DevServerLogsResponse},
+    tasks::CreateAndStartTaskRequest,
+    terminal::{TerminalOutput, TerminalSessionInfo},
 };
 
 // ── MCP request/response types ──────────────────────────────────────────────
@@ -180,6 +184,10 @@ pub struct McpListIssuesRequest {
         description = "The ID of the project to list issues from. Optional if running inside a workspace linked to a remote project."
     )]
     pub project_id: Option<Uuid>,
+    #[schemars(
+        description = "Optional filter in the issue query language. Terms are space-separated and all must match: `status:\"In Review\"`, `priority>=high` (urgent, high, medium, low, none), `assignee:me` (or an email/username, or none), `tag:backend`, `created:`/`updated:`/`due:`/`completed:` with an age (`updated:<7d`, `created:>2w`) or date (`due:<2026-03-01`), `has:pr` (also workspace, assignee, tag, priority, parent, description, due), `no:assignee`, `is:open`/`is:closed`/`is:blocked`, `parent:PROJ-12`, and custom fields by key (`field.severity:high`, `field.points>=3`, `field.owner:none`). Prefix a term with `-` to negate it; any other word is matched against title and description."
+    )]
+    pub query: Option<String>,
     #[schemars(description = "Maximum number of issues to return (default: 50)")]
     pub limit: Option<i32>,
 }
@@ -198,6 +206,18 @@ pub struct IssueSummary {
     pub updated_at: String,
 }
 
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct IssueCustomFieldValue {
+    #[schemars(description = "The custom field key, usable in `list_issues` queries")]
+    pub key: String,
+    #[schemars(description = "The custom field's display name")]
+    pub name: String,
+    #[schemars(
+        description = "The value: a string, number, date (YYYY-MM-DD), user ID or array of selected options"
+    )]
+    pub value: serde_json::Value,
+}
+
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct IssueDetails {
     #[schemars(description = "The unique identifier of the issue")]
@@ -210,6 +230,8 @@ pub struct IssueDetails {
     pub status: String,
     #[schemars(description = "The status ID (UUID)")]
     pub status_id: String,
+    #[schemars(description = "Values of the organization's custom fields set on the issue")]
+    pub custom_fields: Vec<IssueCustomFieldValue>,
     #[schemars(description = "When the issue was created")]
     pub created_at: String,
     #[schemars(description = "When the issue was last updated")]
@@ -311,6 +333,79 @@ pub struct McpLinkWorkspaceResponse {
     pub issue_id: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct McpTailDevServerLogsRequest {
+    #[schemars(
+        description = "The workspace ID. Optional if running inside a workspace; defaults to the current workspace."
+    )]
+    pub workspace_id: Option<Uuid>,
+    #[schemars(description = "Number of trailing lines to return per dev server (default: 200)")]
+    pub lines: Option<usize>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpDevServerLogs {
+    #[schemars(description = "The execution process ID of the dev server")]
+    pub execution_process_id: String,
+    #[schemars(description = "Process status: running, completed, failed or killed")]
+    pub status: String,
+    #[schemars(description = "Exit code, if the dev server has exited")]
+    pub exit_code: Option<i64>,
+    #[schemars(description = "Repository directory the dev server runs in")]
+    pub working_dir: Option<String>,
+    #[schemars(description = "Most recent stdout/stderr output")]
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpTailDevServerLogsResponse {
+    pub workspace_id: String,
+    pub dev_servers: Vec<McpDevServerLogs>,
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct McpReadTerminalOutputRequest {
+    #[schemars(
+        description = "The workspace ID. Optional if running inside a workspace; defaults to the current workspace."
+    )]
+    pub workspace_id: Option<Uuid>,
+    #[schemars(
+        description = "The terminal session to read. Defaults to the most recently started session in the workspace."
+    )]
+    pub session_id: Option<Uuid>,
+    #[schemars(
+        description = "Maximum number of bytes of recent output to return (default: 16384)"
+    )]
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpReadTerminalOutputResponse {
+    pub session_id: String,
+    #[schemars(description = "Whether the terminal's shell has exited")]
+    pub exited: bool,
+    #[schemars(description = "Recent terminal output with ANSI escapes stripped")]
+    pub output: String,
+    #[schemars(description = "Other terminal sessions open in the workspace")]
+    pub other_session_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct McpRestartDevServerRequest {
+    #[schemars(
+        description = "The workspace ID. Optional if running inside a workspace; defaults to the current workspace."
+    )]
+    pub workspace_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpRestartDevServerResponse {
+    pub workspace_id: String,
+    #[schemars(description = "Execution process IDs of the newly started dev servers")]
+    pub execution_process_ids: Vec<String>,
+}
+
 // ── Server struct ───────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -489,6 +584,13 @@ impl TaskServer {
 
 // ── Helpers ─────────────────────────────────────────────────────────────────
 
+fn status_name(status: &ExecutionProcessStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", status))
+}
+
 #[derive(Debug, Deserialize)]
 struct ApiResponseEnvelope<T> {
     success: bool,
@@ -650,6 +752,20 @@ impl TaskServer {
         .unwrap())
     }
 
+    fn resolve_workspace_id(&self, explicit: Option<Uuid>) -> Result<Uuid, CallToolResult> {
+        if let Some(id) = explicit {
+            return Ok(id);
+        }
+        if let Some(ctx) = &self.context {
+            return Ok(ctx.workspace_id);
+        }
+        Err(Self::err(
+            "workspace_id is required (not running inside a workspace)",
+            None::<&str>,
+        )
+        .unwrap())
+    }
+
     /// Fetches project statuses for a project, returning a map of status name → status.
     async fn fetch_project_statuses(
         &self,
@@ -663,6 +779,19 @@ impl TaskServer {
         Ok(response.project_statuses)
     }
 
+    /// Fetches the custom fields of the organization that owns the project.
+    async fn fetch_custom_fields(
+        &self,
+        project_id: Uuid,
+    ) -> Result<Vec<CustomField>, CallToolResult> {
+        let url = self.url(&format!(
+            "/api/remote/custom-fields?project_id={}",
+            project_id
+        ));
+        let response: ListCustomFieldsResponse = self.send_json(self.client.get(&url)).await?;
+        Ok(response.custom_fields)
+    }
+
     /// Resolves a status name to a status_id UUID using project statuses.
     async fn resolve_status_id(
         &self,
@@ -749,17 +878,36 @@ impl TaskServer {
             .await
     }
 
-    /// Converts an Issue to IssueDetails, resolving status_id to name.
+    /// Converts an Issue to IssueDetails, resolving status_id to name and
+    /// custom field keys to their definitions.
     async fn issue_to_details(&self, issue: &Issue) -> IssueDetails {
         let status = self
             .resolve_status_name(issue.project_id, issue.status_id)
             .await;
+        let custom_fields = match custom_field_values(&issue.extension_metadata) {
+            Some(values) if !values.is_empty() => self
+                .fetch_custom_fields(issue.project_id)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|field| {
+                    let value = values.get(&field.key).filter(|value| !value.is_null())?;
+                    Some(IssueCustomFieldValue {
+                        value: value.clone(),
+                        key: field.key,
+                        name: field.name,
+                    })
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
         IssueDetails {
             id: issue.id.to_string(),
             title: issue.title.clone(),
             description: issue.description.clone(),
             status,
             status_id: issue.status_id.to_string(),
+            custom_fields,
             created_at: issue.created_at.to_rfc3339(),
             updated_at: issue.updated_at.to_rfc3339(),
         }
@@ -813,6 +961,8 @@ impl TaskServer {
             priority: None,
             start_date: None,
             target_date: None,
+            estimate: None,
+            estimate_unit: None,
             completed_at: None,
             sort_order: 0.0,
             parent_issue_id: None,
@@ -1022,11 +1172,15 @@ impl TaskServer {
     }
 
     #[tool(
-        description = "List all the issues in a project. `project_id` is optional if running inside a workspace linked to a remote project."
+        description = "List the issues in a project, optionally filtered with `query` (e.g. `status:\"In Review\" priority>=high assignee:me tag:backend updated:<7d has:pr`) to find related work. `project_id` is optional if running inside a workspace linked to a remote project."
     )]
     async fn list_issues(
         &self,
-        Parameters(McpListIssuesRequest { project_id, limit }): Parameters<McpListIssuesRequest>,
+        Parameters(McpListIssuesRequest {
+            project_id,
+            query,
+            limit,
+        }): Parameters<McpListIssuesRequest>,
     ) -> Result<CallToolResult, ErrorData> {
         let project_id = match self.resolve_project_id(project_id) {
             Ok(id) => id,
@@ -1034,7 +1188,11 @@ impl TaskServer {
         };
 
         let url = self.url(&format!("/api/remote/issues?project_id={}", project_id));
-        let response: ListIssuesResponse = match self.send_json(self.client.get(&url)).await {
+        let mut request = self.client.get(&url);
+        if let Some(query) = query.as_deref().filter(|q| !q.trim().is_empty()) {
+            request = request.query(&[("q", query)]);
+        }
+        let response: ListIssuesResponse = match self.send_json(request).await {
             Ok(r) => r,
             Err(e) => return Ok(e),
         };
@@ -1242,6 +1400,8 @@ impl TaskServer {
             priority: None,
             start_date: None,
             target_date: None,
+            estimate: None,
+            estimate_unit: None,
             completed_at: None,
             sort_order: None,
             parent_issue_id: None,
@@ -1260,6 +1420,146 @@ impl TaskServer {
         TaskServer::success(&McpUpdateIssueResponse { issue: details })
     }
 
+    #[tool(
+        description = "Tail the output of the workspace's dev server. Returns the running dev servers, or the most recent one if none is running (e.g. it crashed). `workspace_id` is optional inside a workspace."
+    )]
+    async fn tail_dev_server_logs(
+        &self,
+        Parameters(McpTailDevServerLogsRequest {
+            workspace_id,
+            lines,
+        }): Parameters<McpTailDevServerLogsRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let workspace_id = match self.resolve_workspace_id(workspace_id) {
+            Ok(id) => id,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!(
+            "/api/task-attempts/{}/dev-server/logs",
+            workspace_id
+        ));
+        let mut rb = self.client.get(&url);
+        if let Some(lines) = lines {
+            rb = rb.query(&[("tail", lines)]);
+        }
+        let response: DevServerLogsResponse = match self.send_json(rb).await {
+            Ok(r) => r,
+            Err(e) => return Ok(e),
+        };
+
+        let dev_servers: Vec<McpDevServerLogs> = response
+            .dev_servers
+            .into_iter()
+            .map(|logs| McpDevServerLogs {
+                execution_process_id: logs.execution_process_id.to_string(),
+                status: status_name(&logs.status),
+                exit_code: logs.exit_code,
+                working_dir: logs.working_dir,
+                output: logs.lines.join("\n"),
+            })
+            .collect();
+
+        TaskServer::success(&McpTailDevServerLogsResponse {
+            workspace_id: workspace_id.to_string(),
+            count: dev_servers.len(),
+            dev_servers,
+        })
+    }
+
+    #[tool(
+        description = "Read the recent output of a terminal opened in the workspace. Defaults to the most recently started terminal. `workspace_id` is optional inside a workspace."
+    )]
+    async fn read_terminal_output(
+        &self,
+        Parameters(McpReadTerminalOutputRequest {
+            workspace_id,
+            session_id,
+            max_bytes,
+        }): Parameters<McpReadTerminalOutputRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let workspace_id = match self.resolve_workspace_id(workspace_id) {
+            Ok(id) => id,
+            Err(e) => return Ok(e),
+        };
+
+        let list_url = self.url("/api/terminal/sessions");
+        let sessions: Vec<TerminalSessionInfo> = match self
+            .send_json(
+                self.client
+                    .get(&list_url)
+                    .query(&[("workspace_id", workspace_id)]),
+            )
+            .await
+        {
+            Ok(s) => s,
+            Err(e) => return Ok(e),
+        };
+
+        let Some(session_id) = session_id.or_else(|| sessions.last().map(|s| s.id)) else {
+            return Self::err("No terminal sessions are open in this workspace", None);
+        };
+        if !sessions.iter().any(|s| s.id == session_id) {
+            return Self::err(
+                format!(
+                    "Terminal session {} not found in this workspace",
+                    session_id
+                ),
+                None,
+            );
+        }
+
+        let url = self.url(&format!("/api/terminal/sessions/{}/output", session_id));
+        let mut rb = self.client.get(&url);
+        if let Some(max_bytes) = max_bytes {
+            rb = rb.query(&[("max_bytes", max_bytes)]);
+        }
+        let output: TerminalOutput = match self.send_json(rb).await {
+            Ok(o) => o,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&McpReadTerminalOutputResponse {
+            session_id: output.session_id.to_string(),
+            exited: output.exited,
+            output: output.output,
+            other_session_ids: sessions
+                .iter()
+                .filter(|s| s.id != session_id)
+                .map(|s| s.id.to_string())
+                .collect(),
+        })
+    }
+
+    #[tool(
+        description = "Restart the workspace's dev server: stops any running dev server for the project and starts the configured dev server script. `workspace_id` is optional inside a workspace."
+    )]
+    async fn restart_dev_server(
+        &self,
+        Parameters(McpRestartDevServerRequest { workspace_id }): Parameters<
+            McpRestartDevServerRequest,
+        >,
+    ) -> Result<CallToolResult, ErrorData> {
+        let workspace_id = match self.resolve_workspace_id(workspace_id) {
+            Ok(id) => id,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!(
+            "/api/task-attempts/{}/start-dev-server",
+            workspace_id
+        ));
+        let processes: Vec<ExecutionProcess> = match self.send_json(self.client.post(&url)).await {
+            Ok(p) => p,
+            Err(e) => return Ok(e),
+        };
+
+        TaskServer::success(&McpRestartDevServerResponse {
+            workspace_id: workspace_id.to_string(),
+            execution_process_ids: processes.iter().map(|p| p.id.to_string()).collect(),
+        })
+    }
+
     #[tool(description = "Delete an issue. `issue_id` is required.")]
     async fn delete_issue(
         &self,
@@ -1276,7 +1576,7 @@ impl TaskServer {
     }
 
     #[tool(
-        description = "Get detailed information about a specific issue. You can use `list_issues` to find issue IDs. `issue_id` is required."
+        description = "Get detailed information about a specific issue, including its custom field values. You can use `list_issues` to find issue IDs. `issue_id` is required."
     )]
     async fn get_issue(
         &self,
@@ -1296,7 +1596,7 @@ impl TaskServer {
 #[tool_handler]
 impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
-        let mut instruction = "A task and project management server. If you need to create or update tickets or issues then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_issues` to fetch the `issue_ids` of all the issues in a project. TOOLS: 'list_organizations', 'list_projects', 'list_issues', 'create_issue', 'start_workspace_session', 'get_issue', 'update_issue', 'delete_issue', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script'. Make sure to pass `project_id`, `issue_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
+        let mut instruction = "A task and project management server. If you need to create or update tickets or issues then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_issues` to fetch the `issue_ids` of the issues in a project; pass `query` (e.g. `assignee:me is:open`) to narrow it down. TOOLS: 'list_organizations', 'list_projects', 'list_issues', 'create_issue', 'start_workspace_session', 'get_issue', 'update_issue', 'delete_issue', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script', 'tail_dev_server_logs', 'read_terminal_output', 'restart_dev_server'. Make sure to pass `project_id`, `issue_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
         if self.context.is_some() {
             let context_instruction = "Use 'get_context' to fetch project/issue/workspace metadata for the active Vibe Kanban workspace session when available.";
             instruction = format!("{} {}", context_instruction, instruction);
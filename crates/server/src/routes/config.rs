@@ -129,7 +129,7 @@ async fn get_user_system_info(
 
 async fn update_config(
     State(deployment): State<DeploymentImpl>,
-    Json(new_config): Json<Config>,
+    Json(mut new_config): Json<Config>,
 ) -> ResponseJson<ApiResponse<Config>> {
     let config_path = config_path();
 
@@ -140,8 +140,15 @@ async fn update_config(
         ));
     }
 
+    if let Err(e) = new_config.log_redaction.validate() {
+        return ResponseJson(ApiResponse::error(&e));
+    }
+
     // Get old config state before updating
     let old_config = deployment.config().read().await.clone();
+    // Only the server records which rules the stored logs were scrubbed with
+    new_config.log_redaction.stored_logs_scrubbed_with =
+        old_config.log_redaction.stored_logs_scrubbed_with.clone();
 
     match save_config_to_file(&new_config, &config_path).await {
         Ok(_) => {
@@ -193,6 +200,15 @@ async fn track_config_events(deployment: &DeploymentImpl, old: &Config, new: &Co
 async fn handle_config_events(deployment: &DeploymentImpl, old: &Config, new: &Config) {
     track_config_events(deployment, old, new).await;
 
+    // Scrub stored logs right away rather than on the next start; if this is
+    // interrupted, startup still notices the mismatch and scrubs again
+    if new.log_redaction.rules_hash() != old.log_redaction.rules_hash() {
+        let deployment_clone = deployment.clone();
+        tokio::spawn(async move {
+            deployment_clone.scrub_stored_logs().await;
+        });
+    }
+
     if !old.disclaimer_acknowledged && new.disclaimer_acknowledged {
         // Spawn auto project setup as background task to avoid blocking config response
         let deployment_clone = deployment.clone();
@@ -1,12 +1,12 @@
 use anyhow;
 use axum::{
-    Extension, Router,
+    BoxError, Extension, Router,
     extract::{
         Path, Query, State,
-        ws::{WebSocket, WebSocketUpgrade},
+        ws::{Message, WebSocket, WebSocketUpgrade},
     },
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{IntoResponse, Json as ResponseJson, Sse, sse::Event},
     routing::{get, post},
 };
 use db::models::{
@@ -14,13 +14,26 @@ use db::models::{
     execution_process_repo_state::ExecutionProcessRepoState,
 };
 use deployment::Deployment;
-use futures_util::{SinkExt, StreamExt, TryStreamExt};
+use executors::logs::utils::patch::ConversationPatch;
+use futures_util::{SinkExt, Stream, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use services::services::container::ContainerService;
-use utils::{log_msg::LogMsg, response::ApiResponse};
+use utils::{
+    log_msg::LogMsg,
+    msg_store::{LogCursor, SequencedLogMsg, SequencedLogStream},
+    response::ApiResponse,
+};
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_execution_process_middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_execution_process_middleware,
+    streaming::{
+        LogPollQuery, LogPollResponse, ResumeCursor, log_stream_sse, poll_log_stream,
+        poll_resumable_stream, resumable_stream_sse,
+    },
+};
 
 #[derive(Debug, Deserialize)]
 pub struct SessionExecutionProcessQuery {
@@ -41,115 +54,126 @@ pub async fn stream_raw_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
+    resume: ResumeCursor,
 ) -> Result<impl IntoResponse, ApiError> {
-    // Check if the stream exists before upgrading the WebSocket
-    let _stream = deployment
-        .container()
-        .stream_raw_logs(&exec_id)
-        .await
-        .ok_or_else(|| {
-            ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
-        })?;
+    let stream = raw_log_patches(&deployment, exec_id, resume).await?;
 
     Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_raw_logs_ws(socket, deployment, exec_id).await {
+        if let Err(e) = handle_sequenced_logs_ws(socket, stream).await {
             tracing::warn!("raw logs WS closed: {}", e);
         }
     }))
 }
 
-async fn handle_raw_logs_ws(
-    socket: WebSocket,
-    deployment: DeploymentImpl,
+/// Raw stdout/stderr converted into conversation patches, so raw-log consumers
+/// can share the normalized-log rendering path.
+///
+/// Entry indices number the stdout/stderr lines of the execution, so a resumed
+/// stream starts at the index following the client's last line.
+async fn raw_log_patches(
+    deployment: &DeploymentImpl,
     exec_id: Uuid,
-) -> anyhow::Result<()> {
-    use std::sync::{
-        Arc,
-        atomic::{AtomicUsize, Ordering},
-    };
-
-    use executors::logs::utils::patch::ConversationPatch;
-    use utils::log_msg::LogMsg;
+    resume: ResumeCursor,
+) -> Result<SequencedLogStream, ApiError> {
+    let container = deployment.container();
+    let not_found = || ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound);
 
-    // Get the raw stream and convert to JSON patches on-the-fly
-    let raw_stream = deployment
-        .container()
-        .stream_raw_logs(&exec_id)
+    let mut raw = container
+        .stream_raw_logs(&exec_id, resume.0.as_ref())
         .await
-        .ok_or_else(|| anyhow::anyhow!("Execution process not found"))?;
-
-    let counter = Arc::new(AtomicUsize::new(0));
-    let mut stream = raw_stream.map_ok({
-        let counter = counter.clone();
-        move |m| match m {
-            LogMsg::Stdout(content) => {
-                let index = counter.fetch_add(1, Ordering::SeqCst);
-                let patch = ConversationPatch::add_stdout(index, content);
-                LogMsg::JsonPatch(patch).to_ws_message_unchecked()
-            }
-            LogMsg::Stderr(content) => {
-                let index = counter.fetch_add(1, Ordering::SeqCst);
-                let patch = ConversationPatch::add_stderr(index, content);
-                LogMsg::JsonPatch(patch).to_ws_message_unchecked()
+        .ok_or_else(not_found)?;
+    let mut start_index = 0;
+    if let Some(cursor) = resume.0.as_ref().filter(|_| !raw.replayed) {
+        match container.raw_output_index(&exec_id, cursor).await {
+            Some(index) => start_index = index,
+            // Evicted between the two lookups; start over like any other
+            // cursor that can't be honoured
+            None => {
+                raw = container
+                    .stream_raw_logs(&exec_id, None)
+                    .await
+                    .ok_or_else(not_found)?;
+                raw.replayed = true;
             }
-            LogMsg::Finished => LogMsg::Finished.to_ws_message_unchecked(),
-            _ => unreachable!("Raw stream should only have Stdout/Stderr/Finished"),
         }
-    });
+    }
 
-    // Split socket into sender and receiver
-    let (mut sender, mut receiver) = socket.split();
+    let mut index = start_index as usize;
+    let stream = raw
+        .stream
+        .map_ok(move |m| {
+            let msg = match m.msg {
+                LogMsg::Stdout(content) => {
+                    index += 1;
+                    LogMsg::JsonPatch(ConversationPatch::add_stdout(index - 1, content))
+                }
+                LogMsg::Stderr(content) => {
+                    index += 1;
+                    LogMsg::JsonPatch(ConversationPatch::add_stderr(index - 1, content))
+                }
+                LogMsg::Finished => LogMsg::Finished,
+                _ => unreachable!("Raw stream should only have Stdout/Stderr/Finished"),
+            };
+            SequencedLogMsg { seq: m.seq, msg }
+        })
+        .boxed();
 
-    // Drain (and ignore) any client->server messages so pings/pongs work
-    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+    Ok(SequencedLogStream { stream, ..raw })
+}
 
-    // Forward server messages
-    while let Some(item) = stream.next().await {
-        match item {
-            Ok(msg) => {
-                if sender.send(msg).await.is_err() {
-                    break; // client disconnected
-                }
-            }
-            Err(e) => {
-                tracing::error!("stream error: {}", e);
-                break;
-            }
-        }
-    }
-    Ok(())
+async fn normalized_log_stream(
+    deployment: &DeploymentImpl,
+    exec_id: Uuid,
+    resume: ResumeCursor,
+) -> Result<SequencedLogStream, ApiError> {
+    deployment
+        .container()
+        .stream_normalized_logs(&exec_id, resume.0.as_ref())
+        .await
+        .ok_or_else(|| ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound))
 }
 
 pub async fn stream_normalized_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
     Path(exec_id): Path<Uuid>,
+    resume: ResumeCursor,
 ) -> Result<impl IntoResponse, ApiError> {
-    let stream = deployment
-        .container()
-        .stream_normalized_logs(&exec_id)
-        .await
-        .ok_or_else(|| {
-            ApiError::ExecutionProcess(ExecutionProcessError::ExecutionProcessNotFound)
-        })?;
-
-    // Convert the error type to anyhow::Error and turn TryStream -> Stream<Result<_, _>>
-    let stream = stream.err_into::<anyhow::Error>().into_stream();
+    let stream = normalized_log_stream(&deployment, exec_id, resume).await?;
 
     Ok(ws.on_upgrade(move |socket| async move {
-        if let Err(e) = handle_normalized_logs_ws(socket, stream).await {
+        if let Err(e) = handle_sequenced_logs_ws(socket, stream).await {
             tracing::warn!("normalized logs WS closed: {}", e);
         }
     }))
 }
 
-async fn handle_normalized_logs_ws(
+/// Forward a sequenced log stream, tagging each message with its resume cursor.
+/// A `{"Replayed":true}` message is sent first when the requested cursor could
+/// not be honoured, so the client resets before the full replay.
+async fn handle_sequenced_logs_ws(
     socket: WebSocket,
-    stream: impl futures_util::Stream<Item = anyhow::Result<LogMsg>> + Unpin + Send + 'static,
+    sequenced: SequencedLogStream,
 ) -> anyhow::Result<()> {
-    let mut stream = stream.map_ok(|msg| msg.to_ws_message_unchecked());
     let (mut sender, mut receiver) = socket.split();
     tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    if sequenced.replayed
+        && sender
+            .send(Message::Text(r#"{"Replayed":true}"#.into()))
+            .await
+            .is_err()
+    {
+        return Ok(());
+    }
+
+    let store_id = sequenced.store_id;
+    let mut stream = sequenced.stream.map_ok(move |m| {
+        m.msg.to_ws_message_with_cursor(&LogCursor {
+            store_id,
+            seq: m.seq,
+        })
+    });
     while let Some(item) = stream.next().await {
         match item {
             Ok(msg) => {
@@ -166,6 +190,46 @@ async fn handle_normalized_logs_ws(
     Ok(())
 }
 
+pub async fn stream_raw_logs_sse(
+    State(deployment): State<DeploymentImpl>,
+    Path(exec_id): Path<Uuid>,
+    resume: ResumeCursor,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    let stream = raw_log_patches(&deployment, exec_id, resume).await?;
+    Ok(log_stream_sse(stream))
+}
+
+pub async fn stream_normalized_logs_sse(
+    State(deployment): State<DeploymentImpl>,
+    Path(exec_id): Path<Uuid>,
+    resume: ResumeCursor,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    let stream = normalized_log_stream(&deployment, exec_id, resume).await?;
+    Ok(log_stream_sse(stream))
+}
+
+pub async fn poll_raw_logs(
+    State(deployment): State<DeploymentImpl>,
+    Path(exec_id): Path<Uuid>,
+    resume: ResumeCursor,
+    Query(query): Query<LogPollQuery>,
+) -> Result<ResponseJson<ApiResponse<LogPollResponse>>, ApiError> {
+    let stream = raw_log_patches(&deployment, exec_id, resume).await?;
+    let response = poll_log_stream(stream, resume, query).await;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
+pub async fn poll_normalized_logs(
+    State(deployment): State<DeploymentImpl>,
+    Path(exec_id): Path<Uuid>,
+    resume: ResumeCursor,
+    Query(query): Query<LogPollQuery>,
+) -> Result<ResponseJson<ApiResponse<LogPollResponse>>, ApiError> {
+    let stream = normalized_log_stream(&deployment, exec_id, resume).await?;
+    let response = poll_log_stream(stream, resume, query).await;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 pub async fn stop_execution_process(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -206,9 +270,9 @@ async fn handle_execution_processes_by_session_ws(
     // Get the raw stream and convert LogMsg to WebSocket messages
     let mut stream = deployment
         .events()
-        .stream_execution_processes_for_session_raw(session_id, show_soft_deleted)
+        .stream_execution_processes_for_session_raw(session_id, show_soft_deleted, None)
         .await?
-        .map_ok(|msg| msg.to_ws_message_unchecked());
+        .map_ok(|m| m.msg.to_ws_message_unchecked());
 
     // Split socket into sender and receiver
     let (mut sender, mut receiver) = socket.split();
@@ -233,6 +297,41 @@ async fn handle_execution_processes_by_session_ws(
     Ok(())
 }
 
+pub async fn stream_execution_processes_by_session_sse(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SessionExecutionProcessQuery>,
+    resume: ResumeCursor,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_execution_processes_for_session_raw(
+            query.session_id,
+            query.show_soft_deleted.unwrap_or(false),
+            resume.0.as_ref(),
+        )
+        .await?;
+
+    Ok(resumable_stream_sse(stream))
+}
+
+pub async fn poll_execution_processes_by_session(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<SessionExecutionProcessQuery>,
+    resume: ResumeCursor,
+    Query(poll): Query<LogPollQuery>,
+) -> Result<ResponseJson<ApiResponse<LogPollResponse>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_execution_processes_for_session_raw(
+            query.session_id,
+            query.show_soft_deleted.unwrap_or(false),
+            resume.0.as_ref(),
+        )
+        .await?;
+    let response = poll_resumable_stream(stream, resume, poll).await;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 pub async fn get_execution_process_repo_states(
     Extension(execution_process): Extension<ExecutionProcess>,
     State(deployment): State<DeploymentImpl>,
@@ -250,6 +349,10 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/repo-states", get(get_execution_process_repo_states))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
+        .route("/raw-logs/sse", get(stream_raw_logs_sse))
+        .route("/normalized-logs/sse", get(stream_normalized_logs_sse))
+        .route("/raw-logs/poll", get(poll_raw_logs))
+        .route("/normalized-logs/poll", get(poll_normalized_logs))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_execution_process_middleware,
@@ -260,6 +363,14 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/stream/session/ws",
             get(stream_execution_processes_by_session_ws),
         )
+        .route(
+            "/stream/session/sse",
+            get(stream_execution_processes_by_session_sse),
+        )
+        .route(
+            "/stream/session/poll",
+            get(poll_execution_processes_by_session),
+        )
         .nest("/{id}", workspace_id_router);
 
     Router::new().nest("/execution-processes", workspaces_router)
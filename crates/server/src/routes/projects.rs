@@ -2,28 +2,38 @@ use std::path::PathBuf;
 
 use anyhow;
 use axum::{
-    Extension, Json, Router,
+    BoxError, Extension, Json, Router,
     extract::{
         Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    response::{IntoResponse, Json as ResponseJson, Sse, sse::Event},
+    routing::{delete, get, post},
 };
 use db::models::{
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
     project_repo::{CreateProjectRepo, ProjectRepo},
+    project_secret::{ProjectSecret, UpsertProjectSecret},
     repo::Repo,
 };
 use deployment::Deployment;
-use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use services::services::{file_search::SearchQuery, project::ProjectServiceError};
+use futures_util::{SinkExt, Stream, StreamExt, TryStreamExt};
+use services::services::{
+    file_search::SearchQuery, project::ProjectServiceError, secrets::SecretVault,
+};
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_project_middleware,
+    streaming::{
+        LogPollQuery, LogPollResponse, ResumeCursor, poll_resumable_stream, resumable_stream_sse,
+    },
+};
 
 pub async fn get_projects(
     State(deployment): State<DeploymentImpl>,
@@ -43,12 +53,36 @@ pub async fn stream_projects_ws(
     })
 }
 
+pub async fn stream_projects_sse(
+    State(deployment): State<DeploymentImpl>,
+    resume: ResumeCursor,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_projects_raw(resume.0.as_ref())
+        .await?;
+    Ok(resumable_stream_sse(stream))
+}
+
+pub async fn poll_projects(
+    State(deployment): State<DeploymentImpl>,
+    resume: ResumeCursor,
+    Query(poll): Query<LogPollQuery>,
+) -> Result<ResponseJson<ApiResponse<LogPollResponse>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_projects_raw(resume.0.as_ref())
+        .await?;
+    let response = poll_resumable_stream(stream, resume, poll).await;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 async fn handle_projects_ws(socket: WebSocket, deployment: DeploymentImpl) -> anyhow::Result<()> {
     let mut stream = deployment
         .events()
-        .stream_projects_raw()
+        .stream_projects_raw(None)
         .await?
-        .map_ok(|msg| msg.to_ws_message_unchecked());
+        .map_ok(|m| m.msg.to_ws_message_unchecked());
 
     // Split socket into sender and receiver
     let (mut sender, mut receiver) = socket.split();
@@ -433,6 +467,53 @@ pub async fn get_project_repository(
     }
 }
 
+pub async fn get_project_secrets(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ProjectSecret>>>, ApiError> {
+    let secrets = ProjectSecret::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(secrets)))
+}
+
+pub async fn set_project_secret(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<UpsertProjectSecret>,
+) -> Result<ResponseJson<ApiResponse<ProjectSecret>>, ApiError> {
+    let secret = SecretVault::load()
+        .await?
+        .set(
+            &deployment.db().pool,
+            project.id,
+            payload.name.trim(),
+            &payload.value,
+        )
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_secret_set",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(secret)))
+}
+
+pub async fn delete_project_secret(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Path((_project_id, name)): Path<(Uuid, String)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = ProjectSecret::delete(&deployment.db().pool, project.id, &name).await?;
+    if rows_affected == 0 {
+        return Err(ApiError::BadRequest("Secret not found".to_string()));
+    }
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let project_id_router = Router::new()
         .route(
@@ -445,6 +526,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/repositories",
             get(get_project_repositories).post(add_project_repository),
         )
+        .route("/secrets", get(get_project_secrets).put(set_project_secret))
+        .route("/secrets/{name}", delete(delete_project_secret))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
@@ -457,6 +540,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             get(get_project_repository).delete(delete_project_repository),
         )
         .route("/stream/ws", get(stream_projects_ws))
+        .route("/stream/sse", get(stream_projects_sse))
+        .route("/stream/poll", get(poll_projects))
         .nest("/{id}", project_id_router);
 
     Router::new().nest("/projects", projects_router)
@@ -0,0 +1,32 @@
+use api_types::ListCustomFieldsResponse;
+use axum::{
+    Router,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use serde::Deserialize;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize)]
+pub struct ListCustomFieldsQuery {
+    pub project_id: Uuid,
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/custom-fields", get(list_custom_fields))
+}
+
+/// Lists the custom fields of the organization that owns the project.
+async fn list_custom_fields(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ListCustomFieldsQuery>,
+) -> Result<ResponseJson<ApiResponse<ListCustomFieldsResponse>>, ApiError> {
+    let client = deployment.remote_client()?;
+    let project = client.get_remote_project(query.project_id).await?;
+    let response = client.list_custom_fields(project.organization_id).await?;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
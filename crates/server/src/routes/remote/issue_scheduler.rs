@@ -0,0 +1,76 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    response::Json as ResponseJson,
+    routing::get,
+};
+use db::models::{
+    issue_scheduler::{IssueScheduler, UpsertIssueScheduler},
+    project::{Project, ProjectError},
+    repo::{Repo, RepoError},
+};
+use deployment::Deployment;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/projects/{project_id}/scheduler",
+        get(get_issue_scheduler)
+            .put(upsert_issue_scheduler)
+            .delete(delete_issue_scheduler),
+    )
+}
+
+async fn get_issue_scheduler(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Option<IssueScheduler>>>, ApiError> {
+    let scheduler =
+        IssueScheduler::find_by_remote_project_id(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(scheduler)))
+}
+
+async fn upsert_issue_scheduler(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+    Json(payload): Json<UpsertIssueScheduler>,
+) -> Result<ResponseJson<ApiResponse<IssueScheduler>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if payload.repos.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one repository is required".to_string(),
+        ));
+    }
+    if payload.max_concurrent < 1 {
+        return Err(ApiError::BadRequest(
+            "max_concurrent must be at least 1".to_string(),
+        ));
+    }
+    if payload.ready_status.trim().is_empty() {
+        return Err(ApiError::BadRequest("ready_status is required".to_string()));
+    }
+
+    Project::find_by_id(pool, payload.project_id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+    for repo in &payload.repos {
+        Repo::find_by_id(pool, repo.repo_id)
+            .await?
+            .ok_or(RepoError::NotFound)?;
+    }
+
+    let scheduler = IssueScheduler::upsert(pool, project_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(scheduler)))
+}
+
+async fn delete_issue_scheduler(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    IssueScheduler::delete_by_remote_project_id(&deployment.db().pool, project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
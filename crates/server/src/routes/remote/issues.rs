@@ -16,6 +16,8 @@ use crate::{DeploymentImpl, error::ApiError};
 #[derive(Debug, Deserialize)]
 pub struct ListIssuesQuery {
     pub project_id: Uuid,
+    /// Filter in the issue query language.
+    pub q: Option<String>,
 }
 
 pub fn router() -> Router<DeploymentImpl> {
@@ -32,7 +34,10 @@ async fn list_issues(
     Query(query): Query<ListIssuesQuery>,
 ) -> Result<ResponseJson<ApiResponse<ListIssuesResponse>>, ApiError> {
     let client = deployment.remote_client()?;
-    let response = client.list_issues(query.project_id).await?;
+    let response = match query.q.as_deref().filter(|q| !q.trim().is_empty()) {
+        Some(q) => client.search_issues(query.project_id, q).await?,
+        None => client.list_issues(query.project_id).await?,
+    };
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
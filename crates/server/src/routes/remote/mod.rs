@@ -2,6 +2,8 @@ use axum::Router;
 
 use crate::DeploymentImpl;
 
+mod custom_fields;
+mod issue_scheduler;
 mod issues;
 mod project_statuses;
 mod projects;
@@ -9,6 +11,8 @@ mod workspaces;
 
 pub fn router() -> Router<DeploymentImpl> {
     Router::new()
+        .merge(custom_fields::router())
+        .merge(issue_scheduler::router())
         .merge(issues::router())
         .merge(projects::router())
         .merge(project_statuses::router())
@@ -5,7 +5,10 @@ use axum::{
     response::Json as ResponseJson,
     routing::get,
 };
+use db::models::analytics::{AgentRun, WorkspaceOutcome};
+use deployment::Deployment;
 use serde::Deserialize;
+use services::services::project_analytics::{self, ProjectAnalytics};
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
@@ -20,6 +23,10 @@ pub fn router() -> Router<DeploymentImpl> {
     Router::new()
         .route("/projects", get(list_remote_projects))
         .route("/projects/{project_id}", get(get_remote_project))
+        .route(
+            "/projects/{project_id}/analytics",
+            get(get_project_analytics),
+        )
 }
 
 async fn list_remote_projects(
@@ -39,3 +46,24 @@ async fn get_remote_project(
     let project = client.get_remote_project(project_id).await?;
     Ok(ResponseJson(ApiResponse::success(project)))
 }
+
+async fn get_project_analytics(
+    State(deployment): State<DeploymentImpl>,
+    Path(project_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<ProjectAnalytics>>, ApiError> {
+    let client = deployment.remote_client()?;
+    let timelines = client.list_delivery_timelines(project_id).await?.timelines;
+
+    let pool = &deployment.db().pool;
+    let (outcomes, runs) = match timelines.iter().map(|t| t.workspace_created_at).min() {
+        Some(since) => (
+            WorkspaceOutcome::find_created_since(pool, since).await?,
+            AgentRun::find_for_workspaces_created_since(pool, since).await?,
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    Ok(ResponseJson(ApiResponse::success(
+        project_analytics::summarize(&timelines, &outcomes, &runs),
+    )))
+}
@@ -1,20 +1,26 @@
 use axum::{
-    Json, Router,
+    BoxError, Json, Router,
     extract::{
-        Path, State,
+        Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    response::{IntoResponse, Json as ResponseJson},
+    response::{IntoResponse, Json as ResponseJson, Sse, sse::Event},
     routing::get,
 };
 use db::models::scratch::{CreateScratch, Scratch, ScratchType, UpdateScratch};
 use deployment::Deployment;
-use futures_util::{SinkExt, StreamExt, TryStreamExt};
+use futures_util::{SinkExt, Stream, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    streaming::{
+        LogPollQuery, LogPollResponse, ResumeCursor, poll_resumable_stream, resumable_stream_sse,
+    },
+};
 
 /// Path parameters for scratch routes with composite key
 #[derive(Deserialize)]
@@ -112,6 +118,32 @@ pub async fn stream_scratch_ws(
     })
 }
 
+pub async fn stream_scratch_sse(
+    State(deployment): State<DeploymentImpl>,
+    Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
+    resume: ResumeCursor,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_scratch_raw(id, &scratch_type, resume.0.as_ref())
+        .await?;
+    Ok(resumable_stream_sse(stream))
+}
+
+pub async fn poll_scratch(
+    State(deployment): State<DeploymentImpl>,
+    Path(ScratchPath { scratch_type, id }): Path<ScratchPath>,
+    resume: ResumeCursor,
+    Query(poll): Query<LogPollQuery>,
+) -> Result<ResponseJson<ApiResponse<LogPollResponse>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_scratch_raw(id, &scratch_type, resume.0.as_ref())
+        .await?;
+    let response = poll_resumable_stream(stream, resume, poll).await;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 async fn handle_scratch_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
@@ -120,9 +152,9 @@ async fn handle_scratch_ws(
 ) -> anyhow::Result<()> {
     let mut stream = deployment
         .events()
-        .stream_scratch_raw(id, &scratch_type)
+        .stream_scratch_raw(id, &scratch_type, None)
         .await?
-        .map_ok(|msg| msg.to_ws_message_unchecked());
+        .map_ok(|m| m.msg.to_ws_message_unchecked());
 
     let (mut sender, mut receiver) = socket.split();
 
@@ -158,4 +190,12 @@ pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/scratch/{scratch_type}/{id}/stream/ws",
             get(stream_scratch_ws),
         )
+        .route(
+            "/scratch/{scratch_type}/{id}/stream/sse",
+            get(stream_scratch_sse),
+        )
+        .route(
+            "/scratch/{scratch_type}/{id}/stream/poll",
+            get(poll_scratch),
+        )
 }
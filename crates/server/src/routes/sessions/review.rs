@@ -1,10 +1,11 @@
 use std::path::PathBuf;
 
 use axum::{Extension, Json, extract::State, response::Json as ResponseJson};
+use chrono::{Duration, Utc};
 use db::models::{
     coding_agent_turn::CodingAgentTurn,
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
-    session::Session,
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    session::{CreateSession, Session},
     workspace::{Workspace, WorkspaceError},
     workspace_repo::WorkspaceRepo,
 };
@@ -12,15 +13,19 @@ use deployment::Deployment;
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType,
-        review::{RepoReviewContext as ExecutorRepoReviewContext, ReviewRequest as ReviewAction},
+        review::{
+            RepoReviewContext as ExecutorRepoReviewContext, ReviewFindings,
+            ReviewRequest as ReviewAction,
+        },
     },
-    executors::build_review_prompt,
+    executors::{build_pre_pr_review_prompt, build_review_prompt},
     profile::ExecutorProfileId,
 };
 use serde::{Deserialize, Serialize};
 use services::services::container::ContainerService;
 use ts_rs::TS;
 use utils::response::ApiResponse;
+use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
@@ -87,6 +92,11 @@ pub async fn start_review(
                     repo_id: repo.repo.id,
                     repo_name: repo.repo.display_name,
                     base_commit,
+                    head_commit: deployment
+                        .git()
+                        .get_head_info(&worktree_path)
+                        .ok()
+                        .map(|head| head.oid),
                 });
             }
         }
@@ -138,3 +148,190 @@ pub async fn start_review(
 
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
+
+/// How long a finished review may go without a stored reply before it counts
+/// as failed; the final message is stored just after the process completes
+const PRE_PR_REVIEW_REPLY_GRACE_SECS: i64 = 10;
+
+pub enum PrePrReviewOutcome {
+    Running,
+    Findings(ReviewFindings),
+    /// The agent failed or did not reply with valid findings
+    Failed(String),
+}
+
+/// Start a review agent over `context` for PR creation in repos with a review
+/// gate, or `None` if another process is running in the workspace.
+///
+/// The review runs in a new session of its own so it judges the diff rather
+/// than the reasoning of the conversation that wrote it.
+pub async fn start_pre_pr_review(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    context: Vec<ExecutorRepoReviewContext>,
+) -> Result<Option<ExecutionProcess>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    if ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace.id)
+        .await?
+    {
+        return Ok(None);
+    }
+
+    // Review with the agent that wrote the changes
+    let latest_profile = match Session::find_latest_by_workspace_id(pool, workspace.id).await? {
+        Some(session) => {
+            ExecutionProcess::latest_executor_profile_for_session(pool, session.id).await?
+        }
+        None => None,
+    };
+    let executor_profile_id = match latest_profile {
+        Some(profile) => profile,
+        None => deployment.config().read().await.executor_profile.clone(),
+    };
+
+    let session = Session::create(
+        pool,
+        &CreateSession {
+            executor: Some(executor_profile_id.executor.to_string()),
+        },
+        Uuid::new_v4(),
+        workspace.id,
+    )
+    .await?;
+
+    let action = ExecutorAction::new(
+        ExecutorActionType::ReviewRequest(ReviewAction {
+            executor_profile_id,
+            prompt: build_pre_pr_review_prompt(&context),
+            context: Some(context),
+            session_id: None,
+            working_dir: workspace.agent_working_dir.clone(),
+        }),
+        None,
+    );
+
+    let execution_process = deployment
+        .container()
+        .start_execution(
+            workspace,
+            &session,
+            &action,
+            &ExecutionProcessRunReason::CodingAgent,
+        )
+        .await?;
+
+    Ok(Some(execution_process))
+}
+
+/// Whether `action` is a review started by [`start_pre_pr_review`] whose
+/// context includes exactly `expected`, i.e. the same base and HEAD
+fn is_pre_pr_review_of(action: &ExecutorActionType, expected: &ExecutorRepoReviewContext) -> bool {
+    let ExecutorActionType::ReviewRequest(review) = action else {
+        return false;
+    };
+    review.context.as_deref().is_some_and(|context| {
+        context.contains(expected) && review.prompt == build_pre_pr_review_prompt(context)
+    })
+}
+
+/// Where a review started by [`start_pre_pr_review`] has got to, or `None` if
+/// the process didn't review `expected`, the range the PR would contain now
+pub async fn pre_pr_review_outcome(
+    deployment: &DeploymentImpl,
+    workspace: &Workspace,
+    execution_process_id: Uuid,
+    expected: &ExecutorRepoReviewContext,
+) -> Result<Option<PrePrReviewOutcome>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let process = ExecutionProcess::find_by_id(pool, execution_process_id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Review process not found".to_string()))?;
+    let session = Session::find_by_id(pool, process.session_id).await?;
+    if session.is_none_or(|session| session.workspace_id != workspace.id) {
+        return Err(ApiError::BadRequest(
+            "Process is not a review of this workspace".to_string(),
+        ));
+    }
+    let is_current = process
+        .executor_action()
+        .is_ok_and(|action| is_pre_pr_review_of(action.typ(), expected));
+    if !is_current {
+        return Ok(None);
+    }
+
+    match process.status {
+        ExecutionProcessStatus::Running => return Ok(Some(PrePrReviewOutcome::Running)),
+        ExecutionProcessStatus::Failed | ExecutionProcessStatus::Killed => {
+            return Ok(Some(PrePrReviewOutcome::Failed(
+                "The review agent did not finish".to_string(),
+            )));
+        }
+        ExecutionProcessStatus::Completed => {}
+    }
+
+    let summary = CodingAgentTurn::find_by_execution_process_id(pool, process.id)
+        .await?
+        .and_then(|turn| turn.summary);
+
+    Ok(Some(match summary {
+        Some(summary) => match ReviewFindings::parse(&summary) {
+            Ok(findings) => PrePrReviewOutcome::Findings(findings),
+            Err(e) => PrePrReviewOutcome::Failed(e.to_string()),
+        },
+        None if process.completed_at.is_some_and(|at| {
+            Utc::now() - at < Duration::seconds(PRE_PR_REVIEW_REPLY_GRACE_SECS)
+        }) =>
+        {
+            PrePrReviewOutcome::Running
+        }
+        None => PrePrReviewOutcome::Failed("The review agent did not reply".to_string()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    fn review_action(
+        context: Vec<ExecutorRepoReviewContext>,
+        prompt: String,
+    ) -> ExecutorActionType {
+        ExecutorActionType::ReviewRequest(ReviewAction {
+            executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+            context: Some(context),
+            prompt,
+            session_id: None,
+            working_dir: None,
+        })
+    }
+
+    #[test]
+    fn test_stale_pre_pr_review_is_rejected() {
+        let reviewed = ExecutorRepoReviewContext {
+            repo_id: Uuid::new_v4(),
+            repo_name: "app".to_string(),
+            base_commit: "base".to_string(),
+            head_commit: Some("head-1".to_string()),
+        };
+        let action = review_action(
+            vec![reviewed.clone()],
+            build_pre_pr_review_prompt(std::slice::from_ref(&reviewed)),
+        );
+        assert!(is_pre_pr_review_of(&action, &reviewed));
+
+        // Commits pushed after the review started
+        let pushed = ExecutorRepoReviewContext {
+            head_commit: Some("head-2".to_string()),
+            ..reviewed.clone()
+        };
+        assert!(!is_pre_pr_review_of(&action, &pushed));
+
+        // An ad-hoc review of the same range
+        let ad_hoc = review_action(vec![reviewed.clone()], "Review my changes".to_string());
+        assert!(!is_pre_pr_review_of(&ad_hoc, &reviewed));
+    }
+}
@@ -1,5 +1,6 @@
 pub mod codex_setup;
 pub mod cursor_setup;
+pub mod dev_server;
 pub mod gh_cli_setup;
 pub mod images;
 pub mod pr;
@@ -12,14 +13,14 @@ use std::{
 
 use api_types::CreateWorkspaceRequest;
 use axum::{
-    Extension, Json, Router,
+    BoxError, Extension, Json, Router,
     extract::{
         Path as AxumPath, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{IntoResponse, Json as ResponseJson, Sse, sse::Event},
     routing::{get, post, put},
 };
 use db::models::{
@@ -41,6 +42,7 @@ use executors::{
     executors::{CodingAgent, ExecutorError},
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
+use futures_util::Stream;
 use git::{ConflictOp, GitCliError, GitService, GitServiceError};
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
@@ -54,8 +56,14 @@ use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_workspace_middleware,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_workspace_middleware,
     routes::task_attempts::gh_cli_setup::GhCliSetupError,
+    streaming::{
+        LogPollQuery, LogPollResponse, ResumeCursor, poll_resumable_stream, poll_snapshot_stream,
+        resumable_stream_sse, snapshot_stream_sse,
+    },
 };
 
 #[derive(Debug, Deserialize, Serialize, TS)]
@@ -349,6 +357,36 @@ pub async fn stream_task_attempt_diff_ws(
     })
 }
 
+pub async fn stream_task_attempt_diff_sse(
+    Query(params): Query<DiffStreamQuery>,
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    let _ = Workspace::touch(&deployment.db().pool, workspace.id).await;
+
+    let stream = deployment
+        .container()
+        .stream_diff(&workspace, params.stats_only)
+        .await?;
+    Ok(snapshot_stream_sse(stream))
+}
+
+pub async fn poll_task_attempt_diff(
+    Query(params): Query<DiffStreamQuery>,
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(poll): Query<LogPollQuery>,
+) -> Result<ResponseJson<ApiResponse<LogPollResponse>>, ApiError> {
+    let _ = Workspace::touch(&deployment.db().pool, workspace.id).await;
+
+    let stream = deployment
+        .container()
+        .stream_diff(&workspace, params.stats_only)
+        .await?;
+    let response = poll_snapshot_stream(stream, poll).await;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 async fn handle_task_attempt_diff_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
@@ -408,6 +446,32 @@ pub async fn stream_workspaces_ws(
     })
 }
 
+pub async fn stream_workspaces_sse(
+    Query(query): Query<WorkspaceStreamQuery>,
+    State(deployment): State<DeploymentImpl>,
+    resume: ResumeCursor,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_workspaces_raw(query.archived, query.limit, resume.0.as_ref())
+        .await?;
+    Ok(resumable_stream_sse(stream))
+}
+
+pub async fn poll_workspaces(
+    Query(query): Query<WorkspaceStreamQuery>,
+    State(deployment): State<DeploymentImpl>,
+    resume: ResumeCursor,
+    Query(poll): Query<LogPollQuery>,
+) -> Result<ResponseJson<ApiResponse<LogPollResponse>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_workspaces_raw(query.archived, query.limit, resume.0.as_ref())
+        .await?;
+    let response = poll_resumable_stream(stream, resume, poll).await;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 async fn handle_workspaces_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
@@ -418,9 +482,9 @@ async fn handle_workspaces_ws(
 
     let mut stream = deployment
         .events()
-        .stream_workspaces_raw(archived, limit)
+        .stream_workspaces_raw(archived, limit, None)
         .await?
-        .map_ok(|msg| msg.to_ws_message_unchecked());
+        .map_ok(|m| m.msg.to_ws_message_unchecked());
 
     let (mut sender, mut receiver) = socket.split();
 
@@ -1952,11 +2016,14 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
                 .route("/run-agent-setup", post(run_agent_setup))
                 .route("/gh-cli-setup", post(gh_cli_setup_handler))
                 .route("/start-dev-server", post(start_dev_server))
+                .route("/dev-server/logs", get(dev_server::get_dev_server_logs))
                 .route("/run-setup-script", post(run_setup_script))
                 .route("/run-cleanup-script", post(run_cleanup_script))
                 .route("/run-archive-script", post(run_archive_script))
                 .route("/branch-status", get(get_task_attempt_branch_status))
                 .route("/diff/ws", get(stream_task_attempt_diff_ws))
+                .route("/diff/sse", get(stream_task_attempt_diff_sse))
+                .route("/diff/poll", get(poll_task_attempt_diff))
                 .route("/merge", post(merge_task_attempt))
                 .route("/push", post(push_task_attempt_branch))
                 .route("/push/force", post(force_push_task_attempt_branch))
@@ -1985,6 +2052,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/", get(get_task_attempts).post(create_task_attempt))
         .route("/from-pr", post(pr::create_workspace_from_pr))
         .route("/stream/ws", get(stream_workspaces_ws))
+        .route("/stream/sse", get(stream_workspaces_sse))
+        .route("/stream/poll", get(poll_workspaces))
         .route("/summary", post(workspace_summary::get_workspace_summaries))
         .nest("/{id}", task_attempt_id_router)
         .nest("/{id}/images", images::router(deployment));
@@ -0,0 +1,241 @@
+use axum::{
+    Extension,
+    extract::{Query, State},
+    response::Json as ResponseJson,
+};
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    execution_process_logs::ExecutionProcessLogs,
+    workspace::Workspace,
+};
+use deployment::Deployment;
+use executors::actions::ExecutorActionType;
+use serde::{Deserialize, Serialize};
+use services::services::container::ContainerService;
+use sqlx::SqlitePool;
+use utils::{log_msg::LogMsg, response::ApiResponse};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_TAIL_LINES: usize = 200;
+const MAX_TAIL_LINES: usize = 2000;
+/// Persisted log rows read per query while looking for the tail
+const LOG_ROWS_PER_READ: i64 = 500;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DevServerLogsQuery {
+    /// Number of trailing lines to return per dev server.
+    pub tail: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DevServerLogs {
+    pub execution_process_id: Uuid,
+    pub status: ExecutionProcessStatus,
+    pub exit_code: Option<i64>,
+    /// Repository directory the dev server script runs in.
+    pub working_dir: Option<String>,
+    /// Interleaved stdout and stderr, ANSI escapes stripped.
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DevServerLogsResponse {
+    pub dev_servers: Vec<DevServerLogs>,
+}
+
+/// Tail the output of the workspace's running dev servers, or of the most
+/// recent one if none is running (e.g. it crashed on startup).
+pub async fn get_dev_server_logs(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<DevServerLogsQuery>,
+) -> Result<ResponseJson<ApiResponse<DevServerLogsResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let tail = query
+        .tail
+        .unwrap_or(DEFAULT_TAIL_LINES)
+        .clamp(1, MAX_TAIL_LINES);
+
+    let processes = dev_server_processes(pool, workspace.id).await?;
+    let mut dev_servers = Vec::with_capacity(processes.len());
+    for process in processes {
+        let lines = tail_output(&deployment, process.id, tail).await;
+        let working_dir = process
+            .executor_action()
+            .ok()
+            .and_then(|action| match action.typ() {
+                ExecutorActionType::ScriptRequest(script) => script.working_dir.clone(),
+                _ => None,
+            });
+        dev_servers.push(DevServerLogs {
+            execution_process_id: process.id,
+            status: process.status,
+            exit_code: process.exit_code,
+            working_dir,
+            lines,
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(DevServerLogsResponse {
+        dev_servers,
+    })))
+}
+
+/// The workspace's running dev servers, or the most recent one if none is
+/// running
+async fn dev_server_processes(
+    pool: &SqlitePool,
+    workspace_id: Uuid,
+) -> Result<Vec<ExecutionProcess>, sqlx::Error> {
+    let mut processes =
+        ExecutionProcess::find_running_dev_servers_by_workspace(pool, workspace_id).await?;
+    if processes.is_empty() {
+        processes.extend(
+            ExecutionProcess::find_latest_by_workspace_and_run_reason(
+                pool,
+                workspace_id,
+                &ExecutionProcessRunReason::DevServer,
+            )
+            .await?,
+        );
+    }
+    Ok(processes)
+}
+
+/// Last `tail` lines of an execution process's stdout/stderr. Live processes
+/// are read from the in-memory history; finished ones from the persisted logs.
+/// Both are read from the end and only until `tail` lines are complete.
+async fn tail_output(deployment: &DeploymentImpl, exec_id: Uuid, tail: usize) -> Vec<String> {
+    let mut collector = TailCollector::new(tail);
+    if let Some(store) = deployment.container().get_msg_store_by_id(&exec_id).await {
+        store.visit_history_rev(|msg| match msg {
+            LogMsg::Stdout(s) | LogMsg::Stderr(s) => collector.push(s.clone()),
+            _ => true,
+        });
+        return collector.into_lines();
+    }
+
+    let pool = &deployment.db().pool;
+    let mut before_rowid = None;
+    loop {
+        let rows = match ExecutionProcessLogs::find_rows_before(
+            pool,
+            exec_id,
+            before_rowid,
+            LOG_ROWS_PER_READ,
+        )
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::error!("Failed to read logs for execution {}: {}", exec_id, e);
+                break;
+            }
+        };
+        let Some(last) = rows.last() else {
+            break;
+        };
+        before_rowid = Some(last.rowid);
+
+        let wants_more = rows
+            .iter()
+            .flat_map(|row| row.logs.lines().rev())
+            .filter_map(|line| serde_json::from_str::<LogMsg>(line).ok())
+            .all(|msg| match msg {
+                LogMsg::Stdout(s) | LogMsg::Stderr(s) => collector.push(s),
+                _ => true,
+            });
+        if !wants_more || (rows.len() as i64) < LOG_ROWS_PER_READ {
+            break;
+        }
+    }
+    collector.into_lines()
+}
+
+/// Collects output chunks newest first until they hold the last `tail` lines.
+struct TailCollector {
+    tail: usize,
+    chunks: Vec<String>,
+    newlines: usize,
+}
+
+impl TailCollector {
+    fn new(tail: usize) -> Self {
+        Self {
+            tail,
+            chunks: Vec::new(),
+            newlines: 0,
+        }
+    }
+
+    /// Add the chunk that came before the ones pushed so far. Returns false
+    /// once older chunks can no longer change the result.
+    fn push(&mut self, chunk: String) -> bool {
+        self.newlines += chunk.matches('\n').count();
+        self.chunks.push(chunk);
+        // With `tail` newlines after it, the oldest line kept is complete
+        self.newlines <= self.tail
+    }
+
+    fn into_lines(self) -> Vec<String> {
+        let text: String = self.chunks.into_iter().rev().collect();
+        let text =
+            String::from_utf8_lossy(&strip_ansi_escapes::strip(text.as_bytes())).into_owned();
+        let lines: Vec<&str> = text.lines().collect();
+        lines[lines.len().saturating_sub(self.tail)..]
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(chunks_newest_first: &[&str], tail: usize) -> (Vec<String>, usize) {
+        let mut collector = TailCollector::new(tail);
+        let read = chunks_newest_first
+            .iter()
+            .position(|chunk| !collector.push(chunk.to_string()))
+            .map_or(chunks_newest_first.len(), |i| i + 1);
+        (collector.into_lines(), read)
+    }
+
+    #[test]
+    fn test_tail_keeps_last_lines_and_stops_reading() {
+        let chunks = [
+            "ready\n",
+            "\u{1b}[32mcompiled\u{1b}[0m in 3s\n",
+            " 1\nline 2\n",
+            "line",
+            "line 0\n",
+        ];
+        let (lines, read) = collect(&chunks, 2);
+        assert_eq!(lines, vec!["compiled in 3s", "ready"]);
+        assert_eq!(read, 3, "chunks older than the tail are not read");
+
+        let (lines, read) = collect(&chunks, 10);
+        assert_eq!(
+            lines,
+            vec!["line 0", "line 1", "line 2", "compiled in 3s", "ready"]
+        );
+        assert_eq!(read, chunks.len());
+    }
+
+    #[test]
+    fn test_tail_of_no_output_is_empty() {
+        assert!(collect(&[], 5).0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_workspace_without_dev_server_has_no_processes() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::migrate!("../db/migrations").run(&pool).await.unwrap();
+
+        let processes = dev_server_processes(&pool, Uuid::new_v4()).await.unwrap();
+        assert!(processes.is_empty());
+    }
+}
@@ -4,14 +4,15 @@ use api_types::{PullRequestStatus, UpsertPullRequestRequest};
 use axum::{
     Extension, Json,
     extract::{Query, State},
+    http::StatusCode,
     response::Json as ResponseJson,
 };
 use db::models::{
     coding_agent_turn::CodingAgentTurn,
-    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
     merge::{Merge, MergeStatus},
     project_repo::ProjectRepo,
-    repo::{Repo, RepoError},
+    repo::{PrReviewGate, Repo, RepoError},
     session::{CreateSession, Session},
     task::{CreateTask, Task, TaskStatus},
     workspace::{CreateWorkspace, Workspace, WorkspaceError},
@@ -19,8 +20,10 @@ use db::models::{
 };
 use deployment::Deployment;
 use executors::actions::{
-    ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+    ExecutorAction, ExecutorActionType,
+    coding_agent_follow_up::CodingAgentFollowUpRequest,
     coding_agent_initial::CodingAgentInitialRequest,
+    review::{RepoReviewContext, ReviewFinding, ReviewFindings},
 };
 use git::{GitCliError, GitRemote, GitServiceError};
 use serde::{Deserialize, Serialize};
@@ -37,7 +40,11 @@ use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    routes::sessions::review::{PrePrReviewOutcome, pre_pr_review_outcome, start_pre_pr_review},
+};
 
 #[derive(Debug, Deserialize, Serialize, TS)]
 pub struct CreatePrApiRequest {
@@ -48,6 +55,24 @@ pub struct CreatePrApiRequest {
     pub repo_id: Uuid,
     #[serde(default)]
     pub auto_generate_description: bool,
+    /// The pre-PR review started by an earlier call, for repos with a review gate
+    #[serde(default)]
+    pub review_execution_process_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum CreatePrResponse {
+    Created {
+        url: String,
+    },
+    /// The repo's review gate is reviewing the branch. Call again with
+    /// `review_execution_process_id` to create the PR once it has finished.
+    Reviewing {
+        execution_process_id: Uuid,
+        status: ExecutionProcessStatus,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, TS)]
@@ -60,6 +85,8 @@ pub enum PrError {
     GitCliNotInstalled,
     TargetBranchNotFound { branch: String },
     UnsupportedProvider,
+    ReviewBlocked { findings: Vec<ReviewFinding> },
+    ReviewFailed { message: String },
 }
 
 #[derive(Debug, Serialize, TS)]
@@ -183,11 +210,43 @@ async fn trigger_pr_description_follow_up(
     Ok(())
 }
 
+/// PR description section listing a pre-PR review's findings, most severe
+/// first. `None` when the review found nothing.
+fn review_findings_section(findings: &ReviewFindings) -> Option<String> {
+    if findings.findings.is_empty() {
+        return None;
+    }
+
+    let mut sorted: Vec<&ReviewFinding> = findings.findings.iter().collect();
+    sorted.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+
+    let mut section = String::from("## Pre-PR review\n");
+    for finding in sorted {
+        let location = match finding.line {
+            Some(line) => format!("{}:{}", finding.file, line),
+            None => finding.file.clone(),
+        };
+        section.push_str(&format!(
+            "\n- **{}** `{location}`: {}",
+            finding.severity,
+            finding.message.trim()
+        ));
+    }
+
+    Some(section)
+}
+
 pub async fn create_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreatePrApiRequest>,
-) -> Result<ResponseJson<ApiResponse<String, PrError>>, ApiError> {
+) -> Result<
+    (
+        StatusCode,
+        ResponseJson<ApiResponse<CreatePrResponse, PrError>>,
+    ),
+    ApiError,
+> {
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -231,38 +290,132 @@ pub async fn create_pr(
 
     match git.check_remote_branch_exists(&repo_path, &target_remote.url, &base_branch) {
         Ok(false) => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                PrError::TargetBranchNotFound {
-                    branch: target_branch.clone(),
-                },
-            )));
+            return Ok((
+                StatusCode::OK,
+                ResponseJson(ApiResponse::error_with_data(
+                    PrError::TargetBranchNotFound {
+                        branch: target_branch.clone(),
+                    },
+                )),
+            ));
         }
         Err(GitServiceError::GitCLI(GitCliError::AuthFailed(_))) => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                PrError::GitCliNotLoggedIn,
-            )));
+            return Ok((
+                StatusCode::OK,
+                ResponseJson(ApiResponse::error_with_data(PrError::GitCliNotLoggedIn)),
+            ));
         }
         Err(GitServiceError::GitCLI(GitCliError::NotAvailable)) => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                PrError::GitCliNotInstalled,
-            )));
+            return Ok((
+                StatusCode::OK,
+                ResponseJson(ApiResponse::error_with_data(PrError::GitCliNotInstalled)),
+            ));
         }
         Err(e) => return Err(ApiError::GitService(e)),
         Ok(true) => {}
     }
 
+    let mut body = request.body.clone();
+    if repo.pr_review_gate != PrReviewGate::Off {
+        // The range the PR would contain now; a review of any other range,
+        // e.g. from before later commits, doesn't count
+        let context = RepoReviewContext {
+            repo_id: repo.id,
+            repo_name: repo.display_name.clone(),
+            base_commit: git.get_fork_point(&worktree_path, &target_branch, &workspace.branch)?,
+            head_commit: Some(git.get_head_info(&worktree_path)?.oid),
+        };
+        let review = match request.review_execution_process_id {
+            Some(execution_process_id) => {
+                pre_pr_review_outcome(&deployment, &workspace, execution_process_id, &context)
+                    .await?
+                    .map(|outcome| (execution_process_id, outcome))
+            }
+            None => None,
+        };
+        let Some((execution_process_id, outcome)) = review else {
+            let Some(process) = start_pre_pr_review(&deployment, &workspace, vec![context]).await?
+            else {
+                return Ok((
+                    StatusCode::OK,
+                    ResponseJson(ApiResponse::error_with_data(PrError::ReviewFailed {
+                        message: "Another process is running in this workspace".to_string(),
+                    })),
+                ));
+            };
+
+            return Ok((
+                StatusCode::ACCEPTED,
+                ResponseJson(ApiResponse::success(CreatePrResponse::Reviewing {
+                    execution_process_id: process.id,
+                    status: process.status,
+                })),
+            ));
+        };
+
+        let section = match outcome {
+            PrePrReviewOutcome::Running => {
+                return Ok((
+                    StatusCode::ACCEPTED,
+                    ResponseJson(ApiResponse::success(CreatePrResponse::Reviewing {
+                        execution_process_id,
+                        status: ExecutionProcessStatus::Running,
+                    })),
+                ));
+            }
+            PrePrReviewOutcome::Findings(findings) => {
+                if repo.pr_review_gate == PrReviewGate::Block && findings.has_high_severity() {
+                    return Ok((
+                        StatusCode::OK,
+                        ResponseJson(ApiResponse::error_with_data(PrError::ReviewBlocked {
+                            findings: findings.findings,
+                        })),
+                    ));
+                }
+                review_findings_section(&findings)
+            }
+            PrePrReviewOutcome::Failed(message) => {
+                if repo.pr_review_gate == PrReviewGate::Block {
+                    return Ok((
+                        StatusCode::OK,
+                        ResponseJson(ApiResponse::error_with_data(PrError::ReviewFailed {
+                            message,
+                        })),
+                    ));
+                }
+                tracing::warn!(
+                    "Pre-PR review failed for attempt {}: {}",
+                    workspace.id,
+                    message
+                );
+                Some(format!(
+                    "## Pre-PR review\n\nThe review could not be completed: {message}"
+                ))
+            }
+        };
+
+        if let Some(section) = section {
+            body = Some(match body.filter(|body| !body.trim().is_empty()) {
+                Some(body) => format!("{}\n\n{section}", body.trim_end()),
+                None => section,
+            });
+        }
+    }
+
     if let Err(e) = git.push_to_remote(&worktree_path, &workspace.branch, false) {
         tracing::error!("Failed to push branch to remote: {}", e);
         match e {
             GitServiceError::GitCLI(GitCliError::AuthFailed(_)) => {
-                return Ok(ResponseJson(ApiResponse::error_with_data(
-                    PrError::GitCliNotLoggedIn,
-                )));
+                return Ok((
+                    StatusCode::OK,
+                    ResponseJson(ApiResponse::error_with_data(PrError::GitCliNotLoggedIn)),
+                ));
             }
             GitServiceError::GitCLI(GitCliError::NotAvailable) => {
-                return Ok(ResponseJson(ApiResponse::error_with_data(
-                    PrError::GitCliNotInstalled,
-                )));
+                return Ok((
+                    StatusCode::OK,
+                    ResponseJson(ApiResponse::error_with_data(PrError::GitCliNotInstalled)),
+                ));
             }
             _ => return Err(ApiError::GitService(e)),
         }
@@ -271,14 +424,18 @@ pub async fn create_pr(
     let git_host = match git_host::GitHostService::from_url(&target_remote.url) {
         Ok(host) => host,
         Err(GitHostError::UnsupportedProvider) => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                PrError::UnsupportedProvider,
-            )));
+            return Ok((
+                StatusCode::OK,
+                ResponseJson(ApiResponse::error_with_data(PrError::UnsupportedProvider)),
+            ));
         }
         Err(GitHostError::CliNotInstalled { provider }) => {
-            return Ok(ResponseJson(ApiResponse::error_with_data(
-                PrError::CliNotInstalled { provider },
-            )));
+            return Ok((
+                StatusCode::OK,
+                ResponseJson(ApiResponse::error_with_data(PrError::CliNotInstalled {
+                    provider,
+                })),
+            ));
         }
         Err(e) => return Err(ApiError::GitHost(e)),
     };
@@ -288,7 +445,7 @@ pub async fn create_pr(
     // Create the PR
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
-        body: request.body.clone(),
+        body,
         head_branch: workspace.branch.clone(),
         base_branch: base_branch.clone(),
         draft: request.draft,
@@ -361,7 +518,12 @@ pub async fn create_pr(
                 );
             }
 
-            Ok(ResponseJson(ApiResponse::success(pr_info.url)))
+            Ok((
+                StatusCode::OK,
+                ResponseJson(ApiResponse::success(CreatePrResponse::Created {
+                    url: pr_info.url,
+                })),
+            ))
         }
         Err(e) => {
             tracing::error!(
@@ -371,14 +533,18 @@ pub async fn create_pr(
                 e
             );
             match &e {
-                GitHostError::CliNotInstalled { provider } => Ok(ResponseJson(
-                    ApiResponse::error_with_data(PrError::CliNotInstalled {
+                GitHostError::CliNotInstalled { provider } => Ok((
+                    StatusCode::OK,
+                    ResponseJson(ApiResponse::error_with_data(PrError::CliNotInstalled {
                         provider: *provider,
-                    }),
+                    })),
+                )),
+                GitHostError::AuthFailed(_) => Ok((
+                    StatusCode::OK,
+                    ResponseJson(ApiResponse::error_with_data(PrError::CliNotLoggedIn {
+                        provider,
+                    })),
                 )),
-                GitHostError::AuthFailed(_) => Ok(ResponseJson(ApiResponse::error_with_data(
-                    PrError::CliNotLoggedIn { provider },
-                ))),
                 _ => Err(ApiError::GitHost(e)),
             }
         }
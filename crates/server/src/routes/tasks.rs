@@ -2,26 +2,26 @@ use std::path::PathBuf;
 
 use anyhow;
 use axum::{
-    Extension, Json, Router,
+    BoxError, Extension, Json, Router,
     extract::{
         Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
     http::StatusCode,
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
+    response::{IntoResponse, Json as ResponseJson, Sse, sse::Event},
     routing::{delete, get, post, put},
 };
 use db::models::{
     image::TaskImage,
     repo::{Repo, RepoError},
     task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
-    workspace::{CreateWorkspace, Workspace},
-    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+    workspace::Workspace,
+    workspace_repo::WorkspaceRepo,
 };
 use deployment::Deployment;
 use executors::profile::ExecutorProfileId;
-use futures_util::{SinkExt, StreamExt, TryStreamExt};
+use futures_util::{SinkExt, Stream, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{container::ContainerService, workspace_manager::WorkspaceManager};
 use sqlx::Error as SqlxError;
@@ -30,8 +30,13 @@ use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_task_middleware,
     routes::task_attempts::WorkspaceRepoInput,
+    streaming::{
+        LogPollQuery, LogPollResponse, ResumeCursor, poll_resumable_stream, resumable_stream_sse,
+    },
 };
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -62,6 +67,32 @@ pub async fn stream_tasks_ws(
     })
 }
 
+pub async fn stream_tasks_sse(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskQuery>,
+    resume: ResumeCursor,
+) -> Result<Sse<impl Stream<Item = Result<Event, BoxError>>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_tasks_raw(query.project_id, resume.0.as_ref())
+        .await?;
+    Ok(resumable_stream_sse(stream))
+}
+
+pub async fn poll_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskQuery>,
+    resume: ResumeCursor,
+    Query(poll): Query<LogPollQuery>,
+) -> Result<ResponseJson<ApiResponse<LogPollResponse>>, ApiError> {
+    let stream = deployment
+        .events()
+        .stream_tasks_raw(query.project_id, resume.0.as_ref())
+        .await?;
+    let response = poll_resumable_stream(stream, resume, poll).await;
+    Ok(ResponseJson(ApiResponse::success(response)))
+}
+
 async fn handle_tasks_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
@@ -70,9 +101,9 @@ async fn handle_tasks_ws(
     // Get the raw stream and convert LogMsg to WebSocket messages
     let mut stream = deployment
         .events()
-        .stream_tasks_raw(project_id)
+        .stream_tasks_raw(project_id, None)
         .await?
-        .map_ok(|msg| msg.to_ws_message_unchecked());
+        .map_ok(|m| m.msg.to_ws_message_unchecked());
 
     // Split socket into sender and receiver
     let (mut sender, mut receiver) = socket.split();
@@ -175,57 +206,18 @@ pub async fn create_task_and_start(
         )
         .await;
 
-    let attempt_id = Uuid::new_v4();
-    let git_branch_name = deployment
-        .container()
-        .git_branch_from_workspace(&attempt_id, &task.title)
-        .await;
-
-    // Compute agent_working_dir based on repo count:
-    // - Single repo: join repo name with default_working_dir (if set), or just repo name
-    // - Multiple repos: use None (agent runs in workspace root)
-    let agent_working_dir = if payload.repos.len() == 1 {
-        let repo = Repo::find_by_id(pool, payload.repos[0].repo_id)
+    let mut repos = Vec::with_capacity(payload.repos.len());
+    for input in &payload.repos {
+        let repo = Repo::find_by_id(pool, input.repo_id)
             .await?
             .ok_or(RepoError::NotFound)?;
-        match repo.default_working_dir {
-            Some(subdir) => {
-                let path = PathBuf::from(&repo.name).join(&subdir);
-                Some(path.to_string_lossy().to_string())
-            }
-            None => Some(repo.name),
-        }
-    } else {
-        None
-    };
-
-    let workspace = Workspace::create(
-        pool,
-        &CreateWorkspace {
-            branch: git_branch_name,
-            agent_working_dir,
-        },
-        attempt_id,
-        task.id,
-    )
-    .await?;
-
-    let workspace_repos: Vec<CreateWorkspaceRepo> = payload
-        .repos
-        .iter()
-        .map(|r| CreateWorkspaceRepo {
-            repo_id: r.repo_id,
-            target_branch: r.target_branch.clone(),
-        })
-        .collect();
-    WorkspaceRepo::create_many(&deployment.db().pool, workspace.id, &workspace_repos).await?;
+        repos.push((repo, input.target_branch.clone()));
+    }
 
-    let is_attempt_running = deployment
+    let (workspace, is_attempt_running) = deployment
         .container()
-        .start_workspace(&workspace, payload.executor_profile_id.clone())
-        .await
-        .inspect_err(|err| tracing::error!("Failed to start task attempt: {}", err))
-        .is_ok();
+        .create_and_start_workspace(&task, &repos, payload.executor_profile_id.clone())
+        .await?;
     deployment
         .track_if_analytics_allowed(
             "task_attempt_started",
@@ -408,6 +400,8 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let inner = Router::new()
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
+        .route("/stream/sse", get(stream_tasks_sse))
+        .route("/stream/poll", get(poll_tasks))
         .route("/create-and-start", post(create_task_and_start))
         .nest("/{task_id}", task_id_router);
 
@@ -3,17 +3,22 @@ use std::path::PathBuf;
 use axum::{
     Router,
     extract::{
-        Query, State,
-        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code},
     },
-    response::IntoResponse,
-    routing::get,
+    response::{IntoResponse, Json as ResponseJson},
+    routing::{delete, get},
 };
 use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chrono::{DateTime, Utc};
 use db::models::{workspace::Workspace, workspace_repo::WorkspaceRepo};
 use deployment::Deployment;
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{SinkExt, StreamExt, stream::SplitSink};
+use local_deployment::pty::{PtyAttachment, PtyError, PtyOutput, PtySessionInfo};
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use ts_rs::TS;
+use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
@@ -25,6 +30,52 @@ pub struct TerminalQuery {
     pub cols: u16,
     #[serde(default = "default_rows")]
     pub rows: u16,
+    /// Reattach to an existing session instead of starting a new shell.
+    pub session_id: Option<Uuid>,
+    /// Watch the session without being able to type into or resize it.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TerminalSessionsQuery {
+    pub workspace_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct TerminalSessionInfo {
+    pub id: Uuid,
+    pub workspace_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub attached_clients: usize,
+    pub exited: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TerminalOutputQuery {
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TerminalOutput {
+    pub session_id: Uuid,
+    pub exited: bool,
+    /// Recent scrollback with ANSI escape sequences stripped.
+    pub output: String,
+}
+
+const DEFAULT_OUTPUT_BYTES: usize = 16 * 1024;
+
+impl From<PtySessionInfo> for TerminalSessionInfo {
+    fn from(info: PtySessionInfo) -> Self {
+        Self {
+            id: info.id,
+            workspace_id: info.workspace_id,
+            created_at: info.created_at,
+            attached_clients: info.attached_clients,
+            exited: info.exited,
+        }
+    }
 }
 
 fn default_cols() -> u16 {
@@ -38,14 +89,23 @@ fn default_rows() -> u16 {
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum TerminalCommand {
-    Input { data: String },
-    Resize { cols: u16, rows: u16 },
+    Input {
+        data: String,
+    },
+    Resize {
+        cols: u16,
+        rows: u16,
+    },
+    /// Kill the shell rather than just detaching from it.
+    Close,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum TerminalMessage {
+    Session { session_id: Uuid, read_only: bool },
     Output { data: String },
+    Exit,
     Error { message: String },
 }
 
@@ -54,6 +114,22 @@ pub async fn terminal_ws(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<TerminalQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
+    if let Some(session_id) = query.session_id {
+        if deployment.pty().session_workspace(&session_id) != Some(query.workspace_id) {
+            return Err(PtyError::SessionNotFound(session_id).into());
+        }
+        let attachment = deployment.pty().attach(session_id, query.read_only)?;
+        return Ok(ws.on_upgrade(move |socket| {
+            handle_terminal_ws(socket, deployment, session_id, attachment)
+        }));
+    }
+
+    if query.read_only {
+        return Err(ApiError::BadRequest(
+            "read_only requires an existing session_id".to_string(),
+        ));
+    }
+
     let attempt = Workspace::find_by_id(&deployment.db().pool, query.workspace_id)
         .await?
         .ok_or_else(|| ApiError::BadRequest("Attempt not found".to_string()))?;
@@ -87,49 +163,146 @@ pub async fn terminal_ws(
         }
     }
 
-    Ok(ws.on_upgrade(move |socket| {
-        handle_terminal_ws(socket, deployment, working_dir, query.cols, query.rows)
+    Ok(ws.on_upgrade(move |socket| async move {
+        let attach = match deployment
+            .pty()
+            .create_session(query.workspace_id, working_dir, query.cols, query.rows)
+            .await
+        {
+            Ok(session_id) => deployment
+                .pty()
+                .attach(session_id, false)
+                .map(|attachment| (session_id, attachment)),
+            Err(e) => Err(e),
+        };
+        match attach {
+            Ok((session_id, attachment)) => {
+                handle_terminal_ws(socket, deployment, session_id, attachment).await
+            }
+            Err(e) => {
+                tracing::error!("Failed to create PTY session: {}", e);
+                let _ = send_error(socket, &e.to_string()).await;
+            }
+        }
     }))
 }
 
+/// List the terminal sessions of a workspace, including detached ones that
+/// can be reattached to.
+pub async fn list_terminal_sessions(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TerminalSessionsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TerminalSessionInfo>>>, ApiError> {
+    let sessions = deployment
+        .pty()
+        .list_sessions(query.workspace_id)
+        .into_iter()
+        .map(TerminalSessionInfo::from)
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(sessions)))
+}
+
+/// Read a session's recent output as plain text, e.g. for agents that cannot
+/// attach a terminal.
+pub async fn get_terminal_output(
+    State(deployment): State<DeploymentImpl>,
+    Path(session_id): Path<Uuid>,
+    Query(query): Query<TerminalOutputQuery>,
+) -> Result<ResponseJson<ApiResponse<TerminalOutput>>, ApiError> {
+    let max_bytes = query.max_bytes.unwrap_or(DEFAULT_OUTPUT_BYTES);
+    let (bytes, exited) = deployment.pty().recent_output(session_id, max_bytes)?;
+    let output = String::from_utf8_lossy(&strip_ansi_escapes::strip(&bytes)).into_owned();
+    Ok(ResponseJson(ApiResponse::success(TerminalOutput {
+        session_id,
+        exited,
+        output,
+    })))
+}
+
+pub async fn close_terminal_session(
+    State(deployment): State<DeploymentImpl>,
+    Path(session_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    if deployment.pty().session_workspace(&session_id).is_none() {
+        return Err(PtyError::SessionNotFound(session_id).into());
+    }
+    deployment.pty().close_session(session_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+async fn send_message(
+    sender: &mut SplitSink<WebSocket, Message>,
+    msg: &TerminalMessage,
+) -> Result<(), axum::Error> {
+    let json = serde_json::to_string(msg).unwrap_or_default();
+    sender.send(Message::Text(json.into())).await
+}
+
+/// Drive one client attached to a session. Disconnecting only detaches; the
+/// shell keeps running until it exits, is closed explicitly, or is reaped.
+/// Input from a read-only attachment is rejected by the PTY service.
 async fn handle_terminal_ws(
     socket: WebSocket,
     deployment: DeploymentImpl,
-    working_dir: PathBuf,
-    cols: u16,
-    rows: u16,
+    session_id: Uuid,
+    attachment: PtyAttachment,
 ) {
-    let (session_id, mut output_rx) = match deployment
-        .pty()
-        .create_session(working_dir, cols, rows)
-        .await
-    {
-        Ok(result) => result,
-        Err(e) => {
-            tracing::error!("Failed to create PTY session: {}", e);
-            let _ = send_error(socket, &e.to_string()).await;
-            return;
-        }
-    };
-
     let (mut ws_sender, mut ws_receiver) = socket.split();
 
     let pty_service = deployment.pty().clone();
     let session_id_for_input = session_id;
+    let attachment_id = attachment.id;
+    let read_only = attachment.read_only;
 
     let output_task = tokio::spawn(async move {
-        while let Some(data) = output_rx.recv().await {
-            let msg = TerminalMessage::Output {
-                data: BASE64.encode(&data),
-            };
-            let json = match serde_json::to_string(&msg) {
-                Ok(j) => j,
-                Err(_) => continue,
+        // The attachment lives as long as this task, keeping the session
+        // marked as attached.
+        let mut attachment = attachment;
+        let hello = TerminalMessage::Session {
+            session_id,
+            read_only,
+        };
+        if send_message(&mut ws_sender, &hello).await.is_err() {
+            return ws_sender;
+        }
+        if !attachment.scrollback.is_empty() {
+            let replay = TerminalMessage::Output {
+                data: BASE64.encode(&attachment.scrollback),
             };
-            if ws_sender.send(Message::Text(json.into())).await.is_err() {
-                break;
+            if send_message(&mut ws_sender, &replay).await.is_err() {
+                return ws_sender;
+            }
+        }
+
+        let mut exited = attachment.exited;
+        while !exited {
+            match attachment.output.recv().await {
+                Ok(PtyOutput::Data(data)) => {
+                    let msg = TerminalMessage::Output {
+                        data: BASE64.encode(&data),
+                    };
+                    if send_message(&mut ws_sender, &msg).await.is_err() {
+                        return ws_sender;
+                    }
+                }
+                Ok(PtyOutput::Exited) | Err(RecvError::Closed) => exited = true,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        "Terminal client for session {} lagged, skipped {} chunks",
+                        session_id,
+                        skipped
+                    );
+                }
             }
         }
+
+        let _ = send_message(&mut ws_sender, &TerminalMessage::Exit).await;
+        let _ = ws_sender
+            .send(Message::Close(Some(CloseFrame {
+                code: close_code::NORMAL,
+                reason: "shell exited".into(),
+            })))
+            .await;
         ws_sender
     });
 
@@ -140,11 +313,24 @@ async fn handle_terminal_ws(
                     match cmd {
                         TerminalCommand::Input { data } => {
                             if let Ok(bytes) = BASE64.decode(&data) {
-                                let _ = pty_service.write(session_id_for_input, &bytes).await;
+                                let _ = pty_service
+                                    .write(session_id_for_input, attachment_id, &bytes)
+                                    .await;
                             }
                         }
                         TerminalCommand::Resize { cols, rows } => {
-                            let _ = pty_service.resize(session_id_for_input, cols, rows).await;
+                            let _ = pty_service
+                                .resize(session_id_for_input, attachment_id, cols, rows)
+                                .await;
+                        }
+                        TerminalCommand::Close => {
+                            if pty_service
+                                .close_attached_session(session_id_for_input, attachment_id)
+                                .await
+                                .is_ok()
+                            {
+                                break;
+                            }
                         }
                     }
                 }
@@ -154,7 +340,6 @@ async fn handle_terminal_ws(
         }
     }
 
-    let _ = deployment.pty().close_session(session_id).await;
     output_task.abort();
 }
 
@@ -169,5 +354,15 @@ async fn send_error(mut socket: WebSocket, message: &str) -> Result<(), axum::Er
 }
 
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/terminal/ws", get(terminal_ws))
+    Router::new()
+        .route("/terminal/ws", get(terminal_ws))
+        .route("/terminal/sessions", get(list_terminal_sessions))
+        .route(
+            "/terminal/sessions/{session_id}",
+            delete(close_terminal_session),
+        )
+        .route(
+            "/terminal/sessions/{session_id}/output",
+            get(get_terminal_output),
+        )
 }
@@ -0,0 +1,278 @@
+//! Shared plumbing for exposing `LogMsg` streams over Server-Sent Events and
+//! long-poll, for clients that cannot hold a WebSocket open (e.g. behind
+//! proxies that strip the upgrade).
+//!
+//! Execution process log events carry a `LogCursor` (`<store_id>:<seq>`) as
+//! their id. A reconnecting client sends the last one back via `Last-Event-ID`
+//! (or `?after=`) and only newer messages are delivered. If the cursor can no
+//! longer be honoured the stream starts over and is prefixed with a `replayed`
+//! marker so the client knows to reset its state. Snapshot streams of the
+//! events store (tasks, projects, workspaces, ...) resume the same way, with
+//! cursors into the events store. Each SSE endpoint has a `/poll` counterpart
+//! taking the same cursor; streams that can't resume, like the diff, return a
+//! fresh snapshot on every poll.
+
+use std::time::Duration;
+
+use axum::{
+    BoxError,
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+    response::{
+        Sse,
+        sse::{Event, KeepAlive},
+    },
+};
+use futures_util::{FutureExt, Stream, StreamExt, TryStreamExt, stream::BoxStream};
+use serde::{Deserialize, Serialize};
+use services::services::events::ResumableLogStream;
+use utils::{
+    log_msg::LogMsg,
+    msg_store::{InvalidLogCursor, LogCursor, SequencedLogStream},
+};
+
+use crate::error::ApiError;
+
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+pub const EV_REPLAYED: &str = "replayed";
+
+/// Upper bound on how long a long-poll request is held open waiting for data.
+const MAX_POLL_WAIT: Duration = Duration::from_secs(30);
+const DEFAULT_POLL_WAIT: Duration = Duration::from_secs(25);
+const MAX_POLL_BATCH: usize = 1000;
+
+/// Cursor of the last message the client has already seen, taken from the
+/// `Last-Event-ID` header set by `EventSource` on reconnect, or from the `after`
+/// query parameter (WebSocket, long-poll, or a fresh `EventSource`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResumeCursor(pub Option<LogCursor>);
+
+#[derive(Debug, Deserialize)]
+struct ResumeCursorQuery {
+    after: Option<String>,
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ResumeCursor {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let raw = match parts
+            .headers
+            .get(LAST_EVENT_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            Some(header) => Some(header.to_string()),
+            None => Query::<ResumeCursorQuery>::from_request_parts(parts, state)
+                .await
+                .ok()
+                .and_then(|Query(q)| q.after),
+        };
+
+        match raw.filter(|s| !s.trim().is_empty()) {
+            Some(raw) => raw
+                .parse()
+                .map(|cursor| Self(Some(cursor)))
+                .map_err(|e: InvalidLogCursor| ApiError::BadRequest(e.to_string())),
+            None => Ok(Self(None)),
+        }
+    }
+}
+
+/// Wrap a sequenced log stream as an SSE response whose event ids are resume
+/// cursors.
+pub fn log_stream_sse(
+    sequenced: SequencedLogStream,
+) -> Sse<impl Stream<Item = Result<Event, BoxError>>> {
+    let store_id = sequenced.store_id;
+    let marker = sequenced
+        .replayed
+        .then(|| Ok(Event::default().event(EV_REPLAYED).data("")));
+    let events = sequenced.stream.map(move |item| {
+        item.map(|m| {
+            let cursor = LogCursor {
+                store_id,
+                seq: m.seq,
+            };
+            m.msg.to_sse_event().id(cursor.to_string())
+        })
+        .map_err(BoxError::from)
+    });
+    Sse::new(futures_util::stream::iter(marker).chain(events)).keep_alive(KeepAlive::default())
+}
+
+/// Wrap a snapshot-then-live stream of events as an SSE response whose event
+/// ids are resume cursors. Reconnecting with `Last-Event-ID` continues after
+/// that event; only a cursor the events store no longer holds gets a fresh
+/// snapshot.
+pub fn resumable_stream_sse(
+    stream: ResumableLogStream,
+) -> Sse<impl Stream<Item = Result<Event, BoxError>>> {
+    Sse::new(
+        stream
+            .map_ok(|m| {
+                let event = m.msg.to_sse_event();
+                match m.cursor {
+                    Some(cursor) => event.id(cursor.to_string()),
+                    None => event,
+                }
+            })
+            .err_into::<BoxError>(),
+    )
+    .keep_alive(KeepAlive::default())
+}
+
+/// Wrap a snapshot-then-live `LogMsg` stream that can't resume, e.g. a diff
+/// computed per subscription, as an SSE response. Events carry no id and a
+/// reconnect receives a fresh snapshot.
+pub fn snapshot_stream_sse(
+    stream: BoxStream<'static, Result<LogMsg, std::io::Error>>,
+) -> Sse<impl Stream<Item = Result<Event, BoxError>>> {
+    Sse::new(
+        stream
+            .map_ok(|msg| msg.to_sse_event())
+            .err_into::<BoxError>(),
+    )
+    .keep_alive(KeepAlive::default())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LogPollQuery {
+    /// How long to wait for new messages, in milliseconds.
+    pub wait_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogPollMessage {
+    /// Absent on messages that can't be resumed after, e.g. `Ready`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    pub msg: LogMsg,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogPollResponse {
+    pub messages: Vec<LogPollMessage>,
+    /// Pass back as `after` on the next poll.
+    pub cursor: Option<String>,
+    /// The requested cursor could not be honoured; `messages` start over from
+    /// the beginning of the retained history, or with a fresh snapshot.
+    pub replayed: bool,
+    pub finished: bool,
+}
+
+/// Everything `stream` has available now, or whatever arrives first within
+/// the requested wait.
+async fn next_batch<T>(
+    mut stream: BoxStream<'static, Result<T, std::io::Error>>,
+    query: &LogPollQuery,
+) -> Vec<T> {
+    let wait = query
+        .wait_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_POLL_WAIT)
+        .min(MAX_POLL_WAIT);
+
+    let mut batch = Vec::new();
+    if let Ok(Some(Ok(first))) = tokio::time::timeout(wait, stream.next()).await {
+        batch.push(first);
+        while batch.len() < MAX_POLL_BATCH {
+            match stream.next().now_or_never() {
+                Some(Some(Ok(msg))) => batch.push(msg),
+                _ => break,
+            }
+        }
+    }
+    batch
+}
+
+/// Long-poll fallback: return everything after the resume cursor that is
+/// available now, or wait up to `wait_ms` for the first new message.
+pub async fn poll_log_stream(
+    sequenced: SequencedLogStream,
+    resume: ResumeCursor,
+    query: LogPollQuery,
+) -> LogPollResponse {
+    let store_id = sequenced.store_id;
+    let replayed = sequenced.replayed;
+    let batch = next_batch(sequenced.stream, &query).await;
+
+    let finished = matches!(batch.last().map(|m| &m.msg), Some(LogMsg::Finished));
+    let messages: Vec<LogPollMessage> = batch
+        .into_iter()
+        .map(|m| LogPollMessage {
+            cursor: Some(
+                LogCursor {
+                    store_id,
+                    seq: m.seq,
+                }
+                .to_string(),
+            ),
+            msg: m.msg,
+        })
+        .collect();
+    let cursor = messages
+        .last()
+        .and_then(|m| m.cursor.clone())
+        .or_else(|| resume.0.filter(|_| !replayed).map(|c| c.to_string()));
+
+    LogPollResponse {
+        messages,
+        cursor,
+        replayed,
+        finished,
+    }
+}
+
+/// Long-poll fallback for snapshot streams of the events store. A cursor the
+/// store still holds gets the events after it; otherwise the poll starts over
+/// with a fresh snapshot and `replayed` set.
+pub async fn poll_resumable_stream(
+    stream: ResumableLogStream,
+    resume: ResumeCursor,
+    query: LogPollQuery,
+) -> LogPollResponse {
+    let batch = next_batch(stream, &query).await;
+
+    // `Ready` only ever follows a snapshot
+    let replayed = resume.0.is_some() && batch.iter().any(|m| matches!(m.msg, LogMsg::Ready));
+    let finished = matches!(batch.last().map(|m| &m.msg), Some(LogMsg::Finished));
+    let messages: Vec<LogPollMessage> = batch
+        .into_iter()
+        .map(|m| LogPollMessage {
+            cursor: m.cursor.map(|c| c.to_string()),
+            msg: m.msg,
+        })
+        .collect();
+    let cursor = messages
+        .iter()
+        .rev()
+        .find_map(|m| m.cursor.clone())
+        .or_else(|| resume.0.filter(|_| !replayed).map(|c| c.to_string()));
+
+    LogPollResponse {
+        messages,
+        cursor,
+        replayed,
+        finished,
+    }
+}
+
+/// Long-poll fallback for snapshot streams that can't resume, e.g. a diff
+/// computed per subscription: every poll returns a fresh snapshot.
+pub async fn poll_snapshot_stream(
+    stream: BoxStream<'static, Result<LogMsg, std::io::Error>>,
+    query: LogPollQuery,
+) -> LogPollResponse {
+    let batch = next_batch(stream, &query).await;
+
+    let finished = matches!(batch.last(), Some(LogMsg::Finished));
+    LogPollResponse {
+        messages: batch
+            .into_iter()
+            .map(|msg| LogPollMessage { cursor: None, msg })
+            .collect(),
+        cursor: None,
+        replayed: false,
+        finished,
+    }
+}
@@ -2,6 +2,7 @@ use anyhow::Error;
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utils::redaction::LogRedactionConfig;
 pub use v7::{
     EditorConfig, EditorType, GitHubConfig, NotificationConfig, ShowcaseState, SoundFile,
     ThemeMode, UiLanguage,
@@ -60,6 +61,8 @@ pub struct Config {
     pub commit_reminder_prompt: Option<String>,
     #[serde(default)]
     pub send_message_shortcut: SendMessageShortcut,
+    #[serde(default)]
+    pub log_redaction: LogRedactionConfig,
 }
 
 impl Config {
@@ -89,6 +92,7 @@ impl Config {
             commit_reminder_enabled: true,
             commit_reminder_prompt: None,
             send_message_shortcut: SendMessageShortcut::default(),
+            log_redaction: LogRedactionConfig::default(),
         }
     }
 
@@ -143,6 +147,7 @@ impl Default for Config {
             commit_reminder_enabled: true,
             commit_reminder_prompt: None,
             send_message_shortcut: SendMessageShortcut::default(),
+            log_redaction: LogRedactionConfig::default(),
         }
     }
 }
@@ -1,7 +1,10 @@
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
@@ -21,8 +24,8 @@ use db::{
         repo::Repo,
         session::{CreateSession, Session, SessionError},
         task::{Task, TaskStatus},
-        workspace::{Workspace, WorkspaceError},
-        workspace_repo::WorkspaceRepo,
+        workspace::{CreateWorkspace, Workspace, WorkspaceError},
+        workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
     },
 };
 #[cfg(feature = "qa-mode")]
@@ -37,7 +40,9 @@ use executors::{
     },
     executors::{ExecutorError, StandardCodingAgentExecutor},
     logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
+    oci::OciError,
     profile::ExecutorProfileId,
+    sandbox::SandboxError,
 };
 use futures::{StreamExt, future, stream::BoxStream};
 use git::{GitService, GitServiceError};
@@ -47,17 +52,123 @@ use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
 use utils::{
     log_msg::LogMsg,
-    msg_store::MsgStore,
+    msg_store::{LogCursor, MsgStore, SequencedLogMsg, SequencedLogStream},
+    redaction::Redactor,
     text::{git_branch_id, short_uuid},
 };
 use uuid::Uuid;
 
 use crate::services::{
-    notification::NotificationService, workspace_manager::WorkspaceError as WorkspaceManagerError,
-    worktree_manager::WorktreeError,
+    notification::NotificationService, secrets::SecretError,
+    workspace_manager::WorkspaceError as WorkspaceManagerError, worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
 
+/// The conversation a run's patches build, folded as they are persisted so
+/// that they can be replaced by a single patch once the run finishes.
+struct ConversationSnapshot {
+    conversation: serde_json::Value,
+    last_seq: Option<u64>,
+    /// Cleared if a patch did not apply, in which case the patches are kept
+    applied: bool,
+}
+
+impl Default for ConversationSnapshot {
+    fn default() -> Self {
+        Self {
+            conversation: serde_json::json!({ "entries": [] }),
+            last_seq: None,
+            applied: true,
+        }
+    }
+}
+
+impl ConversationSnapshot {
+    fn apply(&mut self, seq: u64, patch: &Patch) {
+        self.last_seq = Some(seq);
+        if self.applied && json_patch::patch(&mut self.conversation, patch).is_err() {
+            self.applied = false;
+        }
+    }
+
+    /// One patch adding every entry of the final conversation, with the seq of
+    /// the last patch folded in
+    fn into_patch(self) -> Option<(u64, Patch)> {
+        let last_seq = self.last_seq.filter(|_| self.applied)?;
+        let serde_json::Value::Array(entries) = self.conversation.get("entries")?.clone() else {
+            return None;
+        };
+        let ops: Vec<serde_json::Value> = entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| {
+                serde_json::json!({ "op": "add", "path": format!("/entries/{index}"), "value": value })
+            })
+            .collect();
+        let patch = serde_json::from_value(serde_json::Value::Array(ops)).ok()?;
+        Some((last_seq, patch))
+    }
+}
+
+async fn compact_persisted_patches(db: &DBService, execution_id: Uuid, seq: u64, patch: Patch) {
+    let jsonl_line = match serde_json::to_string(&LogMsg::JsonPatch(patch)) {
+        Ok(line) => format!("{line}\n"),
+        Err(e) => {
+            tracing::error!(
+                "Failed to serialize compacted patches for execution {}: {}",
+                execution_id,
+                e
+            );
+            return;
+        }
+    };
+    if let Err(e) =
+        ExecutionProcessLogs::replace_patches(&db.pool, execution_id, &jsonl_line, seq as i64).await
+    {
+        tracing::error!(
+            "Failed to compact persisted patches for execution {}: {}",
+            execution_id,
+            e
+        );
+    }
+}
+
+/// Keep only conversation patches and close with `Finished` once the store's
+/// stream ends. The trailing `Finished` carries the last seq seen from the
+/// store, so resuming from it replays nothing.
+fn normalized_only(sequenced: SequencedLogStream) -> SequencedLogStream {
+    let last_seq = Arc::new(AtomicU64::new(0));
+    let finished_seq = last_seq.clone();
+    let stream = sequenced
+        .stream
+        .inspect(move |msg| {
+            if let Ok(m) = msg {
+                last_seq.store(m.seq, Ordering::Relaxed);
+            }
+        })
+        .filter(|msg| {
+            future::ready(matches!(
+                msg,
+                Ok(SequencedLogMsg {
+                    msg: LogMsg::JsonPatch(..),
+                    ..
+                })
+            ))
+        })
+        .chain(futures::stream::once(async move {
+            Ok::<_, std::io::Error>(SequencedLogMsg {
+                seq: finished_seq.load(Ordering::Relaxed),
+                msg: LogMsg::Finished,
+            })
+        }))
+        .boxed();
+
+    SequencedLogStream {
+        stream,
+        ..sequenced
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -67,6 +178,12 @@ pub enum ContainerError {
     #[error(transparent)]
     ExecutorError(#[from] ExecutorError),
     #[error(transparent)]
+    Sandbox(#[from] SandboxError),
+    #[error(transparent)]
+    Oci(#[from] OciError),
+    #[error(transparent)]
+    Secret(#[from] SecretError),
+    #[error(transparent)]
     Worktree(#[from] WorktreeError),
     #[error(transparent)]
     Workspace(#[from] WorkspaceError),
@@ -406,6 +523,50 @@ pub trait ContainerService {
         Ok(())
     }
 
+    /// Redact logs persisted before redaction was applied to them. Returns
+    /// the number of rows changed.
+    async fn scrub_stored_logs(&self, redactor: &Redactor) -> Result<u64, ContainerError> {
+        const BATCH_SIZE: i64 = 500;
+
+        let pool = &self.db().pool;
+        let mut after_rowid = 0;
+        let mut scrubbed = 0;
+        loop {
+            let rows = ExecutionProcessLogs::find_rows_after(pool, after_rowid, BATCH_SIZE).await?;
+            let Some(last) = rows.last() else {
+                break;
+            };
+            after_rowid = last.rowid;
+
+            for row in rows {
+                let mut changed = false;
+                let mut logs = String::with_capacity(row.logs.len());
+                for line in row.logs.lines() {
+                    let redacted = serde_json::from_str::<LogMsg>(line)
+                        .ok()
+                        .map(|msg| redactor.redact(msg))
+                        .and_then(|msg| serde_json::to_string(&msg).ok())
+                        .filter(|redacted| redacted != line);
+                    match redacted {
+                        Some(redacted) => {
+                            changed = true;
+                            logs.push_str(&redacted);
+                        }
+                        None => logs.push_str(line),
+                    }
+                    logs.push('\n');
+                }
+
+                if changed {
+                    ExecutionProcessLogs::update_row_logs(pool, row.rowid, &logs).await?;
+                    scrubbed += 1;
+                }
+            }
+        }
+
+        Ok(scrubbed)
+    }
+
     fn cleanup_actions_for_repos(&self, repos: &[Repo]) -> Option<ExecutorAction> {
         let repos_with_cleanup: Vec<_> = repos
             .iter()
@@ -794,23 +955,32 @@ pub trait ContainerService {
         }
     }
 
+    /// Stream raw stdout/stderr for an execution, resuming after `after` when
+    /// possible. Live stores and persisted logs share the execution id as
+    /// store id, so cursors survive the process finishing.
     async fn stream_raw_logs(
         &self,
         id: &Uuid,
-    ) -> Option<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>> {
+        after: Option<&LogCursor>,
+    ) -> Option<SequencedLogStream> {
         if let Some(store) = self.get_msg_store_by_id(id).await {
             // First try in-memory store
-            return Some(
-                store
-                    .history_plus_stream()
+            let sequenced = store.sequenced_stream(after);
+            return Some(SequencedLogStream {
+                stream: sequenced
+                    .stream
                     .filter(|msg| {
                         future::ready(matches!(
                             msg,
-                            Ok(LogMsg::Stdout(..) | LogMsg::Stderr(..) | LogMsg::Finished)
+                            Ok(SequencedLogMsg {
+                                msg: LogMsg::Stdout(..) | LogMsg::Stderr(..) | LogMsg::Finished,
+                                ..
+                            })
                         ))
                     })
                     .boxed(),
-            );
+                ..sequenced
+            });
         } else {
             // Fallback: load from DB and create direct stream
             let log_records =
@@ -823,7 +993,7 @@ pub trait ContainerService {
                     }
                 };
 
-            let messages = match ExecutionProcessLogs::parse_logs(&log_records) {
+            let messages = match ExecutionProcessLogs::parse_sequenced_logs(&log_records) {
                 Ok(msgs) => msgs,
                 Err(e) => {
                     tracing::error!("Failed to parse logs for execution {}: {}", id, e);
@@ -831,35 +1001,75 @@ pub trait ContainerService {
                 }
             };
 
+            let finished_seq = messages.last().map(|m| m.seq + 1).unwrap_or(0);
+            let resume_after = after.filter(|c| c.store_id == *id).map(|c| c.seq);
+
             // Direct stream from parsed messages
             let stream = futures::stream::iter(
                 messages
                     .into_iter()
-                    .filter(|m| matches!(m, LogMsg::Stdout(_) | LogMsg::Stderr(_)))
-                    .chain(std::iter::once(LogMsg::Finished))
+                    .filter(|m| matches!(m.msg, LogMsg::Stdout(_) | LogMsg::Stderr(_)))
+                    .chain(std::iter::once(SequencedLogMsg {
+                        seq: finished_seq,
+                        msg: LogMsg::Finished,
+                    }))
+                    .filter(move |m| resume_after.is_none_or(|after| m.seq > after))
                     .map(Ok::<_, std::io::Error>),
             )
             .boxed();
 
-            Some(stream)
+            Some(SequencedLogStream {
+                store_id: *id,
+                replayed: after.is_some() && resume_after.is_none(),
+                stream,
+            })
         }
     }
 
+    /// Number of stdout/stderr messages an execution produced up to and
+    /// including `cursor`, so raw-log consumers can resume their line numbering
+    /// without replaying everything before it. `None` when the cursor belongs to
+    /// another store or is no longer retained.
+    async fn raw_output_index(&self, id: &Uuid, cursor: &LogCursor) -> Option<u64> {
+        if cursor.store_id != *id {
+            return None;
+        }
+        if let Some(store) = self.get_msg_store_by_id(id).await {
+            return store.outputs_through(cursor.seq).await;
+        }
+
+        let log_records = ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id)
+            .await
+            .inspect_err(|e| tracing::error!("Failed to fetch logs for execution {}: {}", id, e))
+            .ok()?;
+        let messages = ExecutionProcessLogs::parse_sequenced_logs(&log_records)
+            .inspect_err(|e| tracing::error!("Failed to parse logs for execution {}: {}", id, e))
+            .ok()?;
+        Some(
+            messages
+                .iter()
+                .filter(|m| {
+                    m.seq <= cursor.seq && matches!(m.msg, LogMsg::Stdout(_) | LogMsg::Stderr(_))
+                })
+                .count() as u64,
+        )
+    }
+
+    /// Stream normalized conversation patches for an execution, resuming after
+    /// `after` when possible. Finished executions replay the patches persisted
+    /// from the live run, compacted into one once it finished, so a cursor
+    /// from before the last patch falls back to a full replay; one from the
+    /// end of the run only gets `Finished`. Executions recorded before patches
+    /// were persisted are re-normalized from their raw output, and cursors into
+    /// them fall back to a full replay as well.
     async fn stream_normalized_logs(
         &self,
         id: &Uuid,
-    ) -> Option<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>> {
+        after: Option<&LogCursor>,
+    ) -> Option<SequencedLogStream> {
         // First try in-memory store (existing behavior)
         if let Some(store) = self.get_msg_store_by_id(id).await {
-            Some(
-                store
-                    .history_plus_stream() // BoxStream<Result<LogMsg, io::Error>>
-                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
-                    .chain(futures::stream::once(async {
-                        Ok::<_, std::io::Error>(LogMsg::Finished)
-                    }))
-                    .boxed(),
-            )
+            Some(normalized_only(store.sequenced_stream(after)))
         } else {
             // Fallback: load from DB and normalize
             let log_records =
@@ -872,6 +1082,42 @@ pub trait ContainerService {
                     }
                 };
 
+            let patches = match ExecutionProcessLogs::parse_sequenced_patches(&log_records) {
+                Ok(patches) => patches,
+                Err(e) => {
+                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    return None;
+                }
+            };
+            if let Some(last_patch_seq) = patches.last().map(|patch| patch.seq) {
+                let finished_seq = log_records
+                    .iter()
+                    .filter_map(|record| record.seq)
+                    .max()
+                    .map(|seq| seq as u64 + 1)
+                    .unwrap_or(0);
+                let resume_after = after
+                    .filter(|c| c.store_id == *id && c.seq >= last_patch_seq)
+                    .map(|c| c.seq);
+                let stream = futures::stream::iter(
+                    patches
+                        .into_iter()
+                        .chain(std::iter::once(SequencedLogMsg {
+                            seq: finished_seq,
+                            msg: LogMsg::Finished,
+                        }))
+                        .filter(move |m| resume_after.is_none_or(|after| m.seq > after))
+                        .map(Ok::<_, std::io::Error>),
+                )
+                .boxed();
+
+                return Some(SequencedLogStream {
+                    store_id: *id,
+                    replayed: after.is_some() && resume_after.is_none(),
+                    stream,
+                });
+            }
+
             let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
                 Ok(msgs) => msgs,
                 Err(e) => {
@@ -883,6 +1129,9 @@ pub trait ContainerService {
             // Create temporary store and populate
             // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
             let temp_store = Arc::new(MsgStore::new());
+            // Persisted logs are already redacted, this marks the entries
+            // normalized from them
+            temp_store.set_redactor(Redactor::default());
             for msg in raw_messages {
                 if matches!(
                     msg,
@@ -1005,15 +1254,7 @@ pub trait ContainerService {
                     return None;
                 }
             }
-            Some(
-                temp_store
-                    .history_plus_stream()
-                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
-                    .chain(futures::stream::once(async {
-                        Ok::<_, std::io::Error>(LogMsg::Finished)
-                    }))
-                    .boxed(),
-            )
+            Some(normalized_only(temp_store.sequenced_stream(after)))
         }
     }
 
@@ -1030,11 +1271,17 @@ pub trait ContainerService {
             };
 
             if let Some(store) = store {
-                let mut stream = store.history_plus_stream();
+                let mut stream = store.sequenced_stream(None).stream;
+                let mut conversation = ConversationSnapshot::default();
 
-                while let Some(Ok(msg)) = stream.next().await {
+                while let Some(Ok(SequencedLogMsg { seq, msg })) = stream.next().await {
+                    if let LogMsg::JsonPatch(patch) = &msg {
+                        conversation.apply(seq, patch);
+                    }
                     match &msg {
-                        LogMsg::Stdout(_) | LogMsg::Stderr(_) => {
+                        // Patches are kept with their seq while the run is live
+                        // and compacted once it finishes
+                        LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_) => {
                             // Serialize this individual message as a JSONL line
                             match serde_json::to_string(&msg) {
                                 Ok(jsonl_line) => {
@@ -1045,6 +1292,7 @@ pub trait ContainerService {
                                         &db.pool,
                                         execution_id,
                                         &jsonl_line_with_newline,
+                                        Some(seq as i64),
                                     )
                                     .await
                                     {
@@ -1098,6 +1346,9 @@ pub trait ContainerService {
                             }
                         }
                         LogMsg::Finished => {
+                            if let Some((seq, patch)) = conversation.into_patch() {
+                                compact_persisted_patches(&db, execution_id, seq, patch).await;
+                            }
                             break;
                         }
                         LogMsg::JsonPatch(_) | LogMsg::Ready => continue,
@@ -1107,6 +1358,65 @@ pub trait ContainerService {
         })
     }
 
+    /// Create a workspace for `task` over `repos` (each with its target
+    /// branch) and start the coding agent in it. Returns the workspace and
+    /// whether the agent was started; start failures are logged rather than
+    /// returned, since the workspace itself was created.
+    async fn create_and_start_workspace(
+        &self,
+        task: &Task,
+        repos: &[(Repo, String)],
+        executor_profile_id: ExecutorProfileId,
+    ) -> Result<(Workspace, bool), ContainerError> {
+        let pool = &self.db().pool;
+        let workspace_id = Uuid::new_v4();
+        let branch = self
+            .git_branch_from_workspace(&workspace_id, &task.title)
+            .await;
+
+        // Compute agent_working_dir based on repo count:
+        // - Single repo: join repo name with default_working_dir (if set), or just repo name
+        // - Multiple repos: use None (agent runs in workspace root)
+        let agent_working_dir = match repos {
+            [(repo, _)] => match &repo.default_working_dir {
+                Some(subdir) => {
+                    let path = PathBuf::from(&repo.name).join(subdir);
+                    Some(path.to_string_lossy().to_string())
+                }
+                None => Some(repo.name.clone()),
+            },
+            _ => None,
+        };
+
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch,
+                agent_working_dir,
+            },
+            workspace_id,
+            task.id,
+        )
+        .await?;
+
+        let workspace_repos: Vec<CreateWorkspaceRepo> = repos
+            .iter()
+            .map(|(repo, target_branch)| CreateWorkspaceRepo {
+                repo_id: repo.id,
+                target_branch: target_branch.clone(),
+            })
+            .collect();
+        WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+
+        let started = self
+            .start_workspace(&workspace, executor_profile_id)
+            .await
+            .inspect_err(|err| tracing::error!("Failed to start task attempt: {}", err))
+            .is_ok();
+
+        Ok((workspace, started))
+    }
+
     async fn start_workspace(
         &self,
         workspace: &Workspace,
@@ -1315,15 +1625,28 @@ pub trait ContainerService {
                     &self.db().pool,
                     execution_process.id,
                     &format!("{json_line}\n"),
+                    None,
                 )
                 .await;
             }
 
             // Emit NextAction with failure context for coding agent requests
-            if let ContainerError::ExecutorError(ExecutorError::ExecutableNotFound { program }) =
-                &start_error
-            {
-                let help_text = format!("The required executable `{program}` is not installed.");
+            let help_text = match &start_error {
+                ContainerError::ExecutorError(ExecutorError::ExecutableNotFound { program }) => {
+                    Some(format!(
+                        "The required executable `{program}` is not installed."
+                    ))
+                }
+                ContainerError::Sandbox(error) => Some(error.to_string()),
+                ContainerError::Secret(
+                    error @ (SecretError::MasterKey(_) | SecretError::Decrypt(_)),
+                ) => Some(error.to_string()),
+                ContainerError::Oci(error @ OciError::RuntimeNotFound(_)) => {
+                    Some(error.to_string())
+                }
+                _ => None,
+            };
+            if let Some(help_text) = help_text {
                 let error_message = NormalizedEntry {
                     timestamp: None,
                     entry_type: NormalizedEntryType::ErrorMessage {
@@ -1338,6 +1661,7 @@ pub trait ContainerService {
                         &self.db().pool,
                         execution_process.id,
                         &format!("{json_line}\n"),
+                        None,
                     )
                     .await;
                 }
@@ -1426,3 +1750,45 @@ pub trait ContainerService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patch(value: serde_json::Value) -> Patch {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn conversation_patches_compact_to_the_final_entries() {
+        let mut conversation = ConversationSnapshot::default();
+        conversation.apply(
+            3,
+            &patch(serde_json::json!([{ "op": "add", "path": "/entries/0", "value": "a" }])),
+        );
+        conversation.apply(
+            5,
+            &patch(serde_json::json!([{ "op": "add", "path": "/entries/1", "value": "b" }])),
+        );
+        conversation.apply(
+            8,
+            &patch(serde_json::json!([{ "op": "replace", "path": "/entries/1", "value": "b2" }])),
+        );
+
+        let (seq, compacted) = conversation.into_patch().unwrap();
+        assert_eq!(seq, 8);
+        let mut rebuilt = serde_json::json!({ "entries": [] });
+        json_patch::patch(&mut rebuilt, &compacted).unwrap();
+        assert_eq!(rebuilt, serde_json::json!({ "entries": ["a", "b2"] }));
+    }
+
+    #[test]
+    fn patches_that_do_not_apply_are_not_compacted() {
+        let mut conversation = ConversationSnapshot::default();
+        conversation.apply(
+            1,
+            &patch(serde_json::json!([{ "op": "replace", "path": "/entries/4", "value": "x" }])),
+        );
+        assert!(conversation.into_patch().is_none());
+    }
+}
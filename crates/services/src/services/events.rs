@@ -23,7 +23,10 @@ pub mod types;
 pub use patches::{
     execution_process_patch, project_patch, scratch_patch, task_patch, workspace_patch,
 };
-pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
+pub use types::{
+    EventError, EventPatch, EventPatchInner, HookTables, RecordTypes, ResumableLogMsg,
+    ResumableLogStream,
+};
 
 #[derive(Clone)]
 pub struct EventService {
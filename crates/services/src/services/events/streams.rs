@@ -1,3 +1,5 @@
+use std::future::Future;
+
 use db::models::{
     execution_process::ExecutionProcess,
     project::Project,
@@ -7,151 +9,239 @@ use db::models::{
 };
 use futures::StreamExt;
 use serde_json::json;
-use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
-use utils::log_msg::LogMsg;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use utils::{
+    log_msg::LogMsg,
+    msg_store::{LogCursor, SequencedEventStream},
+};
 use uuid::Uuid;
 
 use super::{
     EventService,
     patches::execution_process_patch,
-    types::{EventError, EventPatch, RecordTypes},
+    types::{EventError, EventPatch, RecordTypes, ResumableLogMsg, ResumableLogStream},
 };
 
+/// Live events for a snapshot stream
+struct LiveEvents {
+    /// The client's cursor was honoured, so it already has the snapshot
+    resumed: bool,
+    /// Resume point for a snapshot taken now
+    snapshot_cursor: Option<LogCursor>,
+    events: SequencedEventStream,
+}
+
+/// Snapshot (unless resumed) and `Ready`, then the live events `filter` lets
+/// through, each carrying the cursor to resume after it
+fn resumable_stream<F, Fut>(
+    live: LiveEvents,
+    snapshot: Option<LogMsg>,
+    mut filter: F,
+) -> ResumableLogStream
+where
+    F: FnMut(Result<LogMsg, BroadcastStreamRecvError>) -> Fut + Send + 'static,
+    Fut: Future<Output = Option<Result<LogMsg, std::io::Error>>> + Send + 'static,
+{
+    let store_id = live.events.store_id;
+    let initial = snapshot.map(|snapshot| {
+        [
+            Ok(ResumableLogMsg {
+                cursor: live.snapshot_cursor,
+                msg: snapshot,
+            }),
+            Ok(ResumableLogMsg {
+                cursor: None,
+                msg: LogMsg::Ready,
+            }),
+        ]
+    });
+    let events = live.events.stream.filter_map(move |item| {
+        let cursor = item.as_ref().ok().map(|event| LogCursor {
+            store_id,
+            seq: event.seq,
+        });
+        let filtered = filter(item.map(|event| event.msg));
+        async move {
+            filtered
+                .await
+                .map(|res| res.map(|msg| ResumableLogMsg { cursor, msg }))
+        }
+    });
+
+    futures::stream::iter(initial.into_iter().flatten())
+        .chain(events)
+        .boxed()
+}
+
 impl EventService {
+    /// Events after `after` while the store still holds them. Otherwise events
+    /// from now on, and the caller sends a fresh snapshot first.
+    fn live_events(&self, after: Option<&LogCursor>) -> LiveEvents {
+        if let Some(after) = after {
+            let events = self.msg_store.sequenced_events(Some(after));
+            if !events.replayed {
+                return LiveEvents {
+                    resumed: true,
+                    snapshot_cursor: Some(*after),
+                    events,
+                };
+            }
+        }
+
+        // Taken before the snapshot is read, so events racing with it are
+        // delivered again rather than lost
+        let snapshot_cursor = self
+            .msg_store
+            .next_seq()
+            .checked_sub(1)
+            .map(|seq| LogCursor {
+                store_id: self.msg_store.id(),
+                seq,
+            });
+        LiveEvents {
+            resumed: false,
+            snapshot_cursor,
+            events: self.msg_store.sequenced_events(snapshot_cursor.as_ref()),
+        }
+    }
+
     /// Stream raw task messages for a specific project with initial snapshot
     pub async fn stream_tasks_raw(
         &self,
         project_id: Uuid,
-    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
-    {
-        // Get initial snapshot of tasks
-        let tasks = Task::find_by_project_id_with_attempt_status(&self.db.pool, project_id).await?;
-
-        // Convert task array to object keyed by task ID
-        let tasks_map: serde_json::Map<String, serde_json::Value> = tasks
-            .into_iter()
-            .map(|task| (task.id.to_string(), serde_json::to_value(task).unwrap()))
-            .collect();
-
-        let initial_patch = json!([
-            {
-                "op": "replace",
-                "path": "/tasks",
-                "value": tasks_map
-            }
-        ]);
-        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
+        after: Option<&LogCursor>,
+    ) -> Result<ResumableLogStream, EventError> {
+        let live = self.live_events(after);
+        let initial_msg = if live.resumed {
+            None
+        } else {
+            // Get initial snapshot of tasks
+            let tasks =
+                Task::find_by_project_id_with_attempt_status(&self.db.pool, project_id).await?;
+
+            // Convert task array to object keyed by task ID
+            let tasks_map: serde_json::Map<String, serde_json::Value> = tasks
+                .into_iter()
+                .map(|task| (task.id.to_string(), serde_json::to_value(task).unwrap()))
+                .collect();
+
+            let initial_patch = json!([
+                {
+                    "op": "replace",
+                    "path": "/tasks",
+                    "value": tasks_map
+                }
+            ]);
+            Some(LogMsg::JsonPatch(
+                serde_json::from_value(initial_patch).unwrap(),
+            ))
+        };
 
         // Clone necessary data for the async filter
         let db_pool = self.db.pool.clone();
 
         // Get filtered event stream
-        let filtered_stream =
-            BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
-                let db_pool = db_pool.clone();
-                async move {
-                    match msg_result {
-                        Ok(LogMsg::JsonPatch(patch)) => {
-                            // Filter events based on project_id
-                            if let Some(patch_op) = patch.0.first() {
-                                // Check if this is a direct task patch (new format)
-                                if patch_op.path().starts_with("/tasks/") {
-                                    match patch_op {
-                                        json_patch::PatchOperation::Add(op) => {
-                                            // Parse task data directly from value
-                                            if let Ok(task) =
-                                                serde_json::from_value::<TaskWithAttemptStatus>(
-                                                    op.value.clone(),
-                                                )
-                                                && task.project_id == project_id
-                                            {
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
-                                            }
-                                        }
-                                        json_patch::PatchOperation::Replace(op) => {
-                                            // Parse task data directly from value
-                                            if let Ok(task) =
-                                                serde_json::from_value::<TaskWithAttemptStatus>(
-                                                    op.value.clone(),
-                                                )
-                                                && task.project_id == project_id
-                                            {
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
-                                            }
+        let stream = resumable_stream(live, initial_msg, move |msg_result| {
+            let db_pool = db_pool.clone();
+            async move {
+                match msg_result {
+                    Ok(LogMsg::JsonPatch(patch)) => {
+                        // Filter events based on project_id
+                        if let Some(patch_op) = patch.0.first() {
+                            // Check if this is a direct task patch (new format)
+                            if patch_op.path().starts_with("/tasks/") {
+                                match patch_op {
+                                    json_patch::PatchOperation::Add(op) => {
+                                        // Parse task data directly from value
+                                        if let Ok(task) =
+                                            serde_json::from_value::<TaskWithAttemptStatus>(
+                                                op.value.clone(),
+                                            )
+                                            && task.project_id == project_id
+                                        {
+                                            return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        json_patch::PatchOperation::Remove(_) => {
-                                            // For remove operations, we need to check project membership differently
-                                            // We could cache this information or let it pass through for now
-                                            // Since we don't have the task data, we'll allow all removals
-                                            // and let the client handle filtering
+                                    }
+                                    json_patch::PatchOperation::Replace(op) => {
+                                        // Parse task data directly from value
+                                        if let Ok(task) =
+                                            serde_json::from_value::<TaskWithAttemptStatus>(
+                                                op.value.clone(),
+                                            )
+                                            && task.project_id == project_id
+                                        {
                                             return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        _ => {}
                                     }
-                                } else if let Ok(event_patch_value) = serde_json::to_value(patch_op)
-                                    && let Ok(event_patch) =
-                                        serde_json::from_value::<EventPatch>(event_patch_value)
-                                {
-                                    // Handle old EventPatch format for non-task records
-                                    match &event_patch.value.record {
-                                        RecordTypes::Task(task) => {
-                                            if task.project_id == project_id {
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
-                                            }
+                                    json_patch::PatchOperation::Remove(_) => {
+                                        // For remove operations, we need to check project membership differently
+                                        // We could cache this information or let it pass through for now
+                                        // Since we don't have the task data, we'll allow all removals
+                                        // and let the client handle filtering
+                                        return Some(Ok(LogMsg::JsonPatch(patch)));
+                                    }
+                                    _ => {}
+                                }
+                            } else if let Ok(event_patch_value) = serde_json::to_value(patch_op)
+                                && let Ok(event_patch) =
+                                    serde_json::from_value::<EventPatch>(event_patch_value)
+                            {
+                                // Handle old EventPatch format for non-task records
+                                match &event_patch.value.record {
+                                    RecordTypes::Task(task) => {
+                                        if task.project_id == project_id {
+                                            return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        RecordTypes::DeletedTask {
-                                            project_id: Some(deleted_project_id),
-                                            ..
-                                        } => {
-                                            if *deleted_project_id == project_id {
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
-                                            }
+                                    }
+                                    RecordTypes::DeletedTask {
+                                        project_id: Some(deleted_project_id),
+                                        ..
+                                    } => {
+                                        if *deleted_project_id == project_id {
+                                            return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        RecordTypes::Workspace(workspace) => {
-                                            // Check if this workspace belongs to a task in our project
-                                            if let Ok(Some(task)) =
-                                                Task::find_by_id(&db_pool, workspace.task_id).await
-                                                && task.project_id == project_id
-                                            {
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
-                                            }
+                                    }
+                                    RecordTypes::Workspace(workspace) => {
+                                        // Check if this workspace belongs to a task in our project
+                                        if let Ok(Some(task)) =
+                                            Task::find_by_id(&db_pool, workspace.task_id).await
+                                            && task.project_id == project_id
+                                        {
+                                            return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        RecordTypes::DeletedWorkspace {
-                                            task_id: Some(deleted_task_id),
-                                            ..
-                                        } => {
-                                            // Check if deleted workspace belonged to a task in our project
-                                            if let Ok(Some(task)) =
-                                                Task::find_by_id(&db_pool, *deleted_task_id).await
-                                                && task.project_id == project_id
-                                            {
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
-                                            }
+                                    }
+                                    RecordTypes::DeletedWorkspace {
+                                        task_id: Some(deleted_task_id),
+                                        ..
+                                    } => {
+                                        // Check if deleted workspace belonged to a task in our project
+                                        if let Ok(Some(task)) =
+                                            Task::find_by_id(&db_pool, *deleted_task_id).await
+                                            && task.project_id == project_id
+                                        {
+                                            return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        _ => {}
                                     }
+                                    _ => {}
                                 }
                             }
-                            None
                         }
-                        Ok(other) => Some(Ok(other)), // Pass through non-patch messages
-                        Err(_) => None,               // Filter out broadcast errors
+                        None
                     }
+                    Ok(other) => Some(Ok(other)), // Pass through non-patch messages
+                    Err(_) => None,               // Filter out broadcast errors
                 }
-            });
-
-        // Start with initial snapshot, Ready signal, then live updates
-        let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+            }
+        });
 
-        Ok(combined_stream)
+        Ok(stream)
     }
 
     /// Stream raw project messages with initial snapshot
     pub async fn stream_projects_raw(
         &self,
-    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
-    {
+        after: Option<&LogCursor>,
+    ) -> Result<ResumableLogStream, EventError> {
         fn build_projects_snapshot(projects: Vec<Project>) -> LogMsg {
             // Convert projects array to object keyed by project ID
             let projects_map: serde_json::Map<String, serde_json::Value> = projects
@@ -175,55 +265,55 @@ impl EventService {
             LogMsg::JsonPatch(serde_json::from_value(patch).unwrap())
         }
 
-        // Get initial snapshot of projects
-        let projects = Project::find_all(&self.db.pool).await?;
-        let initial_msg = build_projects_snapshot(projects);
+        let live = self.live_events(after);
+        let initial_msg = if live.resumed {
+            None
+        } else {
+            // Get initial snapshot of projects
+            let projects = Project::find_all(&self.db.pool).await?;
+            Some(build_projects_snapshot(projects))
+        };
 
         let db_pool = self.db.pool.clone();
 
         // Get filtered event stream (projects only)
-        let filtered_stream =
-            BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
-                let db_pool = db_pool.clone();
-                async move {
-                    match msg_result {
-                        Ok(LogMsg::JsonPatch(patch)) => {
-                            if let Some(patch_op) = patch.0.first()
-                                && patch_op.path().starts_with("/projects")
-                            {
-                                return Some(Ok(LogMsg::JsonPatch(patch)));
-                            }
-                            None
+        let stream = resumable_stream(live, initial_msg, move |msg_result| {
+            let db_pool = db_pool.clone();
+            async move {
+                match msg_result {
+                    Ok(LogMsg::JsonPatch(patch)) => {
+                        if let Some(patch_op) = patch.0.first()
+                            && patch_op.path().starts_with("/projects")
+                        {
+                            return Some(Ok(LogMsg::JsonPatch(patch)));
                         }
-                        Ok(other) => Some(Ok(other)), // Pass through non-patch messages
-                        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
-                            tracing::warn!(
-                                skipped = skipped,
-                                "projects stream lagged; resyncing snapshot"
-                            );
-
-                            match Project::find_all(&db_pool).await {
-                                Ok(projects) => Some(Ok(build_projects_snapshot(projects))),
-                                Err(err) => {
-                                    tracing::error!(
-                                        error = %err,
-                                        "failed to resync projects after lag"
-                                    );
-                                    Some(Err(std::io::Error::other(format!(
-                                        "failed to resync projects after lag: {err}"
-                                    ))))
-                                }
+                        None
+                    }
+                    Ok(other) => Some(Ok(other)), // Pass through non-patch messages
+                    Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            skipped = skipped,
+                            "projects stream lagged; resyncing snapshot"
+                        );
+
+                        match Project::find_all(&db_pool).await {
+                            Ok(projects) => Some(Ok(build_projects_snapshot(projects))),
+                            Err(err) => {
+                                tracing::error!(
+                                    error = %err,
+                                    "failed to resync projects after lag"
+                                );
+                                Some(Err(std::io::Error::other(format!(
+                                    "failed to resync projects after lag: {err}"
+                                ))))
                             }
                         }
                     }
                 }
-            });
-
-        // Start with initial snapshot, Ready signal, then live updates
-        let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+            }
+        });
 
-        Ok(combined_stream)
+        Ok(stream)
     }
 
     /// Stream execution processes for a specific session with initial snapshot (raw LogMsg format for WebSocket)
@@ -231,129 +321,125 @@ impl EventService {
         &self,
         session_id: Uuid,
         show_soft_deleted: bool,
-    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
-    {
-        // Get execution processes for this session
-        let processes =
-            ExecutionProcess::find_by_session_id(&self.db.pool, session_id, show_soft_deleted)
-                .await?;
-
-        // Convert processes array to object keyed by process ID
-        let processes_map: serde_json::Map<String, serde_json::Value> = processes
-            .into_iter()
-            .map(|process| {
-                (
-                    process.id.to_string(),
-                    serde_json::to_value(process).unwrap(),
-                )
-            })
-            .collect();
-
-        let initial_patch = json!([{
-            "op": "replace",
-            "path": "/execution_processes",
-            "value": processes_map
-        }]);
-        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
+        after: Option<&LogCursor>,
+    ) -> Result<ResumableLogStream, EventError> {
+        let live = self.live_events(after);
+        let initial_msg = if live.resumed {
+            None
+        } else {
+            // Get execution processes for this session
+            let processes =
+                ExecutionProcess::find_by_session_id(&self.db.pool, session_id, show_soft_deleted)
+                    .await?;
+
+            // Convert processes array to object keyed by process ID
+            let processes_map: serde_json::Map<String, serde_json::Value> = processes
+                .into_iter()
+                .map(|process| {
+                    (
+                        process.id.to_string(),
+                        serde_json::to_value(process).unwrap(),
+                    )
+                })
+                .collect();
+
+            let initial_patch = json!([{
+                "op": "replace",
+                "path": "/execution_processes",
+                "value": processes_map
+            }]);
+            Some(LogMsg::JsonPatch(
+                serde_json::from_value(initial_patch).unwrap(),
+            ))
+        };
 
         // Get filtered event stream
-        let filtered_stream =
-            BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
-                async move {
-                    match msg_result {
-                        Ok(LogMsg::JsonPatch(patch)) => {
-                            // Filter events based on session_id
-                            if let Some(patch_op) = patch.0.first() {
-                                // Check if this is a modern execution process patch
-                                if patch_op.path().starts_with("/execution_processes/") {
-                                    match patch_op {
-                                        json_patch::PatchOperation::Add(op) => {
-                                            // Parse execution process data directly from value
-                                            if let Ok(process) =
-                                                serde_json::from_value::<ExecutionProcess>(
-                                                    op.value.clone(),
-                                                )
-                                                && process.session_id == session_id
-                                            {
-                                                if !show_soft_deleted && process.dropped {
-                                                    let remove_patch =
-                                                        execution_process_patch::remove(process.id);
-                                                    return Some(Ok(LogMsg::JsonPatch(
-                                                        remove_patch,
-                                                    )));
-                                                }
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
+        let stream = resumable_stream(live, initial_msg, move |msg_result| {
+            async move {
+                match msg_result {
+                    Ok(LogMsg::JsonPatch(patch)) => {
+                        // Filter events based on session_id
+                        if let Some(patch_op) = patch.0.first() {
+                            // Check if this is a modern execution process patch
+                            if patch_op.path().starts_with("/execution_processes/") {
+                                match patch_op {
+                                    json_patch::PatchOperation::Add(op) => {
+                                        // Parse execution process data directly from value
+                                        if let Ok(process) =
+                                            serde_json::from_value::<ExecutionProcess>(
+                                                op.value.clone(),
+                                            )
+                                            && process.session_id == session_id
+                                        {
+                                            if !show_soft_deleted && process.dropped {
+                                                let remove_patch =
+                                                    execution_process_patch::remove(process.id);
+                                                return Some(Ok(LogMsg::JsonPatch(remove_patch)));
                                             }
+                                            return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        json_patch::PatchOperation::Replace(op) => {
-                                            // Parse execution process data directly from value
-                                            if let Ok(process) =
-                                                serde_json::from_value::<ExecutionProcess>(
-                                                    op.value.clone(),
-                                                )
-                                                && process.session_id == session_id
-                                            {
-                                                if !show_soft_deleted && process.dropped {
-                                                    let remove_patch =
-                                                        execution_process_patch::remove(process.id);
-                                                    return Some(Ok(LogMsg::JsonPatch(
-                                                        remove_patch,
-                                                    )));
-                                                }
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
+                                    }
+                                    json_patch::PatchOperation::Replace(op) => {
+                                        // Parse execution process data directly from value
+                                        if let Ok(process) =
+                                            serde_json::from_value::<ExecutionProcess>(
+                                                op.value.clone(),
+                                            )
+                                            && process.session_id == session_id
+                                        {
+                                            if !show_soft_deleted && process.dropped {
+                                                let remove_patch =
+                                                    execution_process_patch::remove(process.id);
+                                                return Some(Ok(LogMsg::JsonPatch(remove_patch)));
                                             }
-                                        }
-                                        json_patch::PatchOperation::Remove(_) => {
-                                            // For remove operations, we can't verify session_id
-                                            // so we allow all removals and let the client handle filtering
                                             return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        _ => {}
                                     }
+                                    json_patch::PatchOperation::Remove(_) => {
+                                        // For remove operations, we can't verify session_id
+                                        // so we allow all removals and let the client handle filtering
+                                        return Some(Ok(LogMsg::JsonPatch(patch)));
+                                    }
+                                    _ => {}
                                 }
-                                // Fallback to legacy EventPatch format for backward compatibility
-                                else if let Ok(event_patch_value) = serde_json::to_value(patch_op)
-                                    && let Ok(event_patch) =
-                                        serde_json::from_value::<EventPatch>(event_patch_value)
-                                {
-                                    match &event_patch.value.record {
-                                        RecordTypes::ExecutionProcess(process) => {
-                                            if process.session_id == session_id {
-                                                if !show_soft_deleted && process.dropped {
-                                                    let remove_patch =
-                                                        execution_process_patch::remove(process.id);
-                                                    return Some(Ok(LogMsg::JsonPatch(
-                                                        remove_patch,
-                                                    )));
-                                                }
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
+                            }
+                            // Fallback to legacy EventPatch format for backward compatibility
+                            else if let Ok(event_patch_value) = serde_json::to_value(patch_op)
+                                && let Ok(event_patch) =
+                                    serde_json::from_value::<EventPatch>(event_patch_value)
+                            {
+                                match &event_patch.value.record {
+                                    RecordTypes::ExecutionProcess(process) => {
+                                        if process.session_id == session_id {
+                                            if !show_soft_deleted && process.dropped {
+                                                let remove_patch =
+                                                    execution_process_patch::remove(process.id);
+                                                return Some(Ok(LogMsg::JsonPatch(remove_patch)));
                                             }
+                                            return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        RecordTypes::DeletedExecutionProcess {
-                                            session_id: Some(deleted_session_id),
-                                            ..
-                                        } => {
-                                            if *deleted_session_id == session_id {
-                                                return Some(Ok(LogMsg::JsonPatch(patch)));
-                                            }
+                                    }
+                                    RecordTypes::DeletedExecutionProcess {
+                                        session_id: Some(deleted_session_id),
+                                        ..
+                                    } => {
+                                        if *deleted_session_id == session_id {
+                                            return Some(Ok(LogMsg::JsonPatch(patch)));
                                         }
-                                        _ => {}
                                     }
+                                    _ => {}
                                 }
                             }
-                            None
                         }
-                        Ok(other) => Some(Ok(other)), // Pass through non-patch messages
-                        Err(_) => None,               // Filter out broadcast errors
+                        None
                     }
+                    Ok(other) => Some(Ok(other)), // Pass through non-patch messages
+                    Err(_) => None,               // Filter out broadcast errors
                 }
-            });
-
-        // Start with initial snapshot, Ready signal, then live updates
-        let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+            }
+        });
 
-        Ok(combined_stream)
+        Ok(stream)
     }
 
     /// Stream a single scratch item with initial snapshot (raw LogMsg format for WebSocket)
@@ -361,165 +447,174 @@ impl EventService {
         &self,
         scratch_id: Uuid,
         scratch_type: &db::models::scratch::ScratchType,
-    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
-    {
-        // Treat errors (e.g., corrupted/malformed data) the same as "scratch not found"
-        // This prevents the websocket from closing and retrying indefinitely
-        let scratch = match Scratch::find_by_id(&self.db.pool, scratch_id, scratch_type).await {
-            Ok(scratch) => scratch,
-            Err(e) => {
-                tracing::warn!(
-                    scratch_id = %scratch_id,
-                    scratch_type = %scratch_type,
-                    error = %e,
-                    "Failed to load scratch, treating as empty"
-                );
-                None
-            }
-        };
+        after: Option<&LogCursor>,
+    ) -> Result<ResumableLogStream, EventError> {
+        let live = self.live_events(after);
+        let initial_msg = if live.resumed {
+            None
+        } else {
+            // Treat errors (e.g., corrupted/malformed data) the same as "scratch not found"
+            // This prevents the websocket from closing and retrying indefinitely
+            let scratch = match Scratch::find_by_id(&self.db.pool, scratch_id, scratch_type).await {
+                Ok(scratch) => scratch,
+                Err(e) => {
+                    tracing::warn!(
+                        scratch_id = %scratch_id,
+                        scratch_type = %scratch_type,
+                        error = %e,
+                        "Failed to load scratch, treating as empty"
+                    );
+                    None
+                }
+            };
 
-        let initial_patch = json!([{
-            "op": "replace",
-            "path": "/scratch",
-            "value": scratch
-        }]);
-        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
+            let initial_patch = json!([{
+                "op": "replace",
+                "path": "/scratch",
+                "value": scratch
+            }]);
+            Some(LogMsg::JsonPatch(
+                serde_json::from_value(initial_patch).unwrap(),
+            ))
+        };
 
         let type_str = scratch_type.to_string();
 
         // Filter to only this scratch's events by matching id and payload.type in the patch value
-        let filtered_stream =
-            BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
-                let id_str = scratch_id.to_string();
-                let type_str = type_str.clone();
-                async move {
-                    match msg_result {
-                        Ok(LogMsg::JsonPatch(patch)) => {
-                            if let Some(op) = patch.0.first()
-                                && op.path() == "/scratch"
-                            {
-                                // Extract id and payload.type from the patch value
-                                let value = match op {
-                                    json_patch::PatchOperation::Add(a) => Some(&a.value),
-                                    json_patch::PatchOperation::Replace(r) => Some(&r.value),
-                                    json_patch::PatchOperation::Remove(_) => None,
-                                    _ => None,
-                                };
-
-                                let matches = value.is_some_and(|v| {
-                                    let id_matches =
-                                        v.get("id").and_then(|v| v.as_str()) == Some(&id_str);
-                                    let type_matches = v
-                                        .get("payload")
-                                        .and_then(|p| p.get("type"))
-                                        .and_then(|t| t.as_str())
-                                        == Some(&type_str);
-                                    id_matches && type_matches
-                                });
-
-                                if matches {
-                                    return Some(Ok(LogMsg::JsonPatch(patch)));
-                                }
+        let stream = resumable_stream(live, initial_msg, move |msg_result| {
+            let id_str = scratch_id.to_string();
+            let type_str = type_str.clone();
+            async move {
+                match msg_result {
+                    Ok(LogMsg::JsonPatch(patch)) => {
+                        if let Some(op) = patch.0.first()
+                            && op.path() == "/scratch"
+                        {
+                            // Extract id and payload.type from the patch value
+                            let value = match op {
+                                json_patch::PatchOperation::Add(a) => Some(&a.value),
+                                json_patch::PatchOperation::Replace(r) => Some(&r.value),
+                                json_patch::PatchOperation::Remove(_) => None,
+                                _ => None,
+                            };
+
+                            let matches = value.is_some_and(|v| {
+                                let id_matches =
+                                    v.get("id").and_then(|v| v.as_str()) == Some(&id_str);
+                                let type_matches = v
+                                    .get("payload")
+                                    .and_then(|p| p.get("type"))
+                                    .and_then(|t| t.as_str())
+                                    == Some(&type_str);
+                                id_matches && type_matches
+                            });
+
+                            if matches {
+                                return Some(Ok(LogMsg::JsonPatch(patch)));
                             }
-                            None
                         }
-                        Ok(other) => Some(Ok(other)),
-                        Err(_) => None,
+                        None
                     }
+                    Ok(other) => Some(Ok(other)),
+                    Err(_) => None,
                 }
-            });
+            }
+        });
 
-        let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        let combined_stream = initial_stream.chain(filtered_stream).boxed();
-        Ok(combined_stream)
+        Ok(stream)
     }
 
     pub async fn stream_workspaces_raw(
         &self,
         archived: Option<bool>,
         limit: Option<i64>,
-    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
-    {
-        let workspaces = Workspace::find_all_with_status(&self.db.pool, archived, limit).await?;
-        let workspaces_map: serde_json::Map<String, serde_json::Value> = workspaces
-            .into_iter()
-            .map(|ws| (ws.id.to_string(), serde_json::to_value(ws).unwrap()))
-            .collect();
-
-        let initial_patch = json!([{
-            "op": "replace",
-            "path": "/workspaces",
-            "value": workspaces_map
-        }]);
-        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
-
-        let filtered_stream = BroadcastStream::new(self.msg_store.get_receiver()).filter_map(
-            move |msg_result| async move {
-                match msg_result {
-                    Ok(LogMsg::JsonPatch(patch)) => {
-                        if let Some(op) = patch.0.first()
-                            && op.path().starts_with("/workspaces")
-                        {
-                            // If archived filter is set, handle state transitions
-                            if let Some(archived_filter) = archived {
-                                // Extract workspace data from Add/Replace operations
-                                let value = match op {
-                                    json_patch::PatchOperation::Add(a) => Some(&a.value),
-                                    json_patch::PatchOperation::Replace(r) => Some(&r.value),
-                                    json_patch::PatchOperation::Remove(_) => {
-                                        // Allow remove operations through - client will handle
-                                        return Some(Ok(LogMsg::JsonPatch(patch)));
-                                    }
-                                    _ => None,
-                                };
-
-                                if let Some(v) = value
-                                    && let Some(ws_archived) =
-                                        v.get("archived").and_then(|a| a.as_bool())
-                                {
-                                    if ws_archived == archived_filter {
-                                        // Workspace matches this filter
-                                        // Convert Replace to Add since workspace may be new to this filtered stream
-                                        if let json_patch::PatchOperation::Replace(r) = op {
-                                            let add_patch = json_patch::Patch(vec![
-                                                json_patch::PatchOperation::Add(
-                                                    json_patch::AddOperation {
-                                                        path: r.path.clone(),
-                                                        value: r.value.clone(),
-                                                    },
-                                                ),
-                                            ]);
-                                            return Some(Ok(LogMsg::JsonPatch(add_patch)));
-                                        }
-                                        return Some(Ok(LogMsg::JsonPatch(patch)));
-                                    } else {
-                                        // Workspace no longer matches this filter - send remove
-                                        let remove_patch = json_patch::Patch(vec![
-                                            json_patch::PatchOperation::Remove(
-                                                json_patch::RemoveOperation {
-                                                    path: op
-                                                        .path()
-                                                        .to_string()
-                                                        .try_into()
-                                                        .expect("Workspace path should be valid"),
+        after: Option<&LogCursor>,
+    ) -> Result<ResumableLogStream, EventError> {
+        let live = self.live_events(after);
+        let initial_msg = if live.resumed {
+            None
+        } else {
+            let workspaces =
+                Workspace::find_all_with_status(&self.db.pool, archived, limit).await?;
+            let workspaces_map: serde_json::Map<String, serde_json::Value> = workspaces
+                .into_iter()
+                .map(|ws| (ws.id.to_string(), serde_json::to_value(ws).unwrap()))
+                .collect();
+
+            let initial_patch = json!([{
+                "op": "replace",
+                "path": "/workspaces",
+                "value": workspaces_map
+            }]);
+            Some(LogMsg::JsonPatch(
+                serde_json::from_value(initial_patch).unwrap(),
+            ))
+        };
+
+        let stream = resumable_stream(live, initial_msg, move |msg_result| async move {
+            match msg_result {
+                Ok(LogMsg::JsonPatch(patch)) => {
+                    if let Some(op) = patch.0.first()
+                        && op.path().starts_with("/workspaces")
+                    {
+                        // If archived filter is set, handle state transitions
+                        if let Some(archived_filter) = archived {
+                            // Extract workspace data from Add/Replace operations
+                            let value = match op {
+                                json_patch::PatchOperation::Add(a) => Some(&a.value),
+                                json_patch::PatchOperation::Replace(r) => Some(&r.value),
+                                json_patch::PatchOperation::Remove(_) => {
+                                    // Allow remove operations through - client will handle
+                                    return Some(Ok(LogMsg::JsonPatch(patch)));
+                                }
+                                _ => None,
+                            };
+
+                            if let Some(v) = value
+                                && let Some(ws_archived) =
+                                    v.get("archived").and_then(|a| a.as_bool())
+                            {
+                                if ws_archived == archived_filter {
+                                    // Workspace matches this filter
+                                    // Convert Replace to Add since workspace may be new to this filtered stream
+                                    if let json_patch::PatchOperation::Replace(r) = op {
+                                        let add_patch = json_patch::Patch(vec![
+                                            json_patch::PatchOperation::Add(
+                                                json_patch::AddOperation {
+                                                    path: r.path.clone(),
+                                                    value: r.value.clone(),
                                                 },
                                             ),
                                         ]);
-                                        return Some(Ok(LogMsg::JsonPatch(remove_patch)));
+                                        return Some(Ok(LogMsg::JsonPatch(add_patch)));
                                     }
+                                    return Some(Ok(LogMsg::JsonPatch(patch)));
+                                } else {
+                                    // Workspace no longer matches this filter - send remove
+                                    let remove_patch = json_patch::Patch(vec![
+                                        json_patch::PatchOperation::Remove(
+                                            json_patch::RemoveOperation {
+                                                path: op
+                                                    .path()
+                                                    .to_string()
+                                                    .try_into()
+                                                    .expect("Workspace path should be valid"),
+                                            },
+                                        ),
+                                    ]);
+                                    return Some(Ok(LogMsg::JsonPatch(remove_patch)));
                                 }
                             }
-                            return Some(Ok(LogMsg::JsonPatch(patch)));
                         }
-                        None
+                        return Some(Ok(LogMsg::JsonPatch(patch)));
                     }
-                    Ok(other) => Some(Ok(other)),
-                    Err(_) => None,
+                    None
                 }
-            },
-        );
+                Ok(other) => Some(Ok(other)),
+                Err(_) => None,
+            }
+        });
 
-        let initial_stream = futures::stream::iter(vec![Ok(initial_msg), Ok(LogMsg::Ready)]);
-        Ok(initial_stream.chain(filtered_stream).boxed())
+        Ok(stream)
     }
 }
@@ -8,8 +8,20 @@ use sqlx::Error as SqlxError;
 use strum_macros::{Display, EnumString};
 use thiserror::Error;
 use ts_rs::TS;
+use utils::{log_msg::LogMsg, msg_store::LogCursor};
 use uuid::Uuid;
 
+/// A message of a snapshot stream, with the cursor to resume right after it
+/// when it has one
+#[derive(Debug, Clone)]
+pub struct ResumableLogMsg {
+    pub cursor: Option<LogCursor>,
+    pub msg: LogMsg,
+}
+
+pub type ResumableLogStream =
+    futures::stream::BoxStream<'static, Result<ResumableLogMsg, std::io::Error>>;
+
 #[derive(Debug, Error)]
 pub enum EventError {
     #[error(transparent)]
@@ -26,6 +26,20 @@ pub struct AzureRepoInfo {
     pub repo_id: String,
 }
 
+/// Pull request details needed to check out and review its changes
+#[derive(Debug, Clone)]
+pub struct AzurePrDetails {
+    pub title: String,
+    pub description: String,
+    pub project: String,
+    pub repo_name: String,
+    pub remote_url: String,
+    pub source_ref_name: String,
+    pub target_ref_name: String,
+    pub source_commit: String,
+    pub target_commit: String,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AzPrResponse {
@@ -34,12 +48,21 @@ struct AzPrResponse {
     closed_date: Option<String>,
     repository: Option<AzRepository>,
     last_merge_commit: Option<AzCommit>,
+    title: Option<String>,
+    description: Option<String>,
+    source_ref_name: Option<String>,
+    target_ref_name: Option<String>,
+    last_merge_source_commit: Option<AzCommit>,
+    last_merge_target_commit: Option<AzCommit>,
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct AzRepository {
     web_url: Option<String>,
+    name: Option<String>,
+    remote_url: Option<String>,
+    project: Option<AzRepoProject>,
 }
 
 #[derive(Deserialize)]
@@ -309,13 +332,27 @@ impl AzCli {
     }
 
     pub fn view_pr(&self, pr_url: &str) -> Result<PullRequestInfo, AzCliError> {
+        let raw = self.show_pr(pr_url)?;
+        Self::parse_pr_response(&raw)
+    }
+
+    /// Fetch the title, refs and commits of a PR, e.g. to check it out for review.
+    pub fn view_pr_details(&self, pr_url: &str) -> Result<AzurePrDetails, AzCliError> {
+        let raw = self.show_pr(pr_url)?;
+        let pr: AzPrResponse = serde_json::from_str(raw.trim()).map_err(|e| {
+            AzCliError::UnexpectedOutput(format!("Failed to parse PR response: {e}; raw: {raw}"))
+        })?;
+        Self::az_pr_to_details(pr)
+    }
+
+    fn show_pr(&self, pr_url: &str) -> Result<String, AzCliError> {
         let (organization, pr_id) = Self::parse_pr_url(pr_url).ok_or_else(|| {
             AzCliError::UnexpectedOutput(format!("Could not parse Azure DevOps PR URL: {pr_url}"))
         })?;
 
         let org_url = format!("https://dev.azure.com/{}", organization);
 
-        let raw = self.run(
+        self.run(
             [
                 "repos",
                 "pr",
@@ -328,9 +365,7 @@ impl AzCli {
                 "json",
             ],
             None,
-        )?;
-
-        Self::parse_pr_response(&raw)
+        )
     }
 
     pub fn list_prs_for_branch(
@@ -474,6 +509,35 @@ impl AzCli {
         }
     }
 
+    fn az_pr_to_details(pr: AzPrResponse) -> Result<AzurePrDetails, AzCliError> {
+        let missing =
+            |field: &str| AzCliError::UnexpectedOutput(format!("PR response has no {field}"));
+        let repository = pr.repository.ok_or_else(|| missing("repository"))?;
+
+        Ok(AzurePrDetails {
+            title: pr.title.ok_or_else(|| missing("title"))?,
+            description: pr.description.unwrap_or_default(),
+            project: repository
+                .project
+                .map(|project| project.name)
+                .ok_or_else(|| missing("repository.project"))?,
+            repo_name: repository.name.ok_or_else(|| missing("repository.name"))?,
+            remote_url: repository
+                .remote_url
+                .ok_or_else(|| missing("repository.remoteUrl"))?,
+            source_ref_name: pr.source_ref_name.ok_or_else(|| missing("sourceRefName"))?,
+            target_ref_name: pr.target_ref_name.ok_or_else(|| missing("targetRefName"))?,
+            source_commit: pr
+                .last_merge_source_commit
+                .and_then(|c| c.commit_id)
+                .ok_or_else(|| missing("lastMergeSourceCommit"))?,
+            target_commit: pr
+                .last_merge_target_commit
+                .and_then(|c| c.commit_id)
+                .ok_or_else(|| missing("lastMergeTargetCommit"))?,
+        })
+    }
+
     fn parse_pr_threads(raw: &str) -> Result<Vec<UnifiedPrComment>, AzCliError> {
         // REST API returns { "value": [...threads...] } wrapper
         let response: AzThreadsResponse = serde_json::from_str(raw.trim()).map_err(|e| {
@@ -609,6 +673,36 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_az_pr_to_details() {
+        let pr: AzPrResponse = serde_json::from_str(
+            r#"{
+                "pullRequestId": 7,
+                "title": "Add login",
+                "description": null,
+                "sourceRefName": "refs/heads/feature/login",
+                "targetRefName": "refs/heads/main",
+                "lastMergeSourceCommit": { "commitId": "abc123" },
+                "lastMergeTargetCommit": { "commitId": "def456" },
+                "repository": {
+                    "name": "myrepo",
+                    "remoteUrl": "https://myorg@dev.azure.com/myorg/myproject/_git/myrepo",
+                    "project": { "id": "p1", "name": "myproject" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let details = AzCli::az_pr_to_details(pr).unwrap();
+        assert_eq!(details.title, "Add login");
+        assert_eq!(details.description, "");
+        assert_eq!(details.project, "myproject");
+        assert_eq!(details.repo_name, "myrepo");
+        assert_eq!(details.source_ref_name, "refs/heads/feature/login");
+        assert_eq!(details.source_commit, "abc123");
+        assert_eq!(details.target_commit, "def456");
+    }
+
     #[test]
     fn test_urls_match() {
         // Exact match
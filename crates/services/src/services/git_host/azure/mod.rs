@@ -6,8 +6,8 @@ use std::{path::Path, time::Duration};
 
 use async_trait::async_trait;
 use backon::{ExponentialBuilder, Retryable};
-pub use cli::AzCli;
-use cli::{AzCliError, AzureRepoInfo};
+use cli::AzureRepoInfo;
+pub use cli::{AzCli, AzCliError, AzurePrDetails};
 use db::models::merge::PullRequestInfo;
 use tokio::task;
 use tracing::info;
@@ -0,0 +1,538 @@
+//! Starts workspaces for remote issues once they are ready to be worked on.
+//!
+//! An issue is ready when it sits in the scheduler's ready status and nothing
+//! it depends on is still open: no unresolved issue blocks it and none of its
+//! sub-issues are unresolved. Ready issues are started in priority order, up
+//! to the scheduler's concurrency limit, through the same create-and-start
+//! path as the `start_workspace_session` MCP tool.
+//!
+//! Issues created from an auto-start template carry their own executor
+//! profile in `extension_metadata` and are ready regardless of status, and
+//! even while the scheduler is disabled: the scheduler only supplies the local
+//! project, repos and concurrency limit for them.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use api_types::{
+    AUTO_START_METADATA_KEY, CreateWorkspaceRequest, Issue, IssueDependencyEdge, IssuePriority,
+};
+use db::{
+    DBService,
+    models::{
+        issue_scheduler::{IssueScheduler, IssueSchedulerRun},
+        repo::Repo,
+        task::{CreateTask, Task},
+    },
+};
+use executors::profile::ExecutorProfileId;
+use git::GitServiceError;
+use sqlx::error::Error as SqlxError;
+use thiserror::Error;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+use crate::services::{
+    container::{ContainerError, ContainerService},
+    remote_client::{RemoteClient, RemoteClientError},
+};
+
+#[derive(Debug, Error)]
+enum IssueSchedulerError {
+    #[error(transparent)]
+    RemoteClient(#[from] RemoteClientError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error(transparent)]
+    Sqlx(#[from] SqlxError),
+}
+
+/// Service that polls schedulers and starts workspaces for ready issues. A blocker's PR merging is picked up on the next poll, after the PR
+/// monitor has recorded it.
+pub struct IssueSchedulerService<C: ContainerService> {
+    db: DBService,
+    poll_interval: Duration,
+    container: C,
+    remote_client: RemoteClient,
+}
+
+impl<C: ContainerService + Send + Sync + 'static> IssueSchedulerService<C> {
+    pub async fn spawn(
+        db: DBService,
+        container: C,
+        remote_client: RemoteClient,
+    ) -> tokio::task::JoinHandle<()> {
+        let service = Self {
+            db,
+            poll_interval: Duration::from_secs(60),
+            container,
+            remote_client,
+        };
+        tokio::spawn(async move {
+            service.start().await;
+        })
+    }
+
+    async fn start(&self) {
+        info!(
+            "Starting issue scheduler service with interval {:?}",
+            self.poll_interval
+        );
+
+        let mut interval = interval(self.poll_interval);
+
+        loop {
+            interval.tick().await;
+            let schedulers = match IssueScheduler::find_all(&self.db.pool).await {
+                Ok(schedulers) => schedulers,
+                Err(e) => {
+                    error!("Error loading issue schedulers: {}", e);
+                    continue;
+                }
+            };
+            for scheduler in schedulers {
+                if let Err(e) = self.schedule(&scheduler).await {
+                    error!(
+                        "Error scheduling issues for remote project {}: {}",
+                        scheduler.remote_project_id, e
+                    );
+                }
+            }
+        }
+    }
+
+    async fn schedule(&self, scheduler: &IssueScheduler) -> Result<(), IssueSchedulerError> {
+        let pool = &self.db.pool;
+
+        let status_names: HashMap<Uuid, String> = self
+            .remote_client
+            .list_project_statuses(scheduler.remote_project_id)
+            .await?
+            .project_statuses
+            .into_iter()
+            .map(|status| (status.id, status.name))
+            .collect();
+        let issues = self
+            .remote_client
+            .list_issues(scheduler.remote_project_id)
+            .await?
+            .issues;
+        let runs = IssueSchedulerRun::find_states_by_scheduler_id(pool, scheduler.id).await?;
+
+        // Issues whose scheduled workspace has been merged count as resolved
+        // even before anyone moves them on the board.
+        let merged: HashSet<Uuid> = runs
+            .iter()
+            .filter(|run| run.merged)
+            .map(|run| run.issue_id)
+            .collect();
+        let resolved = resolved_issues(scheduler, &issues, &status_names, &merged);
+
+        let active = runs
+            .iter()
+            .filter(|run| run.live && !resolved.contains(&run.issue_id))
+            .count() as i64;
+        let slots = (scheduler.max_concurrent - active).max(0) as usize;
+        if slots == 0 {
+            debug!(
+                "Issue scheduler for remote project {} is at capacity ({} active)",
+                scheduler.remote_project_id, active
+            );
+            return Ok(());
+        }
+
+        let scheduled: HashSet<Uuid> = runs.iter().map(|run| run.issue_id).collect();
+        let edges = self
+            .remote_client
+            .get_dependency_graph(scheduler.remote_project_id)
+            .await?
+            .edges;
+        let candidates = ready_issues(
+            scheduler,
+            &issues,
+            &status_names,
+            &resolved,
+            &scheduled,
+            &edges,
+        );
+
+        for issue in candidates.into_iter().take(slots) {
+            if let Err(e) = self.start_issue(scheduler, issue).await {
+                error!(
+                    "Failed to start workspace for issue {}: {}",
+                    issue.simple_id, e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn start_issue(
+        &self,
+        scheduler: &IssueScheduler,
+        issue: &Issue,
+    ) -> Result<(), IssueSchedulerError> {
+        let pool = &self.db.pool;
+
+        // Base on the freshly fetched target branch so dependents include the
+        // work of blockers that merged since the branch was last pulled.
+        let mut repos = Vec::with_capacity(scheduler.repos.len());
+        for scheduler_repo in scheduler.repos.iter() {
+            let repo = Repo::find_by_id(pool, scheduler_repo.repo_id)
+                .await?
+                .ok_or(SqlxError::RowNotFound)?;
+            let git = self.container.git().clone();
+            let path = repo.path.clone();
+            let branch = scheduler_repo.target_branch.clone();
+            let refreshed =
+                tokio::task::spawn_blocking(move || git.refresh_base_branch(&path, &branch))
+                    .await
+                    .map_err(|e| GitServiceError::IoError(std::io::Error::other(e)))
+                    .and_then(|result| result);
+            let target_branch = match refreshed {
+                Ok(branch) => branch,
+                Err(e) => {
+                    warn!(
+                        "Failed to refresh {} in {}, using local branch: {}",
+                        scheduler_repo.target_branch, repo.name, e
+                    );
+                    scheduler_repo.target_branch.clone()
+                }
+            };
+            repos.push((repo, target_branch));
+        }
+
+        let task = Task::create(
+            pool,
+            &CreateTask::from_title_description(
+                scheduler.project_id,
+                issue.title.clone(),
+                issue.description.clone(),
+            ),
+            Uuid::new_v4(),
+        )
+        .await?;
+        let executor_profile_id =
+            auto_start_profile(issue).unwrap_or_else(|| scheduler.executor_profile_id.0.clone());
+        let workspace = match self
+            .container
+            .create_and_start_workspace(&task, &repos, executor_profile_id)
+            .await
+        {
+            Ok((workspace, true)) => workspace,
+            Ok((workspace, false)) => {
+                // The start failure is logged by the container; leave the
+                // issue unscheduled so the next poll retries it
+                if let Err(e) = self.container.delete(&workspace).await {
+                    warn!(
+                        "Failed to clean up workspace {} for issue {}: {}",
+                        workspace.id, issue.simple_id, e
+                    );
+                }
+                self.discard_task(&task, issue).await;
+                return Ok(());
+            }
+            Err(e) => {
+                self.discard_task(&task, issue).await;
+                return Err(e.into());
+            }
+        };
+        IssueSchedulerRun::create(pool, scheduler.id, issue.id, workspace.id).await?;
+
+        info!(
+            "Scheduled issue {} into workspace {}",
+            issue.simple_id, workspace.id
+        );
+
+        if let Err(e) = self
+            .remote_client
+            .create_workspace(CreateWorkspaceRequest {
+                project_id: scheduler.remote_project_id,
+                local_workspace_id: workspace.id,
+                issue_id: issue.id,
+                name: workspace.name.clone(),
+                archived: Some(workspace.archived),
+                files_changed: None,
+                lines_added: None,
+                lines_removed: None,
+            })
+            .await
+        {
+            warn!(
+                "Failed to link workspace {} to issue {}: {}",
+                workspace.id, issue.simple_id, e
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Don't leave a task behind for an issue that will be retried
+    async fn discard_task(&self, task: &Task, issue: &Issue) {
+        if let Err(e) = Task::delete(&self.db.pool, task.id).await {
+            warn!(
+                "Failed to clean up task {} for issue {}: {}",
+                task.id, issue.simple_id, e
+            );
+        }
+    }
+}
+
+/// Executor profile an auto-start template stamped on the issue, if any.
+fn auto_start_profile(issue: &Issue) -> Option<ExecutorProfileId> {
+    let profile = issue.extension_metadata.get(AUTO_START_METADATA_KEY)?;
+    match serde_json::from_value(profile.clone()) {
+        Ok(profile) => Some(profile),
+        Err(e) => {
+            warn!(
+                "Ignoring invalid auto-start profile on issue {}: {}",
+                issue.simple_id, e
+            );
+            None
+        }
+    }
+}
+
+/// Issues that no longer hold anything up: completed, merged through their
+/// scheduled workspace, or in one of the scheduler's done statuses.
+fn resolved_issues(
+    scheduler: &IssueScheduler,
+    issues: &[Issue],
+    status_names: &HashMap<Uuid, String>,
+    merged: &HashSet<Uuid>,
+) -> HashSet<Uuid> {
+    issues
+        .iter()
+        .filter(|issue| {
+            issue.completed_at.is_some()
+                || merged.contains(&issue.id)
+                || status_names.get(&issue.status_id).is_some_and(|name| {
+                    scheduler
+                        .done_statuses
+                        .iter()
+                        .any(|done| done.eq_ignore_ascii_case(name))
+                })
+        })
+        .map(|issue| issue.id)
+        .collect()
+}
+
+/// Unscheduled issues that are ready and not held up by an unresolved blocker
+/// or sub-issue, in the order they should be started.
+fn ready_issues<'a>(
+    scheduler: &IssueScheduler,
+    issues: &'a [Issue],
+    status_names: &HashMap<Uuid, String>,
+    resolved: &HashSet<Uuid>,
+    scheduled: &HashSet<Uuid>,
+    edges: &[IssueDependencyEdge],
+) -> Vec<&'a Issue> {
+    let open_parents: HashSet<Uuid> = issues
+        .iter()
+        .filter(|issue| !resolved.contains(&issue.id))
+        .filter_map(|issue| issue.parent_issue_id)
+        .collect();
+    let blocked: HashSet<Uuid> = edges
+        .iter()
+        .filter(|edge| !resolved.contains(&edge.blocking_issue_id))
+        .map(|edge| edge.blocked_issue_id)
+        .collect();
+
+    let mut candidates: Vec<&Issue> = issues
+        .iter()
+        .filter(|issue| !resolved.contains(&issue.id) && !scheduled.contains(&issue.id))
+        .filter(|issue| !open_parents.contains(&issue.id) && !blocked.contains(&issue.id))
+        .filter(|issue| {
+            auto_start_profile(issue).is_some()
+                || (scheduler.enabled
+                    && status_names
+                        .get(&issue.status_id)
+                        .is_some_and(|name| name.eq_ignore_ascii_case(&scheduler.ready_status)))
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        priority_rank(a.priority)
+            .cmp(&priority_rank(b.priority))
+            .then(a.sort_order.total_cmp(&b.sort_order))
+    });
+    candidates
+}
+
+fn priority_rank(priority: Option<IssuePriority>) -> u8 {
+    match priority {
+        Some(IssuePriority::Urgent) => 0,
+        Some(IssuePriority::High) => 1,
+        Some(IssuePriority::Medium) => 2,
+        Some(IssuePriority::Low) => 3,
+        None => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api_types::EstimateUnit;
+    use chrono::Utc;
+    use executors::executors::BaseCodingAgent;
+    use sqlx::types::Json;
+
+    use super::*;
+
+    const READY: Uuid = Uuid::from_u128(1);
+    const DONE: Uuid = Uuid::from_u128(2);
+    const BACKLOG: Uuid = Uuid::from_u128(3);
+
+    fn scheduler() -> IssueScheduler {
+        IssueScheduler {
+            id: Uuid::new_v4(),
+            remote_project_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            enabled: true,
+            ready_status: "Ready".to_string(),
+            done_statuses: Json(vec!["Done".to_string()]),
+            executor_profile_id: Json(ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)),
+            repos: Json(Vec::new()),
+            max_concurrent: 2,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn status_names() -> HashMap<Uuid, String> {
+        HashMap::from([
+            (READY, "Ready".to_string()),
+            (DONE, "done".to_string()),
+            (BACKLOG, "Backlog".to_string()),
+        ])
+    }
+
+    fn issue(n: u128, status_id: Uuid) -> Issue {
+        Issue {
+            id: Uuid::from_u128(100 + n),
+            project_id: Uuid::nil(),
+            issue_number: n as i32,
+            simple_id: format!("VK-{n}"),
+            status_id,
+            title: format!("Issue {n}"),
+            description: None,
+            priority: None,
+            start_date: None,
+            target_date: None,
+            estimate: None,
+            estimate_unit: EstimateUnit::default(),
+            completed_at: None,
+            sort_order: n as f64,
+            parent_issue_id: None,
+            parent_issue_sort_order: None,
+            extension_metadata: serde_json::json!({}),
+            creator_user_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn edge(blocking: &Issue, blocked: &Issue) -> IssueDependencyEdge {
+        IssueDependencyEdge {
+            blocking_issue_id: blocking.id,
+            blocked_issue_id: blocked.id,
+        }
+    }
+
+    fn ready_ids(issues: &[Issue], scheduled: &[Uuid], edges: &[IssueDependencyEdge]) -> Vec<Uuid> {
+        let scheduler = scheduler();
+        let resolved = resolved_issues(&scheduler, issues, &status_names(), &HashSet::new());
+        ready_issues(
+            &scheduler,
+            issues,
+            &status_names(),
+            &resolved,
+            &scheduled.iter().copied().collect(),
+            edges,
+        )
+        .into_iter()
+        .map(|issue| issue.id)
+        .collect()
+    }
+
+    #[test]
+    fn only_unblocked_ready_issues_are_started() {
+        let open_blocker = issue(1, BACKLOG);
+        let done_blocker = issue(2, DONE);
+        let blocked = issue(3, READY);
+        let unblocked = issue(4, READY);
+        let not_ready = issue(5, BACKLOG);
+        let edges = [
+            edge(&open_blocker, &blocked),
+            edge(&done_blocker, &unblocked),
+        ];
+        let issues = [open_blocker, done_blocker, blocked, unblocked, not_ready];
+
+        assert_eq!(ready_ids(&issues, &[], &edges), vec![issues[3].id]);
+    }
+
+    #[test]
+    fn parents_wait_for_their_sub_issues() {
+        let parent = issue(1, READY);
+        let mut open_child = issue(2, BACKLOG);
+        open_child.parent_issue_id = Some(parent.id);
+        let issues = [parent, open_child];
+        assert!(ready_ids(&issues, &[], &[]).is_empty());
+
+        let mut issues = issues;
+        issues[1].completed_at = Some(Utc::now());
+        assert_eq!(ready_ids(&issues, &[], &[]), vec![issues[0].id]);
+    }
+
+    #[test]
+    fn scheduled_issues_are_skipped_and_the_rest_ordered_by_priority() {
+        let mut low = issue(1, READY);
+        low.priority = Some(IssuePriority::Low);
+        let mut urgent = issue(2, READY);
+        urgent.priority = Some(IssuePriority::Urgent);
+        let unprioritized = issue(3, READY);
+        let already_scheduled = issue(4, READY);
+        let scheduled = [already_scheduled.id];
+        let issues = [low, urgent, unprioritized, already_scheduled];
+
+        assert_eq!(
+            ready_ids(&issues, &scheduled, &[]),
+            vec![issues[1].id, issues[0].id, issues[2].id]
+        );
+    }
+
+    #[test]
+    fn disabled_schedulers_only_start_auto_start_issues() {
+        let ready = issue(1, READY);
+        let mut auto_start = issue(2, BACKLOG);
+        auto_start.extension_metadata = serde_json::json!({
+            AUTO_START_METADATA_KEY: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+        });
+        let issues = [ready, auto_start];
+
+        let mut scheduler = scheduler();
+        scheduler.enabled = false;
+        let ready = ready_issues(
+            &scheduler,
+            &issues,
+            &status_names(),
+            &HashSet::new(),
+            &HashSet::new(),
+            &[],
+        );
+        assert_eq!(
+            ready.into_iter().map(|issue| issue.id).collect::<Vec<_>>(),
+            vec![issues[1].id]
+        );
+    }
+
+    #[test]
+    fn merged_issues_count_as_resolved() {
+        let issues = [issue(1, READY), issue(2, READY)];
+        let merged = HashSet::from([issues[0].id]);
+        let resolved = resolved_issues(&scheduler(), &issues, &status_names(), &merged);
+        assert_eq!(resolved, HashSet::from([issues[0].id]));
+    }
+}
@@ -11,16 +11,19 @@ pub mod filesystem;
 pub mod filesystem_watcher;
 pub mod git_host;
 pub mod image;
+pub mod issue_scheduler;
 pub mod migration;
 pub mod notification;
 pub mod oauth_credentials;
 pub mod pr_monitor;
 pub mod project;
+pub mod project_analytics;
 #[cfg(feature = "qa-mode")]
 pub mod qa_repos;
 pub mod queued_message;
 pub mod remote_client;
 pub mod remote_sync;
 pub mod repo;
+pub mod secrets;
 pub mod workspace_manager;
 pub mod worktree_manager;
@@ -0,0 +1,283 @@
+//! Project analytics: how issues move from creation through workspaces to
+//! merged pull requests, and how each coding agent fares, week by week.
+//!
+//! Issue, workspace and pull request timestamps come from the remote server;
+//! executors, follow-up turns, local merges and agent runtimes come from the
+//! local database. Only workspaces known to both are counted.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    str::FromStr,
+};
+
+use api_types::IssueDeliveryTimeline;
+use chrono::{DateTime, Datelike, Duration, Utc};
+use db::models::analytics::{AgentRun, WorkspaceOutcome};
+use executors::executors::BaseCodingAgent;
+use serde::Serialize;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ProjectAnalytics {
+    pub weeks: Vec<AgentWeekAnalytics>,
+}
+
+/// Workspaces started in one week with one coding agent. Durations are in
+/// seconds.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AgentWeekAnalytics {
+    /// Monday 00:00 UTC of the week the workspaces were created in.
+    pub week_start: DateTime<Utc>,
+    /// `None` when the workspace's executor isn't a known coding agent.
+    pub executor: Option<BaseCodingAgent>,
+    pub workspaces: u32,
+    pub merged: u32,
+    pub archived_without_merge: u32,
+    /// Share of workspaces archived without being merged.
+    pub archived_without_merge_rate: f64,
+    /// From issue creation to the issue's first workspace.
+    pub median_issue_to_workspace_secs: Option<i64>,
+    pub median_workspace_to_pull_request_secs: Option<i64>,
+    pub median_pull_request_to_merge_secs: Option<i64>,
+    /// From issue creation to merge.
+    pub median_cycle_time_secs: Option<i64>,
+    /// Coding agent runs after the first one, per merged workspace.
+    pub mean_follow_ups_per_merged: Option<f64>,
+    pub median_agent_runtime_secs: Option<i64>,
+}
+
+#[derive(Default)]
+struct Bucket {
+    workspaces: u32,
+    merged: u32,
+    archived_without_merge: u32,
+    issue_to_workspace: Vec<i64>,
+    workspace_to_pull_request: Vec<i64>,
+    pull_request_to_merge: Vec<i64>,
+    cycle_time: Vec<i64>,
+    follow_ups: Vec<i64>,
+    agent_runtime: Vec<i64>,
+}
+
+/// Combine remote delivery timelines with local workspace outcomes and
+/// agent runs. Timelines must be ordered by workspace creation.
+pub fn summarize(
+    timelines: &[IssueDeliveryTimeline],
+    outcomes: &[WorkspaceOutcome],
+    runs: &[AgentRun],
+) -> ProjectAnalytics {
+    let outcomes: HashMap<Uuid, &WorkspaceOutcome> = outcomes.iter().map(|o| (o.id, o)).collect();
+    let mut runtimes: HashMap<Uuid, Vec<i64>> = HashMap::new();
+    for run in runs {
+        runtimes
+            .entry(run.workspace_id)
+            .or_default()
+            .push(seconds_between(run.started_at, run.completed_at));
+    }
+
+    let mut buckets: BTreeMap<(DateTime<Utc>, Option<String>), (Option<BaseCodingAgent>, Bucket)> =
+        BTreeMap::new();
+    let mut issues_started = HashSet::new();
+
+    for timeline in timelines {
+        let Some(local_id) = timeline.local_workspace_id else {
+            continue;
+        };
+        let Some(outcome) = outcomes.get(&local_id) else {
+            continue;
+        };
+
+        let executor = outcome.executor.as_deref().and_then(parse_executor);
+        let key = (
+            week_start(timeline.workspace_created_at),
+            executor.map(|executor| executor.to_string()),
+        );
+        let (_, bucket) = buckets
+            .entry(key)
+            .or_insert_with(|| (executor, Bucket::default()));
+
+        bucket.workspaces += 1;
+        if outcome.merged || timeline.pull_request_merged_at.is_some() {
+            bucket.merged += 1;
+            bucket
+                .follow_ups
+                .push((outcome.coding_agent_runs - 1).max(0));
+        } else if outcome.archived {
+            bucket.archived_without_merge += 1;
+        }
+
+        if issues_started.insert(timeline.issue_id) {
+            bucket.issue_to_workspace.push(seconds_between(
+                timeline.issue_created_at,
+                timeline.workspace_created_at,
+            ));
+        }
+        if let Some(opened_at) = timeline.pull_request_opened_at {
+            bucket
+                .workspace_to_pull_request
+                .push(seconds_between(timeline.workspace_created_at, opened_at));
+            if let Some(merged_at) = timeline.pull_request_merged_at {
+                bucket
+                    .pull_request_to_merge
+                    .push(seconds_between(opened_at, merged_at));
+            }
+        }
+        if let Some(merged_at) = timeline.pull_request_merged_at {
+            bucket
+                .cycle_time
+                .push(seconds_between(timeline.issue_created_at, merged_at));
+        }
+        if let Some(runtimes) = runtimes.get(&local_id) {
+            bucket.agent_runtime.extend(runtimes);
+        }
+    }
+
+    let weeks = buckets
+        .into_iter()
+        .map(
+            |((week_start, _), (executor, mut bucket))| AgentWeekAnalytics {
+                week_start,
+                executor,
+                workspaces: bucket.workspaces,
+                merged: bucket.merged,
+                archived_without_merge: bucket.archived_without_merge,
+                archived_without_merge_rate: f64::from(bucket.archived_without_merge)
+                    / f64::from(bucket.workspaces),
+                median_issue_to_workspace_secs: median(&mut bucket.issue_to_workspace),
+                median_workspace_to_pull_request_secs: median(
+                    &mut bucket.workspace_to_pull_request,
+                ),
+                median_pull_request_to_merge_secs: median(&mut bucket.pull_request_to_merge),
+                median_cycle_time_secs: median(&mut bucket.cycle_time),
+                mean_follow_ups_per_merged: mean(&bucket.follow_ups),
+                median_agent_runtime_secs: median(&mut bucket.agent_runtime),
+            },
+        )
+        .collect();
+
+    ProjectAnalytics { weeks }
+}
+
+/// Sessions store the executor as written by `BaseCodingAgent`'s `Display`,
+/// but older rows may use kebab-case.
+fn parse_executor(executor: &str) -> Option<BaseCodingAgent> {
+    BaseCodingAgent::from_str(&executor.replace('-', "_").to_ascii_uppercase()).ok()
+}
+
+fn week_start(at: DateTime<Utc>) -> DateTime<Utc> {
+    let monday = at.date_naive() - Duration::days(i64::from(at.weekday().num_days_from_monday()));
+    monday.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+fn seconds_between(from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+    (to - from).num_seconds().max(0)
+}
+
+fn median(values: &mut [i64]) -> Option<i64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2
+    } else {
+        values[mid]
+    })
+}
+
+fn mean(values: &[i64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<i64>() as f64 / values.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, day, hour, 0, 0).unwrap()
+    }
+
+    fn outcome(id: Uuid, executor: &str, runs: i64, archived: bool) -> WorkspaceOutcome {
+        WorkspaceOutcome {
+            id,
+            created_at: at(3, 0),
+            archived,
+            executor: Some(executor.to_string()),
+            coding_agent_runs: runs,
+            merged: false,
+        }
+    }
+
+    #[test]
+    fn groups_by_week_and_agent() {
+        let issue = Uuid::new_v4();
+        let (first, second, third) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let timeline =
+            |local_id, workspace_created_at, opened_at, merged_at| IssueDeliveryTimeline {
+                issue_id: issue,
+                workspace_id: Uuid::new_v4(),
+                local_workspace_id: Some(local_id),
+                issue_created_at: at(2, 0),
+                workspace_created_at,
+                pull_request_opened_at: opened_at,
+                pull_request_merged_at: merged_at,
+            };
+
+        // 2026-03-02 is a Monday.
+        let timelines = vec![
+            timeline(first, at(3, 0), None, None),
+            timeline(second, at(4, 0), Some(at(4, 2)), Some(at(5, 2))),
+            timeline(third, at(10, 0), None, None),
+        ];
+        let outcomes = vec![
+            outcome(first, "CLAUDE_CODE", 1, true),
+            outcome(second, "claude-code", 3, false),
+            outcome(third, "CODEX", 1, false),
+        ];
+        let runs = vec![
+            AgentRun {
+                workspace_id: second,
+                started_at: at(4, 0),
+                completed_at: at(4, 1),
+            },
+            AgentRun {
+                workspace_id: first,
+                started_at: at(3, 0),
+                completed_at: at(3, 3),
+            },
+        ];
+
+        let analytics = summarize(&timelines, &outcomes, &runs);
+        assert_eq!(analytics.weeks.len(), 2);
+
+        let claude = &analytics.weeks[0];
+        assert_eq!(claude.week_start, at(2, 0));
+        assert_eq!(claude.executor, Some(BaseCodingAgent::ClaudeCode));
+        assert_eq!(claude.workspaces, 2);
+        assert_eq!(claude.merged, 1);
+        assert_eq!(claude.archived_without_merge, 1);
+        assert_eq!(claude.archived_without_merge_rate, 0.5);
+        // Only the issue's first workspace counts towards time to start.
+        assert_eq!(claude.median_issue_to_workspace_secs, Some(24 * 3600));
+        assert_eq!(claude.median_workspace_to_pull_request_secs, Some(2 * 3600));
+        assert_eq!(claude.median_pull_request_to_merge_secs, Some(24 * 3600));
+        assert_eq!(
+            claude.median_cycle_time_secs,
+            Some(3 * 24 * 3600 + 2 * 3600)
+        );
+        assert_eq!(claude.mean_follow_ups_per_merged, Some(2.0));
+        assert_eq!(claude.median_agent_runtime_secs, Some(2 * 3600));
+
+        let codex = &analytics.weeks[1];
+        assert_eq!(codex.week_start, at(9, 0));
+        assert_eq!(codex.executor, Some(BaseCodingAgent::Codex));
+        assert_eq!(codex.median_issue_to_workspace_secs, None);
+    }
+}
@@ -4,14 +4,17 @@ use std::time::Duration;
 
 use api_types::{
     AcceptInvitationResponse, CreateInvitationRequest, CreateInvitationResponse,
-    CreateIssueRequest, CreateOrganizationRequest, CreateOrganizationResponse,
-    CreateWorkspaceRequest, DeleteResponse, DeleteWorkspaceRequest, GetInvitationResponse,
-    GetOrganizationResponse, HandoffInitRequest, HandoffInitResponse, HandoffRedeemRequest,
-    HandoffRedeemResponse, Issue, ListInvitationsResponse, ListIssuesResponse, ListMembersResponse,
+    CreateIssueRequest, CreateIssueTimeEntryRequest, CreateOrganizationRequest,
+    CreateOrganizationResponse, CreateWorkspaceRequest, DeleteResponse, DeleteWorkspaceRequest,
+    GetInvitationResponse, GetOrganizationResponse, HandoffInitRequest, HandoffInitResponse,
+    HandoffRedeemRequest, HandoffRedeemResponse, Issue, IssueDependencyGraphResponse,
+    IssueTimeEntry, ListCustomFieldsResponse, ListInvitationsResponse,
+    ListIssueDeliveryTimelinesResponse, ListIssuesResponse, ListMembersResponse,
     ListOrganizationsResponse, ListProjectStatusesResponse, ListProjectsResponse, MutationResponse,
     Organization, ProfileResponse, RevokeInvitationRequest, TokenRefreshRequest,
     TokenRefreshResponse, UpdateIssueRequest, UpdateMemberRoleRequest, UpdateMemberRoleResponse,
     UpdateOrganizationRequest, UpdateWorkspaceRequest, UpsertPullRequestRequest, Workspace,
+    WorkspaceFinishedRequest,
 };
 use backon::{ExponentialBuilder, Retryable};
 use chrono::Duration as ChronoDuration;
@@ -587,6 +590,46 @@ impl RemoteClient {
         }
     }
 
+    /// Reports that a coding agent run in a linked workspace ended.
+    pub async fn report_workspace_finished(
+        &self,
+        local_workspace_id: Uuid,
+        success: bool,
+    ) -> Result<(), RemoteClientError> {
+        self.send(
+            reqwest::Method::POST,
+            "/v1/workspaces/finished",
+            true,
+            Some(&WorkspaceFinishedRequest {
+                local_workspace_id,
+                success,
+            }),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Records effort spent on an issue.
+    pub async fn create_issue_time_entry(
+        &self,
+        request: &CreateIssueTimeEntryRequest,
+    ) -> Result<MutationResponse<IssueTimeEntry>, RemoteClientError> {
+        self.post_authed("/v1/issue_time_entries", Some(request))
+            .await
+    }
+
+    /// Lists when each issue-linked workspace of a project got its pull
+    /// request opened and merged.
+    pub async fn list_delivery_timelines(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ListIssueDeliveryTimelinesResponse, RemoteClientError> {
+        self.get_authed(&format!(
+            "/v1/workspaces/delivery-timelines?project_id={project_id}"
+        ))
+        .await
+    }
+
     /// Updates a workspace on the remote server.
     pub async fn update_workspace(
         &self,
@@ -640,6 +683,18 @@ impl RemoteClient {
             .await
     }
 
+    /// Lists issues for a project matching a query in the issue query
+    /// language, e.g. `status:"In Review" assignee:me updated:<7d`.
+    pub async fn search_issues(
+        &self,
+        project_id: Uuid,
+        query: &str,
+    ) -> Result<ListIssuesResponse, RemoteClientError> {
+        let query: String = url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
+        self.get_authed(&format!("/v1/issues?project_id={project_id}&q={query}"))
+            .await
+    }
+
     /// Gets a single issue by ID.
     pub async fn get_issue(&self, issue_id: Uuid) -> Result<Issue, RemoteClientError> {
         self.get_authed(&format!("/v1/issues/{issue_id}")).await
@@ -697,6 +752,19 @@ impl RemoteClient {
             .await
     }
 
+    // ── Issue Relationships ─────────────────────────────────────────────
+
+    /// Dependency graph of a project, including every blocking edge.
+    pub async fn get_dependency_graph(
+        &self,
+        project_id: Uuid,
+    ) -> Result<IssueDependencyGraphResponse, RemoteClientError> {
+        self.get_authed(&format!(
+            "/v1/issue_relationships/graph?project_id={project_id}"
+        ))
+        .await
+    }
+
     // ── Project Statuses ────────────────────────────────────────────────
 
     /// Lists project statuses for a project (used for status name ↔ UUID mapping).
@@ -708,6 +776,19 @@ impl RemoteClient {
             .await
     }
 
+    // ── Custom Fields ───────────────────────────────────────────────────
+
+    /// Lists an organization's custom field definitions.
+    pub async fn list_custom_fields(
+        &self,
+        organization_id: Uuid,
+    ) -> Result<ListCustomFieldsResponse, RemoteClientError> {
+        self.get_authed(&format!(
+            "/v1/custom_fields?organization_id={organization_id}"
+        ))
+        .await
+    }
+
     // ── Pull Requests ───────────────────────────────────────────────────
 
     /// Upserts a pull request on the remote server.
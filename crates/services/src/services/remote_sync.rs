@@ -1,4 +1,5 @@
-use api_types::UpsertPullRequestRequest;
+use api_types::{CreateIssueTimeEntryRequest, TimeEntrySource, UpsertPullRequestRequest};
+use chrono::{DateTime, Utc};
 use tracing::{debug, error};
 use uuid::Uuid;
 
@@ -60,6 +61,143 @@ pub async fn sync_workspace_to_remote(
     }
 }
 
+/// Tells the remote server a coding agent run in the workspace ended, so
+/// watchers of the linked issue are notified. Unlinked workspaces are skipped.
+pub async fn report_workspace_finished(client: &RemoteClient, workspace_id: Uuid, success: bool) {
+    match client.workspace_exists(workspace_id).await {
+        Ok(false) => {
+            debug!(
+                "Workspace {} not found on remote, skipping finished report",
+                workspace_id
+            );
+            return;
+        }
+        Err(RemoteClientError::Auth) => {
+            debug!(
+                "Workspace {} finished report skipped: not authenticated",
+                workspace_id
+            );
+            return;
+        }
+        Err(e) => {
+            error!(
+                "Failed to check workspace {} existence on remote: {}",
+                workspace_id, e
+            );
+            return;
+        }
+        Ok(true) => {}
+    }
+
+    if let Err(e) = client
+        .report_workspace_finished(workspace_id, success)
+        .await
+    {
+        error!(
+            "Failed to report workspace {} finished to remote: {}",
+            workspace_id, e
+        );
+    }
+}
+
+/// Idle time between coding agent runs longer than this is assumed to be
+/// spent elsewhere and is not logged as follow-up work.
+const MAX_FOLLOW_UP_GAP_SECS: i64 = 30 * 60;
+
+/// Effort spent in a workspace, reported against its linked issue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceTimeEntry {
+    /// Reporting the same ID again updates the entry.
+    pub id: Uuid,
+    pub source: TimeEntrySource,
+    pub started_at: DateTime<Utc>,
+    pub duration_seconds: i64,
+}
+
+/// Time entries for a finished coding agent run: the run itself, plus the
+/// time spent reviewing the previous run and writing this follow-up, capped
+/// at [`MAX_FOLLOW_UP_GAP_SECS`]. Both IDs derive from the process ID, so
+/// reporting a run twice updates its entries instead of duplicating them.
+pub fn coding_agent_time_entries(
+    process_id: Uuid,
+    started_at: DateTime<Utc>,
+    completed_at: DateTime<Utc>,
+    prev_completed_at: Option<DateTime<Utc>>,
+) -> Vec<WorkspaceTimeEntry> {
+    let mut entries = Vec::with_capacity(2);
+    if let Some(prev_completed_at) = prev_completed_at
+        && prev_completed_at < started_at
+    {
+        let gap = (started_at - prev_completed_at).num_seconds();
+        entries.push(WorkspaceTimeEntry {
+            id: Uuid::new_v5(&process_id, b"follow-up"),
+            source: TimeEntrySource::Human,
+            started_at: prev_completed_at,
+            duration_seconds: gap.min(MAX_FOLLOW_UP_GAP_SECS),
+        });
+    }
+    entries.push(WorkspaceTimeEntry {
+        id: process_id,
+        source: TimeEntrySource::Agent,
+        started_at,
+        duration_seconds: (completed_at - started_at).num_seconds().max(0),
+    });
+    entries
+}
+
+/// Records time entries on the issue linked to a workspace. Workspaces that
+/// aren't on the remote or aren't linked to an issue are skipped.
+pub async fn report_time_entries(
+    client: &RemoteClient,
+    workspace_id: Uuid,
+    entries: Vec<WorkspaceTimeEntry>,
+) {
+    let workspace = match client.get_workspace_by_local_id(workspace_id).await {
+        Ok(workspace) => workspace,
+        Err(RemoteClientError::Http { status: 404, .. }) => {
+            debug!(
+                "Workspace {} not found on remote, skipping time entries",
+                workspace_id
+            );
+            return;
+        }
+        Err(RemoteClientError::Auth) => {
+            debug!(
+                "Workspace {} time entries skipped: not authenticated",
+                workspace_id
+            );
+            return;
+        }
+        Err(e) => {
+            error!(
+                "Failed to load workspace {} from remote: {}",
+                workspace_id, e
+            );
+            return;
+        }
+    };
+    let Some(issue_id) = workspace.issue_id else {
+        return;
+    };
+
+    for entry in entries {
+        let request = CreateIssueTimeEntryRequest {
+            id: Some(entry.id),
+            issue_id,
+            workspace_id: Some(workspace.id),
+            source: entry.source,
+            started_at: entry.started_at,
+            duration_seconds: entry.duration_seconds,
+        };
+        if let Err(e) = client.create_issue_time_entry(&request).await {
+            error!(
+                "Failed to report time entry for workspace {} to remote: {}",
+                workspace_id, e
+            );
+        }
+    }
+}
+
 /// Syncs PR data to the remote server.
 /// First checks if the workspace exists on remote, then upserts the PR if it does.
 pub async fn sync_pr_to_remote(client: &RemoteClient, request: UpsertPullRequestRequest) {
@@ -98,3 +236,35 @@ pub async fn sync_pr_to_remote(client: &RemoteClient, request: UpsertPullRequest
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+
+    #[test]
+    fn caps_follow_up_gap() {
+        let at = |minute: i64| {
+            Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap() + Duration::minutes(minute)
+        };
+        let process_id = Uuid::new_v4();
+
+        let initial = coding_agent_time_entries(process_id, at(0), at(10), None);
+        assert_eq!(initial.len(), 1);
+        assert_eq!(initial[0].id, process_id);
+        assert_eq!(initial[0].duration_seconds, 600);
+
+        let follow_up = coding_agent_time_entries(process_id, at(100), at(105), Some(at(10)));
+        assert_eq!(follow_up.len(), 2);
+        assert_eq!(follow_up[0].source, TimeEntrySource::Human);
+        assert_eq!(
+            follow_up[0].id,
+            coding_agent_time_entries(process_id, at(100), at(105), Some(at(10)))[0].id
+        );
+        assert_eq!(follow_up[0].started_at, at(10));
+        assert_eq!(follow_up[0].duration_seconds, MAX_FOLLOW_UP_GAP_SECS);
+        assert_eq!(follow_up[1].source, TimeEntrySource::Agent);
+        assert_eq!(follow_up[1].duration_seconds, 300);
+    }
+}
@@ -0,0 +1,252 @@
+//! Encrypted per-project secrets.
+//!
+//! Each value is encrypted with AES-256-GCM under a key derived with
+//! HKDF-SHA256 from a random per-value salt and the master key material. The
+//! material comes from `VK_SECRETS_KEY`, or else from the OS keyring, where it
+//! is generated on first use. Executions get a project's secrets through `${secret:NAME}`
+//! references in their environment variables.
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use db::models::project_secret::ProjectSecret;
+use ring::{
+    aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey},
+    hkdf::{HKDF_SHA256, Salt},
+    rand::{SecureRandom, SystemRandom},
+};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use uuid::Uuid;
+
+const MASTER_KEY_ENV: &str = "VK_SECRETS_KEY";
+const KEYRING_SERVICE: &str = "vibe-kanban";
+const KEYRING_USER: &str = "secrets-master-key";
+const SALT_LEN: usize = 16;
+const KEY_INFO: &[u8] = b"vibe-kanban project secret";
+
+static MASTER_KEY: OnceLock<Vec<u8>> = OnceLock::new();
+
+#[derive(Debug, Error)]
+pub enum SecretError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(
+        "No master key for project secrets ({0}). Set {MASTER_KEY_ENV} or make an OS keyring available."
+    )]
+    MasterKey(String),
+    #[error(
+        "Invalid secret name `{0}`. Use letters, digits and underscores, not starting with a digit."
+    )]
+    InvalidName(String),
+    #[error("Failed to encrypt secret `{0}`")]
+    Encrypt(String),
+    #[error("Failed to decrypt secret `{0}`. The master key may have changed; set it again.")]
+    Decrypt(String),
+}
+
+/// Encrypts and decrypts project secrets with keys derived from the master key
+pub struct SecretVault {
+    material: Vec<u8>,
+    rng: SystemRandom,
+}
+
+impl SecretVault {
+    pub async fn load() -> Result<Self, SecretError> {
+        let material = match MASTER_KEY.get() {
+            Some(material) => material.clone(),
+            None => {
+                // Keyring backends block, e.g. on D-Bus
+                let material = tokio::task::spawn_blocking(load_master_key)
+                    .await
+                    .map_err(|e| SecretError::MasterKey(e.to_string()))??;
+                MASTER_KEY.get_or_init(|| material).clone()
+            }
+        };
+        Ok(Self::with_material(material))
+    }
+
+    fn with_material(material: Vec<u8>) -> Self {
+        Self {
+            material,
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// The AES-256 key for a value encrypted with `salt`
+    fn key(&self, salt: &[u8]) -> LessSafeKey {
+        let okm = Salt::new(HKDF_SHA256, salt)
+            .extract(&self.material)
+            .expand(&[KEY_INFO], &AES_256_GCM)
+            .expect("AES-256 keys are within HKDF-SHA256's output limit");
+        LessSafeKey::new(UnboundKey::from(okm))
+    }
+
+    /// Encrypt and store a secret, replacing any previous value
+    pub async fn set(
+        &self,
+        pool: &SqlitePool,
+        project_id: Uuid,
+        name: &str,
+        value: &str,
+    ) -> Result<ProjectSecret, SecretError> {
+        validate_name(name)?;
+        let encrypted = self.encrypt(project_id, name, value)?;
+        Ok(ProjectSecret::upsert(pool, project_id, name, &encrypted).await?)
+    }
+
+    /// The decrypted secrets of a project, by name. The master key is only
+    /// loaded when the project has any.
+    pub async fn project_secrets(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<HashMap<String, String>, SecretError> {
+        let secrets = ProjectSecret::find_by_project_id(pool, project_id).await?;
+        if secrets.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let vault = Self::load().await?;
+        secrets
+            .into_iter()
+            .map(|secret| {
+                let value = vault.decrypt(&secret)?;
+                Ok((secret.name, value))
+            })
+            .collect()
+    }
+
+    /// Base64 of the salt, the nonce and the ciphertext. The project and name
+    /// are authenticated so a value can't be moved to another secret.
+    fn encrypt(&self, project_id: Uuid, name: &str, value: &str) -> Result<String, SecretError> {
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut salt)
+            .and_then(|()| self.rng.fill(&mut nonce))
+            .map_err(|_| SecretError::Encrypt(name.to_string()))?;
+
+        let mut ciphertext = value.as_bytes().to_vec();
+        self.key(&salt)
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(associated_data(project_id, name)),
+                &mut ciphertext,
+            )
+            .map_err(|_| SecretError::Encrypt(name.to_string()))?;
+
+        let mut combined = salt.to_vec();
+        combined.extend_from_slice(&nonce);
+        combined.extend_from_slice(&ciphertext);
+        Ok(STANDARD.encode(combined))
+    }
+
+    fn decrypt(&self, secret: &ProjectSecret) -> Result<String, SecretError> {
+        let error = || SecretError::Decrypt(secret.name.clone());
+        let decoded = STANDARD
+            .decode(&secret.encrypted_value)
+            .map_err(|_| error())?;
+        if decoded.len() < SALT_LEN + NONCE_LEN {
+            return Err(error());
+        }
+
+        let (salt, rest) = decoded.split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| error())?;
+        let mut buffer = ciphertext.to_vec();
+        let plaintext = self
+            .key(salt)
+            .open_in_place(
+                nonce,
+                Aad::from(associated_data(secret.project_id, &secret.name)),
+                &mut buffer,
+            )
+            .map_err(|_| error())?;
+        String::from_utf8(plaintext.to_vec()).map_err(|_| error())
+    }
+}
+
+/// Secret names follow environment variable naming so references stay
+/// unambiguous
+pub fn validate_name(name: &str) -> Result<(), SecretError> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(SecretError::InvalidName(name.to_string()))
+    }
+}
+
+fn associated_data(project_id: Uuid, name: &str) -> Vec<u8> {
+    format!("{project_id}/{name}").into_bytes()
+}
+
+/// The master key material, which is only used as HKDF input
+fn load_master_key() -> Result<Vec<u8>, SecretError> {
+    if let Ok(material) = std::env::var(MASTER_KEY_ENV)
+        && !material.is_empty()
+    {
+        return Ok(material.into_bytes());
+    }
+
+    let keyring_error = |e: keyring::Error| SecretError::MasterKey(e.to_string());
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(keyring_error)?;
+    let material = match entry.get_password() {
+        Ok(material) => material,
+        Err(keyring::Error::NoEntry) => {
+            let mut bytes = [0u8; 32];
+            SystemRandom::new()
+                .fill(&mut bytes)
+                .map_err(|_| SecretError::MasterKey("no randomness available".to_string()))?;
+            let material = STANDARD.encode(bytes);
+            entry.set_password(&material).map_err(keyring_error)?;
+            tracing::info!("Generated a master key for project secrets in the OS keyring");
+            material
+        }
+        Err(e) => return Err(keyring_error(e)),
+    };
+    Ok(material.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let vault = SecretVault::with_material(b"master key".to_vec());
+        let project_id = Uuid::new_v4();
+        let mut secret = ProjectSecret {
+            id: Uuid::new_v4(),
+            project_id,
+            name: "NPM_TOKEN".to_string(),
+            encrypted_value: vault.encrypt(project_id, "NPM_TOKEN", "s3cret").unwrap(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert_eq!(vault.decrypt(&secret).unwrap(), "s3cret");
+
+        // Every value gets its own salt
+        assert_ne!(
+            vault.encrypt(project_id, "NPM_TOKEN", "s3cret").unwrap(),
+            secret.encrypted_value
+        );
+        let other_vault = SecretVault::with_material(b"other key".to_vec());
+        assert!(other_vault.decrypt(&secret).is_err());
+
+        // Bound to the name it was stored under
+        secret.name = "OTHER".to_string();
+        assert!(vault.decrypt(&secret).is_err());
+
+        assert!(validate_name("_NPM_TOKEN2").is_ok());
+        assert!(validate_name("2FA").is_err());
+        assert!(validate_name("NPM-TOKEN").is_err());
+        assert!(validate_name("").is_err());
+    }
+}
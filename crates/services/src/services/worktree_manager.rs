@@ -114,6 +114,8 @@ impl WorktreeManager {
         // Check if worktree already exists and is properly set up
         if Self::is_worktree_properly_set_up(repo_path, worktree_path).await? {
             trace!("Worktree already properly set up at path: {}", path_str);
+            // Executions may have pointed it elsewhere since it was created
+            GitService::new().register_worktree(repo_path, worktree_path)?;
             return Ok(());
         }
 
@@ -192,7 +194,9 @@ impl WorktreeManager {
         .map_err(|e| WorktreeError::TaskJoin(format!("{e}")))?
     }
 
-    fn find_worktree_git_internal_name(
+    /// The name of the `.git/worktrees/<name>` directory git keeps the
+    /// metadata of the worktree at `worktree_path` in
+    pub fn find_worktree_git_internal_name(
         git_repo_path: &Path,
         worktree_path: &Path,
     ) -> Result<Option<String>, WorktreeError> {
@@ -12,6 +12,7 @@ pub mod msg_store;
 pub mod path;
 pub mod port_file;
 pub mod process;
+pub mod redaction;
 pub mod response;
 pub mod sentry;
 pub mod shell;
@@ -71,6 +71,25 @@ impl LogMsg {
         Message::Text(json.into())
     }
 
+    /// Like `to_ws_message_unchecked`, with the resume cursor attached as a
+    /// `cursor` field so clients can reconnect with `?after=<cursor>`.
+    pub fn to_ws_message_with_cursor(&self, cursor: &impl std::fmt::Display) -> Message {
+        let mut value = match self {
+            LogMsg::Ready => serde_json::json!({ "Ready": true }),
+            LogMsg::Finished => serde_json::json!({ "finished": true }),
+            _ => serde_json::to_value(self)
+                .unwrap_or_else(|_| serde_json::json!({ "error": "serialization_failed" })),
+        };
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert(
+                "cursor".to_string(),
+                serde_json::Value::String(cursor.to_string()),
+            );
+        }
+
+        Message::Text(value.to_string().into())
+    }
+
     /// Rough size accounting for your byte‑budgeted history.
     pub fn approx_bytes(&self) -> usize {
         const OVERHEAD: usize = 8;
@@ -1,32 +1,140 @@
 use std::{
     collections::VecDeque,
+    fmt,
+    str::FromStr,
     sync::{Arc, RwLock},
 };
 
 use axum::response::sse::Event;
-use futures::{StreamExt, TryStreamExt, future};
+use futures::{StreamExt, TryStreamExt, future, stream::BoxStream};
+use serde::{Deserialize, Serialize};
 use tokio::{sync::broadcast, task::JoinHandle};
-use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
+use uuid::Uuid;
 
-use crate::{log_msg::LogMsg, stream_lines::LinesStreamExt};
+use crate::{log_msg::LogMsg, redaction::Redactor, stream_lines::LinesStreamExt};
+
+mod spill;
+
+use spill::{DiskBudget, PendingSegment, Segment, Spill};
 
 // 100 MB Limit
 const HISTORY_BYTES: usize = 100000 * 1024;
 
+/// In-memory history budget for stores that spill older messages to disk.
+const SPILL_MEMORY_BYTES: usize = 4 * 1024 * 1024;
+
 #[derive(Clone)]
 struct StoredMsg {
+    seq: u64,
     msg: LogMsg,
     bytes: usize,
+    /// Stdout/stderr messages pushed before this one.
+    outputs_before: u64,
 }
 
 struct Inner {
     history: VecDeque<StoredMsg>,
     total_bytes: usize,
+    next_seq: u64,
+    /// Stdout/stderr messages pushed so far.
+    outputs: u64,
+    spill: Option<Spill>,
+}
+
+fn is_output(msg: &LogMsg) -> bool {
+    matches!(msg, LogMsg::Stdout(_) | LogMsg::Stderr(_))
+}
+
+/// A `LogMsg` tagged with the sequence number its `MsgStore` assigned to it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SequencedLogMsg {
+    pub seq: u64,
+    pub msg: LogMsg,
+}
+
+/// Resume point in a specific `MsgStore`. Sequence numbers are only comparable
+/// within the store that assigned them, so the cursor carries the store id.
+/// Serialized as `<store_id>:<seq>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogCursor {
+    pub store_id: Uuid,
+    pub seq: u64,
+}
+
+impl fmt::Display for LogCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.store_id, self.seq)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid log cursor: {0}")]
+pub struct InvalidLogCursor(String);
+
+impl FromStr for LogCursor {
+    type Err = InvalidLogCursor;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (store_id, seq) = s
+            .trim()
+            .rsplit_once(':')
+            .ok_or_else(|| InvalidLogCursor(s.to_string()))?;
+        Ok(Self {
+            store_id: store_id
+                .parse()
+                .map_err(|_| InvalidLogCursor(s.to_string()))?,
+            seq: seq.parse().map_err(|_| InvalidLogCursor(s.to_string()))?,
+        })
+    }
+}
+
+/// History-then-live stream of sequenced messages from one store.
+pub struct SequencedLogStream {
+    pub store_id: Uuid,
+    /// Set when the caller asked to resume from a cursor that could not be
+    /// honoured (evicted, or from a different store), so the stream starts over
+    /// from the oldest retained message and the client must reset its state.
+    pub replayed: bool,
+    pub stream: BoxStream<'static, Result<SequencedLogMsg, std::io::Error>>,
+}
+
+impl SequencedLogStream {
+    pub fn cursor(&self, seq: u64) -> LogCursor {
+        LogCursor {
+            store_id: self.store_id,
+            seq,
+        }
+    }
+
+    /// Drop the sequence numbers.
+    pub fn into_log_stream(self) -> BoxStream<'static, Result<LogMsg, std::io::Error>> {
+        self.stream.map_ok(|m| m.msg).boxed()
+    }
+}
+
+/// Like `SequencedLogStream`, but a lagging subscriber gets `Lagged` errors
+/// for the live messages it missed.
+pub struct SequencedEventStream {
+    pub store_id: Uuid,
+    pub replayed: bool,
+    pub stream: BoxStream<'static, Result<SequencedLogMsg, BroadcastStreamRecvError>>,
+}
+
+struct Subscription {
+    replayed: bool,
+    history: BoxStream<'static, Result<SequencedLogMsg, std::io::Error>>,
+    rx: broadcast::Receiver<LogMsg>,
+    next_live: u64,
 }
 
 pub struct MsgStore {
-    inner: RwLock<Inner>,
+    id: Uuid,
+    /// Shared with background spill writes.
+    inner: Arc<RwLock<Inner>>,
     sender: broadcast::Sender<LogMsg>,
+    /// Applied to every pushed message before it is stored or broadcast
+    redactor: RwLock<Option<Arc<Redactor>>>,
 }
 
 impl Default for MsgStore {
@@ -37,30 +145,172 @@ impl Default for MsgStore {
 
 impl MsgStore {
     pub fn new() -> Self {
+        Self::with_id(Uuid::new_v4())
+    }
+
+    /// Create a store whose cursors are tied to `id`, e.g. the execution
+    /// process id, so they stay valid when the same messages are later replayed
+    /// from persisted logs.
+    pub fn with_id(id: Uuid) -> Self {
+        Self::build(id, None)
+    }
+
+    /// Like `with_id`, but instead of evicting old messages once the history
+    /// outgrows its budget, page them out to a temp file so that
+    /// `history_plus_stream` stays complete while memory stays bounded. Falls
+    /// back to plain eviction if the temp file cannot be created. All such
+    /// stores share one disk budget, so use it only for processes whose output
+    /// can grow without bound.
+    pub fn with_spill(id: Uuid) -> Self {
+        Self::with_spill_budget(id, SPILL_MEMORY_BYTES, DiskBudget::shared())
+    }
+
+    fn with_spill_budget(id: Uuid, memory_bytes: usize, disk: Arc<DiskBudget>) -> Self {
+        let spill = Spill::new(memory_bytes, disk)
+            .inspect_err(|e| {
+                tracing::warn!("Failed to create log spill file, evicting instead: {e}")
+            })
+            .ok();
+        Self::build(id, spill)
+    }
+
+    fn build(id: Uuid, spill: Option<Spill>) -> Self {
         let (sender, _) = broadcast::channel(10000);
         Self {
-            inner: RwLock::new(Inner {
+            id,
+            inner: Arc::new(RwLock::new(Inner {
                 history: VecDeque::with_capacity(32),
                 total_bytes: 0,
-            }),
+                next_seq: 0,
+                outputs: 0,
+                spill,
+            })),
             sender,
+            redactor: RwLock::new(None),
         }
     }
 
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Redact messages pushed from now on, so neither persisted logs nor
+    /// streams see the secrets. Secrets split across two pushes are not caught.
+    pub fn set_redactor(&self, redactor: Redactor) {
+        *self.redactor.write().unwrap() = Some(Arc::new(redactor));
+    }
+
     pub fn push(&self, msg: LogMsg) {
-        let _ = self.sender.send(msg.clone()); // live listeners
+        let redactor = self.redactor.read().unwrap().clone();
+        let msg = match redactor {
+            Some(redactor) => redactor.redact(msg),
+            None => msg,
+        };
         let bytes = msg.approx_bytes();
 
-        let mut inner = self.inner.write().unwrap();
-        while inner.total_bytes.saturating_add(bytes) > HISTORY_BYTES {
-            if let Some(front) = inner.history.pop_front() {
-                inner.total_bytes = inner.total_bytes.saturating_sub(front.bytes);
-            } else {
-                break;
+        let pending = {
+            let mut inner = self.inner.write().unwrap();
+            let seq = inner.next_seq;
+            inner.next_seq += 1;
+            // Broadcast under the lock so live listeners observe messages in seq order.
+            let _ = self.sender.send(msg.clone());
+            if inner.spill.is_none() {
+                while inner.total_bytes.saturating_add(bytes) > HISTORY_BYTES {
+                    if let Some(front) = inner.history.pop_front() {
+                        inner.total_bytes = inner.total_bytes.saturating_sub(front.bytes);
+                    } else {
+                        break;
+                    }
+                }
+            }
+            let outputs_before = inner.outputs;
+            if is_output(&msg) {
+                inner.outputs += 1;
+            }
+            inner.history.push_back(StoredMsg {
+                seq,
+                msg,
+                bytes,
+                outputs_before,
+            });
+            inner.total_bytes = inner.total_bytes.saturating_add(bytes);
+            Self::take_spill_batch(&mut inner)
+        };
+
+        if let Some(pending) = pending {
+            Self::spill(self.inner.clone(), pending);
+        }
+    }
+
+    /// Once the in-memory history outgrows its budget, pick the oldest messages
+    /// down to half the budget for a single segment. They stay in memory until
+    /// the segment is on disk.
+    fn take_spill_batch(inner: &mut Inner) -> Option<PendingSegment> {
+        let spill = inner.spill.as_mut()?;
+        if inner.total_bytes <= spill.memory_bytes {
+            return None;
+        }
+
+        let keep_bytes = spill.memory_bytes / 2;
+        let mut remaining = inner.total_bytes;
+        let batch = inner
+            .history
+            .iter()
+            .take(inner.history.len().saturating_sub(1))
+            .take_while(|stored| {
+                let take = remaining > keep_bytes;
+                remaining = remaining.saturating_sub(stored.bytes);
+                take
+            });
+        spill.begin_write(batch)
+    }
+
+    /// Write `pending` outside the store lock, then keep spilling whatever
+    /// piled up meanwhile. Runs on the blocking pool when inside a runtime.
+    fn spill(inner: Arc<RwLock<Inner>>, pending: PendingSegment) {
+        let write = move || {
+            let mut pending = Some(pending);
+            while let Some(segment) = pending.take() {
+                let written = segment.write();
+                let mut inner = inner.write().unwrap();
+                Self::finish_spill(&mut inner, written);
+                pending = Self::take_spill_batch(&mut inner);
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(write);
+            }
+            Err(_) => write(),
+        }
+    }
+
+    /// Drop a written segment's messages from memory, or keep them there if
+    /// the write failed.
+    fn finish_spill(inner: &mut Inner, written: std::io::Result<Segment>) {
+        let Some(spill) = inner.spill.as_mut() else {
+            return;
+        };
+        match written {
+            Ok(segment) => {
+                let last_seq = segment.last_seq();
+                spill.finish_write(segment);
+                while let Some(front) = inner.history.front()
+                    && front.seq <= last_seq
+                {
+                    let bytes = front.bytes;
+                    inner.history.pop_front();
+                    inner.total_bytes = inner.total_bytes.saturating_sub(bytes);
+                }
+            }
+            Err(e) => {
+                // Messages already on disk can no longer be extended
+                // contiguously; drop the spill file and behave like a plain
+                // evicting store. The failed batch is still in memory.
+                tracing::warn!("Failed to spill log history to disk, evicting instead: {e}");
+                inner.spill = None;
             }
         }
-        inner.history.push_back(StoredMsg { msg, bytes });
-        inner.total_bytes = inner.total_bytes.saturating_add(bytes);
     }
 
     // Convenience
@@ -91,6 +341,8 @@ impl MsgStore {
         self.sender.subscribe()
     }
 
+    /// Messages currently held in memory. For a spilling store this is only
+    /// the most recent window; use `history_plus_stream` for everything.
     pub fn get_history(&self) -> Vec<LogMsg> {
         self.inner
             .read()
@@ -101,17 +353,168 @@ impl MsgStore {
             .collect()
     }
 
+    /// Visit the messages held in memory newest first until `f` returns false.
+    pub fn visit_history_rev(&self, mut f: impl FnMut(&LogMsg) -> bool) {
+        let inner = self.inner.read().unwrap();
+        for stored in inner.history.iter().rev() {
+            if !f(&stored.msg) {
+                break;
+            }
+        }
+    }
+
+    /// Sequence number the next pushed message will get.
+    pub fn next_seq(&self) -> u64 {
+        self.inner.read().unwrap().next_seq
+    }
+
+    /// Number of stdout/stderr messages with a seq up to and including `seq`,
+    /// for consumers that number output lines rather than messages. `None`
+    /// once `seq` has been evicted, or if it has not been assigned yet.
+    pub async fn outputs_through(&self, seq: u64) -> Option<u64> {
+        let next = seq.checked_add(1)?;
+        let spilled = {
+            let inner = self.inner.read().unwrap();
+            if next >= inner.next_seq {
+                return (next == inner.next_seq).then_some(inner.outputs);
+            }
+            match inner.history.front() {
+                Some(front) if next >= front.seq => {
+                    return inner
+                        .history
+                        .get((next - front.seq) as usize)
+                        .map(|s| s.outputs_before);
+                }
+                _ => inner.spill.as_ref()?.outputs_before(next)?,
+            }
+        };
+        spilled
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to read spilled log history: {e}"))
+            .ok()
+    }
+
     /// History then live, as `LogMsg`.
     pub fn history_plus_stream(
         &self,
     ) -> futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>> {
-        let (history, rx) = (self.get_history(), self.get_receiver());
+        self.sequenced_stream(None).into_log_stream()
+    }
+
+    /// History then live with sequence numbers, starting right after `after`.
+    /// Falls back to the full retained history (flagged as `replayed`) when the
+    /// cursor belongs to another store or has already been evicted.
+    pub fn sequenced_stream(&self, after: Option<&LogCursor>) -> SequencedLogStream {
+        let subscription = self.subscribe(after);
+        let mut next_live = subscription.next_live;
+        let live = BroadcastStream::new(subscription.rx).filter_map(move |res| {
+            let item = match res {
+                Ok(msg) => {
+                    let seq = next_live;
+                    next_live += 1;
+                    Some(Ok(SequencedLogMsg { seq, msg }))
+                }
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                    next_live += skipped;
+                    None
+                }
+            };
+            future::ready(item)
+        });
 
-        let hist = futures::stream::iter(history.into_iter().map(Ok::<_, std::io::Error>));
-        let live = BroadcastStream::new(rx)
-            .filter_map(|res| async move { res.ok().map(Ok::<_, std::io::Error>) });
+        SequencedLogStream {
+            store_id: self.id,
+            replayed: subscription.replayed,
+            stream: Box::pin(subscription.history.chain(live)),
+        }
+    }
 
-        Box::pin(hist.chain(live))
+    /// Like `sequenced_stream`, but live messages missed by a lagging
+    /// subscriber are reported as `Lagged` rather than skipped, for consumers
+    /// that resync from their own snapshot.
+    pub fn sequenced_events(&self, after: Option<&LogCursor>) -> SequencedEventStream {
+        let subscription = self.subscribe(after);
+        let mut next_live = subscription.next_live;
+        let history = subscription.history.filter_map(|res| {
+            future::ready(
+                res.inspect_err(|e| tracing::warn!("Failed to read spilled history: {e}"))
+                    .ok()
+                    .map(Ok),
+            )
+        });
+        let live = BroadcastStream::new(subscription.rx).map(move |res| match res {
+            Ok(msg) => {
+                let seq = next_live;
+                next_live += 1;
+                Ok(SequencedLogMsg { seq, msg })
+            }
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                next_live += skipped;
+                Err(BroadcastStreamRecvError::Lagged(skipped))
+            }
+        });
+
+        SequencedEventStream {
+            store_id: self.id,
+            replayed: subscription.replayed,
+            stream: Box::pin(history.chain(live)),
+        }
+    }
+
+    /// Retained history after `after` plus a receiver for everything pushed
+    /// later.
+    fn subscribe(&self, after: Option<&LogCursor>) -> Subscription {
+        // Snapshot history and subscribe under the same lock as `push`, so the
+        // first live message is exactly `next_seq`.
+        let inner = self.inner.read().unwrap();
+        let first_in_memory = inner
+            .history
+            .front()
+            .map(|m| m.seq)
+            .unwrap_or(inner.next_seq);
+        let first_retained = inner
+            .spill
+            .as_ref()
+            .and_then(Spill::first_seq)
+            .unwrap_or(first_in_memory);
+        let resume_from = after
+            .filter(|c| c.store_id == self.id)
+            .map(|c| c.seq + 1)
+            .filter(|from| (first_retained..=inner.next_seq).contains(from));
+        let replayed = after.is_some() && resume_from.is_none();
+        let from = resume_from.unwrap_or(0);
+
+        let history: Vec<SequencedLogMsg> = inner
+            .history
+            .iter()
+            .filter(|m| m.seq >= from)
+            .map(|m| SequencedLogMsg {
+                seq: m.seq,
+                msg: m.msg.clone(),
+            })
+            .collect();
+        let spilled = inner
+            .spill
+            .as_ref()
+            .filter(|_| from < first_in_memory)
+            .map(|spill| spill.stream_from(from));
+        let rx = self.sender.subscribe();
+        let next_live = inner.next_seq;
+        drop(inner);
+
+        let history = futures::stream::iter(spilled)
+            .flatten()
+            .chain(futures::stream::iter(
+                history.into_iter().map(Ok::<_, std::io::Error>),
+            ))
+            .boxed();
+
+        Subscription {
+            replayed,
+            history,
+            rx,
+            next_live,
+        }
     }
 
     pub fn stdout_chunked_stream(
@@ -179,3 +582,195 @@ impl MsgStore {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_pushed_messages() {
+        let store = MsgStore::new();
+        store.set_redactor(Redactor::default().with_values(["hunter2-token"]));
+        store.push_stdout("NPM_TOKEN=hunter2-token abc\n");
+        store.push_patch(
+            serde_json::from_value(serde_json::json!([
+                { "op": "add", "path": "/entries/0", "value": { "content": "\"hunter2-token\"" } }
+            ]))
+            .unwrap(),
+        );
+
+        let history = store.get_history();
+        assert!(
+            matches!(&history[0], LogMsg::Stdout(s) if s == "NPM_TOKEN=[REDACTED:secret] abc\n")
+        );
+        let LogMsg::JsonPatch(patch) = &history[1] else {
+            panic!("expected a patch");
+        };
+        assert_eq!(
+            serde_json::to_value(patch).unwrap()[0]["value"]["content"],
+            "\"[REDACTED:secret]\""
+        );
+    }
+
+    #[test]
+    fn cursor_round_trips() {
+        let cursor = LogCursor {
+            store_id: Uuid::new_v4(),
+            seq: 42,
+        };
+        assert_eq!(cursor.to_string().parse::<LogCursor>().unwrap(), cursor);
+        assert!("not-a-cursor".parse::<LogCursor>().is_err());
+    }
+
+    #[tokio::test]
+    async fn sequenced_stream_resumes_after_cursor() {
+        let store = MsgStore::new();
+        store.push_stdout("a");
+        store.push_stdout("b");
+        store.push_stdout("c");
+
+        let after = LogCursor {
+            store_id: store.id(),
+            seq: 0,
+        };
+        let resumed = store.sequenced_stream(Some(&after));
+        assert!(!resumed.replayed);
+        store.push_stdout("d");
+
+        let seqs: Vec<u64> = resumed
+            .stream
+            .take(3)
+            .map(|m| m.unwrap().seq)
+            .collect()
+            .await;
+        assert_eq!(seqs, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn sequenced_events_resume_after_cursor() {
+        let store = MsgStore::new();
+        store.push_stdout("a");
+        store.push_stdout("b");
+
+        let after = LogCursor {
+            store_id: store.id(),
+            seq: 1,
+        };
+        let events = store.sequenced_events(Some(&after));
+        assert!(!events.replayed);
+        store.push_stdout("c");
+
+        let first = events.stream.take(1).collect::<Vec<_>>().await;
+        assert_eq!(first[0].as_ref().unwrap().seq, 2);
+    }
+
+    #[tokio::test]
+    async fn sequenced_stream_replays_for_foreign_cursor() {
+        let store = MsgStore::new();
+        store.push_stdout("a");
+        store.push_stdout("b");
+
+        let foreign = LogCursor {
+            store_id: Uuid::new_v4(),
+            seq: 0,
+        };
+        let replayed = store.sequenced_stream(Some(&foreign));
+        assert!(replayed.replayed);
+        let first = replayed.stream.take(1).collect::<Vec<_>>().await;
+        assert_eq!(first[0].as_ref().unwrap().seq, 0);
+    }
+
+    #[tokio::test]
+    async fn spilled_history_stays_complete() {
+        let store = MsgStore::with_spill_budget(Uuid::new_v4(), 256, DiskBudget::shared());
+        for i in 0..200 {
+            store.push_stdout(format!("line {i}"));
+        }
+        store.push_finished();
+
+        // Segments are written in the background
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while store.get_history().len() >= 201 {
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("history was never spilled");
+
+        let seqs: Vec<u64> = store
+            .sequenced_stream(None)
+            .stream
+            .take(201)
+            .map(|m| m.unwrap().seq)
+            .collect()
+            .await;
+        assert_eq!(seqs, (0..201).collect::<Vec<_>>());
+
+        let after = LogCursor {
+            store_id: store.id(),
+            seq: 9,
+        };
+        let resumed = store.sequenced_stream(Some(&after));
+        assert!(!resumed.replayed);
+        let first = resumed.stream.take(1).collect::<Vec<_>>().await;
+        assert_eq!(first[0].as_ref().unwrap().seq, 10);
+    }
+
+    #[tokio::test]
+    async fn outputs_through_counts_spilled_and_live_output() {
+        let store = MsgStore::with_spill_budget(Uuid::new_v4(), 256, DiskBudget::shared());
+        for i in 0..100 {
+            store.push_stdout(format!("line {i}"));
+            store.push_session_id(format!("session {i}"));
+        }
+
+        // seq 9 is the fifth session id, after five stdout lines
+        assert_eq!(store.outputs_through(9).await, Some(5));
+        assert_eq!(store.outputs_through(198).await, Some(100));
+        assert_eq!(store.outputs_through(199).await, Some(100));
+        assert_eq!(store.outputs_through(200).await, None);
+    }
+
+    #[tokio::test]
+    async fn spilling_stores_share_a_disk_budget() {
+        let budget = DiskBudget::new(4 * 1024);
+        let first = MsgStore::with_spill_budget(Uuid::new_v4(), 256, budget.clone());
+        let second = MsgStore::with_spill_budget(Uuid::new_v4(), 256, budget.clone());
+        for i in 0..500 {
+            first.push_stdout(format!("first {i}"));
+            second.push_stdout(format!("second {i}"));
+        }
+
+        // Segments are written in the background
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while first.get_history().len() >= 500 || second.get_history().len() >= 500 {
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("history was never spilled");
+
+        // Each store may keep one segment over the shared budget
+        assert!(budget.used() > 0);
+        assert!(budget.used() <= 4 * 1024 + 2 * 1024);
+        let seqs: Vec<u64> = second
+            .sequenced_stream(None)
+            .stream
+            .take(1)
+            .map(|m| m.unwrap().seq)
+            .collect()
+            .await;
+        assert!(seqs[0] > 0, "the oldest segments were dropped");
+
+        // Released once in-flight writes let go of the stores
+        drop(first);
+        drop(second);
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while budget.used() > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .expect("dropped stores kept their disk budget");
+    }
+}
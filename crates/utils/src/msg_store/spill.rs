@@ -0,0 +1,288 @@
+//! Disk-backed segment store for `MsgStore` history that no longer fits in
+//! memory. Evicted messages are appended to named temp files, deleted once
+//! dropped, in segments of JSON lines and paged back in one segment at a time
+//! when a subscriber needs them. Segments are written outside the store lock;
+//! files are rotated every `FILE_BYTES` and the oldest segments are dropped once
+//! the spilled history exceeds `DISK_BYTES`, or once all stores sharing a
+//! `DiskBudget` together exceed it.
+
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write},
+    sync::{
+        Arc, LazyLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use futures::{
+    StreamExt,
+    future::{BoxFuture, FutureExt},
+    stream::BoxStream,
+};
+use tempfile::NamedTempFile;
+
+use super::{SequencedLogMsg, StoredMsg, is_output};
+
+/// Size after which new segments go to a fresh file.
+const FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Spilled history budget. Beyond it the oldest segments are dropped, the same
+/// way a non-spilling store evicts.
+const DISK_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Spilled history budget shared by every store in the process.
+const SHARED_DISK_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+static SHARED_BUDGET: LazyLock<Arc<DiskBudget>> =
+    LazyLock::new(|| DiskBudget::new(SHARED_DISK_BYTES));
+
+/// Disk space a set of stores spill into together. Once it is used up, a store
+/// that spills more drops its own oldest segments.
+pub(super) struct DiskBudget {
+    limit: u64,
+    used: AtomicU64,
+}
+
+impl DiskBudget {
+    pub(super) fn new(limit: u64) -> Arc<Self> {
+        Arc::new(Self {
+            limit,
+            used: AtomicU64::new(0),
+        })
+    }
+
+    /// The budget of stores created with `MsgStore::with_spill`.
+    pub(super) fn shared() -> Arc<Self> {
+        SHARED_BUDGET.clone()
+    }
+
+    #[cfg(test)]
+    pub(super) fn used(&self) -> u64 {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    fn exceeded(&self) -> bool {
+        self.used.load(Ordering::Relaxed) > self.limit
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Segment {
+    file: Arc<NamedTempFile>,
+    first_seq: u64,
+    last_seq: u64,
+    offset: u64,
+    len: u64,
+    /// Stdout/stderr messages pushed before the segment's first message.
+    outputs_before: u64,
+}
+
+impl Segment {
+    pub(super) fn last_seq(&self) -> u64 {
+        self.last_seq
+    }
+}
+
+/// A batch of in-memory messages to append to disk without holding the store
+/// lock. The messages stay in memory until the written segment is registered.
+pub(super) struct PendingSegment {
+    /// `None` when the current file is full and the batch starts a new one.
+    file: Option<Arc<NamedTempFile>>,
+    offset: u64,
+    msgs: Vec<SequencedLogMsg>,
+    outputs_before: u64,
+}
+
+impl PendingSegment {
+    pub(super) fn write(self) -> io::Result<Segment> {
+        let (Some(first), Some(last)) = (self.msgs.first(), self.msgs.last()) else {
+            return Err(io::Error::other("empty spill segment"));
+        };
+        let (first_seq, last_seq) = (first.seq, last.seq);
+
+        let (file, offset) = match self.file {
+            Some(file) => (file, self.offset),
+            None => (Arc::new(new_file()?), 0),
+        };
+
+        let mut buf = Vec::new();
+        for msg in &self.msgs {
+            serde_json::to_writer(&mut buf, msg)?;
+            buf.push(b'\n');
+        }
+
+        let mut handle = file.as_file();
+        handle.seek(SeekFrom::Start(offset))?;
+        handle.write_all(&buf)?;
+
+        Ok(Segment {
+            file,
+            first_seq,
+            last_seq,
+            offset,
+            len: buf.len() as u64,
+            outputs_before: self.outputs_before,
+        })
+    }
+}
+
+pub(super) struct Spill {
+    file: Arc<NamedTempFile>,
+    end: u64,
+    segments: VecDeque<Segment>,
+    disk_bytes: u64,
+    budget: Arc<DiskBudget>,
+    /// Set while a `PendingSegment` is being written; one write at a time keeps
+    /// segments contiguous.
+    writing: bool,
+    /// In-memory history budget of the owning store.
+    pub(super) memory_bytes: usize,
+}
+
+fn new_file() -> io::Result<NamedTempFile> {
+    tempfile::Builder::new().prefix("vk-msgs-").tempfile()
+}
+
+impl Spill {
+    pub(super) fn new(memory_bytes: usize, budget: Arc<DiskBudget>) -> io::Result<Self> {
+        Ok(Self {
+            file: Arc::new(new_file()?),
+            end: 0,
+            segments: VecDeque::new(),
+            disk_bytes: 0,
+            budget,
+            writing: false,
+            memory_bytes,
+        })
+    }
+
+    /// Oldest sequence number available on disk.
+    pub(super) fn first_seq(&self) -> Option<u64> {
+        self.segments.front().map(|s| s.first_seq)
+    }
+
+    /// Start spilling `msgs` (contiguous, in seq order, oldest in memory).
+    /// `None` if a write is already in flight or there is nothing to write.
+    pub(super) fn begin_write<'a>(
+        &mut self,
+        msgs: impl IntoIterator<Item = &'a StoredMsg>,
+    ) -> Option<PendingSegment> {
+        if self.writing {
+            return None;
+        }
+        let mut msgs = msgs.into_iter().peekable();
+        let outputs_before = msgs.peek()?.outputs_before;
+        let msgs = msgs
+            .map(|stored| SequencedLogMsg {
+                seq: stored.seq,
+                msg: stored.msg.clone(),
+            })
+            .collect();
+
+        self.writing = true;
+        Some(PendingSegment {
+            file: (self.end < FILE_BYTES).then(|| self.file.clone()),
+            offset: self.end,
+            msgs,
+            outputs_before,
+        })
+    }
+
+    /// Register a segment written from the last `begin_write`.
+    pub(super) fn finish_write(&mut self, segment: Segment) {
+        self.writing = false;
+        if !Arc::ptr_eq(&segment.file, &self.file) {
+            self.file = segment.file.clone();
+        }
+        self.end = segment.offset + segment.len;
+        self.disk_bytes += segment.len;
+        self.budget.used.fetch_add(segment.len, Ordering::Relaxed);
+        self.segments.push_back(segment);
+
+        // A file is deleted once none of its segments (or readers) are left.
+        while (self.disk_bytes > DISK_BYTES || self.budget.exceeded()) && self.segments.len() > 1 {
+            if let Some(dropped) = self.segments.pop_front() {
+                self.disk_bytes -= dropped.len;
+                self.budget.used.fetch_sub(dropped.len, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Stream every spilled message with `seq >= from`, reading one segment
+    /// at a time. Segments are immutable once written, so the snapshot taken
+    /// here stays valid while the store keeps spilling.
+    pub(super) fn stream_from(
+        &self,
+        from: u64,
+    ) -> BoxStream<'static, Result<SequencedLogMsg, io::Error>> {
+        let segments: Vec<Segment> = self
+            .segments
+            .iter()
+            .filter(|s| s.last_seq >= from)
+            .cloned()
+            .collect();
+
+        futures::stream::iter(segments)
+            .then(move |segment| async move {
+                tokio::task::spawn_blocking(move || read_segment(&segment, from))
+                    .await
+                    .map_err(io::Error::other)?
+            })
+            .flat_map(|res| match res {
+                Ok(msgs) => futures::stream::iter(msgs.into_iter().map(Ok)).boxed(),
+                Err(e) => futures::stream::once(async { Err(e) }).boxed(),
+            })
+            .boxed()
+    }
+
+    /// Count the stdout/stderr messages before `seq`, reading the segment that
+    /// holds it. `None` if `seq` is not on disk.
+    pub(super) fn outputs_before(&self, seq: u64) -> Option<BoxFuture<'static, io::Result<u64>>> {
+        let segment = self
+            .segments
+            .iter()
+            .find(|s| s.first_seq <= seq && seq <= s.last_seq)?
+            .clone();
+        Some(
+            async move {
+                let outputs_before = segment.outputs_before;
+                let msgs =
+                    tokio::task::spawn_blocking(move || read_segment(&segment, segment.first_seq))
+                        .await
+                        .map_err(io::Error::other)??;
+                let in_segment = msgs
+                    .iter()
+                    .filter(|m| m.seq < seq && is_output(&m.msg))
+                    .count() as u64;
+                Ok(outputs_before + in_segment)
+            }
+            .boxed(),
+        )
+    }
+}
+
+impl Drop for Spill {
+    fn drop(&mut self) {
+        self.budget
+            .used
+            .fetch_sub(self.disk_bytes, Ordering::Relaxed);
+    }
+}
+
+fn read_segment(segment: &Segment, from: u64) -> io::Result<Vec<SequencedLogMsg>> {
+    // A separate handle per read so concurrent readers don't share a cursor
+    // with the writer.
+    let mut handle = segment.file.reopen()?;
+    handle.seek(SeekFrom::Start(segment.offset))?;
+    let reader = BufReader::new(handle.take(segment.len));
+
+    let mut msgs = Vec::new();
+    for line in reader.lines() {
+        let msg: SequencedLogMsg = serde_json::from_str(&line?)?;
+        if msg.seq >= from {
+            msgs.push(msg);
+        }
+    }
+    Ok(msgs)
+}
@@ -34,6 +34,8 @@ import type { GhCliSetupError } from 'shared/types';
 import { useUserSystem } from '@/components/ConfigProvider';
 import { defineModal } from '@/lib/modals';
 
+const REVIEW_POLL_INTERVAL_MS = 3000;
+
 interface CreatePRDialogProps {
   attempt: Workspace;
   task: TaskWithAttemptStatus;
@@ -56,6 +58,7 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
     const [prBody, setPrBody] = useState('');
     const [prBaseBranch, setPrBaseBranch] = useState('');
     const [creatingPR, setCreatingPR] = useState(false);
+    const [reviewing, setReviewing] = useState(false);
     const [error, setError] = useState<string | null>(null);
     const [ghCliHelp, setGhCliHelp] = useState<GhCliSupportContent | null>(
       null
@@ -139,14 +142,29 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
         setError(ui.message);
       };
 
-      const result = await attemptsApi.createPR(attempt.id, {
-        title: prTitle,
-        body: prBody || null,
-        target_branch: prBaseBranch || null,
-        draft: isDraft,
-        auto_generate_description: autoGenerateDescription,
-        repo_id: repoId,
-      });
+      const createPR = (reviewExecutionProcessId: string | null) =>
+        attemptsApi.createPR(attempt.id, {
+          title: prTitle,
+          body: prBody || null,
+          target_branch: prBaseBranch || null,
+          draft: isDraft,
+          auto_generate_description: autoGenerateDescription,
+          repo_id: repoId,
+          review_execution_process_id: reviewExecutionProcessId,
+        });
+
+      // Repos with a review gate review the branch first; ask again until
+      // the review has finished and the PR is created
+      let result = await createPR(null);
+      while (result.success && result.data.type === 'reviewing') {
+        setReviewing(true);
+        const { execution_process_id } = result.data;
+        await new Promise((resolve) =>
+          setTimeout(resolve, REVIEW_POLL_INTERVAL_MS)
+        );
+        result = await createPR(execution_process_id);
+      }
+      setReviewing(false);
 
       if (result.success) {
         setPrTitle('');
@@ -218,6 +236,24 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
           );
           setGhCliHelp(null);
           return;
+        } else if (result.error.type === 'review_blocked') {
+          const findings = result.error.findings
+            .map(
+              (f) =>
+                `[${f.severity}] ${f.file}${f.line != null ? `:${f.line}` : ''}: ${f.message}`
+            )
+            .join('\n');
+          setError(`${t('createPrDialog.errors.reviewBlocked')}\n${findings}`);
+          setGhCliHelp(null);
+          return;
+        } else if (result.error.type === 'review_failed') {
+          setError(
+            t('createPrDialog.errors.reviewFailed', {
+              message: result.error.message,
+            })
+          );
+          setGhCliHelp(null);
+          return;
         }
       }
 
@@ -368,7 +404,11 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
                     </AlertDescription>
                   </Alert>
                 )}
-                {error && <Alert variant="destructive">{error}</Alert>}
+                {error && (
+                  <Alert variant="destructive" className="whitespace-pre-line">
+                    {error}
+                  </Alert>
+                )}
               </div>
             )}
             <DialogFooter>
@@ -383,7 +423,9 @@ const CreatePRDialogImpl = NiceModal.create<CreatePRDialogProps>(
                 {creatingPR ? (
                   <>
                     <Loader2 className="mr-2 h-4 w-4 animate-spin" />
-                    {t('createPrDialog.creating')}
+                    {reviewing
+                      ? t('createPrDialog.reviewing')
+                      : t('createPrDialog.creating')}
                   </>
                 ) : (
                   t('createPrDialog.createButton')
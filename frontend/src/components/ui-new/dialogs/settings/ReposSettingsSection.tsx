@@ -6,7 +6,12 @@ import { GitBranchIcon, SpinnerIcon } from '@phosphor-icons/react';
 import { useRepoBranches } from '@/hooks/useRepoBranches';
 import { useScriptPlaceholders } from '@/hooks/useScriptPlaceholders';
 import { repoApi } from '@/lib/api';
-import type { Repo, UpdateRepo } from 'shared/types';
+import type {
+  ContainerRuntime,
+  PrReviewGate,
+  Repo,
+  UpdateRepo,
+} from 'shared/types';
 import { SearchableDropdownContainer } from '../../containers/SearchableDropdownContainer';
 import {
   DropdownMenu,
@@ -21,6 +26,7 @@ import {
   SettingsInput,
   SettingsTextarea,
   SettingsCheckbox,
+  SettingsSelect,
   SettingsSaveBar,
 } from './SettingsComponents';
 
@@ -28,12 +34,17 @@ interface RepoScriptsFormState {
   display_name: string;
   default_working_dir: string;
   default_target_branch: string;
+  pr_review_gate: PrReviewGate;
   setup_script: string;
   parallel_setup_script: boolean;
   cleanup_script: string;
   archive_script: string;
   copy_files: string;
   dev_server_script: string;
+  container_runtime: ContainerRuntime;
+  container_image: string;
+  container_dockerfile: string;
+  container_ports: string;
 }
 
 function repoToFormState(repo: Repo): RepoScriptsFormState {
@@ -41,15 +52,27 @@ function repoToFormState(repo: Repo): RepoScriptsFormState {
     display_name: repo.display_name,
     default_working_dir: repo.default_working_dir ?? '',
     default_target_branch: repo.default_target_branch ?? '',
+    pr_review_gate: repo.pr_review_gate,
     setup_script: repo.setup_script ?? '',
     parallel_setup_script: repo.parallel_setup_script,
     cleanup_script: repo.cleanup_script ?? '',
     archive_script: repo.archive_script ?? '',
     copy_files: repo.copy_files ?? '',
     dev_server_script: repo.dev_server_script ?? '',
+    container_runtime: repo.container_config.runtime,
+    container_image: repo.container_config.image ?? '',
+    container_dockerfile: repo.container_config.dockerfile ?? '',
+    container_ports: repo.container_config.ports.join(', '),
   };
 }
 
+function parsePorts(ports: string): number[] {
+  return ports
+    .split(',')
+    .map((port) => Number(port.trim()))
+    .filter((port) => Number.isInteger(port) && port > 0 && port < 65536);
+}
+
 interface ReposSettingsSectionProps {
   initialState?: { repoId?: string };
 }
@@ -164,12 +187,19 @@ export function ReposSettingsSection({
         display_name: draft.display_name.trim() || null,
         default_working_dir: draft.default_working_dir.trim() || null,
         default_target_branch: draft.default_target_branch.trim() || null,
+        pr_review_gate: draft.pr_review_gate,
         setup_script: draft.setup_script.trim() || null,
         cleanup_script: draft.cleanup_script.trim() || null,
         archive_script: draft.archive_script.trim() || null,
         copy_files: draft.copy_files.trim() || null,
         parallel_setup_script: draft.parallel_setup_script,
         dev_server_script: draft.dev_server_script.trim() || null,
+        container_config: {
+          runtime: draft.container_runtime,
+          image: draft.container_image.trim() || null,
+          dockerfile: draft.container_dockerfile.trim() || null,
+          ports: parsePorts(draft.container_ports),
+        },
       };
 
       const updatedRepo = await repoApi.update(selectedRepo.id, updateData);
@@ -373,6 +403,20 @@ export function ReposSettingsSection({
                 }
               />
             </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.general.prReviewGate.label')}
+              description={t('settings.repos.general.prReviewGate.helper')}
+            >
+              <SettingsSelect
+                value={draft.pr_review_gate}
+                options={(['off', 'warn', 'block'] as const).map((gate) => ({
+                  value: gate,
+                  label: t(`settings.repos.general.prReviewGate.${gate}`),
+                }))}
+                onChange={(value) => updateDraft({ pr_review_gate: value })}
+              />
+            </SettingsField>
           </SettingsCard>
 
           {/* Scripts settings */}
@@ -452,6 +496,61 @@ export function ReposSettingsSection({
             </SettingsField>
           </SettingsCard>
 
+          {/* Container settings */}
+          <SettingsCard
+            title={t('settings.repos.container.title')}
+            description={t('settings.repos.container.description')}
+          >
+            <SettingsField
+              label={t('settings.repos.container.runtime.label')}
+              description={t('settings.repos.container.runtime.helper')}
+            >
+              <SettingsSelect
+                value={draft.container_runtime}
+                options={(['docker', 'podman'] as const).map((runtime) => ({
+                  value: runtime,
+                  label: t(`settings.repos.container.runtime.${runtime}`),
+                }))}
+                onChange={(value) => updateDraft({ container_runtime: value })}
+              />
+            </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.container.image.label')}
+              description={t('settings.repos.container.image.helper')}
+            >
+              <SettingsInput
+                value={draft.container_image}
+                onChange={(value) => updateDraft({ container_image: value })}
+                placeholder="node:22"
+              />
+            </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.container.dockerfile.label')}
+              description={t('settings.repos.container.dockerfile.helper')}
+            >
+              <SettingsInput
+                value={draft.container_dockerfile}
+                onChange={(value) =>
+                  updateDraft({ container_dockerfile: value })
+                }
+                placeholder="Dockerfile"
+              />
+            </SettingsField>
+
+            <SettingsField
+              label={t('settings.repos.container.ports.label')}
+              description={t('settings.repos.container.ports.helper')}
+            >
+              <SettingsInput
+                value={draft.container_ports}
+                onChange={(value) => updateDraft({ container_ports: value })}
+                placeholder="3000, 5173"
+              />
+            </SettingsField>
+          </SettingsCard>
+
           <SettingsSaveBar
             show={hasUnsavedChanges}
             saving={saving}
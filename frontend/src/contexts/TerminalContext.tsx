@@ -227,6 +227,7 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
       string,
       {
         endpoint: string;
+        sessionId: string | null;
         retryCount: number;
         retryTimer: ReturnType<typeof setTimeout> | null;
         intentionallyClosed: boolean;
@@ -268,6 +269,10 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
 
     const conn = terminalConnectionsRef.current.get(tabId);
     if (conn) {
+      // Sessions survive disconnects, so kill the shell explicitly
+      if (conn.ws.readyState === WebSocket.OPEN) {
+        conn.ws.send(JSON.stringify({ type: 'close' }));
+      }
       conn.ws.close();
       terminalConnectionsRef.current.delete(tabId);
     }
@@ -359,6 +364,7 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
       }
       reconnectStateRef.current.set(tabId, {
         endpoint,
+        sessionId: null,
         retryCount: 0,
         retryTimer: null,
         intentionallyClosed: false,
@@ -370,11 +376,16 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
           return;
         }
 
-        // Create new WebSocket
-        const wsEndpoint = endpoint.replace(/^http/, 'ws');
+        // Create new WebSocket, reattaching to the existing shell if we have one
+        const sessionEndpoint = reconnectState.sessionId
+          ? `${endpoint}&session_id=${reconnectState.sessionId}`
+          : endpoint;
+        const wsEndpoint = sessionEndpoint.replace(/^http/, 'ws');
         const ws = new WebSocket(wsEndpoint);
+        let opened = false;
 
         ws.onopen = () => {
+          opened = true;
           // Reset retry count on successful connection
           const state = reconnectStateRef.current.get(tabId);
           if (state) {
@@ -386,7 +397,17 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
           try {
             const msg = JSON.parse(event.data);
             const callbacks = connectionCallbacksRef.current.get(tabId);
-            if (msg.type === 'output' && msg.data && callbacks) {
+            if (msg.type === 'session') {
+              const state = reconnectStateRef.current.get(tabId);
+              if (state) {
+                // On reattach the server replays its scrollback, so reset
+                // the terminal first to avoid duplicating what's on screen
+                if (state.sessionId === msg.session_id) {
+                  callbacks?.onData('\x1bc');
+                }
+                state.sessionId = msg.session_id;
+              }
+            } else if (msg.type === 'output' && msg.data && callbacks) {
               callbacks.onData(decodeBase64(msg.data));
             } else if (msg.type === 'exit' && callbacks) {
               callbacks.onExit?.();
@@ -411,6 +432,11 @@ export function TerminalProvider({ children }: TerminalProviderProps) {
             return;
           }
 
+          // The session we tried to reattach to is gone; start a new one
+          if (!opened) {
+            state.sessionId = null;
+          }
+
           // Exponential backoff: 500ms, 1s, 2s, 4s, 8s (max), up to 6 retries
           const maxRetries = 6;
           if (state.retryCount < maxRetries) {
@@ -8,6 +8,7 @@ import {
   ResetProcessRequest,
   EditorType,
   CreatePrApiRequest,
+  CreatePrResponse,
   CreateTask,
   CreateAndStartTaskRequest,
   CreateTaskAttemptBody,
@@ -22,6 +23,8 @@ import {
   RepoWithTargetBranch,
   CreateProject,
   CreateProjectRepo,
+  ProjectSecret,
+  UpsertProjectSecret,
   UpdateRepo,
   SearchMode,
   SearchResult,
@@ -328,6 +331,32 @@ export const projectsApi = {
     );
     return handleApiResponse<void>(response);
   },
+
+  getSecrets: async (projectId: string): Promise<ProjectSecret[]> => {
+    const response = await makeRequest(`/api/projects/${projectId}/secrets`);
+    return handleApiResponse<ProjectSecret[]>(response);
+  },
+
+  setSecret: async (
+    projectId: string,
+    data: UpsertProjectSecret
+  ): Promise<ProjectSecret> => {
+    const response = await makeRequest(`/api/projects/${projectId}/secrets`, {
+      method: 'PUT',
+      body: JSON.stringify(data),
+    });
+    return handleApiResponse<ProjectSecret>(response);
+  },
+
+  deleteSecret: async (projectId: string, name: string): Promise<void> => {
+    const response = await makeRequest(
+      `/api/projects/${projectId}/secrets/${encodeURIComponent(name)}`,
+      {
+        method: 'DELETE',
+      }
+    );
+    return handleApiResponse<void>(response);
+  },
 };
 
 // Task Management APIs
@@ -691,12 +720,12 @@ export const attemptsApi = {
   createPR: async (
     attemptId: string,
     data: CreatePrApiRequest
-  ): Promise<Result<string, PrError>> => {
+  ): Promise<Result<CreatePrResponse, PrError>> => {
     const response = await makeRequest(`/api/task-attempts/${attemptId}/pr`, {
       method: 'POST',
       body: JSON.stringify(data),
     });
-    return handleApiResponseAsResult<string, PrError>(response);
+    return handleApiResponseAsResult<CreatePrResponse, PrError>(response);
   },
 
   startDevServer: async (attemptId: string): Promise<ExecutionProcess[]> => {
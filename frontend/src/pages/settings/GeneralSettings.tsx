@@ -20,10 +20,12 @@ import { Label } from '@/components/ui/label';
 import { Input } from '@/components/ui/input';
 import { Alert, AlertDescription } from '@/components/ui/alert';
 import { Checkbox } from '@/components/ui/checkbox';
-import { FolderOpen, Loader2, Volume2 } from 'lucide-react';
+import { FolderOpen, Loader2, Trash2, Volume2 } from 'lucide-react';
 import {
   DEFAULT_PR_DESCRIPTION_PROMPT,
   EditorType,
+  LogRedactionConfig,
+  RedactionRule,
   SoundFile,
   ThemeMode,
   UiLanguage,
@@ -122,6 +124,36 @@ export function GeneralSettings() {
     [config]
   );
 
+  // Replaces rather than merges, so removed rules stay removed
+  const updateLogRedaction = useCallback(
+    (patch: Partial<LogRedactionConfig>) => {
+      setDraft((prev: typeof config) => {
+        if (!prev) return prev;
+        const next = {
+          ...prev,
+          log_redaction: { ...prev.log_redaction, ...patch },
+        };
+        if (!isEqual(next, config)) {
+          setDirty(true);
+        }
+        return next;
+      });
+    },
+    [config]
+  );
+
+  const updateRedactionRule = (
+    index: number,
+    patch: Partial<RedactionRule>
+  ) => {
+    if (!draft) return;
+    updateLogRedaction({
+      rules: draft.log_redaction.rules.map((rule, i) =>
+        i === index ? { ...rule, ...patch } : rule
+      ),
+    });
+  };
+
   // Optional: warn on tab close/navigation with unsaved changes
   useEffect(() => {
     const handler = (e: BeforeUnloadEvent) => {
@@ -576,6 +608,112 @@ export function GeneralSettings() {
         </CardContent>
       </Card>
 
+      <Card>
+        <CardHeader>
+          <CardTitle>{t('settings.general.logRedaction.title')}</CardTitle>
+          <CardDescription>
+            {t('settings.general.logRedaction.description')}
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="redaction-builtin"
+              checked={draft?.log_redaction.builtin_detectors ?? true}
+              onCheckedChange={(checked: boolean) =>
+                updateLogRedaction({ builtin_detectors: checked })
+              }
+            />
+            <div className="space-y-0.5">
+              <Label htmlFor="redaction-builtin" className="cursor-pointer">
+                {t('settings.general.logRedaction.builtin.label')}
+              </Label>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.general.logRedaction.builtin.helper')}
+              </p>
+            </div>
+          </div>
+          <div className="flex items-center space-x-2">
+            <Checkbox
+              id="redaction-high-entropy"
+              checked={draft?.log_redaction.detect_high_entropy ?? false}
+              onCheckedChange={(checked: boolean) =>
+                updateLogRedaction({ detect_high_entropy: checked })
+              }
+            />
+            <div className="space-y-0.5">
+              <Label
+                htmlFor="redaction-high-entropy"
+                className="cursor-pointer"
+              >
+                {t('settings.general.logRedaction.highEntropy.label')}
+              </Label>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.general.logRedaction.highEntropy.helper')}
+              </p>
+            </div>
+          </div>
+          <div className="space-y-2">
+            <Label>{t('settings.general.logRedaction.rules.label')}</Label>
+            {draft?.log_redaction.rules.map((rule, index) => (
+              <div key={index} className="flex gap-2">
+                <Input
+                  className="w-40"
+                  placeholder={t(
+                    'settings.general.logRedaction.rules.namePlaceholder'
+                  )}
+                  value={rule.name}
+                  onChange={(e) =>
+                    updateRedactionRule(index, { name: e.target.value })
+                  }
+                />
+                <Input
+                  className="flex-1 font-mono"
+                  placeholder={t(
+                    'settings.general.logRedaction.rules.patternPlaceholder'
+                  )}
+                  value={rule.pattern}
+                  onChange={(e) =>
+                    updateRedactionRule(index, { pattern: e.target.value })
+                  }
+                />
+                <Button
+                  variant="outline"
+                  size="sm"
+                  onClick={() =>
+                    updateLogRedaction({
+                      rules: draft.log_redaction.rules.filter(
+                        (_, i) => i !== index
+                      ),
+                    })
+                  }
+                  className="px-3"
+                >
+                  <Trash2 className="h-4 w-4" />
+                </Button>
+              </div>
+            ))}
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() =>
+                updateLogRedaction({
+                  rules: [
+                    ...(draft?.log_redaction.rules ?? []),
+                    { name: '', pattern: '' },
+                  ],
+                })
+              }
+            >
+              {t('settings.general.logRedaction.rules.add')}
+            </Button>
+            <p className="text-sm text-muted-foreground">
+              {t('settings.general.logRedaction.rules.helper')}
+            </p>
+          </div>
+        </CardContent>
+      </Card>
+
       <Card>
         <CardHeader>
           <CardTitle>{t('settings.general.notifications.title')}</CardTitle>
@@ -20,6 +20,8 @@ import {
 } from '@/components/ui/select';
 import { Label } from '@/components/ui/label';
 import { Input } from '@/components/ui/input';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Textarea } from '@/components/ui/textarea';
 import { Alert, AlertDescription } from '@/components/ui/alert';
 import { Loader2, Plus, Trash2 } from 'lucide-react';
 import { useProjects } from '@/hooks/useProjects';
@@ -27,18 +29,60 @@ import { useProjectMutations } from '@/hooks/useProjectMutations';
 import { RepoPickerDialog } from '@/components/dialogs/shared/RepoPickerDialog';
 import { projectsApi } from '@/lib/api';
 import { repoBranchKeys } from '@/hooks/useRepoBranches';
-import type { Project, Repo, UpdateProject } from 'shared/types';
+import type {
+  EgressMode,
+  EgressPolicy,
+  Project,
+  ProjectSecret,
+  Repo,
+  SandboxConfig,
+  UpdateProject,
+} from 'shared/types';
+
+const envVarsToText = (vars: Record<string, string>) =>
+  Object.keys(vars)
+    .sort()
+    .map((key) => `${key}=${vars[key]}`)
+    .join('\n');
+
+// One KEY=value per line; lines without a key are dropped
+const textToEnvVars = (text: string) =>
+  Object.fromEntries(
+    text
+      .split('\n')
+      .map((line) => line.trim())
+      .filter((line) => line && !line.startsWith('='))
+      .map((line) => {
+        const separator = line.indexOf('=');
+        return separator === -1
+          ? [line, '']
+          : [line.slice(0, separator).trim(), line.slice(separator + 1)];
+      })
+  );
 
 interface ProjectFormState {
   name: string;
+  sandbox: SandboxConfig;
+  egress: EgressPolicy;
+  envVars: string;
 }
 
 function projectToFormState(project: Project): ProjectFormState {
   return {
     name: project.name,
+    sandbox: project.sandbox_config,
+    egress: project.egress_policy,
+    envVars: envVarsToText(project.env_vars),
   };
 }
 
+const EGRESS_MODES: EgressMode[] = ['allow_all', 'deny_all', 'allowlist'];
+
+const pathsToText = (paths: string[]) => paths.join('\n');
+
+const cleanPaths = (paths: string[]) =>
+  paths.map((path) => path.trim()).filter(Boolean);
+
 export function ProjectSettings() {
   const [searchParams, setSearchParams] = useSearchParams();
   const navigate = useNavigate();
@@ -72,6 +116,14 @@ export function ProjectSettings() {
   const [addingRepo, setAddingRepo] = useState(false);
   const [deletingRepoId, setDeletingRepoId] = useState<string | null>(null);
 
+  // Secrets state
+  const [secrets, setSecrets] = useState<ProjectSecret[]>([]);
+  const [secretError, setSecretError] = useState<string | null>(null);
+  const [secretName, setSecretName] = useState('');
+  const [secretValue, setSecretValue] = useState('');
+  const [savingSecret, setSavingSecret] = useState(false);
+  const [deletingSecret, setDeletingSecret] = useState<string | null>(null);
+
   // Check for unsaved changes (project name)
   const hasUnsavedChanges = useMemo(() => {
     if (!draft || !selectedProject) return false;
@@ -199,6 +251,74 @@ export function ProjectSettings() {
       .finally(() => setLoadingRepos(false));
   }, [selectedProjectId]);
 
+  // Fetch secrets when project changes
+  useEffect(() => {
+    setSecretError(null);
+    if (!selectedProjectId) {
+      setSecrets([]);
+      return;
+    }
+
+    projectsApi
+      .getSecrets(selectedProjectId)
+      .then(setSecrets)
+      .catch((err) => {
+        setSecretError(
+          err instanceof Error
+            ? err.message
+            : t('settings.projects.secrets.loadError')
+        );
+        setSecrets([]);
+      });
+  }, [selectedProjectId, t]);
+
+  const handleSetSecret = async () => {
+    if (!selectedProjectId || !secretName.trim()) return;
+
+    setSavingSecret(true);
+    setSecretError(null);
+    try {
+      const secret = await projectsApi.setSecret(selectedProjectId, {
+        name: secretName.trim(),
+        value: secretValue,
+      });
+      setSecrets((prev) =>
+        [...prev.filter((s) => s.name !== secret.name), secret].sort((a, b) =>
+          a.name.localeCompare(b.name)
+        )
+      );
+      setSecretName('');
+      setSecretValue('');
+    } catch (err) {
+      setSecretError(
+        err instanceof Error
+          ? err.message
+          : t('settings.projects.secrets.saveError')
+      );
+    } finally {
+      setSavingSecret(false);
+    }
+  };
+
+  const handleDeleteSecret = async (name: string) => {
+    if (!selectedProjectId) return;
+
+    setDeletingSecret(name);
+    setSecretError(null);
+    try {
+      await projectsApi.deleteSecret(selectedProjectId, name);
+      setSecrets((prev) => prev.filter((s) => s.name !== name));
+    } catch (err) {
+      setSecretError(
+        err instanceof Error
+          ? err.message
+          : t('settings.projects.secrets.deleteError')
+      );
+    } finally {
+      setDeletingSecret(null);
+    }
+  };
+
   const handleAddRepository = async () => {
     if (!selectedProjectId) return;
 
@@ -292,6 +412,16 @@ export function ProjectSettings() {
     try {
       const updateData: UpdateProject = {
         name: draft.name.trim(),
+        sandbox_config: {
+          ...draft.sandbox,
+          read_only_paths: cleanPaths(draft.sandbox.read_only_paths),
+          writable_paths: cleanPaths(draft.sandbox.writable_paths),
+        },
+        egress_policy: {
+          ...draft.egress,
+          allowed_hosts: cleanPaths(draft.egress.allowed_hosts),
+        },
+        env_vars: textToEnvVars(draft.envVars),
       };
 
       updateProject.mutate({
@@ -317,6 +447,20 @@ export function ProjectSettings() {
     });
   };
 
+  const updateSandbox = (updates: Partial<SandboxConfig>) => {
+    setDraft((prev) => {
+      if (!prev) return prev;
+      return { ...prev, sandbox: { ...prev.sandbox, ...updates } };
+    });
+  };
+
+  const updateEgress = (updates: Partial<EgressPolicy>) => {
+    setDraft((prev) => {
+      if (!prev) return prev;
+      return { ...prev, egress: { ...prev.egress, ...updates } };
+    });
+  };
+
   if (projectsLoading) {
     return (
       <div className="flex items-center justify-center py-8">
@@ -425,6 +569,182 @@ export function ProjectSettings() {
                 </p>
               </div>
 
+              <div className="space-y-4 pt-4 border-t">
+                <div className="flex items-center space-x-2">
+                  <Checkbox
+                    id="sandbox-enabled"
+                    checked={draft.sandbox.enabled}
+                    onCheckedChange={(checked: boolean) =>
+                      updateSandbox({ enabled: checked })
+                    }
+                  />
+                  <div className="space-y-0.5">
+                    <Label htmlFor="sandbox-enabled" className="cursor-pointer">
+                      {t('settings.projects.sandbox.enabled.label')}
+                    </Label>
+                    <p className="text-sm text-muted-foreground">
+                      {t('settings.projects.sandbox.enabled.helper')}
+                    </p>
+                  </div>
+                </div>
+
+                {draft.sandbox.enabled && (
+                  <>
+                    <div className="flex items-center space-x-2">
+                      <Checkbox
+                        id="sandbox-disable-network"
+                        checked={draft.sandbox.disable_network}
+                        onCheckedChange={(checked: boolean) =>
+                          updateSandbox({ disable_network: checked })
+                        }
+                      />
+                      <div className="space-y-0.5">
+                        <Label
+                          htmlFor="sandbox-disable-network"
+                          className="cursor-pointer"
+                        >
+                          {t('settings.projects.sandbox.disableNetwork.label')}
+                        </Label>
+                        <p className="text-sm text-muted-foreground">
+                          {t('settings.projects.sandbox.disableNetwork.helper')}
+                        </p>
+                      </div>
+                    </div>
+
+                    <div className="flex items-center space-x-2">
+                      <Checkbox
+                        id="sandbox-hide-home"
+                        checked={draft.sandbox.hide_home}
+                        onCheckedChange={(checked: boolean) =>
+                          updateSandbox({ hide_home: checked })
+                        }
+                      />
+                      <div className="space-y-0.5">
+                        <Label
+                          htmlFor="sandbox-hide-home"
+                          className="cursor-pointer"
+                        >
+                          {t('settings.projects.sandbox.hideHome.label')}
+                        </Label>
+                        <p className="text-sm text-muted-foreground">
+                          {t('settings.projects.sandbox.hideHome.helper')}
+                        </p>
+                      </div>
+                    </div>
+
+                    <div className="space-y-2">
+                      <Label htmlFor="sandbox-read-only-paths">
+                        {t('settings.projects.sandbox.readOnlyPaths.label')}
+                      </Label>
+                      <Textarea
+                        id="sandbox-read-only-paths"
+                        value={pathsToText(draft.sandbox.read_only_paths)}
+                        onChange={(e) =>
+                          updateSandbox({
+                            read_only_paths: e.target.value.split('\n'),
+                          })
+                        }
+                        placeholder="~/.nvm"
+                        className="font-mono text-sm"
+                      />
+                      <p className="text-sm text-muted-foreground">
+                        {t('settings.projects.sandbox.readOnlyPaths.helper')}
+                      </p>
+                    </div>
+
+                    <div className="space-y-2">
+                      <Label htmlFor="sandbox-writable-paths">
+                        {t('settings.projects.sandbox.writablePaths.label')}
+                      </Label>
+                      <Textarea
+                        id="sandbox-writable-paths"
+                        value={pathsToText(draft.sandbox.writable_paths)}
+                        onChange={(e) =>
+                          updateSandbox({
+                            writable_paths: e.target.value.split('\n'),
+                          })
+                        }
+                        placeholder="~/.cargo/registry"
+                        className="font-mono text-sm"
+                      />
+                      <p className="text-sm text-muted-foreground">
+                        {t('settings.projects.sandbox.writablePaths.helper')}
+                      </p>
+                    </div>
+                  </>
+                )}
+              </div>
+
+              <div className="space-y-4 pt-4 border-t">
+                <div className="space-y-2">
+                  <Label htmlFor="egress-mode">
+                    {t('settings.projects.egress.mode.label')}
+                  </Label>
+                  <Select
+                    value={draft.egress.mode}
+                    onValueChange={(mode) =>
+                      updateEgress({ mode: mode as EgressMode })
+                    }
+                  >
+                    <SelectTrigger id="egress-mode">
+                      <SelectValue />
+                    </SelectTrigger>
+                    <SelectContent>
+                      {EGRESS_MODES.map((mode) => (
+                        <SelectItem key={mode} value={mode}>
+                          {t(`settings.projects.egress.mode.options.${mode}`)}
+                        </SelectItem>
+                      ))}
+                    </SelectContent>
+                  </Select>
+                  <p className="text-sm text-muted-foreground">
+                    {t('settings.projects.egress.mode.helper')}
+                  </p>
+                </div>
+
+                {draft.egress.mode === 'allowlist' && (
+                  <div className="space-y-2">
+                    <Label htmlFor="egress-allowed-hosts">
+                      {t('settings.projects.egress.allowedHosts.label')}
+                    </Label>
+                    <Textarea
+                      id="egress-allowed-hosts"
+                      value={pathsToText(draft.egress.allowed_hosts)}
+                      onChange={(e) =>
+                        updateEgress({
+                          allowed_hosts: e.target.value.split('\n'),
+                        })
+                      }
+                      placeholder={
+                        'api.anthropic.com\ngithub.com\nregistry.npmjs.org'
+                      }
+                      className="font-mono text-sm"
+                    />
+                    <p className="text-sm text-muted-foreground">
+                      {t('settings.projects.egress.allowedHosts.helper')}
+                    </p>
+                  </div>
+                )}
+              </div>
+
+              <div className="space-y-2 pt-4 border-t">
+                <Label htmlFor="project-env-vars">
+                  {t('settings.projects.envVars.label')}
+                </Label>
+                <Textarea
+                  id="project-env-vars"
+                  value={draft.envVars}
+                  onChange={(e) => updateDraft({ envVars: e.target.value })}
+                  placeholder={
+                    'NODE_ENV=development\nNPM_TOKEN=${secret:NPM_TOKEN}'
+                  }
+                  className="font-mono text-sm"
+                />
+                <p className="text-sm text-muted-foreground">
+                  {t('settings.projects.envVars.helper')}
+                </p>
+              </div>
+
               {/* Save Button */}
               <div className="flex items-center justify-between pt-4 border-t">
                 {hasUnsavedChanges ? (
@@ -542,6 +862,91 @@ export function ProjectSettings() {
             </CardContent>
           </Card>
 
+          {/* Secrets Section */}
+          <Card>
+            <CardHeader>
+              <CardTitle>{t('settings.projects.secrets.title')}</CardTitle>
+              <CardDescription>
+                {t('settings.projects.secrets.description')}
+              </CardDescription>
+            </CardHeader>
+            <CardContent className="space-y-4">
+              {secretError && (
+                <Alert variant="destructive">
+                  <AlertDescription>{secretError}</AlertDescription>
+                </Alert>
+              )}
+
+              <div className="space-y-2">
+                {secrets.map((secret) => (
+                  <div
+                    key={secret.id}
+                    className="flex items-center justify-between p-3 border rounded-md"
+                  >
+                    <div className="min-w-0 flex-1">
+                      <div className="font-mono text-sm">{secret.name}</div>
+                      <div className="text-sm text-muted-foreground truncate font-mono">
+                        {`\${secret:${secret.name}}`}
+                      </div>
+                    </div>
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      onClick={() => handleDeleteSecret(secret.name)}
+                      disabled={deletingSecret === secret.name}
+                      title={t('settings.projects.secrets.delete')}
+                    >
+                      {deletingSecret === secret.name ? (
+                        <Loader2 className="h-4 w-4 animate-spin" />
+                      ) : (
+                        <Trash2 className="h-4 w-4" />
+                      )}
+                    </Button>
+                  </div>
+                ))}
+
+                {secrets.length === 0 && (
+                  <div className="text-center py-4 text-sm text-muted-foreground">
+                    {t('settings.projects.secrets.empty')}
+                  </div>
+                )}
+              </div>
+
+              <div className="flex gap-2">
+                <Input
+                  value={secretName}
+                  onChange={(e) => setSecretName(e.target.value)}
+                  placeholder="NPM_TOKEN"
+                  className="font-mono text-sm"
+                  aria-label={t('settings.projects.secrets.name')}
+                />
+                <Input
+                  type="password"
+                  value={secretValue}
+                  onChange={(e) => setSecretValue(e.target.value)}
+                  placeholder={t('settings.projects.secrets.value')}
+                  autoComplete="off"
+                  aria-label={t('settings.projects.secrets.value')}
+                />
+                <Button
+                  variant="outline"
+                  onClick={handleSetSecret}
+                  disabled={savingSecret || !secretName.trim()}
+                >
+                  {savingSecret ? (
+                    <Loader2 className="h-4 w-4 mr-2 animate-spin" />
+                  ) : (
+                    <Plus className="h-4 w-4 mr-2" />
+                  )}
+                  {t('settings.projects.secrets.set')}
+                </Button>
+              </div>
+              <p className="text-sm text-muted-foreground">
+                {t('settings.projects.secrets.helper')}
+              </p>
+            </CardContent>
+          </Card>
+
           {/* Sticky Save Button for Project Name */}
           {hasUnsavedChanges && (
             <div className="sticky bottom-0 z-10 bg-background/80 backdrop-blur-sm border-t py-4">
@@ -524,6 +524,7 @@ export type GitHubAppRepository = {
   github_repo_id: number;
   repo_full_name: string;
   review_enabled: boolean;
+  review_inline_comments: boolean;
 };
 
 export type GitHubAppStatus = {
@@ -600,6 +601,28 @@ export async function updateRepositoryReviewEnabled(
   return res.json();
 }
 
+export async function updateRepositoryReviewInlineComments(
+  orgId: string,
+  repoId: string,
+  enabled: boolean,
+): Promise<GitHubAppRepository> {
+  const res = await authenticatedFetch(
+    `${API_BASE}/v1/organizations/${orgId}/github-app/repositories/${repoId}/review-inline-comments`,
+    {
+      method: "PATCH",
+      headers: { "Content-Type": "application/json" },
+      body: JSON.stringify({ enabled }),
+    },
+  );
+  if (!res.ok) {
+    const error = await res.json().catch(() => ({}));
+    throw new Error(
+      error.error || `Failed to update repository (${res.status})`,
+    );
+  }
+  return res.json();
+}
+
 export async function fetchGitHubAppRepositories(
   orgId: string,
 ): Promise<GitHubAppRepository[]> {
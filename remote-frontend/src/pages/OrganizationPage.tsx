@@ -21,6 +21,7 @@ import {
   getGitHubAppInstallUrl,
   disconnectGitHubApp,
   updateRepositoryReviewEnabled,
+  updateRepositoryReviewInlineComments,
   fetchGitHubAppRepositories,
   bulkUpdateRepositoryReviewEnabled,
   getBillingStatus,
@@ -371,6 +372,39 @@ export default function OrganizationPage() {
     }
   };
 
+  const handleToggleRepoInlineComments = async (
+    repoId: string,
+    enabled: boolean,
+  ) => {
+    if (!orgId) return;
+
+    setRepoToggleLoading(repoId);
+
+    try {
+      const updatedRepo = await updateRepositoryReviewInlineComments(
+        orgId,
+        repoId,
+        enabled,
+      );
+      setRepositories((prev) =>
+        prev.map((r) =>
+          r.id === repoId
+            ? {
+                ...r,
+                review_inline_comments: updatedRepo.review_inline_comments,
+              }
+            : r,
+        ),
+      );
+    } catch (e) {
+      setGithubAppError(
+        e instanceof Error ? e.message : "Failed to update repository",
+      );
+    } finally {
+      setRepoToggleLoading(null);
+    }
+  };
+
   const handleBulkToggle = async (enabled: boolean) => {
     if (!orgId) return;
 
@@ -1038,6 +1072,28 @@ export default function OrganizationPage() {
                                   <span className="text-sm text-gray-700 truncate flex-1 mr-3">
                                     {repo.repo_full_name}
                                   </span>
+                                  <label
+                                    className="inline-flex items-center mr-3 text-xs text-gray-500 whitespace-nowrap cursor-pointer"
+                                    title="Post review findings to the PR as inline comments"
+                                  >
+                                    <input
+                                      type="checkbox"
+                                      checked={repo.review_inline_comments}
+                                      onChange={(e) =>
+                                        handleToggleRepoInlineComments(
+                                          repo.id,
+                                          e.target.checked,
+                                        )
+                                      }
+                                      disabled={
+                                        !repo.review_enabled ||
+                                        repoToggleLoading === repo.id ||
+                                        bulkLoading
+                                      }
+                                      className="mr-1"
+                                    />
+                                    Inline comments
+                                  </label>
                                   <label className="relative inline-flex items-center cursor-pointer">
                                     <input
                                       type="checkbox"
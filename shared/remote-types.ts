@@ -8,7 +8,14 @@ export type Project = { id: string, organization_id: string, name: string, color
 
 export type Notification = { id: string, organization_id: string, user_id: string, notification_type: NotificationType, payload: JsonValue, issue_id: string | null, comment_id: string | null, seen: boolean, dismissed_at: string | null, created_at: string, };
 
-export type NotificationType = "IssueCommentAdded" | "IssueStatusChanged" | "IssueAssigneeChanged" | "IssueDeleted";
+export type NotificationType = "IssueCommentAdded" | "IssueStatusChanged" | "IssueAssigneeChanged" | "IssueDeleted" | "IssueMentioned" | "IssueWorkspaceFinished" | "IssuePullRequestOpened" | "IssuePullRequestMerged" | "IssueBlocked" | "IssueUnblocked";
+
+/**
+ * How often unseen notifications are emailed as a digest.
+ */
+export type DigestFrequency = "off" | "daily" | "weekly";
+
+export type NotificationDigestSettings = { frequency: DigestFrequency, last_sent_at: string | null, };
 
 export type Workspace = { id: string, project_id: string, owner_user_id: string, issue_id: string | null, local_workspace_id: string | null, name: string | null, archived: boolean, files_changed: number | null, lines_added: number | null, lines_removed: number | null, created_at: string, updated_at: string, };
 
@@ -16,7 +23,24 @@ export type ProjectStatus = { id: string, project_id: string, name: string, colo
 
 export type Tag = { id: string, project_id: string, name: string, color: string, };
 
-export type Issue = { id: string, project_id: string, issue_number: number, simple_id: string, status_id: string, title: string, description: string | null, priority: IssuePriority | null, start_date: string | null, target_date: string | null, completed_at: string | null, sort_order: number, parent_issue_id: string | null, parent_issue_sort_order: number | null, extension_metadata: JsonValue, creator_user_id: string | null, created_at: string, updated_at: string, };
+export type CustomField = { id: string, organization_id: string, 
+/**
+ * Stable identifier used in `extension_metadata` and issue queries.
+ */
+key: string, name: string, field_type: CustomFieldType, 
+/**
+ * Allowed values of select and multi-select fields.
+ */
+options: Array<string>, sort_order: number, created_at: string, updated_at: string, };
+
+/**
+ * How a custom field's value is stored in `extension_metadata`:
+ * text, select, user (UUID) and date (`YYYY-MM-DD`) values are strings,
+ * numbers are JSON numbers and multi-select values are arrays of strings.
+ */
+export type CustomFieldType = "text" | "number" | "select" | "multi_select" | "user" | "date";
+
+export type Issue = { id: string, project_id: string, issue_number: number, simple_id: string, status_id: string, title: string, description: string | null, priority: IssuePriority | null, start_date: string | null, target_date: string | null, estimate: number | null, estimate_unit: EstimateUnit, completed_at: string | null, sort_order: number, parent_issue_id: string | null, parent_issue_sort_order: number | null, extension_metadata: JsonValue, creator_user_id: string | null, created_at: string, updated_at: string, };
 
 export type IssueAssignee = { id: string, issue_id: string, user_id: string, assigned_at: string, };
 
@@ -28,12 +52,38 @@ export type IssueRelationship = { id: string, issue_id: string, related_issue_id
 
 export type IssueRelationshipType = "blocking" | "related" | "has_duplicate";
 
+export type IssueTemplate = { id: string, project_id: string, name: string, title: string, description: string | null, status_id: string, priority: IssuePriority | null, tag_ids: Array<string>, assignee_ids: Array<string>, 
+/**
+ * Five-field cron expression (`minute hour day-of-month month
+ * day-of-week`) evaluated in UTC. Templates without one are only
+ * instantiated manually.
+ */
+recurrence: string | null, next_run_at: string | null, 
+/**
+ * Executor profile the local scheduler starts a workspace with for
+ * issues created from this template.
+ */
+auto_start_executor_profile: AutoStartExecutorProfile | null, creator_user_id: string | null, created_at: string, updated_at: string, };
+
+export type IssueView = { id: string, project_id: string, owner_user_id: string | null, name: string, 
+/**
+ * Query in the issue query language, e.g.
+ * `status:"In Review" priority>=high assignee:me`.
+ */
+query: string, sort_order: number, created_at: string, updated_at: string, };
+
+export type IssueTimeEntry = { id: string, issue_id: string, workspace_id: string | null, user_id: string, source: TimeEntrySource, started_at: string, duration_seconds: bigint, created_at: string, };
+
+export type TimeEntrySource = "agent" | "human" | "manual";
+
 export type IssueComment = { id: string, issue_id: string, author_id: string | null, parent_id: string | null, message: string, created_at: string, updated_at: string, };
 
 export type IssueCommentReaction = { id: string, comment_id: string, user_id: string, emoji: string, created_at: string, };
 
 export type IssuePriority = "urgent" | "high" | "medium" | "low";
 
+export type EstimateUnit = "points" | "hours";
+
 export type PullRequestStatus = "open" | "merged" | "closed";
 
 export type PullRequest = { id: string, url: string, number: number, status: PullRequestStatus, merged_at: string | null, merge_commit_sha: string | null, target_branch_name: string, issue_id: string, workspace_id: string | null, created_at: string, updated_at: string, };
@@ -64,6 +114,8 @@ id?: string, organization_id: string, seen: boolean, };
 
 export type UpdateNotificationRequest = { seen: boolean | null, };
 
+export type UpdateNotificationDigestSettingsRequest = { frequency: DigestFrequency, };
+
 export type CreateTagRequest = { 
 /**
  * Optional client-generated ID. If not provided, server generates one.
@@ -73,6 +125,18 @@ id?: string, project_id: string, name: string, color: string, };
 
 export type UpdateTagRequest = { name: string | null, color: string | null, };
 
+export type CreateCustomFieldRequest = { 
+/**
+ * Optional client-generated ID. If not provided, server generates one.
+ * Using client-generated IDs enables stable optimistic updates.
+ */
+id?: string, organization_id: string, key: string, name: string, field_type: CustomFieldType, options: Array<string>, sort_order: number, };
+
+/**
+ * A field's key and type can't change once issues may hold values for it.
+ */
+export type UpdateCustomFieldRequest = { name: string | null, options: Array<string> | null, sort_order: number | null, };
+
 export type CreateProjectStatusRequest = { 
 /**
  * Optional client-generated ID. If not provided, server generates one.
@@ -87,9 +151,13 @@ export type CreateIssueRequest = {
  * Optional client-generated ID. If not provided, server generates one.
  * Using client-generated IDs enables stable optimistic updates.
  */
-id?: string, project_id: string, status_id: string, title: string, description: string | null, priority: IssuePriority | null, start_date: string | null, target_date: string | null, completed_at: string | null, sort_order: number, parent_issue_id: string | null, parent_issue_sort_order: number | null, extension_metadata: JsonValue, };
+id?: string, project_id: string, status_id: string, title: string, description: string | null, priority: IssuePriority | null, start_date: string | null, target_date: string | null, estimate?: number, 
+/**
+ * Defaults to points.
+ */
+estimate_unit?: EstimateUnit, completed_at: string | null, sort_order: number, parent_issue_id: string | null, parent_issue_sort_order: number | null, extension_metadata: JsonValue, };
 
-export type UpdateIssueRequest = { status_id?: string | null, title?: string | null, description?: string | null | null, priority?: IssuePriority | null | null, start_date?: string | null | null, target_date?: string | null | null, completed_at?: string | null | null, sort_order?: number | null, parent_issue_id?: string | null | null, parent_issue_sort_order?: number | null | null, extension_metadata?: JsonValue | null, };
+export type UpdateIssueRequest = { status_id?: string | null, title?: string | null, description?: string | null | null, priority?: IssuePriority | null | null, start_date?: string | null | null, target_date?: string | null | null, estimate?: number | null | null, estimate_unit?: EstimateUnit | null, completed_at?: string | null | null, sort_order?: number | null, parent_issue_id?: string | null | null, parent_issue_sort_order?: number | null | null, extension_metadata?: JsonValue | null, };
 
 export type CreateIssueAssigneeRequest = { 
 /**
@@ -119,6 +187,38 @@ export type CreateIssueRelationshipRequest = {
  */
 id?: string, issue_id: string, related_issue_id: string, relationship_type: IssueRelationshipType, };
 
+export type AutoStartExecutorProfile = { executor: string, variant?: string, };
+
+export type CreateIssueTemplateRequest = { 
+/**
+ * Optional client-generated ID. If not provided, server generates one.
+ * Using client-generated IDs enables stable optimistic updates.
+ */
+id?: string, project_id: string, name: string, title: string, description: string | null, status_id: string, priority: IssuePriority | null, tag_ids: Array<string>, assignee_ids: Array<string>, recurrence: string | null, auto_start_executor_profile: AutoStartExecutorProfile | null, };
+
+export type UpdateIssueTemplateRequest = { name?: string | null, title?: string | null, description?: string | null | null, status_id?: string | null, priority?: IssuePriority | null | null, tag_ids?: Array<string> | null, assignee_ids?: Array<string> | null, recurrence?: string | null | null, auto_start_executor_profile?: AutoStartExecutorProfile | null | null, };
+
+export type CreateIssueViewRequest = { 
+/**
+ * Optional client-generated ID. If not provided, server generates one.
+ * Using client-generated IDs enables stable optimistic updates.
+ */
+id?: string, project_id: string, name: string, query: string, 
+/**
+ * Share the view with the whole organization instead of keeping it
+ * private to its creator.
+ */
+shared: boolean, sort_order: number, };
+
+export type UpdateIssueViewRequest = { name?: string | null, query?: string | null, shared?: boolean | null, sort_order?: number | null, };
+
+export type CreateIssueTimeEntryRequest = { 
+/**
+ * Optional client-generated ID. If not provided, server generates one.
+ * Reporting an entry again with the same ID replaces its duration.
+ */
+id?: string, issue_id: string, workspace_id?: string, source: TimeEntrySource, started_at: string, duration_seconds: bigint, };
+
 export type CreateIssueCommentRequest = { 
 /**
  * Optional client-generated ID. If not provided, server generates one.
@@ -179,6 +279,12 @@ export const USERS_SHAPE = defineShape<User>(
   '/v1/shape/users'
 );
 
+export const ORGANIZATION_CUSTOM_FIELDS_SHAPE = defineShape<CustomField>(
+  'custom_fields',
+  ['organization_id'] as const,
+  '/v1/shape/custom_fields'
+);
+
 export const PROJECT_TAGS_SHAPE = defineShape<Tag>(
   'tags',
   ['project_id'] as const,
@@ -288,6 +394,11 @@ export const TAG_MUTATION = defineMutation<Tag, CreateTagRequest, UpdateTagReque
   '/v1/tags'
 );
 
+export const CUSTOM_FIELD_MUTATION = defineMutation<CustomField, CreateCustomFieldRequest, UpdateCustomFieldRequest>(
+  'CustomField',
+  '/v1/custom_fields'
+);
+
 export const PROJECT_STATUS_MUTATION = defineMutation<ProjectStatus, CreateProjectStatusRequest, UpdateProjectStatusRequest>(
   'ProjectStatus',
   '/v1/project_statuses'
@@ -318,6 +429,21 @@ export const ISSUE_RELATIONSHIP_MUTATION = defineMutation<IssueRelationship, Cre
   '/v1/issue_relationships'
 );
 
+export const ISSUE_TEMPLATE_MUTATION = defineMutation<IssueTemplate, CreateIssueTemplateRequest, UpdateIssueTemplateRequest>(
+  'IssueTemplate',
+  '/v1/issue_templates'
+);
+
+export const ISSUE_TIME_ENTRY_MUTATION = defineMutation<IssueTimeEntry, CreateIssueTimeEntryRequest, unknown>(
+  'IssueTimeEntry',
+  '/v1/issue_time_entries'
+);
+
+export const ISSUE_VIEW_MUTATION = defineMutation<IssueView, CreateIssueViewRequest, UpdateIssueViewRequest>(
+  'IssueView',
+  '/v1/issue_views'
+);
+
 export const ISSUE_COMMENT_MUTATION = defineMutation<IssueComment, CreateIssueCommentRequest, UpdateIssueCommentRequest>(
   'IssueComment',
   '/v1/issue_comments'
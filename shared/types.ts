@@ -4,11 +4,53 @@
 
 // If you are an AI, and you absolutely have to edit this file, please confirm with the user first.
 
-export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, default_agent_working_dir: string | null, remote_project_id: string | null, sandbox_config: SandboxConfig, egress_policy: EgressPolicy, 
+/**
+ * Environment variables for every execution in the project, scripts
+ * included. Values may reference secrets as `${secret:NAME}`.
+ */
+env_vars: Record<string, string>, created_at: Date, updated_at: Date, };
 
 export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, };
 
-export type UpdateProject = { name: string | null, };
+export type UpdateProject = { name: string | null, sandbox_config?: SandboxConfig | null, egress_policy?: EgressPolicy | null, env_vars?: Record<string, string> | null, };
+
+export type SandboxConfig = { 
+/**
+ * Run the project's executions under bubblewrap
+ */
+enabled: boolean, 
+/**
+ * Cut coding agents off from the network. Scripts keep network access so
+ * setup scripts can still install dependencies.
+ */
+disable_network: boolean, 
+/**
+ * Hide the home directory instead of mounting it read-only. Agent state
+ * directories are still mounted, but tools installed under the home
+ * directory have to be listed in `read_only_paths`.
+ */
+hide_home: boolean, 
+/**
+ * Extra paths mounted read-only, e.g. `~/.nvm` when the home is hidden
+ */
+read_only_paths: Array<string>, 
+/**
+ * Extra paths mounted writable
+ */
+writable_paths: Array<string>, };
+
+export type EgressMode = "allow_all" | "deny_all" | "allowlist";
+
+/**
+ * Per-project egress policy
+ */
+export type EgressPolicy = { mode: EgressMode, 
+/**
+ * Hosts reachable in `allowlist` mode. An entry also allows its
+ * subdomains, so `github.com` allows `api.github.com`.
+ */
+allowed_hosts: Array<string>, };
 
 export type SearchResult = { path: string, is_file: boolean, match_type: SearchMatchType, 
 /**
@@ -18,20 +60,73 @@ score: bigint, };
 
 export type SearchMatchType = "FileName" | "DirectoryName" | "FullPath";
 
-export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, created_at: Date, updated_at: Date, };
+export type Repo = { id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, pr_review_gate: PrReviewGate, container_config: ContainerConfig, created_at: Date, updated_at: Date, };
+
+export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, archive_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, default_working_dir?: string | null, pr_review_gate?: PrReviewGate, container_config?: ContainerConfig, };
+
+export type PrReviewGate = "off" | "warn" | "block";
+
+export type ContainerRuntime = "docker" | "podman";
 
-export type UpdateRepo = { display_name?: string | null, setup_script?: string | null, cleanup_script?: string | null, archive_script?: string | null, copy_files?: string | null, parallel_setup_script?: boolean | null, dev_server_script?: string | null, default_target_branch?: string | null, default_working_dir?: string | null, };
+/**
+ * Per-repo container settings. Executions run in a container when the repo
+ * has an image or a Dockerfile.
+ */
+export type ContainerConfig = { runtime: ContainerRuntime, 
+/**
+ * Image to run, e.g. `node:22`. It has to provide the coding agent CLIs
+ * (or `npx`) and whatever the scripts need.
+ */
+image: string | null, 
+/**
+ * Dockerfile to build the image from, relative to the repo root, which is
+ * also the build context. Takes precedence over `image`.
+ */
+dockerfile: string | null, 
+/**
+ * Ports published for dev servers, each on a free host port. Dev servers
+ * have to listen on `0.0.0.0` inside the container.
+ */
+ports: Array<number>, };
 
 export type ProjectRepo = { id: string, project_id: string, repo_id: string, };
 
 export type CreateProjectRepo = { display_name: string, git_repo_path: string, };
 
+/**
+ * A project secret. The value is encrypted with the local master key and
+ * never leaves the server; environment variables reference it as
+ * `${secret:NAME}`.
+ */
+export type ProjectSecret = { id: string, project_id: string, name: string, created_at: string, updated_at: string, };
+
+export type UpsertProjectSecret = { name: string, value: string, };
+
 export type WorkspaceRepo = { id: string, workspace_id: string, repo_id: string, target_branch: string, created_at: Date, updated_at: Date, };
 
 export type CreateWorkspaceRepo = { repo_id: string, target_branch: string, };
 
 export type RepoWithTargetBranch = { target_branch: string, id: string, path: string, name: string, display_name: string, setup_script: string | null, cleanup_script: string | null, archive_script: string | null, copy_files: string | null, parallel_setup_script: boolean, dev_server_script: string | null, default_target_branch: string | null, default_working_dir: string | null, created_at: Date, updated_at: Date, };
 
+export type SchedulerRepo = { repo_id: string, target_branch: string, };
+
+export type IssueScheduler = { id: string, remote_project_id: string, project_id: string, 
+/**
+ * Whether issues in the ready status are picked up. Issues created from
+ * an auto-start template are started either way.
+ */
+enabled: boolean, 
+/**
+ * Name of the status an issue must be in to be picked up.
+ */
+ready_status: string, 
+/**
+ * Status names that count a blocker as resolved.
+ */
+done_statuses: Array<string>, executor_profile_id: ExecutorProfileId, repos: Array<SchedulerRepo>, max_concurrent: bigint, created_at: Date, updated_at: Date, };
+
+export type UpsertIssueScheduler = { project_id: string, enabled: boolean, ready_status: string, done_statuses: Array<string>, executor_profile_id: ExecutorProfileId, repos: Array<SchedulerRepo>, max_concurrent: bigint, };
+
 export type Tag = { id: string, tag_name: string, content: string, created_at: string, updated_at: string, };
 
 export type CreateTag = { tag_name: string, content: string, };
@@ -304,7 +399,13 @@ export type OpenEditorResponse = { url: string | null, };
 
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
 
-export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
+export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, 
+/**
+ * The pre-PR review started by an earlier call, for repos with a review gate
+ */
+review_execution_process_id: string | null, };
+
+export type CreatePrResponse = { "type": "created", url: string, } | { "type": "reviewing", execution_process_id: string, status: ExecutionProcessStatus, };
 
 export type ImageResponse = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
@@ -330,7 +431,7 @@ export type GitOperationError = { "type": "merge_conflicts", message: string, op
 
 export type PushError = { "type": "force_push_required" };
 
-export type PrError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "unsupported_provider" };
+export type PrError = { "type": "cli_not_installed", provider: ProviderKind, } | { "type": "cli_not_logged_in", provider: ProviderKind, } | { "type": "git_cli_not_logged_in" } | { "type": "git_cli_not_installed" } | { "type": "target_branch_not_found", branch: string, } | { "type": "unsupported_provider" } | { "type": "review_blocked", findings: Array<ReviewFinding>, } | { "type": "review_failed", message: string, };
 
 export type RunScriptError = { "type": "no_script_configured" } | { "type": "process_already_running" };
 
@@ -428,13 +529,15 @@ export type WorkspaceSummaryResponse = { summaries: Array<WorkspaceSummary>, };
 
 export type DiffStats = { files_changed: number, lines_added: number, lines_removed: number, };
 
+export type TerminalSessionInfo = { id: string, workspace_id: string, created_at: string, attached_clients: number, exited: boolean, };
+
 export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
 export type SearchMode = "taskform" | "settings";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, remote_onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, commit_reminder_enabled: boolean, commit_reminder_prompt: string | null, send_message_shortcut: SendMessageShortcut, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, remote_onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, commit_reminder_enabled: boolean, commit_reminder_prompt: string | null, send_message_shortcut: SendMessageShortcut, log_redaction: LogRedactionConfig, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -456,8 +559,73 @@ export type ShowcaseState = { seen_features: Array<string>, };
 
 export type SendMessageShortcut = "ModifierEnter" | "Enter";
 
+/**
+ * Log redaction settings
+ */
+export type LogRedactionConfig = { 
+/**
+ * Apply the built-in detectors for private keys, AWS keys and GitHub
+ * tokens
+ */
+builtin_detectors: boolean, 
+/**
+ * Also redact long random-looking tokens. Can catch unknown secrets, but
+ * also e.g. long encoded data, so it is off by default.
+ */
+detect_high_entropy: boolean, rules: Array<RedactionRule>, 
+/**
+ * `rules_hash` of the settings the stored logs were last scrubbed with
+ */
+stored_logs_scrubbed_with: string | null, };
+
+/**
+ * A custom redaction rule
+ */
+export type RedactionRule = { 
+/**
+ * Shown in the redaction marker, e.g. `[REDACTED:stripe_key]`
+ */
+name: string, 
+/**
+ * Regular expression to redact. If it has a capture group named `secret`,
+ * only that group is redacted.
+ */
+pattern: string, };
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
+export type ProjectAnalytics = { weeks: Array<AgentWeekAnalytics>, };
+
+/**
+ * Workspaces started in one week with one coding agent. Durations are in
+ * seconds.
+ */
+export type AgentWeekAnalytics = { 
+/**
+ * Monday 00:00 UTC of the week the workspaces were created in.
+ */
+week_start: string, 
+/**
+ * `None` when the workspace's executor isn't a known coding agent.
+ */
+executor: BaseCodingAgent | null, workspaces: number, merged: number, archived_without_merge: number, 
+/**
+ * Share of workspaces archived without being merged.
+ */
+archived_without_merge_rate: number, 
+/**
+ * From issue creation to the issue's first workspace.
+ */
+median_issue_to_workspace_secs: bigint | null, median_workspace_to_pull_request_secs: bigint | null, median_pull_request_to_merge_secs: bigint | null, 
+/**
+ * From issue creation to merge.
+ */
+median_cycle_time_secs: bigint | null, 
+/**
+ * Coding agent runs after the first one, per merged workspace.
+ */
+mean_follow_ups_per_merged: number | null, median_agent_runtime_secs: bigint | null, };
+
 export type QueuedMessage = { 
 /**
  * The session this message is queued for
@@ -605,7 +773,23 @@ session_id: string | null,
  */
 working_dir: string | null, };
 
-export type RepoReviewContext = { repo_id: string, repo_name: string, base_commit: string, };
+export type RepoReviewContext = { repo_id: string, repo_name: string, base_commit: string, 
+/**
+ * HEAD when the review started, the end of the reviewed range
+ */
+head_commit: string | null, };
+
+export type FindingSeverity = "low" | "medium" | "high";
+
+export type ReviewFinding = { severity: FindingSeverity, 
+/**
+ * Path relative to the repository root
+ */
+file: string, 
+/**
+ * Line in the new version of the file, if the finding is about one line
+ */
+line: number | null, message: string, };
 
 export type CommandExitStatus = { "type": "exit_code", code: number, } | { "type": "success", success: boolean, };
 